@@ -1,37 +1,60 @@
 use std::collections::{HashMap, HashSet};
 use std::env;
 use std::fs;
-use std::net::TcpStream;
+use std::net::{TcpStream, ToSocketAddrs};
 use std::path::{Path, PathBuf};
 use std::process::Command as ProcessCommand;
 use std::sync::Arc;
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use anyhow::{Result, bail};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
 use crossbeam_channel::Sender;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ssh2::Session;
 
-use crate::backend::{FsBackend, backend_from_spec, is_archive_file_path};
+use crate::backend::{
+    FsBackend, backend_from_spec, is_archive_file_path, prune_idle_sftp_sessions,
+};
+use crate::config::{config_dir_path, parse_tables, parse_value, string_line};
+use crate::errors::{ErrorClass, HostKeyError, HostKeyInfo};
 use crate::find::{
     cancel_running_find, is_fd_available, is_rg_available, parse_content_search_input,
     parse_find_input, spawn_fd_search, spawn_rg_search,
 };
+use crate::fs::{group_id, user_id};
+use crate::history::{history_from_toml, history_to_toml};
+use crate::hotlist::{
+    HotlistEntry, HotlistTarget, filter_hotlist, group_hotlist, hotlist_from_toml, hotlist_to_toml,
+    move_hotlist_entry,
+};
 use crate::jobs::WorkerPool;
+use crate::known_hosts::trust_host_key;
+use crate::listing::{DiveRequest, ListingRequest, spawn_dive_listing, spawn_listing};
 use crate::menu::{MenuAction, menu_group_index_by_hotkey, top_menu_groups};
+use crate::mode::ModeSpec;
 use crate::model::{
-    AppState, ArchiveConnectionInfo, BackendSpec, BatchProgressState, Command, DialogButton,
-    DialogButtonRole, DialogState, DialogTone, Event, FindKind, FindPanelState, FindProgressState,
-    FindRequest, FindUpdate, FsEntry, FsEntryType, Job, JobKind, JobRequest, JobStatus, JobUpdate,
-    PanelId, ScreenMode, SftpAuth, SftpConnectionInfo, SortMode, TerminalSize, ViewerMode,
-    ViewerState,
+    AppState, ArchiveConnectionInfo, AttributeChange, BackendSpec, BatchProgressState,
+    ByteProgress, Command, CopyOptions, DialogButton, DialogButtonRole, DialogField, DialogList,
+    DialogState, DialogTone, DirListing, DiveUpdate, Event, FindKind, FindPanelState,
+    FindProgressState, FindRequest, FindUpdate, FsEntry, FsEntryType, HistoryEntry, Job,
+    JobControl, JobKind, JobRequest, JobStatus, JobUpdate, ListingUpdate, PanelId, PanelState,
+    PanelTabs, PropertiesField, PropertiesState, ScreenMode, SftpAuth, SftpConnectionInfo,
+    SortMode, TerminalSize, TextInput, TrashItem, TrashPanelState, ViewerMode, ViewerState,
 };
+use crate::rename::{
+    CaseMode, RenamePreview, RenameRule, check_renames, names_to_edit_text, parse_edited_names,
+    preview_renames,
+};
+use crate::ssh_config::{SshConfig, load_ssh_config};
 use crate::theme::{DirColorsTheme, load_theme_from_environment};
 use crate::viewer::{
     VIEWER_PREVIEW_LIMIT_BYTES, jump_to_next_match, load_viewer_state_from_preview,
     refresh_viewer_search, set_viewer_mode,
 };
-use crate::{runtime, terminal};
+use crate::watch::DirWatch;
+use crate::{runtime, terminal, trash};
 
 pub struct App {
     state: AppState,
@@ -46,6 +69,7 @@ pub struct App {
     next_job_id: u64,
     next_batch_id: u64,
     next_find_id: u64,
+    next_listing_id: u64,
     pending_confirmation: Option<PendingConfirmation>,
     pending_rename: Option<PendingRename>,
     pending_mask: Option<PendingMask>,
@@ -55,23 +79,70 @@ pub struct App {
     pending_conflict: Option<PendingConflict>,
     pending_find: Option<PendingFind>,
     pending_editor_choice: Option<PendingEditorChoice>,
+    pending_host_key: Option<PendingHostKey>,
+    pending_batch_failures: Option<PendingBatchFailures>,
+    pending_batch_rename: Option<PendingBatchRename>,
+    pending_editor_rename: Option<PendingEditorRename>,
+    pending_symlink_edit: Option<PendingSymlinkEdit>,
+    pending_pick: Option<PendingPick>,
+    /// Directory the "add to hotlist" form is about.
+    pending_hotlist_add: Option<HotlistTarget>,
+    pending_trash_purge: bool,
     pending_viewer_search: bool,
     batch_progress: HashMap<u64, BatchProgress>,
+    job_controls: HashMap<u64, JobControl>,
+    job_requests: HashMap<u64, JobRequest>,
     left_active_find_id: Option<u64>,
     right_active_find_id: Option<u64>,
+    left_listing: Option<ActiveListing>,
+    right_listing: Option<ActiveListing>,
+    left_watch: Option<DirWatch>,
+    right_watch: Option<DirWatch>,
+    left_parked: Vec<ParkedBackend>,
+    right_parked: Vec<ParkedBackend>,
     force_full_redraw: bool,
     last_left_local_cwd: PathBuf,
     last_right_local_cwd: PathBuf,
     input_mode: Option<InputMode>,
+    /// `Ctrl+X` was pressed and the next key picks a link action.
+    pending_ctrl_x: bool,
+}
+
+/// Listing running in the background for a panel. `select` is the path the
+/// cursor moves to once it arrives; without one the cursor stays on the entry
+/// it is on. `rerun` is set when the directory changed after it started.
+struct ActiveListing {
+    id: u64,
+    cwd: PathBuf,
+    cancel: Arc<AtomicBool>,
+    select: Option<PathBuf>,
+    /// History entry being revisited; it becomes the current one only once
+    /// the listing arrives.
+    history_index: Option<usize>,
+    update_status: bool,
+    rerun: bool,
+}
+
+/// Backend of a tab that is not shown, in the same order as the panel states
+/// in `PanelTabs::parked`.
+struct ParkedBackend {
+    backend: Arc<dyn FsBackend>,
+    spec: BackendSpec,
+    last_local_cwd: PathBuf,
 }
 
 enum PendingConfirmation {
     DeleteOne {
+        kind: JobKind,
         path: PathBuf,
         name: String,
         is_directory: bool,
     },
     Batch(BatchPlan),
+    /// Abort of every unfinished job behind the progress overlay `progress_id`.
+    AbortProgress {
+        progress_id: u64,
+    },
 }
 
 struct PendingRename {
@@ -81,11 +152,53 @@ struct PendingRename {
     destination_dir: PathBuf,
 }
 
+/// Symlink at `path` whose target is being edited; `target` is the current one.
+struct PendingSymlinkEdit {
+    path: PathBuf,
+    name: String,
+    target: PathBuf,
+}
+
+/// Pick-list popup; row `i` of the dialog list opens `targets[i]`.
+enum PendingPick {
+    /// Indices into the panel's history, newest first.
+    History {
+        panel_id: PanelId,
+        targets: Vec<usize>,
+    },
+    /// The hotlist as loaded; `targets` are the entries the filter shows.
+    /// While a removal waits for a yes, `removing` keeps the popup to go
+    /// back to.
+    Hotlist {
+        panel_id: PanelId,
+        entries: Vec<HotlistEntry>,
+        targets: Vec<usize>,
+        removing: Option<Box<DialogState>>,
+    },
+}
+
 struct PendingMask {
     panel_id: PanelId,
     select: bool,
 }
 
+/// Names handed to the editor for a vidir-style rename. `text` is what the
+/// editor gets next: the numbered list at first, the user's edit afterwards.
+struct PendingEditorRename {
+    paths: Vec<PathBuf>,
+    names: Vec<String>,
+    existing: HashSet<String>,
+    text: String,
+}
+
+/// Pattern rename form over `paths`, whose names are `names`; `existing` holds
+/// every name in their directory for the collision check.
+struct PendingBatchRename {
+    paths: Vec<PathBuf>,
+    names: Vec<String>,
+    existing: HashSet<String>,
+}
+
 struct PendingMkdir {
     panel_id: PanelId,
     base_dir: PathBuf,
@@ -93,8 +206,36 @@ struct PendingMkdir {
 
 struct PendingSftpConnect {
     panel_id: PanelId,
-    stage: SftpConnectStage,
-    draft: SftpConnectDraft,
+}
+
+/// Login methods a server offers, asked before `Auto` settles on one.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SftpAuthHint {
+    PasswordOnly,
+    KeyOnly,
+    Either,
+}
+
+/// Where the SFTP connect form sends the panel.
+enum SftpConnectTarget {
+    Local,
+    Bookmark(String),
+    Sftp(SftpConnectionInfo),
+}
+
+/// Key of the form field at fault and the message to show for it.
+type FieldError = (&'static str, String);
+
+struct PendingHostKey {
+    info: HostKeyInfo,
+    resume: HostKeyResume,
+}
+
+enum HostKeyResume {
+    Attach {
+        panel_id: PanelId,
+        conn: SftpConnectionInfo,
+    },
 }
 
 struct PendingFind {
@@ -124,17 +265,18 @@ enum BookmarkAction {
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum BookmarkStage {
     Name,
-    Address,
-    Login,
-    Password,
+    /// The add/edit form with all bookmark fields.
+    Form,
     ConfirmDelete,
 }
 
+/// Saved SFTP server. An unset `port` or empty `user` is taken from
+/// `~/.ssh/config` for the host, then from the defaults.
 #[derive(Clone)]
 struct SftpBookmark {
     name: String,
     host: String,
-    port: u16,
+    port: Option<u16>,
     user: String,
     root_path: PathBuf,
     password: Option<String>,
@@ -145,12 +287,24 @@ impl SftpBookmark {
         Self {
             name: String::new(),
             host: String::new(),
-            port: 22,
+            port: None,
             user: String::new(),
             root_path: PathBuf::from("/"),
             password: None,
         }
     }
+
+    /// `[user@]host[:port]` as written in the bookmark.
+    fn endpoint(&self) -> String {
+        let mut endpoint = self.host.clone();
+        if !self.user.is_empty() {
+            endpoint = format!("{}@{endpoint}", self.user);
+        }
+        if let Some(port) = self.port {
+            endpoint = format!("{endpoint}:{port}");
+        }
+        endpoint
+    }
 }
 
 struct PendingEditorChoice {
@@ -160,6 +314,7 @@ struct PendingEditorChoice {
 
 enum EditorChoiceContext {
     OpenFile(PathBuf),
+    RenameNames(PendingEditorRename),
     SettingsOnly,
 }
 
@@ -169,35 +324,14 @@ struct EditorCandidate {
     command: String,
 }
 
-#[derive(Clone)]
-struct SftpConnectDraft {
-    host: String,
-    port: u16,
-    root_path: PathBuf,
-    user: Option<String>,
-}
-
-#[derive(Clone, Copy, PartialEq, Eq)]
-enum SftpConnectStage {
-    Address,
-    Login,
-    Password,
-    KeyPath,
-}
-
-#[derive(Clone, Copy, PartialEq, Eq)]
-enum SftpAuthHint {
-    PasswordOnly,
-    KeyOnly,
-    Either,
-}
-
 #[derive(Clone)]
 struct BatchOpItem {
     source: PathBuf,
     destination: Option<PathBuf>,
     name: String,
+    size_bytes: u64,
     overwrite_destination: bool,
+    attributes: Option<AttributeChange>,
 }
 
 struct BatchPlan {
@@ -212,7 +346,162 @@ struct BatchProgress {
     total: usize,
     completed: usize,
     failed: usize,
+    canceled: usize,
+    paused: bool,
     current_file: String,
+    job_bytes: HashMap<u64, ByteProgress>,
+    current_bytes: ByteProgress,
+    /// Entries done/discovered per recursive attribute job.
+    job_entries: HashMap<u64, (usize, usize)>,
+    failures: Vec<BatchFailure>,
+}
+
+impl BatchProgress {
+    fn new(kind: JobKind, total: usize, current_file: String) -> Self {
+        Self {
+            kind,
+            total,
+            completed: 0,
+            failed: 0,
+            canceled: 0,
+            paused: false,
+            current_file,
+            job_bytes: HashMap::new(),
+            current_bytes: ByteProgress::default(),
+            job_entries: HashMap::new(),
+            failures: Vec::new(),
+        }
+    }
+
+    fn snapshot(&self, batch_id: u64) -> BatchProgressState {
+        BatchProgressState {
+            batch_id,
+            operation: self.kind,
+            current_file: self.current_file.clone(),
+            completed: self.completed,
+            total: self.total,
+            failed: self.failed,
+            bytes: self.bytes(),
+            entries: self.entries(),
+            paused: self.paused,
+        }
+    }
+
+    fn entries(&self) -> Option<(usize, usize)> {
+        if self.kind != JobKind::Chattr || self.job_entries.is_empty() {
+            return None;
+        }
+        Some(
+            self.job_entries
+                .values()
+                .fold((0, 0), |(done, total), (job_done, job_total)| {
+                    (done + job_done, total + job_total)
+                }),
+        )
+    }
+
+    /// Sums the per-job counters into batch totals; the file-level fields come
+    /// from the most recent progress event.
+    fn bytes(&self) -> Option<ByteProgress> {
+        if !matches!(self.kind, JobKind::Copy | JobKind::Move) {
+            return None;
+        }
+        let mut bytes = ByteProgress {
+            file_done: self.current_bytes.file_done,
+            file_total: self.current_bytes.file_total,
+            ..ByteProgress::default()
+        };
+        for job in self.job_bytes.values() {
+            bytes.done = bytes.done.saturating_add(job.done);
+            bytes.total = bytes.total.saturating_add(job.total);
+            bytes.bytes_per_sec = bytes.bytes_per_sec.saturating_add(job.bytes_per_sec);
+        }
+        Some(bytes)
+    }
+}
+
+const DEFAULT_TRASH_PURGE_DAYS: u64 = 30;
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+const BATCH_FAILURE_DETAIL_LINES: usize = 12;
+const BATCH_FAILURE_DETAIL_WIDTH: usize = 72;
+/// Choices of the SFTP connect form's `Auth` dropdown, by index.
+const SFTP_AUTH_OPTIONS: [&str; 4] = ["Auto", "Password", "Key file", "Agent"];
+const SFTP_AUTH_AUTO: usize = 0;
+const SFTP_AUTH_PASSWORD: usize = 1;
+const SFTP_AUTH_KEY_FILE: usize = 2;
+const SFTP_AUTH_AGENT: usize = 3;
+const SFTP_PROBE_TIMEOUT: Duration = Duration::from_secs(8);
+/// Buttons of the batch failure dialog, by index.
+const BATCH_FAILURES_RETRY: usize = 0;
+const BATCH_FAILURES_SKIP: usize = 1;
+const BATCH_FAILURES_TOGGLE_DETAILS: usize = 2;
+/// Buttons of the rename-in-editor review: the form's Apply and Cancel with
+/// Edit again between them.
+const EDITOR_RENAME_APPLY: usize = 0;
+const EDITOR_RENAME_EDIT_AGAIN: usize = 1;
+/// Buttons of the pick popups, by index: history has Go and Cancel, the
+/// hotlist has Add current and Remove between them.
+const PICK_GO: usize = 0;
+const HOTLIST_ADD_CURRENT: usize = 1;
+const HOTLIST_REMOVE: usize = 2;
+
+#[derive(Clone)]
+struct BatchFailure {
+    job_id: u64,
+    class: ErrorClass,
+    message: String,
+}
+
+struct PendingBatchFailures {
+    kind: JobKind,
+    total: usize,
+    failures: Vec<BatchFailure>,
+    showing_details: bool,
+}
+
+impl PendingBatchFailures {
+    fn summary(&self) -> String {
+        let counts = ErrorClass::ALL
+            .iter()
+            .filter_map(|class| {
+                let count = self
+                    .failures
+                    .iter()
+                    .filter(|failure| failure.class == *class)
+                    .count();
+                (count > 0).then(|| format!("{}: {count}", class.label()))
+            })
+            .collect::<Vec<_>>();
+        format!(
+            "batch {} finished: total {} / failed {}\n\n{}",
+            operation_name(self.kind),
+            self.total,
+            self.failures.len(),
+            counts.join(", ")
+        )
+    }
+
+    fn details(&self) -> String {
+        let mut lines = self
+            .failures
+            .iter()
+            .take(BATCH_FAILURE_DETAIL_LINES)
+            .map(|failure| {
+                clip_text(
+                    &format!("[{}] {}", failure.class.label(), failure.message),
+                    BATCH_FAILURE_DETAIL_WIDTH,
+                )
+            })
+            .collect::<Vec<_>>();
+        let hidden = self
+            .failures
+            .len()
+            .saturating_sub(BATCH_FAILURE_DETAIL_LINES);
+        if hidden > 0 {
+            lines.push(format!("... and {hidden} more (see job manager)"));
+        }
+        lines.join("\n")
+    }
 }
 
 struct PendingConflict {
@@ -223,6 +512,11 @@ struct PendingConflict {
     ready: Vec<BatchOpItem>,
     skipped: usize,
     apply_all: Option<ConflictPolicy>,
+    copy_options: CopyOptions,
+    /// The current item waits for its dive-into check to come back.
+    awaiting_dive: bool,
+    /// Item found not to be a directory pair; it meets a normal conflict.
+    no_dive: Option<usize>,
 }
 
 #[derive(Clone, Copy)]
@@ -268,6 +562,7 @@ impl App {
             next_job_id: 1,
             next_batch_id: 1,
             next_find_id: 1,
+            next_listing_id: 1,
             pending_confirmation: None,
             pending_rename: None,
             pending_mask: None,
@@ -277,16 +572,34 @@ impl App {
             pending_conflict: None,
             pending_find: None,
             pending_editor_choice: None,
+            pending_host_key: None,
+            pending_batch_failures: None,
+            pending_trash_purge: false,
             pending_viewer_search: false,
+            pending_batch_rename: None,
+            pending_editor_rename: None,
+            pending_symlink_edit: None,
+            pending_pick: None,
+            pending_hotlist_add: None,
             batch_progress: HashMap::new(),
+            job_controls: HashMap::new(),
+            job_requests: HashMap::new(),
             left_active_find_id: None,
             right_active_find_id: None,
+            left_listing: None,
+            right_listing: None,
+            left_watch: None,
+            right_watch: None,
+            left_parked: Vec::new(),
+            right_parked: Vec::new(),
             force_full_redraw: false,
             last_left_local_cwd: normalized_cwd.clone(),
             last_right_local_cwd: normalized_cwd,
             input_mode: None,
+            pending_ctrl_x: false,
         };
 
+        app.load_history();
         let _ = app.reload_panel(PanelId::Left, true)?;
         let _ = app.reload_panel(PanelId::Right, false)?;
         Ok(app)
@@ -317,6 +630,10 @@ impl App {
                     return redraw;
                 }
 
+                if let Some(redraw) = self.handle_properties_input(&key) {
+                    return redraw;
+                }
+
                 if let Some(redraw) = self.handle_top_menu_input(&key) {
                     return redraw;
                 }
@@ -328,6 +645,13 @@ impl App {
                     return false;
                 }
 
+                if self.state.screen_mode == ScreenMode::Jobs {
+                    if let Some(cmd) = map_job_manager_key_to_command(&key) {
+                        return self.apply_command(cmd);
+                    }
+                    return false;
+                }
+
                 if let Some(redraw) = self.handle_search_input(&key) {
                     return redraw;
                 }
@@ -340,19 +664,37 @@ impl App {
                     return redraw;
                 }
 
+                if let Some(redraw) = self.handle_batch_progress_input(&key) {
+                    return redraw;
+                }
+
+                if let Some(redraw) = self.handle_ctrl_x_input(&key) {
+                    return redraw;
+                }
+
                 if let Some(cmd) = map_key_to_command(&key) {
                     self.apply_command(cmd)
                 } else {
                     false
                 }
             }
-            Event::Tick => false,
+            Event::Tick => {
+                prune_idle_sftp_sessions();
+                let loading = self.state.left_panel.loading || self.state.right_panel.loading;
+                if loading {
+                    self.state.spinner_frame = self.state.spinner_frame.wrapping_add(1);
+                }
+                loading
+            }
             Event::Resize { width, height } => {
                 self.state.terminal_size = TerminalSize { width, height };
                 true
             }
             Event::Job(update) => self.handle_job_update(update),
             Event::Find(update) => self.handle_find_update(update),
+            Event::Listing(update) => self.handle_listing_update(update),
+            Event::Dive(update) => self.handle_dive_update(update),
+            Event::DirChanged { panel_id, dir } => self.handle_dir_changed(panel_id, dir),
         }
     }
 
@@ -360,6 +702,7 @@ impl App {
         let command_result: Result<bool> = match command {
             Command::Quit => {
                 self.running = false;
+                self.save_history();
                 Ok(false)
             }
             Command::OpenTopMenu => self.open_top_menu(),
@@ -368,18 +711,8 @@ impl App {
                     PanelId::Left => PanelId::Right,
                     PanelId::Right => PanelId::Left,
                 };
-                self.state.top_menu.open = false;
                 self.input_mode = None;
-                self.pending_confirmation = None;
-                self.pending_rename = None;
-                self.pending_mask = None;
-                self.pending_mkdir = None;
-                self.pending_sftp_connect = None;
-                self.pending_bookmark = None;
-                self.pending_conflict = None;
-                self.pending_find = None;
-                self.pending_editor_choice = None;
-                self.pending_viewer_search = false;
+                self.clear_pending_prompts();
                 self.state.dialog = None;
                 Ok(true)
             }
@@ -423,6 +756,31 @@ impl App {
             Command::ViewerSearchNext => self.viewer_search_next(),
             Command::ViewerSearchPrev => self.viewer_search_prev(),
             Command::OpenEditor => self.open_editor(),
+            Command::OpenJobManager => self.open_job_manager(),
+            Command::OpenTrash => self.open_trash_view(),
+            Command::OpenProperties => self.open_properties(),
+            Command::NewTab => self.open_tab(false),
+            Command::DuplicateTab => self.open_tab(true),
+            Command::CloseTab => self.close_tab(),
+            Command::NextTab => self.cycle_tab(1),
+            Command::PrevTab => self.cycle_tab(-1),
+            Command::SwitchTab(index) => self.switch_tab(index),
+            Command::HistoryBack => self.step_history(false),
+            Command::HistoryForward => self.step_history(true),
+            Command::OpenHistory => self.open_history_popup(),
+            Command::OpenHotlist => self.open_hotlist_popup(),
+            Command::AddToHotlist => self.open_hotlist_add(),
+            Command::EmptyTrash => self.confirm_empty_trash(),
+            Command::PurgeTrash => self.open_trash_purge_prompt(),
+            Command::CloseJobManager => self.close_job_manager(),
+            Command::JobManagerUp => self.move_job_manager_selection(-1),
+            Command::JobManagerDown => self.move_job_manager_selection(1),
+            Command::JobManagerTop => self.move_job_manager_selection(isize::MIN),
+            Command::JobManagerBottom => self.move_job_manager_selection(isize::MAX),
+            Command::JobManagerCancel => self.cancel_selected_job(),
+            Command::JobManagerRetry => self.retry_selected_job(),
+            Command::JobManagerRetryAllFailed => self.retry_failed_jobs(),
+            Command::JobManagerClearFinished => self.clear_finished_jobs(),
             Command::SelectRangeUp => self.select_range_up(),
             Command::SelectRangeDown => self.select_range_down(),
             Command::Refresh => self.refresh_all(),
@@ -431,7 +789,14 @@ impl App {
             Command::GoHome => self.go_to_home(),
             Command::Copy => self.queue_copy(),
             Command::Move => self.queue_move(),
+            Command::Rename => self.queue_rename(),
+            Command::BatchRename => self.open_batch_rename(),
+            Command::EditorRename => self.open_editor_rename(),
+            Command::Symlink => self.queue_link(JobKind::Symlink),
+            Command::Hardlink => self.queue_link(JobKind::Hardlink),
+            Command::EditSymlink => self.open_symlink_edit(),
             Command::Delete => self.queue_delete(),
+            Command::DeletePermanently => self.queue_delete_permanently(),
             Command::Mkdir => self.queue_mkdir(),
             Command::ToggleSort => self.toggle_sort(),
             Command::StartSearch => self.start_search(),
@@ -451,13 +816,41 @@ impl App {
     }
 
     fn handle_job_update(&mut self, update: JobUpdate) -> bool {
+        if update.status.is_terminal() {
+            self.job_controls.remove(&update.id);
+        }
+        // A running job that reports an error class failed on one of its
+        // items (recursive chattr) and keeps going.
+        let item_failure = (update.status == JobStatus::Running && update.error_class.is_some())
+            .then(|| update.message.clone())
+            .flatten();
+        if let Some(message) = item_failure {
+            self.push_log(message);
+        }
+        if let Some(warning) = update.warning.clone() {
+            self.push_log(warning);
+        }
+
         if let Some(batch_id) = update.batch_id {
             return self.handle_batch_job_update(batch_id, update);
         }
 
         if self.batch_progress.is_empty() {
+            let paused = update.status == JobStatus::Paused;
+            // Pause/resume notices carry no counters; keep the last ones shown.
+            let keeps_counters = update.batch_total.is_none()
+                && self
+                    .state
+                    .batch_progress
+                    .as_ref()
+                    .is_some_and(|progress| progress.batch_id == update.id);
             match update.status {
-                JobStatus::Running => {
+                JobStatus::Running | JobStatus::Paused if keeps_counters => {
+                    if let Some(progress) = self.state.batch_progress.as_mut() {
+                        progress.paused = paused;
+                    }
+                }
+                JobStatus::Running | JobStatus::Paused => {
                     let total = update.batch_total.unwrap_or(1).max(1);
                     let completed = update.batch_completed.unwrap_or(0).min(total);
                     let current_file = update
@@ -471,9 +864,12 @@ impl App {
                         completed,
                         total,
                         failed: 0,
+                        bytes: update.bytes,
+                        entries: None,
+                        paused,
                     });
                 }
-                JobStatus::Done | JobStatus::Failed => {
+                JobStatus::Done | JobStatus::Failed | JobStatus::Canceled => {
                     if self
                         .state
                         .batch_progress
@@ -487,7 +883,10 @@ impl App {
             }
         }
 
-        let needs_reload = update.status == JobStatus::Done;
+        let needs_reload = matches!(update.status, JobStatus::Done | JobStatus::Canceled);
+        let renamed_to = (update.kind == JobKind::Rename && update.status == JobStatus::Done)
+            .then(|| update.destination.clone())
+            .flatten();
         let has_failed = update.status == JobStatus::Failed;
         let next_status_line = match update.status {
             JobStatus::Failed => update
@@ -498,7 +897,11 @@ impl App {
                 .message
                 .clone()
                 .unwrap_or_else(|| "job finished".to_string()),
-            JobStatus::Queued | JobStatus::Running => "job updated".to_string(),
+            JobStatus::Canceled => update
+                .message
+                .clone()
+                .unwrap_or_else(|| "job canceled".to_string()),
+            JobStatus::Queued | JobStatus::Running | JobStatus::Paused => "job updated".to_string(),
         };
 
         self.upsert_job(update);
@@ -517,6 +920,9 @@ impl App {
                 self.show_alert(format!("refresh right failed: {err}"));
             }
         }
+        if let Some(path) = renamed_to {
+            self.select_when_listed(self.state.active_panel, path);
+        }
 
         true
     }
@@ -628,7 +1034,7 @@ impl App {
 
     fn handle_batch_job_update(&mut self, batch_id: u64, mut update: JobUpdate) -> bool {
         let has_failed = update.status == JobStatus::Failed;
-        let is_terminal = matches!(update.status, JobStatus::Done | JobStatus::Failed);
+        let is_terminal = update.status.is_terminal();
         let has_running = update.status == JobStatus::Running;
         let message = update
             .message
@@ -636,7 +1042,7 @@ impl App {
             .unwrap_or_else(|| "batch job updated".to_string());
 
         let mut should_log_failure = false;
-        let mut finished: Option<(JobKind, usize, usize)> = None;
+        let mut finished = false;
 
         if let Some(progress) = self.batch_progress.get_mut(&batch_id) {
             let item_label = update
@@ -647,32 +1053,59 @@ impl App {
                 progress.current_file = item_label.clone();
             }
 
+            if let Some(bytes) = update.bytes {
+                let estimate = progress
+                    .job_bytes
+                    .get(&update.id)
+                    .map_or(0, |job| job.total);
+                progress.job_bytes.insert(
+                    update.id,
+                    ByteProgress {
+                        total: bytes.total.max(estimate),
+                        ..bytes
+                    },
+                );
+                progress.current_bytes = bytes;
+            }
+
+            if let (JobKind::Chattr, Some(done), Some(total)) =
+                (progress.kind, update.batch_completed, update.batch_total)
+            {
+                progress.job_entries.insert(update.id, (done, total));
+            }
+
             if is_terminal {
                 progress.completed = progress.completed.saturating_add(1);
                 if has_failed {
                     progress.failed = progress.failed.saturating_add(1);
                 }
+                if update.status == JobStatus::Canceled {
+                    progress.canceled = progress.canceled.saturating_add(1);
+                }
+                if let Some(job) = progress.job_bytes.get_mut(&update.id) {
+                    job.done = job.total;
+                    job.bytes_per_sec = 0;
+                }
+                if let Some((done, total)) = progress.job_entries.get_mut(&update.id) {
+                    *done = *total;
+                }
             }
 
             if has_failed {
                 should_log_failure = true;
+                progress.failures.push(BatchFailure {
+                    job_id: update.id,
+                    class: update.error_class.unwrap_or(ErrorClass::Io),
+                    message: message.clone(),
+                });
             }
 
             update.current_item = Some(progress.current_file.clone());
             update.batch_completed = Some(progress.completed);
             update.batch_total = Some(progress.total);
-            self.state.batch_progress = Some(BatchProgressState {
-                batch_id,
-                operation: progress.kind,
-                current_file: progress.current_file.clone(),
-                completed: progress.completed,
-                total: progress.total,
-                failed: progress.failed,
-            });
+            self.state.batch_progress = Some(progress.snapshot(batch_id));
 
-            if progress.completed >= progress.total {
-                finished = Some((progress.kind, progress.total, progress.failed));
-            }
+            finished = progress.completed >= progress.total;
         }
 
         self.upsert_job(update);
@@ -681,21 +1114,34 @@ impl App {
             self.push_log(message);
         }
 
-        if let Some((kind, total, failed)) = finished {
-            self.batch_progress.remove(&batch_id);
+        let finished = finished
+            .then(|| self.batch_progress.remove(&batch_id))
+            .flatten();
+        if let Some(progress) = finished {
             self.sync_visible_batch_progress(None);
-            if failed > 0 {
-                self.show_alert(format!(
-                    "batch {} finished: total {} / failed {}",
-                    operation_name(kind),
-                    total,
-                    failed
+            if progress.canceled > 0 {
+                self.push_log(format!(
+                    "batch {} aborted: {} of {} item(s) canceled, failed {}",
+                    operation_name(progress.kind),
+                    progress.canceled,
+                    progress.total,
+                    progress.failed
                 ));
+            } else if progress.failed > 0 {
+                let pending = PendingBatchFailures {
+                    kind: progress.kind,
+                    total: progress.total,
+                    failures: progress.failures,
+                    showing_details: false,
+                };
+                self.clear_pending_prompts();
+                self.push_log(pending.summary());
+                self.show_batch_failures(pending);
             } else {
                 self.push_log(format!(
                     "batch {} finished: {} item(s)",
-                    operation_name(kind),
-                    total
+                    operation_name(progress.kind),
+                    progress.total
                 ));
             }
 
@@ -718,6 +1164,10 @@ impl App {
             job.current_item = update.current_item.clone();
             job.batch_completed = update.batch_completed;
             job.batch_total = update.batch_total;
+            job.error_class = update.error_class;
+            if update.bytes.is_some() {
+                job.bytes = update.bytes;
+            }
             if update.destination.is_some() {
                 job.destination = update.destination.clone();
             }
@@ -765,10 +1215,26 @@ impl App {
         self.backend(self.state.active_panel)
     }
 
-    fn inactive_backend(&self) -> &dyn FsBackend {
+    /// Handle on a panel's backend for work that runs on another thread.
+    fn shared_backend(&self, id: PanelId) -> Arc<dyn FsBackend> {
+        match id {
+            PanelId::Left => Arc::clone(&self.left_backend),
+            PanelId::Right => Arc::clone(&self.right_backend),
+        }
+    }
+
+    /// Backend that receives the results of `kind`: the other panel for
+    /// copy/move, the active one for renames, the local filesystem for trash
+    /// restores.
+    fn destination_backend(&self, kind: JobKind) -> Arc<dyn FsBackend> {
+        match kind {
+            JobKind::Restore => return backend_from_spec(&BackendSpec::Local),
+            JobKind::Rename => return backend_from_spec(self.active_backend_spec()),
+            _ => {}
+        }
         match self.state.active_panel {
-            PanelId::Left => self.right_backend.as_ref(),
-            PanelId::Right => self.left_backend.as_ref(),
+            PanelId::Left => Arc::clone(&self.right_backend),
+            PanelId::Right => Arc::clone(&self.left_backend),
         }
     }
 
@@ -797,11 +1263,13 @@ impl App {
             PanelId::Left => {
                 self.left_backend = backend;
                 self.left_backend_spec = spec;
+                self.left_watch = None;
                 self.state.left_panel.backend_label = label;
             }
             PanelId::Right => {
                 self.right_backend = backend;
                 self.right_backend_spec = spec;
+                self.right_watch = None;
                 self.state.right_panel.backend_label = label;
             }
         }
@@ -835,11 +1303,71 @@ impl App {
         }
     }
 
+    fn listing_mut(&mut self, panel_id: PanelId) -> &mut Option<ActiveListing> {
+        match panel_id {
+            PanelId::Left => &mut self.left_listing,
+            PanelId::Right => &mut self.right_listing,
+        }
+    }
+
+    /// Stops the panel's running listing, if any, so its result is dropped.
+    fn cancel_listing(&mut self, panel_id: PanelId) -> Option<ActiveListing> {
+        let listing = self.listing_mut(panel_id).take()?;
+        listing.cancel.store(true, Ordering::Relaxed);
+        self.panel_mut(panel_id).loading = false;
+        Some(listing)
+    }
+
+    fn watch_mut(&mut self, panel_id: PanelId) -> &mut Option<DirWatch> {
+        match panel_id {
+            PanelId::Left => &mut self.left_watch,
+            PanelId::Right => &mut self.right_watch,
+        }
+    }
+
+    /// Starts watching `dir` for the panel unless it is already watched.
+    /// Archives never change under the panel, so they are not watched.
+    fn watch_panel(&mut self, panel_id: PanelId, dir: &Path) {
+        let watched = self
+            .watch_mut(panel_id)
+            .as_ref()
+            .is_some_and(|watch| watch.dir == dir);
+        if watched {
+            return;
+        }
+        let backend = self.shared_backend(panel_id);
+        let dir = dir.to_path_buf();
+        let event_tx = self.event_tx.clone();
+        let watch = match self.backend_spec(panel_id) {
+            BackendSpec::Local => Some(DirWatch::local(panel_id, dir, backend, event_tx)),
+            BackendSpec::Sftp(_) => Some(DirWatch::polling(panel_id, dir, backend, event_tx)),
+            BackendSpec::Archive(_) => None,
+        };
+        *self.watch_mut(panel_id) = watch;
+    }
+
+    /// Moves the cursor to `path` once the panel's listing arrives, or right
+    /// away when the panel is not loading.
+    fn select_when_listed(&mut self, panel_id: PanelId, path: PathBuf) {
+        if let Some(listing) = self.listing_mut(panel_id).as_mut() {
+            listing.select = Some(path);
+        } else {
+            self.panel_mut(panel_id).select_path(&path);
+        }
+    }
+
     fn path_exists_on_backend(&self, backend: &dyn FsBackend, path: &Path) -> bool {
-        backend.stat_entry(path).is_ok()
+        backend.entry_exists(path)
     }
 
     fn reload_panel(&mut self, panel_id: PanelId, update_status: bool) -> Result<bool> {
+        let previous = self.cancel_listing(panel_id);
+        if self.panel(panel_id).trash_view.is_some() || self.panel(panel_id).find_view.is_some() {
+            *self.watch_mut(panel_id) = None;
+        }
+        if self.panel(panel_id).trash_view.is_some() {
+            return self.load_trash_view(panel_id, update_status);
+        }
         let (cwd, sort_mode, show_hidden, find_view) = {
             let panel = self.panel_mut(panel_id);
             (
@@ -864,28 +1392,124 @@ impl App {
             }
             return Ok(true);
         }
-        match self
-            .backend(panel_id)
-            .list_dir(&cwd, sort_mode, show_hidden)
-        {
-            Ok(entries) => {
-                let panel = self.panel_mut(panel_id);
+
+        // A reload of the same directory keeps the old entries on screen
+        // until the new ones arrive; a new directory starts out empty.
+        let panel = self.panel_mut(panel_id);
+        if !panel.lists_directory(&cwd) {
+            panel.set_entries(Vec::new());
+        }
+        panel.error_message = None;
+        panel.loading = true;
+        let select = previous
+            .filter(|listing| listing.cwd == cwd)
+            .and_then(|listing| listing.select);
+        let backend = self.shared_backend(panel_id);
+        let id = self.next_listing_id;
+        self.next_listing_id = self.next_listing_id.saturating_add(1);
+        let cancel = Arc::new(AtomicBool::new(false));
+        spawn_listing(
+            ListingRequest {
+                id,
+                panel_id,
+                backend,
+                cwd: cwd.clone(),
+                sort_mode,
+                show_hidden,
+                cancel: Arc::clone(&cancel),
+            },
+            self.event_tx.clone(),
+        );
+        if update_status {
+            self.state.status_line = format!("Loading {}", cwd.display());
+        }
+        *self.listing_mut(panel_id) = Some(ActiveListing {
+            id,
+            cwd,
+            cancel,
+            select,
+            history_index: None,
+            update_status,
+            rerun: false,
+        });
+        Ok(true)
+    }
+
+    fn handle_listing_update(&mut self, update: ListingUpdate) -> bool {
+        let is_current = self
+            .listing_mut(update.panel_id)
+            .as_ref()
+            .is_some_and(|listing| listing.id == update.id);
+        if !is_current {
+            return false;
+        }
+        let Some(listing) = self.cancel_listing(update.panel_id) else {
+            return false;
+        };
+        // A find or trash view opened meanwhile owns the entries now.
+        let panel = self.panel(update.panel_id);
+        if panel.find_view.is_some() || panel.trash_view.is_some() || panel.cwd != update.cwd {
+            return true;
+        }
+        match update.result {
+            Ok(DirListing { cwd, entries }) => {
+                let panel = self.panel_mut(update.panel_id);
+                let select = listing
+                    .select
+                    .or_else(|| panel.selected_entry().map(|entry| entry.path.clone()));
+                panel.cwd = cwd.clone();
                 panel.set_entries(entries);
-                panel.error_message = None;
-                if update_status {
+                if let Some(path) = select {
+                    panel.select_path(&path);
+                }
+                if listing.update_status {
                     self.state.status_line = format!("Loaded {}", cwd.display());
                 }
-                Ok(true)
+                let visit = HistoryEntry {
+                    backend: self.backend_spec(update.panel_id).clone(),
+                    path: cwd.clone(),
+                };
+                let history = &mut self.panel_mut(update.panel_id).history;
+                if let Some(index) = listing
+                    .history_index
+                    .filter(|index| *index < history.entries.len())
+                {
+                    history.position = index;
+                }
+                history.record(visit);
+                self.watch_panel(update.panel_id, &cwd);
+                if listing.rerun {
+                    return self.reload_panel(update.panel_id, false).unwrap_or(true);
+                }
             }
             Err(err) => {
-                let panel = self.panel_mut(panel_id);
-                panel.all_entries.clear();
-                panel.entries.clear();
+                let panel = self.panel_mut(update.panel_id);
+                panel.set_entries(Vec::new());
                 panel.selected_index = 0;
-                panel.error_message = Some(err.to_string());
-                Err(err.into())
+                panel.error_message = Some(err.clone());
+                *self.watch_mut(update.panel_id) = None;
+                self.show_alert(err);
             }
         }
+        true
+    }
+
+    /// Reloads a panel whose directory changed on disk. The reload keeps the
+    /// cursor on the same entry and keeps marks on entries that still exist.
+    fn handle_dir_changed(&mut self, panel_id: PanelId, dir: PathBuf) -> bool {
+        let is_watched = self
+            .watch_mut(panel_id)
+            .as_ref()
+            .is_some_and(|watch| watch.dir == dir);
+        let panel = self.panel(panel_id);
+        if !is_watched || panel.cwd != dir || panel.find_view.is_some() {
+            return false;
+        }
+        if let Some(listing) = self.listing_mut(panel_id).as_mut() {
+            listing.rerun = true;
+            return false;
+        }
+        self.reload_panel(panel_id, false).unwrap_or(false)
     }
 
     fn reload_theme(&mut self) -> Result<bool> {
@@ -914,6 +1538,14 @@ impl App {
             return self.open_find_result_entry(panel_id, entry);
         }
 
+        if self.panel(panel_id).trash_view.is_some() {
+            if entry.is_virtual && entry.name == ".." {
+                return self.exit_trash_view(panel_id);
+            }
+            self.push_log("trash: F6 restores, F8 deletes permanently");
+            return Ok(true);
+        }
+
         if entry.is_virtual
             && entry.name == ".."
             && matches!(self.active_backend_spec(), BackendSpec::Archive(_))
@@ -930,16 +1562,30 @@ impl App {
             }
         }
 
-        if entry.entry_type != FsEntryType::Directory {
-            self.push_log(format!("{} is not a directory", entry.name));
+        let is_directory = match entry.entry_type {
+            FsEntryType::Directory => true,
+            // A link is followed when what it points to is a directory.
+            FsEntryType::Symlink => {
+                !entry.link_broken
+                    && self
+                        .active_backend()
+                        .stat_entry(&entry.path)
+                        .is_ok_and(|target| target.entry_type == FsEntryType::Directory)
+            }
+            _ => false,
+        };
+        if !is_directory {
+            self.push_log(if entry.link_broken {
+                format!("{} is a broken symlink", entry.name)
+            } else {
+                format!("{} is not a directory", entry.name)
+            });
             return Ok(true);
         }
 
-        let next_path = self
-            .active_backend()
-            .normalize_existing_path("open", &entry.path)?;
+        // The listing resolves the path off the UI thread.
         let panel = self.active_panel_mut();
-        panel.cwd = next_path;
+        panel.cwd = entry.path.clone();
         panel.selected_index = 0;
         panel.clear_selection_anchor();
         self.reload_panel(self.state.active_panel, true)
@@ -950,6 +1596,9 @@ impl App {
         if self.panel(panel_id).find_view.is_some() {
             return self.exit_find_view(panel_id);
         }
+        if self.panel(panel_id).trash_view.is_some() {
+            return self.exit_trash_view(panel_id);
+        }
         let current = self.active_panel().cwd.clone();
         if matches!(self.active_backend_spec(), BackendSpec::Archive(_))
             && current == Path::new("/")
@@ -961,74 +1610,695 @@ impl App {
             return Ok(false);
         };
 
-        let normalized = self
-            .active_backend()
-            .normalize_existing_path("parent", parent)?;
+        let parent = parent.to_path_buf();
         let panel = self.active_panel_mut();
-        panel.cwd = normalized;
+        panel.cwd = parent;
         panel.find_view = None;
+        panel.trash_view = None;
         panel.selected_index = 0;
         panel.clear_selection_anchor();
         self.reload_panel(self.state.active_panel, true)
     }
 
     fn go_to_home(&mut self) -> Result<bool> {
-        let normalized = match self.active_backend_spec() {
-            BackendSpec::Local => {
-                let home = env::var_os("HOME")
-                    .map(PathBuf::from)
-                    .ok_or_else(|| anyhow::anyhow!("HOME environment variable is not set"))?;
-                self.active_backend()
-                    .normalize_existing_path("home", &home)?
-            }
-            BackendSpec::Sftp(info) => self
-                .active_backend()
-                .normalize_existing_path("home", &info.root_path)?,
-            BackendSpec::Archive(_) => self
-                .active_backend()
-                .normalize_existing_path("home", Path::new("/"))?,
+        let home = match self.active_backend_spec() {
+            BackendSpec::Local => env::var_os("HOME")
+                .map(PathBuf::from)
+                .ok_or_else(|| anyhow::anyhow!("HOME environment variable is not set"))?,
+            BackendSpec::Sftp(info) => info.root_path.clone(),
+            BackendSpec::Archive(_) => PathBuf::from("/"),
         };
         let panel = self.active_panel_mut();
-        panel.cwd = normalized;
+        panel.cwd = home;
+        panel.trash_view = None;
         panel.selected_index = 0;
         panel.clear_selection_anchor();
         self.reload_panel(self.state.active_panel, true)
     }
 
-    fn open_viewer(&mut self) -> Result<bool> {
-        let entry = self.selected_action_target_entry()?;
-        if entry.entry_type == FsEntryType::Directory {
-            return Err(anyhow::anyhow!(
-                "viewer is available for files only (selected '{}')",
-                entry.name
-            ));
+    fn tabs_mut(&mut self, panel_id: PanelId) -> &mut PanelTabs {
+        match panel_id {
+            PanelId::Left => &mut self.state.left_tabs,
+            PanelId::Right => &mut self.state.right_tabs,
         }
+    }
 
-        let path = self
-            .active_backend()
-            .normalize_existing_path("viewer", &entry.path)?;
-        let (bytes, truncated) = self
-            .active_backend()
-            .read_file_preview(path.as_path(), VIEWER_PREVIEW_LIMIT_BYTES)?;
-        let viewer_state = load_viewer_state_from_preview(
-            path.clone(),
-            entry.name,
-            entry.size_bytes,
-            bytes,
-            truncated,
-        );
+    fn parked_mut(&mut self, panel_id: PanelId) -> &mut Vec<ParkedBackend> {
+        match panel_id {
+            PanelId::Left => &mut self.left_parked,
+            PanelId::Right => &mut self.right_parked,
+        }
+    }
+
+    /// Backend of the shown tab, as it would be kept while the tab is parked.
+    fn shown_backend(&self, panel_id: PanelId) -> ParkedBackend {
+        let backend = match panel_id {
+            PanelId::Left => Arc::clone(&self.left_backend),
+            PanelId::Right => Arc::clone(&self.right_backend),
+        };
+        ParkedBackend {
+            backend,
+            spec: self.backend_spec(panel_id).clone(),
+            last_local_cwd: self.last_local_cwd(panel_id),
+        }
+    }
+
+    /// Stops whatever runs for the shown tab (listing, watch, find) so that
+    /// nothing lands in the tab that replaces it.
+    fn quiesce_shown_tab(&mut self, panel_id: PanelId) {
+        self.cancel_listing(panel_id);
+        *self.watch_mut(panel_id) = None;
+        if let Some(find_id) = self.active_find_id(panel_id) {
+            let _ = cancel_running_find(find_id);
+            self.set_active_find_id(panel_id, None);
+        }
+        if self
+            .state
+            .find_progress
+            .as_ref()
+            .is_some_and(|progress| progress.panel_id == panel_id)
+        {
+            self.state.find_progress = None;
+        }
+    }
+
+    /// Shows `panel` with `parked` as its backend and reloads it.
+    fn show_tab(
+        &mut self,
+        panel_id: PanelId,
+        panel: PanelState,
+        parked: ParkedBackend,
+    ) -> Result<bool> {
+        match panel_id {
+            PanelId::Left => {
+                self.left_backend = parked.backend;
+                self.left_backend_spec = parked.spec;
+            }
+            PanelId::Right => {
+                self.right_backend = parked.backend;
+                self.right_backend_spec = parked.spec;
+            }
+        }
+        self.set_last_local_cwd(panel_id, parked.last_local_cwd);
+        *self.panel_mut(panel_id) = panel;
+        self.reload_panel(panel_id, true)
+    }
+
+    /// Opens a tab next to the current one on the same backend and directory:
+    /// a fresh one, or with `duplicate` a copy with the same sort, filter,
+    /// marks and cursor.
+    fn open_tab(&mut self, duplicate: bool) -> Result<bool> {
+        let panel_id = self.state.active_panel;
+        self.quiesce_shown_tab(panel_id);
+        let current = self.panel(panel_id).clone();
+        let tab = if duplicate {
+            current.clone()
+        } else {
+            let cwd = current
+                .trash_view
+                .as_ref()
+                .map_or_else(|| current.cwd.clone(), |trash| trash.return_cwd.clone());
+            PanelState {
+                backend_label: current.backend_label.clone(),
+                ..PanelState::new(cwd)
+            }
+        };
+        let parked = self.shown_backend(panel_id);
+        let kept = self.shown_backend(panel_id);
+        let tabs = self.tabs_mut(panel_id);
+        let position = tabs.active;
+        tabs.parked.insert(position, current);
+        tabs.active = position + 1;
+        self.parked_mut(panel_id).insert(position, kept);
+        let count = self.tabs_mut(panel_id).count();
+        self.push_log(format!(
+            "{} tab {}/{count}",
+            if duplicate { "duplicated" } else { "new" },
+            position + 2
+        ));
+        self.show_tab(panel_id, tab, parked)
+    }
+
+    fn close_tab(&mut self) -> Result<bool> {
+        let panel_id = self.state.active_panel;
+        let tabs = self.tabs_mut(panel_id);
+        if tabs.parked.is_empty() {
+            self.push_log("the last tab of a panel cannot be closed");
+            return Ok(true);
+        }
+        // The tab to the right takes over; closing the last one moves left.
+        let next = tabs.active.min(tabs.parked.len() - 1);
+        let panel = tabs.parked.remove(next);
+        tabs.active = next;
+        let parked = self.parked_mut(panel_id).remove(next);
+        self.quiesce_shown_tab(panel_id);
+        self.show_tab(panel_id, panel, parked)
+    }
+
+    fn cycle_tab(&mut self, step: isize) -> Result<bool> {
+        let tabs = self.tabs_mut(self.state.active_panel);
+        let count = tabs.count() as isize;
+        let next = (tabs.active as isize + step).rem_euclid(count);
+        self.switch_tab(next as usize)
+    }
+
+    /// Shows the tab at strip position `index` on the active side.
+    fn switch_tab(&mut self, index: usize) -> Result<bool> {
+        let panel_id = self.state.active_panel;
+        let tabs = self.tabs_mut(panel_id);
+        if index == tabs.active || index >= tabs.count() {
+            return Ok(false);
+        }
+        self.quiesce_shown_tab(panel_id);
+        let current = self.panel(panel_id).clone();
+        let kept = self.shown_backend(panel_id);
+        let tabs = self.tabs_mut(panel_id);
+        // With the current tab parked back in place, the strip is exactly
+        // `parked`, so `index` addresses it directly.
+        let position = tabs.active;
+        tabs.parked.insert(position, current);
+        let panel = tabs.parked.remove(index);
+        tabs.active = index;
+        let parked_backends = self.parked_mut(panel_id);
+        parked_backends.insert(position, kept);
+        let parked = parked_backends.remove(index);
+        self.show_tab(panel_id, panel, parked)
+    }
+
+    /// Goes one entry back or forward in the active panel's history.
+    fn step_history(&mut self, forward: bool) -> Result<bool> {
+        let panel_id = self.state.active_panel;
+        let history = &self.panel(panel_id).history;
+        let target = if forward {
+            history.forward()
+        } else {
+            history.back()
+        };
+        let Some(index) = target else {
+            self.push_log(if forward {
+                "history: nothing to go forward to"
+            } else {
+                "history: nothing to go back to"
+            });
+            return Ok(false);
+        };
+        self.show_history_entry(panel_id, index)
+    }
+
+    /// Shows history entry `index`. The entry becomes the current one when
+    /// its listing arrives, so a failed connect or listing leaves the
+    /// history where it was.
+    fn show_history_entry(&mut self, panel_id: PanelId, index: usize) -> Result<bool> {
+        let Some(entry) = self.panel(panel_id).history.entries.get(index).cloned() else {
+            return Ok(false);
+        };
+        let redraw = self.show_location(panel_id, entry)?;
+        if let Some(listing) = self.listing_mut(panel_id).as_mut() {
+            listing.history_index = Some(index);
+        }
+        Ok(redraw)
+    }
+
+    /// Shows `location.path` on the location's backend, connecting the panel
+    /// to that backend first when it is on another one.
+    fn show_location(&mut self, panel_id: PanelId, location: HistoryEntry) -> Result<bool> {
+        if !same_backend(self.backend_spec(panel_id), &location.backend) {
+            match location.backend {
+                BackendSpec::Local => {
+                    return self.attach_panel_to_local(panel_id, location.path);
+                }
+                BackendSpec::Sftp(conn) => {
+                    let conn = SftpConnectionInfo {
+                        root_path: location.path,
+                        ..conn
+                    };
+                    return self.attach_panel_to_sftp(panel_id, conn);
+                }
+                BackendSpec::Archive(_) => {
+                    if matches!(self.backend_spec(panel_id), BackendSpec::Local) {
+                        self.set_last_local_cwd(panel_id, self.panel(panel_id).cwd.clone());
+                    }
+                    self.set_panel_backend(panel_id, location.backend);
+                }
+            }
+        }
+        self.set_active_find_id(panel_id, None);
+        let panel = self.panel_mut(panel_id);
+        panel.cwd = location.path;
+        panel.find_view = None;
+        panel.trash_view = None;
+        panel.search_query.clear();
+        panel.selected_index = 0;
+        panel.clear_selection_anchor();
+        self.reload_panel(panel_id, true)
+    }
+
+    fn open_history_popup(&mut self) -> Result<bool> {
+        let panel_id = self.state.active_panel;
+        let history = &self.panel(panel_id).history;
+        if history.entries.is_empty() {
+            self.push_log("history: no directories visited yet");
+            return Ok(true);
+        }
+        let targets = (0..history.entries.len()).rev().collect::<Vec<_>>();
+        let lines = targets
+            .iter()
+            .map(|idx| {
+                let marker = if *idx == history.position { '*' } else { ' ' };
+                let label = history_entry_label(&history.entries[*idx]);
+                (format!("{marker} {label}"), false)
+            })
+            .collect();
+        let selected = targets
+            .iter()
+            .position(|idx| *idx == history.position)
+            .unwrap_or(0);
+
+        let mut dialog = form_dialog(
+            "Directory History",
+            "Up/Down picks a directory, Enter goes there.",
+            Vec::new(),
+            DialogTone::Default,
+        );
+        dialog.buttons[0].label = "Go".to_string();
+        dialog.buttons[0].accelerator = Some('g');
+        let mut list = DialogList {
+            title: format!("{} history", panel_name(panel_id)),
+            lines,
+            offset: 0,
+            selected: Some(selected),
+        };
+        list.move_selection(0);
+        dialog.list = Some(list);
 
         self.input_mode = None;
-        self.pending_confirmation = None;
-        self.pending_rename = None;
-        self.pending_mask = None;
-        self.pending_mkdir = None;
-        self.pending_sftp_connect = None;
-        self.pending_bookmark = None;
-        self.pending_conflict = None;
-        self.pending_find = None;
-        self.pending_editor_choice = None;
-        self.pending_viewer_search = false;
+        self.clear_pending_prompts();
+        self.state.dialog = Some(dialog);
+        self.pending_pick = Some(PendingPick::History { panel_id, targets });
+        Ok(true)
+    }
+
+    fn apply_pick(&mut self) -> bool {
+        let pending = self.pending_pick.take();
+        let selected = self
+            .state
+            .dialog
+            .as_ref()
+            .and_then(|dialog| dialog.list.as_ref())
+            .and_then(|list| list.selected);
+        self.state.dialog = None;
+        let (Some(pending), Some(selected)) = (pending, selected) else {
+            return true;
+        };
+        let result = match pending {
+            PendingPick::History { panel_id, targets } => match targets.get(selected) {
+                Some(index) => self.show_history_entry(panel_id, *index),
+                None => Ok(true),
+            },
+            PendingPick::Hotlist {
+                panel_id,
+                entries,
+                targets,
+                ..
+            } => match targets.get(selected) {
+                Some(index) => self.go_to_hotlist_entry(panel_id, &entries[*index]),
+                None => Ok(true),
+            },
+        };
+        if let Err(err) = result {
+            self.show_alert(err.to_string());
+        }
+        true
+    }
+
+    /// Reads the saved histories into the two panels; the current visit is
+    /// recorded after them when the first listing arrives.
+    fn load_history(&mut self) {
+        let Some(path) = history_path() else {
+            return;
+        };
+        let Ok(text) = fs::read_to_string(path) else {
+            return;
+        };
+        let (mut left, mut right) = history_from_toml(&text);
+        // Passwords are not saved; SFTP entries of password bookmarks get the
+        // password back from the bookmark.
+        let bookmarks = load_sftp_bookmarks().unwrap_or_default();
+        let ssh_config = load_ssh_config();
+        for entry in left.entries.iter_mut().chain(right.entries.iter_mut()) {
+            let BackendSpec::Sftp(info) = &mut entry.backend else {
+                continue;
+            };
+            let password = bookmarks
+                .iter()
+                .find(|bookmark| {
+                    let conn = bookmark_connection_info(bookmark, &ssh_config);
+                    conn.host == info.host && conn.user == info.user && conn.port == info.port
+                })
+                .and_then(|bookmark| bookmark.password.clone())
+                .filter(|password| !password.is_empty());
+            if let Some(password) = password {
+                info.auth = SftpAuth::Password(password);
+            }
+        }
+        self.state.left_panel.history = left;
+        self.state.right_panel.history = right;
+    }
+
+    fn save_history(&mut self) {
+        let Some(path) = history_path() else {
+            return;
+        };
+        let text = history_to_toml(
+            &self.state.left_panel.history,
+            &self.state.right_panel.history,
+        );
+        let saved = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&path, text));
+        if let Err(err) = saved {
+            tracing::warn!("failed to save history to {}: {err}", path.display());
+        }
+    }
+
+    fn apply_pick_action(&mut self, button_idx: usize) -> bool {
+        // `None` for the history, otherwise whether a removal is being asked.
+        let hotlist = match &self.pending_pick {
+            Some(PendingPick::Hotlist { removing, .. }) => Some(removing.is_some()),
+            _ => None,
+        };
+        match (hotlist, button_idx) {
+            (Some(true), _) => {
+                let remove = self.dialog_button_role(button_idx) == DialogButtonRole::Primary;
+                self.finish_hotlist_removal(remove)
+            }
+            (_, PICK_GO) => self.apply_pick(),
+            (Some(false), HOTLIST_ADD_CURRENT) => self.open_hotlist_add().unwrap_or_else(|err| {
+                self.show_alert(err.to_string());
+                true
+            }),
+            (Some(false), HOTLIST_REMOVE) => self.confirm_hotlist_removal(),
+            _ => {
+                self.clear_pending_prompts();
+                self.state.dialog = None;
+                true
+            }
+        }
+    }
+
+    fn open_hotlist_popup(&mut self) -> Result<bool> {
+        let panel_id = self.state.active_panel;
+        let entries = load_hotlist()?;
+
+        let mut dialog = input_dialog(
+            "Directory Hotlist",
+            "Type to filter, Up/Down to pick, Ctrl+Up/Down to reorder.",
+            String::new(),
+            DialogTone::Default,
+        );
+        dialog.buttons = vec![
+            DialogButton {
+                label: "Go".to_string(),
+                accelerator: Some('g'),
+                role: DialogButtonRole::Primary,
+            },
+            DialogButton {
+                label: "Add current".to_string(),
+                accelerator: Some('a'),
+                role: DialogButtonRole::Secondary,
+            },
+            DialogButton {
+                label: "Remove".to_string(),
+                accelerator: Some('r'),
+                role: DialogButtonRole::Secondary,
+            },
+            DialogButton {
+                label: "Cancel".to_string(),
+                accelerator: Some('c'),
+                role: DialogButtonRole::Secondary,
+            },
+        ];
+
+        self.input_mode = None;
+        self.clear_pending_prompts();
+        self.state.dialog = Some(dialog);
+        self.pending_pick = Some(PendingPick::Hotlist {
+            panel_id,
+            entries,
+            targets: Vec::new(),
+            removing: None,
+        });
+        self.refresh_hotlist_popup(None);
+        Ok(true)
+    }
+
+    /// Re-filters the hotlist popup by its input, keeping entry `keep` (or the
+    /// selected one) selected while it is still shown.
+    fn refresh_hotlist_popup(&mut self, keep: Option<usize>) {
+        let keep = keep.or_else(|| self.selected_hotlist_entry());
+        let Some(PendingPick::Hotlist {
+            entries, targets, ..
+        }) = self.pending_pick.as_mut()
+        else {
+            return;
+        };
+        let Some(dialog) = self.state.dialog.as_mut() else {
+            return;
+        };
+        *targets = filter_hotlist(entries, dialog.input_text().unwrap_or_default());
+        let selected = keep
+            .and_then(|keep| targets.iter().position(|idx| *idx == keep))
+            .unwrap_or(0);
+        dialog.list = Some(hotlist_dialog_list(entries, targets, selected));
+    }
+
+    /// Index into the hotlist of the entry selected in the popup.
+    fn selected_hotlist_entry(&self) -> Option<usize> {
+        let Some(PendingPick::Hotlist { targets, .. }) = self.pending_pick.as_ref() else {
+            return None;
+        };
+        let list = self.state.dialog.as_ref()?.list.as_ref()?;
+        targets.get(list.selected?).copied()
+    }
+
+    /// Ctrl+Up/Down in the hotlist popup: moves the selected entry, or its
+    /// whole group at the group's edge, and saves the new order.
+    fn reorder_hotlist(&mut self, key: &KeyEvent) -> Option<bool> {
+        let up = match key.code {
+            KeyCode::Up => true,
+            KeyCode::Down => false,
+            _ => return None,
+        };
+        if !key.modifiers.contains(KeyModifiers::CONTROL) {
+            return None;
+        }
+        let selected = self.selected_hotlist_entry()?;
+        let Some(PendingPick::Hotlist { entries, .. }) = self.pending_pick.as_mut() else {
+            return None;
+        };
+        let Some(moved) = move_hotlist_entry(entries, selected, up) else {
+            return Some(false);
+        };
+        let saved = save_hotlist(entries);
+        self.refresh_hotlist_popup(Some(moved));
+        if let (Err(err), Some(dialog)) = (saved, self.state.dialog.as_mut()) {
+            dialog.error = Some(err.to_string());
+        }
+        Some(true)
+    }
+
+    /// Asks before the selected entry is removed; the popup comes back
+    /// either way.
+    fn confirm_hotlist_removal(&mut self) -> bool {
+        let Some(selected) = self.selected_hotlist_entry() else {
+            return false;
+        };
+        let Some(PendingPick::Hotlist {
+            entries, removing, ..
+        }) = self.pending_pick.as_mut()
+        else {
+            return false;
+        };
+        let entry = &entries[selected];
+        let body = format!(
+            "Remove '{}' ({}) from the hotlist?",
+            entry.name,
+            entry.target.label()
+        );
+        *removing = self
+            .state
+            .dialog
+            .replace(confirm_dialog(body))
+            .map(Box::new);
+        true
+    }
+
+    fn finish_hotlist_removal(&mut self, remove: bool) -> bool {
+        let Some(PendingPick::Hotlist { removing, .. }) = self.pending_pick.as_mut() else {
+            return false;
+        };
+        self.state.dialog = removing.take().map(|popup| *popup);
+        if remove {
+            self.remove_hotlist_entry()
+        } else {
+            true
+        }
+    }
+
+    fn remove_hotlist_entry(&mut self) -> bool {
+        let Some(selected) = self.selected_hotlist_entry() else {
+            return false;
+        };
+        let Some(PendingPick::Hotlist { entries, .. }) = self.pending_pick.as_mut() else {
+            return false;
+        };
+        let removed = entries.remove(selected);
+        let saved = save_hotlist(entries);
+        let keep = selected.min(entries.len().saturating_sub(1));
+        self.refresh_hotlist_popup(Some(keep));
+        match saved {
+            Ok(()) => self.push_log(format!("hotlist: removed {}", removed.name)),
+            Err(err) => {
+                if let Some(dialog) = self.state.dialog.as_mut() {
+                    dialog.error = Some(err.to_string());
+                }
+            }
+        }
+        true
+    }
+
+    fn go_to_hotlist_entry(&mut self, panel_id: PanelId, entry: &HotlistEntry) -> Result<bool> {
+        let location = match &entry.target {
+            HotlistTarget::Local(path) => HistoryEntry {
+                backend: BackendSpec::Local,
+                path: path.clone(),
+            },
+            HotlistTarget::Sftp { bookmark, path } => HistoryEntry {
+                backend: BackendSpec::Sftp(bookmark_connection(bookmark)?),
+                path: path.clone(),
+            },
+            HotlistTarget::Archive { archive, path } => HistoryEntry {
+                backend: BackendSpec::Archive(ArchiveConnectionInfo {
+                    archive_path: archive.clone(),
+                }),
+                path: path.clone(),
+            },
+        };
+        let redraw = self.show_location(panel_id, location)?;
+        self.push_log(format!("hotlist: {}", entry.name));
+        Ok(redraw)
+    }
+
+    /// Form naming the active panel's directory for the hotlist. Opened from
+    /// the hotlist popup, the group defaults to the selected entry's.
+    fn open_hotlist_add(&mut self) -> Result<bool> {
+        let panel_id = self.state.active_panel;
+        let panel = self.panel(panel_id);
+        if panel.trash_view.is_some() {
+            bail!("the trash view cannot be added to the hotlist");
+        }
+        let cwd = panel.cwd.clone();
+        let target = match self.backend_spec(panel_id) {
+            BackendSpec::Local => HotlistTarget::Local(cwd.clone()),
+            BackendSpec::Sftp(info) => {
+                let bookmark = bookmark_for_connection(info)?.ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "no SFTP bookmark for {}@{}; add one first (Left/Right -> Bookmark Add)",
+                        info.user,
+                        info.host
+                    )
+                })?;
+                HotlistTarget::Sftp {
+                    bookmark,
+                    path: cwd.clone(),
+                }
+            }
+            BackendSpec::Archive(info) => HotlistTarget::Archive {
+                archive: info.archive_path.clone(),
+                path: cwd.clone(),
+            },
+        };
+        let name = cwd
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| cwd.display().to_string());
+        let group = match (self.selected_hotlist_entry(), &self.pending_pick) {
+            (Some(idx), Some(PendingPick::Hotlist { entries, .. })) => entries[idx].group.clone(),
+            _ => String::new(),
+        };
+
+        let dialog = form_dialog(
+            "Add to Hotlist",
+            &target.label(),
+            vec![
+                DialogField::text("name", "Name", name),
+                DialogField::text("group", "Group", group),
+            ],
+            DialogTone::Default,
+        );
+        self.input_mode = None;
+        self.clear_pending_prompts();
+        self.state.dialog = Some(dialog);
+        self.pending_hotlist_add = Some(target);
+        Ok(true)
+    }
+
+    fn apply_hotlist_add(&mut self) -> bool {
+        let Some(dialog) = self.state.dialog.as_ref() else {
+            return false;
+        };
+        let name = dialog.text("name").trim().to_string();
+        let group = dialog.text("group").trim().to_string();
+        if name.is_empty() {
+            return self.reject_dialog_field("name", "hotlist entry name cannot be empty".into());
+        }
+        let Some(target) = self.pending_hotlist_add.take() else {
+            return false;
+        };
+        self.state.dialog = None;
+
+        let added = load_hotlist().and_then(|mut entries| {
+            entries.push(HotlistEntry {
+                name: name.clone(),
+                group,
+                target,
+            });
+            group_hotlist(&mut entries);
+            save_hotlist(&entries)
+        });
+        match added {
+            Ok(()) => self.push_log(format!("hotlist: added {name}")),
+            Err(err) => self.show_alert(err.to_string()),
+        }
+        true
+    }
+
+    fn open_viewer(&mut self) -> Result<bool> {
+        let entry = self.selected_action_target_entry()?;
+        if entry.entry_type == FsEntryType::Directory {
+            return Err(anyhow::anyhow!(
+                "viewer is available for files only (selected '{}')",
+                entry.name
+            ));
+        }
+
+        let path = self
+            .active_backend()
+            .normalize_existing_path("viewer", &entry.path)?;
+        let (bytes, truncated) = self
+            .active_backend()
+            .read_file_preview(path.as_path(), VIEWER_PREVIEW_LIMIT_BYTES)?;
+        let viewer_state = load_viewer_state_from_preview(
+            path.clone(),
+            entry.name,
+            entry.size_bytes,
+            bytes,
+            truncated,
+        );
+
+        self.input_mode = None;
+        self.clear_pending_prompts();
         self.state.dialog = None;
         self.state.viewer = Some(viewer_state);
         self.state.screen_mode = ScreenMode::Viewer;
@@ -1066,6 +2336,24 @@ impl App {
             return Ok(true);
         }
 
+        let status = self.run_editor_suspended(editor, path)?;
+        if !status.success() {
+            self.show_alert(format!("editor exited with status: {status}"));
+            return Ok(true);
+        }
+
+        self.reload_panel(PanelId::Left, false)?;
+        self.reload_panel(PanelId::Right, false)?;
+        self.push_log(format!("editor closed ({editor}): {}", path.display()));
+        Ok(true)
+    }
+
+    /// Hands the terminal to `editor` on `path` and takes it back afterwards.
+    fn run_editor_suspended(
+        &mut self,
+        editor: &str,
+        path: &Path,
+    ) -> Result<std::process::ExitStatus> {
         runtime::set_input_poll_paused(true);
         if let Err(err) = terminal::suspend_for_external_process() {
             runtime::set_input_poll_paused(false);
@@ -1076,18 +2364,255 @@ impl App {
         let resume_result = terminal::resume_after_external_process();
         runtime::set_input_poll_paused(false);
 
-        resume_result?;
-        self.force_full_redraw = true;
-        let status = run_result?;
-        if !status.success() {
-            self.show_alert(format!("editor exited with status: {status}"));
+        resume_result?;
+        self.force_full_redraw = true;
+        run_result
+    }
+
+    /// vidir-style rename: the marked names (or the whole directory) go to
+    /// the editor one per line, and the edited list comes back for review.
+    fn open_editor_rename(&mut self) -> Result<bool> {
+        let panel = self.active_panel();
+        if panel.trash_view.is_some() || panel.find_view.is_some() {
+            self.show_alert("rename in editor works on a directory listing");
+            return Ok(true);
+        }
+        if is_archive_backend(self.active_backend_spec()) {
+            self.show_alert("rename inside archive VFS is not supported (read-only)");
+            return Ok(true);
+        }
+
+        let mut entries = self.active_panel_selected_entries();
+        if entries.is_empty() {
+            entries = panel
+                .all_entries
+                .iter()
+                .filter(|entry| !entry.is_virtual)
+                .cloned()
+                .collect();
+        }
+        if entries.is_empty() {
+            self.show_alert("nothing to rename: the directory is empty");
+            return Ok(true);
+        }
+        if let Some(entry) = entries.iter().find(|entry| entry.name.contains('\n')) {
+            self.show_alert(format!(
+                "cannot edit names with line breaks: {:?}",
+                entry.name
+            ));
+            return Ok(true);
+        }
+
+        let names = entries
+            .iter()
+            .map(|entry| entry.name.clone())
+            .collect::<Vec<_>>();
+        let pending = PendingEditorRename {
+            text: names_to_edit_text(&names),
+            paths: entries.into_iter().map(|entry| entry.path).collect(),
+            names,
+            existing: self
+                .active_panel()
+                .all_entries
+                .iter()
+                .filter(|entry| !entry.is_virtual)
+                .map(|entry| entry.name.clone())
+                .collect(),
+        };
+        match self.resolve_editor_command() {
+            Some(editor) => self.edit_names_in_editor(editor.as_str(), pending),
+            None => self.start_editor_chooser(EditorChoiceContext::RenameNames(pending)),
+        }
+    }
+
+    fn edit_names_in_editor(
+        &mut self,
+        editor: &str,
+        mut pending: PendingEditorRename,
+    ) -> Result<bool> {
+        let path = write_rename_file(&pending.text)?;
+        let status = self.run_editor_suspended(editor, &path);
+        let edited = fs::read_to_string(&path);
+        let _ = fs::remove_file(&path);
+        let status = status?;
+        if !status.success() {
+            self.show_alert(format!("editor exited with status: {status}"));
+            return Ok(true);
+        }
+        pending.text =
+            edited.map_err(|err| anyhow::anyhow!("cannot read {}: {err}", path.display()))?;
+        self.review_editor_rename(pending);
+        Ok(true)
+    }
+
+    /// Lists the renames and removals the edit asks for. Conflicts and
+    /// unreadable lines keep Apply from running, but the text can be edited again.
+    fn review_editor_rename(&mut self, pending: PendingEditorRename) {
+        let (previews, removed, error) = match parse_edited_names(&pending.names, &pending.text) {
+            Ok(edits) => {
+                let (previews, removed) = editor_rename_changes(&pending, &edits);
+                let conflicts = previews
+                    .iter()
+                    .filter(|preview| preview.problem.is_some())
+                    .count();
+                let error = (conflicts > 0)
+                    .then(|| format!("{conflicts} name(s) conflict; edit again to fix them"));
+                (previews, removed, error)
+            }
+            Err(err) => (Vec::new(), Vec::new(), Some(err.to_string())),
+        };
+        if previews.is_empty() && removed.is_empty() && error.is_none() {
+            self.state.status_line = "rename in editor: nothing changed".to_string();
+            return;
+        }
+
+        let fields = if removed.is_empty() {
+            Vec::new()
+        } else {
+            vec![DialogField::checkbox(
+                "delete",
+                &format!(
+                    "Delete the {} entries whose lines were removed",
+                    removed.len()
+                ),
+                false,
+            )]
+        };
+        let mut dialog = form_dialog(
+            "Rename in Editor",
+            &format!(
+                "{} rename(s), {} removed line(s)",
+                previews.len(),
+                removed.len()
+            ),
+            fields,
+            DialogTone::Warning,
+        );
+        dialog.buttons.insert(
+            EDITOR_RENAME_EDIT_AGAIN,
+            DialogButton {
+                label: "Edit again".to_string(),
+                accelerator: Some('e'),
+                role: DialogButtonRole::Secondary,
+            },
+        );
+        let mut lines = previews.iter().map(rename_preview_line).collect::<Vec<_>>();
+        lines.extend(
+            removed
+                .iter()
+                .map(|idx| (format!("{}  (line removed)", pending.names[*idx]), false)),
+        );
+        dialog.list = Some(DialogList {
+            title: "Changes".to_string(),
+            lines,
+            offset: 0,
+            selected: None,
+        });
+        dialog.error = error;
+
+        self.input_mode = None;
+        self.clear_pending_prompts();
+        self.pending_editor_rename = Some(pending);
+        self.state.dialog = Some(dialog);
+    }
+
+    fn apply_editor_rename_action(&mut self, button_idx: usize) -> bool {
+        let Some(pending) = self.pending_editor_rename.take() else {
+            return false;
+        };
+
+        let result = match button_idx {
+            EDITOR_RENAME_APPLY => self.apply_editor_rename(pending),
+            EDITOR_RENAME_EDIT_AGAIN => {
+                self.state.dialog = None;
+                match self.resolve_editor_command() {
+                    Some(editor) => self.edit_names_in_editor(editor.as_str(), pending),
+                    None => self.start_editor_chooser(EditorChoiceContext::RenameNames(pending)),
+                }
+            }
+            _ => {
+                self.state.dialog = None;
+                self.push_log("rename in editor canceled");
+                Ok(true)
+            }
+        };
+        result.unwrap_or_else(|err| {
+            self.show_alert(err.to_string());
+            true
+        })
+    }
+
+    fn apply_editor_rename(&mut self, pending: PendingEditorRename) -> Result<bool> {
+        let delete_removed = self
+            .state
+            .dialog
+            .as_ref()
+            .is_some_and(|dialog| dialog.checkbox("delete"));
+        let Ok(edits) = parse_edited_names(&pending.names, &pending.text) else {
+            // The parse error is on screen; only Edit again can fix it.
+            self.pending_editor_rename = Some(pending);
+            return Ok(true);
+        };
+        let (previews, removed) = editor_rename_changes(&pending, &edits);
+        if previews.iter().any(|preview| preview.problem.is_some()) {
+            self.pending_editor_rename = Some(pending);
             return Ok(true);
         }
+        self.state.dialog = None;
 
-        self.reload_panel(PanelId::Left, false)?;
-        self.reload_panel(PanelId::Right, false)?;
-        self.push_log(format!("editor closed ({editor}): {}", path.display()));
-        Ok(true)
+        let renames = pending
+            .paths
+            .iter()
+            .zip(&pending.names)
+            .zip(&edits)
+            .filter_map(|((path, old), edit)| {
+                let new = edit.as_ref().filter(|new| *new != old)?;
+                Some(BatchOpItem {
+                    source: path.clone(),
+                    destination: Some(path.with_file_name(new)),
+                    name: old.clone(),
+                    size_bytes: 0,
+                    overwrite_destination: false,
+                    attributes: None,
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut removals = Vec::new();
+        if delete_removed {
+            for idx in removed {
+                self.guard_delete_target(&pending.paths[idx])?;
+                removals.push(BatchOpItem {
+                    source: pending.paths[idx].clone(),
+                    destination: None,
+                    name: pending.names[idx].clone(),
+                    size_bytes: 0,
+                    overwrite_destination: false,
+                    attributes: None,
+                });
+            }
+        }
+
+        let removal_kind = if matches!(self.active_backend_spec(), BackendSpec::Local) {
+            JobKind::Trash
+        } else {
+            JobKind::Delete
+        };
+        let mut redraw = true;
+        for (kind, items) in [(JobKind::Rename, renames), (removal_kind, removals)] {
+            if items.is_empty() {
+                continue;
+            }
+            let batch_id = self.next_batch_id;
+            self.next_batch_id += 1;
+            let plan = BatchPlan {
+                batch_id,
+                kind,
+                summary: batch_summary(kind, items.len(), 0, 0, Path::new("")),
+                items,
+            };
+            redraw = self.execute_batch_plan(plan)?;
+        }
+        Ok(redraw)
     }
 
     fn open_editor_settings(&mut self) -> Result<bool> {
@@ -1111,19 +2636,11 @@ impl App {
             .unwrap_or(1);
 
         self.input_mode = None;
-        self.pending_confirmation = None;
-        self.pending_rename = None;
-        self.pending_mask = None;
-        self.pending_mkdir = None;
-        self.pending_sftp_connect = None;
-        self.pending_bookmark = None;
-        self.pending_conflict = None;
-        self.pending_find = None;
+        self.clear_pending_prompts();
         self.pending_editor_choice = Some(PendingEditorChoice {
             context,
             options: candidates.clone(),
         });
-        self.pending_viewer_search = false;
         self.state.dialog = Some(input_dialog(
             "Editor Setup",
             build_editor_choice_body(candidates.as_slice()).as_str(),
@@ -1142,6 +2659,441 @@ impl App {
             .or_else(load_saved_editor_command)
     }
 
+    fn open_job_manager(&mut self) -> Result<bool> {
+        if self.state.dialog.is_some() || self.state.screen_mode != ScreenMode::Normal {
+            return Ok(false);
+        }
+
+        self.state.top_menu.open = false;
+        self.state.screen_mode = ScreenMode::Jobs;
+        let active = self
+            .state
+            .jobs
+            .iter()
+            .position(|job| !job.status.is_terminal());
+        self.state.job_manager.selected =
+            active.unwrap_or_else(|| self.state.jobs.len().saturating_sub(1));
+        self.state.status_line = format!("job manager: {} job(s)", self.state.jobs.len());
+        Ok(true)
+    }
+
+    fn close_job_manager(&mut self) -> Result<bool> {
+        if self.state.screen_mode != ScreenMode::Jobs {
+            return Ok(false);
+        }
+
+        self.state.screen_mode = ScreenMode::Normal;
+        self.state.status_line = "job manager closed".to_string();
+        Ok(true)
+    }
+
+    fn move_job_manager_selection(&mut self, delta: isize) -> Result<bool> {
+        let last = self.state.jobs.len().saturating_sub(1);
+        let selected = self.state.job_manager.selected.min(last);
+        self.state.job_manager.selected = selected.saturating_add_signed(delta).min(last);
+        Ok(true)
+    }
+
+    fn selected_manager_job(&self) -> Option<&Job> {
+        self.state.jobs.get(self.state.job_manager.selected)
+    }
+
+    fn cancel_selected_job(&mut self) -> Result<bool> {
+        let Some(job) = self.selected_manager_job() else {
+            return Ok(false);
+        };
+        let job_id = job.id;
+        let Some(control) = self
+            .job_controls
+            .get(&job_id)
+            .filter(|_| !job.status.is_terminal())
+        else {
+            self.state.status_line = format!("job #{job_id} is not running");
+            return Ok(true);
+        };
+        control.cancel();
+        self.push_log(format!("job #{job_id}: cancel requested"));
+        Ok(true)
+    }
+
+    fn retry_selected_job(&mut self) -> Result<bool> {
+        let Some(job) = self.selected_manager_job() else {
+            return Ok(false);
+        };
+        let job_id = job.id;
+        if !matches!(job.status, JobStatus::Failed | JobStatus::Canceled) {
+            self.state.status_line = format!("job #{job_id} did not fail; nothing to retry");
+            return Ok(true);
+        }
+        self.retry_jobs(&[job_id])
+    }
+
+    fn retry_failed_jobs(&mut self) -> Result<bool> {
+        let failed = self
+            .state
+            .jobs
+            .iter()
+            .filter(|job| job.status == JobStatus::Failed)
+            .map(|job| job.id)
+            .collect::<Vec<_>>();
+        if failed.is_empty() {
+            self.state.status_line = "no failed jobs to retry".to_string();
+            return Ok(true);
+        }
+        self.retry_jobs(&failed)
+    }
+
+    /// Resubmits the original requests as new standalone jobs.
+    fn retry_jobs(&mut self, job_ids: &[u64]) -> Result<bool> {
+        let mut retried = 0usize;
+        for job_id in job_ids {
+            let Some(original) = self.job_requests.get(job_id).cloned() else {
+                continue;
+            };
+            let queued_message = format!(
+                "{} retry queued: {}",
+                operation_name(original.kind),
+                source_item_label(&original.source)
+            );
+            let request = JobRequest {
+                batch_id: None,
+                control: JobControl::default(),
+                ..original
+            };
+            self.submit_job_request(request, queued_message, false)?;
+            retried += 1;
+        }
+        self.push_log(format!("retry queued: {retried} job(s)"));
+        Ok(true)
+    }
+
+    fn show_batch_failures(&mut self, pending: PendingBatchFailures) {
+        self.state.dialog = Some(if pending.showing_details {
+            batch_failures_dialog(pending.details(), true)
+        } else {
+            batch_failures_dialog(pending.summary(), false)
+        });
+        self.pending_batch_failures = Some(pending);
+    }
+
+    fn apply_batch_failures_action(&mut self, button_idx: usize) -> bool {
+        let Some(pending) = self.pending_batch_failures.take() else {
+            return false;
+        };
+
+        match button_idx {
+            BATCH_FAILURES_RETRY => {
+                self.state.dialog = None;
+                if let Err(err) = self.retry_batch_failures(pending) {
+                    self.show_alert(format!("retry failed items failed: {err}"));
+                }
+            }
+            BATCH_FAILURES_TOGGLE_DETAILS => {
+                let showing_details = !pending.showing_details;
+                self.show_batch_failures(PendingBatchFailures {
+                    showing_details,
+                    ..pending
+                });
+            }
+            BATCH_FAILURES_SKIP => {
+                self.state.dialog = None;
+                self.push_log(format!(
+                    "batch {}: skipped {} failed item(s)",
+                    operation_name(pending.kind),
+                    pending.failures.len()
+                ));
+            }
+            _ => self.pending_batch_failures = Some(pending),
+        }
+        true
+    }
+
+    /// Re-enqueues the original requests of the failed items as a new batch,
+    /// so the retry gets its own progress overlay and summary.
+    fn retry_batch_failures(&mut self, pending: PendingBatchFailures) -> Result<bool> {
+        let requests = pending
+            .failures
+            .iter()
+            .filter_map(|failure| self.job_requests.get(&failure.job_id).cloned())
+            .collect::<Vec<_>>();
+        if requests.is_empty() {
+            self.push_log("retry: failed jobs are no longer available");
+            return Ok(false);
+        }
+
+        let batch_id = self.next_batch_id;
+        self.next_batch_id += 1;
+        let first_file = requests
+            .first()
+            .map(|request| source_item_label(&request.source))
+            .unwrap_or_else(|| "-".to_string());
+        let total = requests.len();
+        self.batch_progress.insert(
+            batch_id,
+            BatchProgress::new(pending.kind, total, first_file),
+        );
+        self.sync_visible_batch_progress(Some(batch_id));
+
+        for original in requests {
+            let estimate = self
+                .state
+                .jobs
+                .iter()
+                .find(|job| job.id == original.id)
+                .and_then(|job| job.bytes)
+                .map_or(0, |bytes| bytes.total);
+            let queued_message = format!(
+                "{} retry queued: {}",
+                operation_name(original.kind),
+                source_item_label(&original.source)
+            );
+            let request = JobRequest {
+                batch_id: Some(batch_id),
+                control: JobControl::default(),
+                ..original
+            };
+            match self.submit_job_request(request, queued_message, false) {
+                Ok(job_id) => self.seed_job_bytes(batch_id, job_id, estimate),
+                Err(err) => {
+                    self.batch_progress.remove(&batch_id);
+                    self.sync_visible_batch_progress(None);
+                    return Err(err);
+                }
+            }
+        }
+
+        self.push_log(format!(
+            "batch {} retry queued: {} failed item(s)",
+            operation_name(pending.kind),
+            total
+        ));
+        Ok(true)
+    }
+
+    fn clear_finished_jobs(&mut self) -> Result<bool> {
+        let before = self.state.jobs.len();
+        self.state.jobs.retain(|job| !job.status.is_terminal());
+        let job_requests = &mut self.job_requests;
+        let remaining = &self.state.jobs;
+        job_requests.retain(|job_id, _| remaining.iter().any(|job| job.id == *job_id));
+        let cleared = before - self.state.jobs.len();
+        self.state.job_manager.selected = self
+            .state
+            .job_manager
+            .selected
+            .min(self.state.jobs.len().saturating_sub(1));
+        self.state.status_line = format!("cleared {cleared} finished job(s)");
+        Ok(true)
+    }
+
+    fn open_properties(&mut self) -> Result<bool> {
+        if self.active_panel().trash_view.is_some() {
+            self.show_alert("properties are not available in the trash view");
+            return Ok(true);
+        }
+        if is_archive_backend(self.active_backend_spec()) {
+            self.show_alert("archive VFS is read-only: attributes cannot be changed");
+            return Ok(true);
+        }
+
+        let entry = self.selected_action_target_entry()?;
+        let targets = if self.active_panel().selected_paths.is_empty() {
+            vec![entry.path.clone()]
+        } else {
+            self.active_panel_selected_entries()
+                .into_iter()
+                .map(|entry| entry.path)
+                .collect()
+        };
+        let properties = self.active_backend().entry_properties(&entry.path)?;
+        self.state.properties = Some(PropertiesState {
+            targets,
+            mode: properties.mode,
+            owner: account_label(properties.owner.as_ref(), properties.uid),
+            group: account_label(properties.group.as_ref(), properties.gid),
+            modified: properties
+                .modified_at
+                .map(format_properties_time)
+                .unwrap_or_default(),
+            recursive: false,
+            file_mask: String::new(),
+            dir_mask: String::new(),
+            properties,
+            field: PropertiesField::Mode,
+            grid_row: 0,
+            grid_col: 0,
+        });
+        self.state.status_line = format!("properties: {}", entry.name);
+        Ok(true)
+    }
+
+    fn handle_properties_input(&mut self, key: &KeyEvent) -> Option<bool> {
+        let properties = self.state.properties.as_mut()?;
+        let redraw = match key.code {
+            KeyCode::Esc | KeyCode::F(10) => {
+                self.state.properties = None;
+                self.state.status_line = "properties closed".to_string();
+                true
+            }
+            KeyCode::Enter => self.confirm_properties(),
+            KeyCode::Tab => {
+                properties.field = properties.field.next();
+                true
+            }
+            KeyCode::BackTab => {
+                properties.field = properties.field.prev();
+                true
+            }
+            KeyCode::Char(' ') if properties.field == PropertiesField::Recursive => {
+                properties.recursive = !properties.recursive;
+                true
+            }
+            _ if properties.field == PropertiesField::Mode => match key.code {
+                KeyCode::Up => {
+                    properties.move_cursor(-1, 0);
+                    true
+                }
+                KeyCode::Down => {
+                    properties.move_cursor(1, 0);
+                    true
+                }
+                KeyCode::Left => {
+                    properties.move_cursor(0, -1);
+                    true
+                }
+                KeyCode::Right => {
+                    properties.move_cursor(0, 1);
+                    true
+                }
+                KeyCode::Char(' ') | KeyCode::Insert => {
+                    properties.toggle_cursor_bit();
+                    true
+                }
+                // Typing octal digits shifts them in, so "755" yields 0755.
+                KeyCode::Char(c @ '0'..='7') => {
+                    let digit = c.to_digit(8).unwrap_or(0);
+                    properties.mode = ((properties.mode << 3) | digit) & 0o7777;
+                    true
+                }
+                _ => false,
+            },
+            KeyCode::Backspace => properties.field_value_mut().and_then(String::pop).is_some(),
+            KeyCode::Char(c)
+                if key.modifiers.is_empty() || key.modifiers == KeyModifiers::SHIFT =>
+            {
+                if let Some(value) = properties.field_value_mut() {
+                    value.push(c);
+                }
+                true
+            }
+            _ => false,
+        };
+        // The editor is modal: keys it does not use are swallowed.
+        Some(redraw)
+    }
+
+    fn confirm_properties(&mut self) -> bool {
+        match self.properties_batch_plan() {
+            Ok(Some(plan)) => {
+                self.state.dialog = Some(confirm_dialog(plan.summary.clone()));
+                self.pending_confirmation = Some(PendingConfirmation::Batch(plan));
+            }
+            Ok(None) => {
+                self.state.properties = None;
+                self.state.status_line = "properties: nothing changed".to_string();
+            }
+            Err(err) => self.show_alert(err.to_string()),
+        }
+        true
+    }
+
+    /// Turns the edited fields into a chattr batch over the targets. Only fields
+    /// that differ from the inspected entry are applied.
+    fn properties_batch_plan(&mut self) -> Result<Option<BatchPlan>> {
+        let Some(state) = self.state.properties.as_ref() else {
+            return Ok(None);
+        };
+        let original = &state.properties;
+        let local = matches!(self.active_backend_spec(), BackendSpec::Local);
+        let mut change = AttributeChange::default();
+        let mut lines = Vec::new();
+
+        let grid_mode = (state.mode != original.mode).then_some(ModeSpec::Absolute(state.mode));
+        change.file_mode =
+            parse_mode_mask(state.file_mask.as_str(), "files")?.or(grid_mode.clone());
+        change.dir_mode = parse_mode_mask(state.dir_mask.as_str(), "dirs")?.or(grid_mode);
+        if change.file_mode == change.dir_mode {
+            if let Some(mode) = change.file_mode.as_ref() {
+                lines.push(format!("mode: {:04o} -> {mode}", original.mode));
+            }
+        } else {
+            if let Some(mode) = change.file_mode.as_ref() {
+                lines.push(format!("files: {mode}"));
+            }
+            if let Some(mode) = change.dir_mode.as_ref() {
+                lines.push(format!("dirs: {mode}"));
+            }
+        }
+
+        let original_owner = account_label(original.owner.as_ref(), original.uid);
+        let owner = state.owner.trim();
+        if !owner.is_empty() && owner != original_owner {
+            change.uid = Some(resolve_account_id(owner, local, "user", user_id)?);
+            lines.push(format!("owner: {original_owner} -> {owner}"));
+        }
+
+        let original_group = account_label(original.group.as_ref(), original.gid);
+        let group = state.group.trim();
+        if !group.is_empty() && group != original_group {
+            change.gid = Some(resolve_account_id(group, local, "group", group_id)?);
+            lines.push(format!("group: {original_group} -> {group}"));
+        }
+
+        let original_modified = original
+            .modified_at
+            .map(format_properties_time)
+            .unwrap_or_default();
+        let modified = state.modified.trim();
+        if !modified.is_empty() && modified != original_modified {
+            change.modified = Some(parse_properties_time(modified)?);
+            lines.push(format!("mtime: {original_modified} -> {modified}"));
+        }
+
+        if change.is_empty() {
+            return Ok(None);
+        }
+        if state.recursive {
+            change.recursive = true;
+            lines.push("recursive: directories and their contents".to_string());
+        }
+
+        let items = state
+            .targets
+            .iter()
+            .map(|path| BatchOpItem {
+                source: path.clone(),
+                destination: None,
+                name: source_item_label(path),
+                size_bytes: 0,
+                overwrite_destination: false,
+                attributes: Some(change.clone()),
+            })
+            .collect::<Vec<_>>();
+        let summary = format!(
+            "{}\n{}",
+            batch_summary(JobKind::Chattr, items.len(), 0, 0, Path::new("")),
+            lines.join("\n")
+        );
+        let batch_id = self.next_batch_id;
+        self.next_batch_id += 1;
+        Ok(Some(BatchPlan {
+            batch_id,
+            kind: JobKind::Chattr,
+            items,
+            summary,
+        }))
+    }
+
     fn close_viewer(&mut self) -> Result<bool> {
         if self.state.screen_mode != ScreenMode::Viewer {
             return Ok(false);
@@ -1149,7 +3101,7 @@ impl App {
 
         self.state.screen_mode = ScreenMode::Normal;
         self.state.viewer = None;
-        self.pending_viewer_search = false;
+        self.clear_pending_prompts();
         self.state.status_line = "viewer closed".to_string();
         Ok(true)
     }
@@ -1285,55 +3237,39 @@ impl App {
     fn start_sftp_connect(&mut self) -> Result<bool> {
         let panel_id = self.state.active_panel;
         self.input_mode = None;
-        self.pending_confirmation = None;
-        self.pending_rename = None;
-        self.pending_mask = None;
-        self.pending_conflict = None;
-        self.pending_find = None;
-        self.pending_editor_choice = None;
-        self.pending_bookmark = None;
-        self.pending_viewer_search = false;
-        let default_value = match self.backend_spec(panel_id) {
+        self.clear_pending_prompts();
+        // Reconnecting from an SFTP panel starts from its current settings.
+        let (address, login, auth) = match self.backend_spec(panel_id) {
             BackendSpec::Sftp(info) => {
-                format!("{}:{}{}", info.host, info.port, info.root_path.display())
+                let auth = match info.auth {
+                    SftpAuth::Password(_) => SFTP_AUTH_PASSWORD,
+                    SftpAuth::KeyFile { .. } => SFTP_AUTH_KEY_FILE,
+                    SftpAuth::Agent => SFTP_AUTH_AGENT,
+                };
+                (
+                    format!("{}:{}{}", info.host, info.port, info.root_path.display()),
+                    info.user.clone(),
+                    auth,
+                )
             }
-            BackendSpec::Local | BackendSpec::Archive(_) => "192.168.1.250:22/".to_string(),
+            BackendSpec::Local | BackendSpec::Archive(_) => (
+                "192.168.1.250:22/".to_string(),
+                String::new(),
+                SFTP_AUTH_AUTO,
+            ),
         };
-        self.pending_sftp_connect = Some(PendingSftpConnect {
-            panel_id,
-            stage: SftpConnectStage::Address,
-            draft: SftpConnectDraft {
-                host: String::new(),
-                port: 22,
-                root_path: PathBuf::from("/"),
-                user: None,
-            },
-        });
-        self.state.dialog = Some(input_dialog(
-            "SFTP Connect",
-            "Enter address: host[:port][/path], 'local', or '@bookmark_name'",
-            default_value,
-            DialogTone::Default,
-        ));
-        self.state.status_line = "sftp connect: enter address".to_string();
+        self.pending_sftp_connect = Some(PendingSftpConnect { panel_id });
+        self.state.dialog = Some(sftp_connect_dialog(address, login, auth));
+        self.state.status_line = "sftp connect: fill in the form".to_string();
         Ok(true)
-    }
-
-    fn disconnect_sftp_panel(&mut self, panel_id: PanelId) -> Result<bool> {
-        if !matches!(self.backend_spec(panel_id), BackendSpec::Sftp(_)) {
-            return Ok(false);
-        }
-        self.input_mode = None;
-        self.pending_confirmation = None;
-        self.pending_rename = None;
-        self.pending_mask = None;
-        self.pending_mkdir = None;
-        self.pending_sftp_connect = None;
-        self.pending_bookmark = None;
-        self.pending_conflict = None;
-        self.pending_find = None;
-        self.pending_editor_choice = None;
-        self.pending_viewer_search = false;
+    }
+
+    fn disconnect_sftp_panel(&mut self, panel_id: PanelId) -> Result<bool> {
+        if !matches!(self.backend_spec(panel_id), BackendSpec::Sftp(_)) {
+            return Ok(false);
+        }
+        self.input_mode = None;
+        self.clear_pending_prompts();
         self.state.dialog = None;
 
         let local_cwd = self.last_local_cwd(panel_id);
@@ -1352,16 +3288,7 @@ impl App {
 
     fn start_search(&mut self) -> Result<bool> {
         let panel_id = self.state.active_panel;
-        self.pending_confirmation = None;
-        self.pending_rename = None;
-        self.pending_mask = None;
-        self.pending_mkdir = None;
-        self.pending_sftp_connect = None;
-        self.pending_bookmark = None;
-        self.pending_conflict = None;
-        self.pending_find = None;
-        self.pending_editor_choice = None;
-        self.pending_viewer_search = false;
+        self.clear_pending_prompts();
         self.state.dialog = None;
         self.input_mode = Some(InputMode::Search(panel_id));
         let query = self.panel_mut(panel_id).search_query.clone();
@@ -1386,14 +3313,7 @@ impl App {
         }
 
         self.input_mode = None;
-        self.pending_confirmation = None;
-        self.pending_rename = None;
-        self.pending_mask = None;
-        self.pending_mkdir = None;
-        self.pending_sftp_connect = None;
-        self.pending_bookmark = None;
-        self.pending_conflict = None;
-        self.pending_viewer_search = false;
+        self.clear_pending_prompts();
         let default_hidden = self.panel(panel_id).show_hidden;
         self.pending_find = Some(PendingFind {
             panel_id,
@@ -1426,14 +3346,7 @@ impl App {
         }
 
         self.input_mode = None;
-        self.pending_confirmation = None;
-        self.pending_rename = None;
-        self.pending_mask = None;
-        self.pending_mkdir = None;
-        self.pending_sftp_connect = None;
-        self.pending_bookmark = None;
-        self.pending_conflict = None;
-        self.pending_viewer_search = false;
+        self.clear_pending_prompts();
         let default_hidden = self.panel(panel_id).show_hidden;
         self.pending_find = Some(PendingFind {
             panel_id,
@@ -1454,16 +3367,7 @@ impl App {
 
     fn start_bookmark_connect_prompt(&mut self) -> Result<bool> {
         self.input_mode = None;
-        self.pending_confirmation = None;
-        self.pending_rename = None;
-        self.pending_mask = None;
-        self.pending_mkdir = None;
-        self.pending_sftp_connect = None;
-        self.pending_bookmark = None;
-        self.pending_conflict = None;
-        self.pending_find = None;
-        self.pending_editor_choice = None;
-        self.pending_viewer_search = false;
+        self.clear_pending_prompts();
         self.state.dialog = None;
 
         let bookmarks = load_sftp_bookmarks()?;
@@ -1491,47 +3395,29 @@ impl App {
 
     fn start_bookmark_add_prompt(&mut self) -> Result<bool> {
         self.input_mode = None;
-        self.pending_confirmation = None;
-        self.pending_rename = None;
-        self.pending_mask = None;
-        self.pending_mkdir = None;
-        self.pending_sftp_connect = None;
-        self.pending_bookmark = None;
-        self.pending_conflict = None;
-        self.pending_find = None;
-        self.pending_editor_choice = None;
-        self.pending_viewer_search = false;
+        self.clear_pending_prompts();
         self.state.dialog = None;
 
+        let draft = SftpBookmark {
+            host: "example.com".to_string(),
+            user: env::var("USER").unwrap_or_default(),
+            ..SftpBookmark::empty()
+        };
+        self.state.dialog = Some(bookmark_form_dialog("Bookmark Add", &draft));
         self.pending_bookmark = Some(PendingBookmark {
             panel_id: self.state.active_panel,
             action: BookmarkAction::Add,
-            stage: BookmarkStage::Name,
+            stage: BookmarkStage::Form,
             lookup_name: None,
-            draft: SftpBookmark::empty(),
+            draft,
         });
-        self.state.dialog = Some(input_dialog(
-            "Bookmark Add",
-            "Enter bookmark name",
-            String::new(),
-            DialogTone::Default,
-        ));
-        self.state.status_line = "bookmark add: enter name".to_string();
+        self.state.status_line = "bookmark add: fill in the form".to_string();
         Ok(true)
     }
 
     fn start_bookmark_edit_prompt(&mut self) -> Result<bool> {
         self.input_mode = None;
-        self.pending_confirmation = None;
-        self.pending_rename = None;
-        self.pending_mask = None;
-        self.pending_mkdir = None;
-        self.pending_sftp_connect = None;
-        self.pending_bookmark = None;
-        self.pending_conflict = None;
-        self.pending_find = None;
-        self.pending_editor_choice = None;
-        self.pending_viewer_search = false;
+        self.clear_pending_prompts();
         self.state.dialog = None;
 
         let bookmarks = load_sftp_bookmarks()?;
@@ -1559,16 +3445,7 @@ impl App {
 
     fn start_bookmark_delete_prompt(&mut self) -> Result<bool> {
         self.input_mode = None;
-        self.pending_confirmation = None;
-        self.pending_rename = None;
-        self.pending_mask = None;
-        self.pending_mkdir = None;
-        self.pending_sftp_connect = None;
-        self.pending_bookmark = None;
-        self.pending_conflict = None;
-        self.pending_find = None;
-        self.pending_editor_choice = None;
-        self.pending_viewer_search = false;
+        self.clear_pending_prompts();
         self.state.dialog = None;
 
         let bookmarks = load_sftp_bookmarks()?;
@@ -1628,14 +3505,7 @@ impl App {
     fn start_mask_prompt(&mut self, select: bool) -> Result<bool> {
         let panel_id = self.state.active_panel;
         self.input_mode = None;
-        self.pending_confirmation = None;
-        self.pending_rename = None;
-        self.pending_sftp_connect = None;
-        self.pending_bookmark = None;
-        self.pending_conflict = None;
-        self.pending_find = None;
-        self.pending_editor_choice = None;
-        self.pending_viewer_search = false;
+        self.clear_pending_prompts();
         self.pending_mask = Some(PendingMask { panel_id, select });
         let title = if select {
             "Select by mask"
@@ -1670,7 +3540,12 @@ impl App {
         }
 
         if let Some(plan) = self.build_batch_plan_from_selection(JobKind::Copy)? {
-            self.state.dialog = Some(confirm_dialog(plan.summary.clone()));
+            self.state.dialog = Some(copy_options_dialog(
+                JobKind::Copy,
+                plan.summary.clone(),
+                None,
+                &self.state.copy_options,
+            ));
             self.pending_confirmation = Some(PendingConfirmation::Batch(plan));
             return Ok(true);
         }
@@ -1679,33 +3554,376 @@ impl App {
         self.open_rename_prompt(JobKind::Copy, &entry)
     }
 
-    fn queue_move(&mut self) -> Result<bool> {
-        if is_archive_backend(self.active_backend_spec()) {
-            self.show_alert("move from archive VFS is not supported (read-only)");
-            return Ok(true);
-        }
-        if is_archive_backend(self.inactive_backend_spec()) {
-            self.show_alert("move into archive VFS is not supported yet");
-            return Ok(true);
+    fn queue_move(&mut self) -> Result<bool> {
+        if self.active_panel().trash_view.is_some() {
+            return self.confirm_restore_selected_trash();
+        }
+        if is_archive_backend(self.active_backend_spec()) {
+            self.show_alert("move from archive VFS is not supported (read-only)");
+            return Ok(true);
+        }
+        if is_archive_backend(self.inactive_backend_spec()) {
+            self.show_alert("move into archive VFS is not supported yet");
+            return Ok(true);
+        }
+
+        if let Some(plan) = self.build_batch_plan_from_selection(JobKind::Move)? {
+            self.state.dialog = Some(copy_options_dialog(
+                JobKind::Move,
+                plan.summary.clone(),
+                None,
+                &self.state.copy_options,
+            ));
+            self.pending_confirmation = Some(PendingConfirmation::Batch(plan));
+            return Ok(true);
+        }
+
+        let entry = self.selected_action_target_entry()?;
+        self.open_rename_prompt(JobKind::Move, &entry)
+    }
+
+    fn queue_rename(&mut self) -> Result<bool> {
+        if self.active_panel().trash_view.is_some() {
+            self.show_alert("rename is not available in the trash view");
+            return Ok(true);
+        }
+        if is_archive_backend(self.active_backend_spec()) {
+            self.show_alert("rename inside archive VFS is not supported (read-only)");
+            return Ok(true);
+        }
+        if !self.active_panel().selected_paths.is_empty() {
+            return self.open_batch_rename();
+        }
+
+        let entry = self.selected_action_target_entry()?;
+        self.open_rename_prompt(JobKind::Rename, &entry)
+    }
+
+    /// Links the marked entries (or the current one) into the other panel's
+    /// directory. Links never cross backends, so both panels must show the
+    /// same filesystem.
+    fn queue_link(&mut self, kind: JobKind) -> Result<bool> {
+        let verb = operation_name(kind);
+        if self.active_panel().trash_view.is_some() {
+            self.show_alert(format!("{verb} is not available in the trash view"));
+            return Ok(true);
+        }
+        if is_archive_backend(self.active_backend_spec())
+            || is_archive_backend(self.inactive_backend_spec())
+        {
+            self.show_alert(format!(
+                "{verb} inside archive VFS is not supported (read-only)"
+            ));
+            return Ok(true);
+        }
+        if self.active_backend_spec() != self.inactive_backend_spec() {
+            self.show_alert(format!("{verb} needs both panels on the same filesystem"));
+            return Ok(true);
+        }
+
+        let mut entries = self.active_panel_selected_entries();
+        if entries.is_empty() {
+            entries.push(self.selected_action_target_entry()?);
+        }
+        if kind == JobKind::Hardlink
+            && entries
+                .iter()
+                .any(|entry| entry.entry_type == FsEntryType::Directory)
+        {
+            self.show_alert("directories cannot be hardlinked");
+            return Ok(true);
+        }
+
+        if let Some(plan) = self.build_batch_plan_from_selection(kind)? {
+            self.state.dialog = Some(link_dialog(kind, plan.summary.clone(), None));
+            self.pending_confirmation = Some(PendingConfirmation::Batch(plan));
+            return Ok(true);
+        }
+
+        self.open_rename_prompt(kind, &entries[0])
+    }
+
+    /// Edits where the symlink under the cursor points; the link is replaced
+    /// by a job once the new target is applied.
+    fn open_symlink_edit(&mut self) -> Result<bool> {
+        if self.active_panel().trash_view.is_some() {
+            self.show_alert("symlinks cannot be edited in the trash view");
+            return Ok(true);
+        }
+        if is_archive_backend(self.active_backend_spec()) {
+            self.show_alert("symlink inside archive VFS is not supported (read-only)");
+            return Ok(true);
+        }
+        let entry = self.selected_action_target_entry()?;
+        let Ok(target) = self.active_backend().read_link(&entry.path) else {
+            self.show_alert(format!("'{}' is not a symlink", entry.name));
+            return Ok(true);
+        };
+
+        self.input_mode = None;
+        self.clear_pending_prompts();
+        self.state.dialog = Some(input_dialog(
+            "Edit Symlink",
+            &format!("Target of '{}':", entry.name),
+            target.display().to_string(),
+            DialogTone::Default,
+        ));
+        self.state.status_line = format!("edit symlink: {}", entry.name);
+        self.pending_symlink_edit = Some(PendingSymlinkEdit {
+            path: entry.path,
+            name: entry.name,
+            target,
+        });
+        Ok(true)
+    }
+
+    fn apply_symlink_edit(&mut self) -> bool {
+        let pending = self.pending_symlink_edit.take();
+        let requested = self
+            .state
+            .dialog
+            .as_ref()
+            .and_then(|dialog| dialog.input_text())
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default();
+        self.state.dialog = None;
+
+        let Some(pending) = pending else {
+            return true;
+        };
+        if requested.is_empty() {
+            self.show_alert("symlink target cannot be empty");
+            return true;
+        }
+        let target = PathBuf::from(&requested);
+        if target == pending.target {
+            return true;
+        }
+
+        // The target is written as typed, relative or not.
+        let mut request = self.job_request(JobKind::Symlink, target, Some(pending.path), None);
+        request.copy_options.relative_symlinks = false;
+        request.copy_options.replace_link = true;
+        let queued = format!("symlink queued: {} -> {requested}", pending.name);
+        match self.submit_job_request(request, queued, true) {
+            Ok(_) => true,
+            Err(err) => {
+                self.show_alert(err.to_string());
+                true
+            }
+        }
+    }
+
+    /// MC-style `Ctrl+X` prefix: `s` makes symlinks, `l` hardlinks and
+    /// `Ctrl+S` edits a symlink's target.
+    fn handle_ctrl_x_input(&mut self, key: &KeyEvent) -> Option<bool> {
+        if std::mem::take(&mut self.pending_ctrl_x) {
+            let command = match key.code {
+                KeyCode::Char('s' | 'S' | '\u{13}')
+                    if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                {
+                    Some(Command::EditSymlink)
+                }
+                KeyCode::Char('s') => Some(Command::Symlink),
+                KeyCode::Char('l') => Some(Command::Hardlink),
+                KeyCode::Char('h') => Some(Command::AddToHotlist),
+                _ => None,
+            };
+            return Some(match command {
+                Some(command) => self.apply_command(command),
+                None => {
+                    self.state.status_line = "Ctrl+X: no action on that key".to_string();
+                    true
+                }
+            });
+        }
+
+        let is_ctrl_x = key.modifiers.contains(KeyModifiers::CONTROL)
+            && matches!(key.code, KeyCode::Char('x' | 'X' | '\u{18}'));
+        if !is_ctrl_x {
+            return None;
+        }
+        self.pending_ctrl_x = true;
+        self.state.status_line =
+            "Ctrl+X: s symlink, l hardlink, Ctrl+S edit symlink target, h add to hotlist"
+                .to_string();
+        Some(true)
+    }
+
+    /// Pattern rename of the marked entries (or the current one) with a live
+    /// preview of the new names.
+    fn open_batch_rename(&mut self) -> Result<bool> {
+        let panel = self.active_panel();
+        if panel.trash_view.is_some() || panel.find_view.is_some() {
+            self.show_alert("batch rename works on a directory listing");
+            return Ok(true);
+        }
+        if is_archive_backend(self.active_backend_spec()) {
+            self.show_alert("rename inside archive VFS is not supported (read-only)");
+            return Ok(true);
+        }
+
+        let mut entries = self.active_panel_selected_entries();
+        if entries.is_empty() {
+            entries.push(self.selected_action_target_entry()?);
+        }
+        let existing = self
+            .active_panel()
+            .all_entries
+            .iter()
+            .filter(|entry| !entry.is_virtual)
+            .map(|entry| entry.name.clone())
+            .collect();
+
+        self.input_mode = None;
+        self.clear_pending_prompts();
+        let count = entries.len();
+        self.pending_batch_rename = Some(PendingBatchRename {
+            paths: entries.iter().map(|entry| entry.path.clone()).collect(),
+            names: entries.into_iter().map(|entry| entry.name).collect(),
+            existing,
+        });
+        self.state.dialog = Some(form_dialog(
+            "Batch Rename",
+            "Tokens: [N] name, [E] extension, [C] counter\n\
+             Find is a regex; $1 in Replace inserts a group",
+            vec![
+                DialogField::text("template", "Template", "[N].[E]"),
+                DialogField::text("find", "Find", ""),
+                DialogField::text("replace", "Replace", ""),
+                DialogField::dropdown("case", "Case", &CaseMode::LABELS, 0),
+                DialogField::text("counter_start", "Counter start", "1"),
+                DialogField::text("counter_step", "Counter step", "1"),
+                DialogField::text("counter_digits", "Counter digits", "1"),
+            ],
+            DialogTone::Default,
+        ));
+        self.refresh_batch_rename_preview();
+        self.state.status_line = format!("batch rename: {count} item(s)");
+        Ok(true)
+    }
+
+    /// Recomputes the preview after an edit. A rule that does not parse keeps
+    /// the last preview and shows why.
+    fn refresh_batch_rename_preview(&mut self) {
+        let (Some(pending), Some(dialog)) = (
+            self.pending_batch_rename.as_ref(),
+            self.state.dialog.as_mut(),
+        ) else {
+            return;
+        };
+        let previews = match batch_rename_rule(dialog) {
+            Ok(rule) => preview_renames(&rule, &pending.names, &pending.existing),
+            Err(err) => {
+                dialog.error = Some(err.to_string());
+                return;
+            }
+        };
+
+        let changed = previews
+            .iter()
+            .filter(|preview| preview.is_change())
+            .count();
+        let conflicts = previews
+            .iter()
+            .filter(|preview| preview.problem.is_some())
+            .count();
+        let mut list = DialogList {
+            title: format!("Preview: {changed} of {} renamed", previews.len()),
+            lines: previews.iter().map(rename_preview_line).collect(),
+            offset: dialog.list.as_ref().map_or(0, |list| list.offset),
+            selected: None,
+        };
+        list.scroll(0);
+        dialog.list = Some(list);
+        dialog.error = (conflicts > 0).then(|| format!("{conflicts} name(s) conflict"));
+    }
+
+    fn apply_batch_rename(&mut self) -> bool {
+        let Some(pending) = self.pending_batch_rename.take() else {
+            return true;
+        };
+        let Some(dialog) = self.state.dialog.as_ref() else {
+            return true;
+        };
+        let previews = match batch_rename_rule(dialog) {
+            Ok(rule) => preview_renames(&rule, &pending.names, &pending.existing),
+            Err(_) => {
+                // The error is already on screen; keep the form open.
+                self.pending_batch_rename = Some(pending);
+                return true;
+            }
+        };
+        if previews.iter().any(|preview| preview.problem.is_some()) {
+            self.pending_batch_rename = Some(pending);
+            return true;
+        }
+
+        self.state.dialog = None;
+        let items = pending
+            .paths
+            .iter()
+            .zip(&previews)
+            .filter(|(_, preview)| preview.is_change())
+            .map(|(path, preview)| BatchOpItem {
+                source: path.clone(),
+                destination: Some(path.with_file_name(&preview.new)),
+                name: preview.old.clone(),
+                size_bytes: 0,
+                overwrite_destination: false,
+                attributes: None,
+            })
+            .collect::<Vec<_>>();
+        if items.is_empty() {
+            self.state.status_line = "batch rename: nothing to change".to_string();
+            return true;
+        }
+
+        let batch_id = self.next_batch_id;
+        self.next_batch_id += 1;
+        let plan = BatchPlan {
+            batch_id,
+            kind: JobKind::Rename,
+            summary: batch_summary(JobKind::Rename, items.len(), 0, 0, Path::new("")),
+            items,
+        };
+        match self.execute_batch_plan(plan) {
+            Ok(redraw) => redraw,
+            Err(err) => {
+                self.show_alert(err.to_string());
+                true
+            }
+        }
+    }
+
+    fn queue_delete(&mut self) -> Result<bool> {
+        if self.active_panel().trash_view.is_some() {
+            return self.confirm_purge_selected_trash();
         }
+        self.queue_removal(false)
+    }
 
-        if let Some(plan) = self.build_batch_plan_from_selection(JobKind::Move)? {
-            self.state.dialog = Some(confirm_dialog(plan.summary.clone()));
-            self.pending_confirmation = Some(PendingConfirmation::Batch(plan));
-            return Ok(true);
+    fn queue_delete_permanently(&mut self) -> Result<bool> {
+        if self.active_panel().trash_view.is_some() {
+            return self.confirm_purge_selected_trash();
         }
-
-        let entry = self.selected_action_target_entry()?;
-        self.open_rename_prompt(JobKind::Move, &entry)
+        self.queue_removal(true)
     }
 
-    fn queue_delete(&mut self) -> Result<bool> {
+    /// Local items go to the trash unless `permanent` is set; other backends
+    /// have no trash, so their delete is always permanent.
+    fn queue_removal(&mut self, permanent: bool) -> Result<bool> {
         if is_archive_backend(self.active_backend_spec()) {
             self.show_alert("delete inside archive VFS is not supported (read-only)");
             return Ok(true);
         }
 
-        if let Some(plan) = self.build_batch_plan_from_selection(JobKind::Delete)? {
+        let kind = if !permanent && matches!(self.active_backend_spec(), BackendSpec::Local) {
+            JobKind::Trash
+        } else {
+            JobKind::Delete
+        };
+        if let Some(plan) = self.build_batch_plan_from_selection(kind)? {
             self.state.dialog = Some(confirm_dialog(plan.summary.clone()));
             self.pending_confirmation = Some(PendingConfirmation::Batch(plan));
             return Ok(true);
@@ -1718,20 +3936,25 @@ impl App {
         self.guard_delete_target(&path)?;
 
         self.pending_confirmation = Some(PendingConfirmation::DeleteOne {
+            kind,
             path,
             name: entry.name.clone(),
             is_directory: entry.entry_type == FsEntryType::Directory,
         });
-        self.state.dialog = Some(confirm_dialog(
+        self.state.dialog = Some(confirm_dialog(if kind == JobKind::Trash {
             if entry.entry_type == FsEntryType::Directory {
-                format!(
-                    "Delete directory '{}' recursively and permanently?",
-                    entry.name
-                )
+                format!("Move directory '{}' to trash?", entry.name)
             } else {
-                format!("Delete '{}' permanently?", entry.name)
-            },
-        ));
+                format!("Move '{}' to trash?", entry.name)
+            }
+        } else if entry.entry_type == FsEntryType::Directory {
+            format!(
+                "Delete directory '{}' recursively and permanently?",
+                entry.name
+            )
+        } else {
+            format!("Delete '{}' permanently?", entry.name)
+        }));
         Ok(true)
     }
 
@@ -1766,7 +3989,7 @@ impl App {
             }
 
             let destination = match kind {
-                JobKind::Copy | JobKind::Move => {
+                JobKind::Copy | JobKind::Move | JobKind::Symlink | JobKind::Hardlink => {
                     let target = destination_dir.join(&entry.name);
                     if source == target {
                         return Err(anyhow::anyhow!(
@@ -1783,10 +4006,16 @@ impl App {
                     }
                     Some(target)
                 }
-                JobKind::Delete | JobKind::Mkdir => None,
+                JobKind::Rename
+                | JobKind::Delete
+                | JobKind::Mkdir
+                | JobKind::Trash
+                | JobKind::Restore
+                | JobKind::Purge
+                | JobKind::Chattr => None,
             };
 
-            if kind == JobKind::Delete {
+            if matches!(kind, JobKind::Delete | JobKind::Trash) {
                 self.guard_delete_target(&source)?;
             }
 
@@ -1798,7 +4027,9 @@ impl App {
                 source,
                 destination,
                 name: entry.name,
+                size_bytes: entry.size_bytes,
                 overwrite_destination: false,
+                attributes: None,
             });
         }
 
@@ -1829,6 +4060,9 @@ impl App {
     }
 
     fn queue_mkdir(&mut self) -> Result<bool> {
+        if self.active_panel().trash_view.is_some() {
+            return self.open_trash_purge_prompt();
+        }
         if is_archive_backend(self.active_backend_spec()) {
             self.show_alert("mkdir inside archive VFS is not supported (read-only)");
             return Ok(true);
@@ -1843,16 +4077,8 @@ impl App {
             .unwrap_or_else(|| "new_dir".to_string());
 
         self.input_mode = None;
-        self.pending_confirmation = None;
-        self.pending_rename = None;
-        self.pending_mask = None;
+        self.clear_pending_prompts();
         self.pending_mkdir = Some(PendingMkdir { panel_id, base_dir });
-        self.pending_sftp_connect = None;
-        self.pending_bookmark = None;
-        self.pending_conflict = None;
-        self.pending_find = None;
-        self.pending_editor_choice = None;
-        self.pending_viewer_search = false;
         self.state.dialog = Some(input_dialog(
             "Create directory",
             "Enter directory name",
@@ -1865,16 +4091,22 @@ impl App {
 
     fn open_rename_prompt(&mut self, kind: JobKind, entry: &FsEntry) -> Result<bool> {
         self.input_mode = None;
-        self.pending_confirmation = None;
-        self.pending_mask = None;
-        self.pending_mkdir = None;
-        self.pending_sftp_connect = None;
-        self.pending_bookmark = None;
-        self.pending_conflict = None;
-        self.pending_find = None;
-        self.pending_editor_choice = None;
-        self.pending_viewer_search = false;
-        let destination_dir = self.inactive_panel_cwd();
+        self.clear_pending_prompts();
+        // A rename stays next to the entry, which in a find view need not be
+        // the panel's directory.
+        let destination_dir = match kind {
+            JobKind::Rename => entry
+                .path
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| self.active_panel().cwd.clone()),
+            _ => self.inactive_panel_cwd(),
+        };
+        let link_body = format!(
+            "Link '{}' into {} as:",
+            entry.name,
+            destination_dir.display()
+        );
         self.pending_rename = Some(PendingRename {
             kind,
             source_path: entry.path.clone(),
@@ -1885,19 +4117,37 @@ impl App {
         let verb = match kind {
             JobKind::Copy => "Copy as",
             JobKind::Move => "Move as",
-            _ => "Rename as",
+            JobKind::Symlink => "Symlink as",
+            JobKind::Hardlink => "Hardlink as",
+            _ => "Rename",
         };
         let body = match kind {
             JobKind::Copy => format!("Copy '{}' with new name:", entry.name),
             JobKind::Move => format!("Move '{}' with new name:", entry.name),
-            _ => "Edit target name:".to_string(),
+            JobKind::Symlink | JobKind::Hardlink => link_body,
+            _ => format!("Rename '{}' to:", entry.name),
         };
-        self.state.dialog = Some(input_dialog(
-            verb,
-            body.as_str(),
-            entry.name.clone(),
-            DialogTone::Default,
-        ));
+        self.state.dialog = Some(match kind {
+            JobKind::Copy | JobKind::Move => copy_options_dialog(
+                kind,
+                body,
+                Some(entry.name.clone()),
+                &self.state.copy_options,
+            ),
+            JobKind::Symlink | JobKind::Hardlink => {
+                link_dialog(kind, body, Some(entry.name.clone()))
+            }
+            _ => {
+                let mut dialog =
+                    input_dialog(verb, body.as_str(), String::new(), DialogTone::Default);
+                let stem_len = match entry.entry_type {
+                    FsEntryType::Directory => entry.name.len(),
+                    _ => file_stem_len(&entry.name),
+                };
+                dialog.input = Some(TextInput::with_selection(entry.name.clone(), 0..stem_len));
+                dialog
+            }
+        });
         self.state.status_line = format!("{verb}: {}", entry.name);
         Ok(true)
     }
@@ -1921,58 +4171,141 @@ impl App {
         queued_message: impl Into<String>,
         log_message: bool,
     ) -> Result<bool> {
-        let queued_message = queued_message.into();
-        let source_backend = self.active_backend_spec().clone();
+        let request = self.job_request(kind, source, destination, batch_id);
+        self.submit_job_request(request, queued_message.into(), log_message)
+            .map(|_| true)
+    }
+
+    fn job_request(
+        &self,
+        kind: JobKind,
+        source: PathBuf,
+        destination: Option<PathBuf>,
+        batch_id: Option<u64>,
+    ) -> JobRequest {
+        // Trash items always live on the local filesystem, whatever the panel shows.
+        let source_backend = match kind {
+            JobKind::Restore | JobKind::Purge => BackendSpec::Local,
+            _ => self.active_backend_spec().clone(),
+        };
         let destination_backend = match kind {
             JobKind::Copy | JobKind::Move => Some(self.inactive_backend_spec().clone()),
-            JobKind::Delete | JobKind::Mkdir => None,
+            JobKind::Rename | JobKind::Symlink | JobKind::Hardlink => {
+                Some(self.active_backend_spec().clone())
+            }
+            JobKind::Restore => Some(BackendSpec::Local),
+            JobKind::Delete
+            | JobKind::Mkdir
+            | JobKind::Trash
+            | JobKind::Purge
+            | JobKind::Chattr => None,
         };
-        let request = JobRequest {
+        JobRequest {
             id: self.next_job_id,
             batch_id,
             kind,
             source_backend,
             destination_backend,
-            source: source.clone(),
-            destination: destination.clone(),
-        };
+            source,
+            destination,
+            attributes: None,
+            copy_options: self.state.copy_options,
+            control: JobControl::default(),
+        }
+    }
+
+    /// Counts `total` bytes for `job_id` in its batch until the job reports its
+    /// own progress.
+    fn seed_job_bytes(&mut self, batch_id: u64, job_id: u64, total: u64) {
+        if let Some(progress) = self.batch_progress.get_mut(&batch_id) {
+            progress.job_bytes.entry(job_id).or_insert(ByteProgress {
+                total,
+                ..ByteProgress::default()
+            });
+        }
+    }
+
+    /// Registers `request` under the next job id and hands it to the workers,
+    /// returning that id. The request is kept so the job manager can resubmit
+    /// it later.
+    fn submit_job_request(
+        &mut self,
+        mut request: JobRequest,
+        queued_message: String,
+        log_message: bool,
+    ) -> Result<u64> {
+        request.id = self.next_job_id;
         self.next_job_id += 1;
+        self.job_controls
+            .insert(request.id, request.control.clone());
+        self.job_requests.insert(request.id, request.clone());
 
         self.state.jobs.push(Job {
             id: request.id,
-            batch_id,
+            batch_id: request.batch_id,
             kind: request.kind,
             status: JobStatus::Queued,
-            source,
-            destination,
+            source: request.source.clone(),
+            destination: request.destination.clone(),
             current_item: None,
             batch_completed: None,
             batch_total: None,
-            message: Some(queued_message.to_string()),
+            bytes: None,
+            error_class: None,
+            message: Some(queued_message.clone()),
         });
         if log_message {
             self.push_log(queued_message);
         }
+        let id = request.id;
         self.workers.submit(request)?;
-        Ok(true)
+        Ok(id)
     }
 
     fn execute_batch_plan(&mut self, plan: BatchPlan) -> Result<bool> {
         match plan.kind {
-            JobKind::Copy | JobKind::Move => {
-                self.start_conflict_resolution(plan.kind, Some(plan.batch_id), plan.items)
-            }
-            JobKind::Delete | JobKind::Mkdir => {
-                self.enqueue_batch_jobs(plan.kind, Some(plan.batch_id), plan.items, 0)
+            JobKind::Copy
+            | JobKind::Move
+            | JobKind::Rename
+            | JobKind::Restore
+            | JobKind::Symlink
+            | JobKind::Hardlink => self.start_conflict_resolution(
+                plan.kind,
+                Some(plan.batch_id),
+                plan.items,
+                self.state.copy_options,
+                None,
+            ),
+            JobKind::Delete | JobKind::Mkdir | JobKind::Trash | JobKind::Purge => self
+                .enqueue_batch_jobs(
+                    plan.kind,
+                    Some(plan.batch_id),
+                    plan.items,
+                    0,
+                    self.state.copy_options,
+                ),
+            JobKind::Chattr => {
+                self.state.properties = None;
+                self.enqueue_batch_jobs(
+                    plan.kind,
+                    Some(plan.batch_id),
+                    plan.items,
+                    0,
+                    self.state.copy_options,
+                )
             }
         }
     }
 
+    /// Walks `items` asking about existing destinations; `policy` answers
+    /// every conflict up front instead.
     fn start_conflict_resolution(
         &mut self,
         kind: JobKind,
         batch_id: Option<u64>,
         items: Vec<BatchOpItem>,
+        copy_options: CopyOptions,
+        policy: Option<ConflictPolicy>,
     ) -> Result<bool> {
         if items.is_empty() {
             return Ok(false);
@@ -1985,7 +4318,10 @@ impl App {
             next_index: 0,
             ready: Vec::new(),
             skipped: 0,
-            apply_all: None,
+            apply_all: policy,
+            copy_options,
+            awaiting_dive: false,
+            no_dive: None,
         });
         self.continue_conflict_resolution()
     }
@@ -2009,13 +4345,38 @@ impl App {
                 continue;
             };
 
-            if !self.path_exists_on_backend(self.inactive_backend(), destination.as_path()) {
+            if !self.path_exists_on_backend(
+                self.destination_backend(pending.kind).as_ref(),
+                destination.as_path(),
+            ) {
                 pending.ready.push(current);
                 pending.next_index += 1;
                 self.pending_conflict = Some(pending);
                 continue;
             }
 
+            if pending.kind == JobKind::Copy
+                && pending.copy_options.dive_into_existing
+                && pending.no_dive != Some(pending.next_index)
+            {
+                // Telling directory pairs apart takes a listing, so it runs
+                // off the UI thread and resolution resumes when it answers.
+                spawn_dive_listing(
+                    DiveRequest {
+                        source_backend: self.shared_backend(self.state.active_panel),
+                        destination_backend: self.destination_backend(pending.kind),
+                        source: current.source.clone(),
+                        destination: destination.clone(),
+                        follow_symlinks: pending.copy_options.follow_symlinks,
+                    },
+                    self.event_tx.clone(),
+                );
+                pending.awaiting_dive = true;
+                self.pending_conflict = Some(pending);
+                self.state.status_line = format!("Checking {}", destination.display());
+                return Ok(true);
+            }
+
             if let Some(policy) = pending.apply_all {
                 self.pending_conflict = Some(pending);
                 let action = match policy {
@@ -2032,7 +4393,8 @@ impl App {
                 pending.next_index + 1,
                 pending.items.len()
             );
-            let body = self.build_conflict_dialog_body(&current, destination.as_path());
+            let body =
+                self.build_conflict_dialog_body(pending.kind, &current, destination.as_path());
             self.state.dialog = Some(conflict_dialog(title, body));
             self.pending_conflict = Some(pending);
             return Ok(true);
@@ -2045,7 +4407,7 @@ impl App {
         };
 
         if action == ConflictAction::Cancel {
-            self.pending_conflict = None;
+            self.clear_pending_prompts();
             self.state.dialog = None;
             self.push_log("conflict resolution canceled");
             return true;
@@ -2104,7 +4466,8 @@ impl App {
                 pending.next_index += 1;
             }
             ConflictAction::Rename => {
-                let renamed = self.suggest_renamed_destination(destination.as_path())?;
+                let renamed =
+                    self.suggest_renamed_destination(pending.kind, destination.as_path())?;
                 item.destination = Some(renamed.clone());
                 pending.ready.push(item);
                 pending.next_index += 1;
@@ -2115,7 +4478,11 @@ impl App {
                 ));
             }
             ConflictAction::OverwriteIfNewer => {
-                if self.should_overwrite_if_newer(item.source.as_path(), destination.as_path())? {
+                if self.should_overwrite_if_newer(
+                    pending.kind,
+                    item.source.as_path(),
+                    destination.as_path(),
+                )? {
                     item.overwrite_destination = true;
                     pending.ready.push(item);
                 } else {
@@ -2135,17 +4502,14 @@ impl App {
     }
 
     fn finalize_conflict_resolution(&mut self, pending: PendingConflict) -> Result<bool> {
-        let destination_backend = backend_from_spec(self.inactive_backend_spec());
+        let destination_backend = self.destination_backend(pending.kind);
         let mut ready = Vec::with_capacity(pending.ready.len());
         let mut skipped = pending.skipped;
 
         for mut item in pending.ready {
             if item.overwrite_destination {
                 if let Some(destination) = item.destination.as_ref() {
-                    if destination_backend
-                        .stat_entry(destination.as_path())
-                        .is_ok()
-                    {
+                    if destination_backend.entry_exists(destination.as_path()) {
                         match destination_backend.remove_path(destination.as_path()) {
                             Ok(()) => {}
                             Err(err) => {
@@ -2164,7 +4528,62 @@ impl App {
             ready.push(item);
         }
 
-        self.enqueue_batch_jobs(pending.kind, pending.batch_id, ready, skipped)
+        self.enqueue_batch_jobs(
+            pending.kind,
+            pending.batch_id,
+            ready,
+            skipped,
+            pending.copy_options,
+        )
+    }
+
+    fn handle_dive_update(&mut self, update: DiveUpdate) -> bool {
+        let Some(mut pending) = self.pending_conflict.take() else {
+            return false;
+        };
+        // A resolution started since the check went out has moved on.
+        let current = pending.items.get(pending.next_index);
+        let answers_current = pending.awaiting_dive
+            && current.is_some_and(|item| {
+                item.source == update.source
+                    && item.destination.as_ref() == Some(&update.destination)
+            });
+        if !answers_current {
+            self.pending_conflict = Some(pending);
+            return false;
+        }
+        pending.awaiting_dive = false;
+
+        match update.result {
+            Ok(Some(children)) => {
+                // Copy into the existing directory: its children take the
+                // item's place and meet their own conflicts one by one.
+                let children = children.into_iter().map(|child| BatchOpItem {
+                    destination: Some(update.destination.join(&child.name)),
+                    source: child.path,
+                    name: child.name,
+                    size_bytes: child.size_bytes,
+                    overwrite_destination: false,
+                    attributes: None,
+                });
+                pending
+                    .items
+                    .splice(pending.next_index..=pending.next_index, children);
+            }
+            Ok(None) => pending.no_dive = Some(pending.next_index),
+            Err(err) => {
+                self.show_alert(format!(
+                    "copy into {} failed: {err}",
+                    update.destination.display()
+                ));
+                return true;
+            }
+        }
+        self.pending_conflict = Some(pending);
+        if let Err(err) = self.continue_conflict_resolution() {
+            self.show_alert(err.to_string());
+        }
+        true
     }
 
     fn enqueue_batch_jobs(
@@ -2173,6 +4592,7 @@ impl App {
         batch_id: Option<u64>,
         items: Vec<BatchOpItem>,
         skipped: usize,
+        copy_options: CopyOptions,
     ) -> Result<bool> {
         if items.is_empty() {
             if skipped > 0 {
@@ -2192,30 +4612,25 @@ impl App {
                 .first()
                 .map(|item| item.name.clone())
                 .unwrap_or_else(|| "-".to_string());
-            self.batch_progress.insert(
-                batch_id,
-                BatchProgress {
-                    kind,
-                    total,
-                    completed: 0,
-                    failed: 0,
-                    current_file: first_file,
-                },
-            );
+            self.batch_progress
+                .insert(batch_id, BatchProgress::new(kind, total, first_file));
             self.sync_visible_batch_progress(Some(batch_id));
 
             for item in items {
-                if let Err(err) = self.enqueue_job_with_options(
-                    kind,
-                    item.source,
-                    item.destination,
-                    Some(batch_id),
-                    format!("{} queued: {}", operation_name(kind), item.name),
-                    false,
-                ) {
-                    self.batch_progress.remove(&batch_id);
-                    self.sync_visible_batch_progress(None);
-                    return Err(err);
+                let queued_message = format!("{} queued: {}", operation_name(kind), item.name);
+                let mut request =
+                    self.job_request(kind, item.source, item.destination, Some(batch_id));
+                request.attributes = item.attributes;
+                request.copy_options = copy_options;
+                match self.submit_job_request(request, queued_message, false) {
+                    // Listing sizes seed the batch byte total until the job
+                    // reports its own.
+                    Ok(job_id) => self.seed_job_bytes(batch_id, job_id, item.size_bytes),
+                    Err(err) => {
+                        self.batch_progress.remove(&batch_id);
+                        self.sync_visible_batch_progress(None);
+                        return Err(err);
+                    }
                 }
             }
 
@@ -2238,14 +4653,9 @@ impl App {
             let mut queued = 0usize;
             for item in items {
                 let queue_message = copy_move_item_message(kind, &item);
-                self.enqueue_job_with_options(
-                    kind,
-                    item.source,
-                    item.destination,
-                    None,
-                    queue_message,
-                    true,
-                )?;
+                let mut request = self.job_request(kind, item.source, item.destination, None);
+                request.copy_options = copy_options;
+                self.submit_job_request(request, queue_message, true)?;
                 queued += 1;
             }
             if skipped > 0 {
@@ -2260,9 +4670,14 @@ impl App {
         }
     }
 
-    fn build_conflict_dialog_body(&self, item: &BatchOpItem, destination: &Path) -> String {
+    fn build_conflict_dialog_body(
+        &self,
+        kind: JobKind,
+        item: &BatchOpItem,
+        destination: &Path,
+    ) -> String {
         let source_meta = self.active_backend().stat_entry(item.source.as_path()).ok();
-        let target_meta = self.inactive_backend().stat_entry(destination).ok();
+        let target_meta = self.destination_backend(kind).stat_entry(destination).ok();
         let source_size = source_meta
             .as_ref()
             .map(|entry| entry.size_bytes)
@@ -2313,7 +4728,7 @@ impl App {
         })
     }
 
-    fn suggest_renamed_destination(&self, destination: &Path) -> Result<PathBuf> {
+    fn suggest_renamed_destination(&self, kind: JobKind, destination: &Path) -> Result<PathBuf> {
         let parent = destination.parent().ok_or_else(|| {
             anyhow::anyhow!(
                 "destination has no parent for rename fallback: {}",
@@ -2325,7 +4740,7 @@ impl App {
             .map(|name| name.to_string_lossy().to_string())
             .ok_or_else(|| anyhow::anyhow!("destination has no file name"))?;
         let (stem, ext) = split_name_and_extension(file_name.as_str());
-        let backend = backend_from_spec(self.inactive_backend_spec());
+        let backend = self.destination_backend(kind);
 
         for idx in 1..=10_000usize {
             let candidate_name = if ext.is_empty() {
@@ -2345,9 +4760,17 @@ impl App {
         ))
     }
 
-    fn should_overwrite_if_newer(&self, source: &Path, destination: &Path) -> Result<bool> {
+    fn should_overwrite_if_newer(
+        &self,
+        kind: JobKind,
+        source: &Path,
+        destination: &Path,
+    ) -> Result<bool> {
         let source_entry = self.active_backend().stat_entry(source)?;
-        let destination_entry = self.inactive_backend().stat_entry(destination)?;
+        // A dangling link has nothing to compare against and is replaced.
+        let Ok(destination_entry) = self.destination_backend(kind).stat_entry(destination) else {
+            return Ok(true);
+        };
         if let Some(ordering) =
             compare_mtime(source_entry.modified_at, destination_entry.modified_at)
         {
@@ -2358,44 +4781,17 @@ impl App {
 
     fn sync_visible_batch_progress(&mut self, preferred_batch_id: Option<u64>) {
         let preferred = preferred_batch_id.and_then(|batch_id| {
-            self.batch_progress.get(&batch_id).map(|progress| {
-                (
-                    batch_id,
-                    progress.kind,
-                    progress.current_file.clone(),
-                    progress.completed,
-                    progress.total,
-                    progress.failed,
-                )
-            })
+            self.batch_progress
+                .get(&batch_id)
+                .map(|progress| progress.snapshot(batch_id))
         });
 
-        let selected = preferred.or_else(|| {
+        self.state.batch_progress = preferred.or_else(|| {
             self.batch_progress
                 .iter()
                 .max_by_key(|(batch_id, _)| *batch_id)
-                .map(|(batch_id, progress)| {
-                    (
-                        *batch_id,
-                        progress.kind,
-                        progress.current_file.clone(),
-                        progress.completed,
-                        progress.total,
-                        progress.failed,
-                    )
-                })
+                .map(|(batch_id, progress)| progress.snapshot(*batch_id))
         });
-
-        self.state.batch_progress = selected.map(
-            |(batch_id, operation, current_file, completed, total, failed)| BatchProgressState {
-                batch_id,
-                operation,
-                current_file,
-                completed,
-                total,
-                failed,
-            },
-        );
     }
 
     fn apply_find_results(
@@ -2418,9 +4814,11 @@ impl App {
         panel_entries.extend(entries);
         let matches = panel_entries.len().saturating_sub(1);
 
+        self.cancel_listing(panel_id);
         {
             let panel = self.panel_mut(panel_id);
             panel.cwd = root.clone();
+            panel.trash_view = None;
             panel.find_view = Some(FindPanelState {
                 kind,
                 root: root.clone(),
@@ -2467,14 +4865,220 @@ impl App {
         {
             self.state.find_progress = None;
         }
-        let panel = self.panel_mut(panel_id);
-        panel.find_view = None;
-        panel.cwd = find_view.root.clone();
-        panel.selected_paths.clear();
-        panel.clear_search();
-        panel.selected_index = 0;
-        panel.clear_selection_anchor();
-        self.reload_panel(panel_id, true)
+        let panel = self.panel_mut(panel_id);
+        panel.find_view = None;
+        panel.trash_view = None;
+        panel.cwd = find_view.root.clone();
+        panel.selected_paths.clear();
+        panel.clear_search();
+        panel.selected_index = 0;
+        panel.clear_selection_anchor();
+        self.reload_panel(panel_id, true)
+    }
+
+    fn open_trash_view(&mut self) -> Result<bool> {
+        let panel_id = self.state.active_panel;
+        if self.panel(panel_id).trash_view.is_some() {
+            return self.reload_panel(panel_id, true);
+        }
+        if !matches!(self.backend_spec(panel_id), BackendSpec::Local) {
+            self.show_alert("trash is available on local panels only");
+            return Ok(true);
+        }
+        let home = trash::home_trash_dir().ok_or_else(|| {
+            anyhow::anyhow!("cannot resolve trash directory (HOME/XDG_DATA_HOME)")
+        })?;
+        if self.panel(panel_id).find_view.is_some() {
+            self.exit_find_view(panel_id)?;
+        }
+
+        let panel = self.panel_mut(panel_id);
+        panel.trash_view = Some(TrashPanelState {
+            return_cwd: panel.cwd.clone(),
+            items: Vec::new(),
+        });
+        panel.cwd = home.root;
+        panel.selected_paths.clear();
+        panel.clear_search();
+        panel.selected_index = 0;
+        panel.clear_selection_anchor();
+        self.reload_panel(panel_id, true)
+    }
+
+    /// Lists every trash directory into the panel; the Modified column shows
+    /// the deletion date from `.trashinfo`.
+    fn load_trash_view(&mut self, panel_id: PanelId, update_status: bool) -> Result<bool> {
+        let items = trash::list_trash();
+        let backend = backend_from_spec(&BackendSpec::Local);
+        let mut entries = items
+            .iter()
+            .filter_map(|item| {
+                let mut entry = backend.stat_entry(&item.trashed_path).ok()?;
+                entry.modified_at = item.deleted_at;
+                Some(entry)
+            })
+            .collect::<Vec<_>>();
+        let count = entries.len();
+
+        let panel = self.panel_mut(panel_id);
+        let Some(trash_view) = panel.trash_view.as_mut() else {
+            return Ok(false);
+        };
+        trash_view.items = items;
+        let return_cwd = trash_view.return_cwd.clone();
+        sort_find_entries(entries.as_mut_slice(), panel.sort_mode);
+        let mut panel_entries = Vec::with_capacity(count.saturating_add(1));
+        panel_entries.push(parent_link_entry(return_cwd));
+        panel_entries.extend(entries);
+        panel.set_entries(panel_entries);
+        panel.error_message = None;
+        if update_status {
+            self.state.status_line = format!("Trash view: {count} item(s)");
+        }
+        Ok(true)
+    }
+
+    fn exit_trash_view(&mut self, panel_id: PanelId) -> Result<bool> {
+        let Some(trash_view) = self.panel_mut(panel_id).trash_view.take() else {
+            return Ok(false);
+        };
+        let panel = self.panel_mut(panel_id);
+        panel.cwd = trash_view.return_cwd;
+        panel.selected_paths.clear();
+        panel.clear_search();
+        panel.selected_index = 0;
+        panel.clear_selection_anchor();
+        self.reload_panel(panel_id, true)
+    }
+
+    /// Marked trash items, or the item under the cursor when nothing is marked.
+    fn selected_trash_items(&self) -> Result<Vec<TrashItem>> {
+        let panel = self.active_panel();
+        let Some(trash_view) = panel.trash_view.as_ref() else {
+            return Ok(Vec::new());
+        };
+        let entries = if panel.selected_paths.is_empty() {
+            vec![self.selected_action_target_entry()?]
+        } else {
+            self.active_panel_selected_entries()
+        };
+        Ok(entries
+            .iter()
+            .filter_map(|entry| trash_view.item(&entry.path).cloned())
+            .collect())
+    }
+
+    fn trash_batch_plan(&mut self, kind: JobKind, items: Vec<TrashItem>) -> Option<BatchPlan> {
+        if items.is_empty() {
+            return None;
+        }
+        let items = items
+            .into_iter()
+            .map(|item| {
+                let name = item
+                    .original_path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| item.original_path.display().to_string());
+                let size_bytes = fs::symlink_metadata(&item.trashed_path)
+                    .map(|metadata| metadata.len())
+                    .unwrap_or(0);
+                BatchOpItem {
+                    source: item.trashed_path,
+                    destination: (kind == JobKind::Restore).then_some(item.original_path),
+                    name,
+                    size_bytes,
+                    overwrite_destination: false,
+                    attributes: None,
+                }
+            })
+            .collect::<Vec<_>>();
+        let total_bytes = items.iter().map(|item| item.size_bytes).sum();
+        let batch_id = self.next_batch_id;
+        self.next_batch_id += 1;
+        Some(BatchPlan {
+            batch_id,
+            kind,
+            summary: batch_summary(kind, items.len(), total_bytes, 0, Path::new("")),
+            items,
+        })
+    }
+
+    fn confirm_trash_plan(&mut self, plan: Option<BatchPlan>) -> Result<bool> {
+        let Some(plan) = plan else {
+            self.push_log("trash: nothing to do");
+            return Ok(true);
+        };
+        self.state.dialog = Some(confirm_dialog(plan.summary.clone()));
+        self.pending_confirmation = Some(PendingConfirmation::Batch(plan));
+        Ok(true)
+    }
+
+    /// Restores go through the regular conflict matrix once confirmed.
+    fn confirm_restore_selected_trash(&mut self) -> Result<bool> {
+        let items = self.selected_trash_items()?;
+        let plan = self.trash_batch_plan(JobKind::Restore, items);
+        self.confirm_trash_plan(plan)
+    }
+
+    fn confirm_purge_selected_trash(&mut self) -> Result<bool> {
+        let items = self.selected_trash_items()?;
+        let plan = self.trash_batch_plan(JobKind::Purge, items);
+        self.confirm_trash_plan(plan)
+    }
+
+    fn confirm_empty_trash(&mut self) -> Result<bool> {
+        let plan = self.trash_batch_plan(JobKind::Purge, trash::list_trash());
+        self.confirm_trash_plan(plan)
+    }
+
+    fn open_trash_purge_prompt(&mut self) -> Result<bool> {
+        self.input_mode = None;
+        self.clear_pending_prompts();
+        self.pending_trash_purge = true;
+        self.state.dialog = Some(input_dialog(
+            "Purge trash",
+            "Permanently delete items trashed more than N days ago",
+            DEFAULT_TRASH_PURGE_DAYS.to_string(),
+            DialogTone::Warning,
+        ));
+        self.state.status_line = "trash purge: enter age in days".to_string();
+        Ok(true)
+    }
+
+    fn apply_trash_purge(&mut self) -> bool {
+        self.clear_pending_prompts();
+        let value = self
+            .state
+            .dialog
+            .as_ref()
+            .and_then(|dialog| dialog.input_text())
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default();
+        self.state.dialog = None;
+
+        let Ok(days) = value.parse::<u64>() else {
+            self.show_alert(format!("invalid number of days: '{value}'"));
+            return true;
+        };
+        let cutoff = SystemTime::now()
+            .checked_sub(Duration::from_secs(days.saturating_mul(SECONDS_PER_DAY)))
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        let items = trash::list_trash()
+            .into_iter()
+            .filter(|item| item.deleted_at.is_some_and(|deleted| deleted <= cutoff))
+            .collect::<Vec<_>>();
+        let Some(plan) = self.trash_batch_plan(JobKind::Purge, items) else {
+            self.push_log(format!("trash: nothing older than {days} day(s)"));
+            return true;
+        };
+        match self.execute_batch_plan(plan) {
+            Ok(redraw) => redraw,
+            Err(err) => {
+                self.show_alert(err.to_string());
+                true
+            }
+        }
     }
 
     fn open_find_result_entry(&mut self, panel_id: PanelId, entry: FsEntry) -> Result<bool> {
@@ -2491,13 +5095,11 @@ impl App {
                 .map(Path::to_path_buf)
                 .unwrap_or_else(|| self.panel(panel_id).cwd.clone())
         };
-        let normalized = self
-            .backend(panel_id)
-            .normalize_existing_path("find_jump", target_dir.as_path())?;
         {
             let panel = self.panel_mut(panel_id);
             panel.find_view = None;
-            panel.cwd = normalized;
+            panel.trash_view = None;
+            panel.cwd = target_dir;
             panel.clear_search();
             panel.selected_paths.clear();
             panel.selected_index = 0;
@@ -2505,14 +5107,7 @@ impl App {
         }
         let redraw = self.reload_panel(panel_id, true)?;
         if entry.entry_type != FsEntryType::Directory {
-            if let Some(position) = self
-                .panel(panel_id)
-                .entries
-                .iter()
-                .position(|candidate| candidate.path == entry.path)
-            {
-                self.panel_mut(panel_id).selected_index = position;
-            }
+            self.select_when_listed(panel_id, entry.path);
         }
         Ok(redraw)
     }
@@ -2535,6 +5130,7 @@ impl App {
         let panel = self.panel_mut(panel_id);
         panel.cwd = normalized;
         panel.find_view = None;
+        panel.trash_view = None;
         panel.search_query.clear();
         panel.selected_index = 0;
         panel.clear_selection_anchor();
@@ -2551,7 +5147,18 @@ impl App {
         }
         let backend_spec = BackendSpec::Sftp(conn.clone());
         let backend = backend_from_spec(&backend_spec);
-        let normalized = backend.normalize_existing_path("connect_sftp", &conn.root_path)?;
+        let normalized = match backend.normalize_existing_path("connect_sftp", &conn.root_path) {
+            Ok(path) => path,
+            Err(err) => {
+                let Some(host_key_err) = err.downcast_ref::<HostKeyError>() else {
+                    return Err(err);
+                };
+                return Ok(self.prompt_host_key(
+                    host_key_err.clone(),
+                    HostKeyResume::Attach { panel_id, conn },
+                ));
+            }
+        };
         self.set_panel_backend(panel_id, backend_spec);
         self.set_active_find_id(panel_id, None);
         if self
@@ -2565,6 +5172,7 @@ impl App {
         let panel = self.panel_mut(panel_id);
         panel.cwd = normalized;
         panel.find_view = None;
+        panel.trash_view = None;
         panel.search_query.clear();
         panel.selected_index = 0;
         panel.clear_selection_anchor();
@@ -2608,6 +5216,7 @@ impl App {
         let panel = self.panel_mut(panel_id);
         panel.cwd = PathBuf::from("/");
         panel.find_view = None;
+        panel.trash_view = None;
         panel.search_query.clear();
         panel.selected_index = 0;
         panel.clear_selection_anchor();
@@ -2695,6 +5304,43 @@ impl App {
                 return Some(self.activate_dialog_button(button_idx));
             }
         }
+        if let Some(redraw) = self.reorder_hotlist(key) {
+            return Some(redraw);
+        }
+        let page = DialogList::HEIGHT as isize;
+        let list = self
+            .state
+            .dialog
+            .as_mut()
+            .and_then(|dialog| dialog.list.as_mut());
+        let pick_list = list.as_ref().is_some_and(|list| list.selected.is_some());
+        let scroll = match key.code {
+            KeyCode::PageUp => Some(-page),
+            KeyCode::PageDown => Some(page),
+            KeyCode::Up if pick_list => Some(-1),
+            KeyCode::Down if pick_list => Some(1),
+            _ => None,
+        };
+        if let (Some(delta), Some(list)) = (scroll, list) {
+            if pick_list {
+                list.move_selection(delta);
+            } else {
+                list.scroll(delta);
+            }
+            return Some(true);
+        }
+        if let Some(redraw) = self.handle_dialog_field_key(key) {
+            if self.pending_batch_rename.is_some() {
+                self.refresh_batch_rename_preview();
+            }
+            return Some(redraw);
+        }
+        if let Some(redraw) = self.edit_dialog_input(key) {
+            if matches!(self.pending_pick, Some(PendingPick::Hotlist { .. })) {
+                self.refresh_hotlist_popup(None);
+            }
+            return Some(redraw);
+        }
 
         match key.code {
             KeyCode::Esc => Some(self.cancel_dialog()),
@@ -2719,14 +5365,83 @@ impl App {
                     .unwrap_or(0);
                 Some(self.activate_dialog_button(button_idx))
             }
-            KeyCode::Backspace => Some(self.edit_dialog_input_backspace()),
-            KeyCode::Char(c)
+            _ => Some(false),
+        }
+    }
+
+    /// Keys for a focused form field: a text input takes the same line editing
+    /// as the dialog's input, Space toggles, Left/Right pick a radio or
+    /// dropdown option, Up/Down move between fields. Returns `None` for keys
+    /// the rest of the dialog handles, such as Enter, Esc and Tab.
+    fn handle_dialog_field_key(&mut self, key: &KeyEvent) -> Option<bool> {
+        let dialog = self.state.dialog.as_mut()?;
+        // Only a focused field takes keys here.
+        dialog.focused_field?;
+        match key.code {
+            KeyCode::Down => {
+                dialog.focus_next();
+                return Some(true);
+            }
+            KeyCode::Up => {
+                dialog.focus_prev();
+                return Some(true);
+            }
+            _ => {}
+        }
+        let field = dialog.focused_field_mut()?;
+        if let Some(input) = field.text_input_mut() {
+            return edit_text_input(input, key);
+        }
+        match key.code {
+            KeyCode::Char(' ') => field.toggle(),
+            KeyCode::Left => field.select_step(-1),
+            KeyCode::Right => field.select_step(1),
+            // Only text fields take typing; the dialog's own input does
+            // while the buttons have focus.
+            KeyCode::Backspace => return Some(false),
+            KeyCode::Char(_)
                 if key.modifiers.is_empty() || key.modifiers == KeyModifiers::SHIFT =>
             {
-                Some(self.edit_dialog_input_char(c))
+                return Some(false);
             }
-            _ => Some(false),
+            _ => return None,
         }
+        Some(true)
+    }
+
+    /// Options chosen in a copy/move form, which also become the defaults for
+    /// the next one, or in a symlink form, which leaves them alone. `None`
+    /// when the dialog is not such a form.
+    fn copy_options_from_dialog(&mut self) -> Option<(CopyOptions, Option<ConflictPolicy>)> {
+        let dialog = self.state.dialog.as_ref()?;
+        let conflict = dialog.selected("conflict")?;
+        let policy = match conflict {
+            1 => Some(ConflictPolicy::Overwrite),
+            2 => Some(ConflictPolicy::Skip),
+            3 => Some(ConflictPolicy::OverwriteIfNewer),
+            _ => None,
+        };
+        if let Some(link_target) = dialog.selected("link_target") {
+            let options = CopyOptions {
+                relative_symlinks: link_target == 1,
+                ..self.state.copy_options
+            };
+            return Some((options, policy));
+        }
+        let options = CopyOptions {
+            follow_symlinks: dialog.checkbox("follow_symlinks"),
+            preserve_attributes: dialog.checkbox("preserve"),
+            // Moves do not offer diving, so they keep the last copy's choice.
+            dive_into_existing: match dialog.field("dive") {
+                Some(_) => dialog.checkbox("dive"),
+                None => self.state.copy_options.dive_into_existing,
+            },
+            stable_symlinks: dialog.checkbox("stable_symlinks"),
+            skip_errors: dialog.checkbox("skip_errors"),
+            ..CopyOptions::default()
+        };
+        self.state.copy_options = options;
+        Some((options, policy))
     }
 
     fn activate_dialog_button(&mut self, button_idx: usize) -> bool {
@@ -2734,12 +5449,35 @@ impl App {
             return self.apply_conflict_dialog_action(button_idx);
         }
 
+        if self.pending_batch_failures.is_some() {
+            return self.apply_batch_failures_action(button_idx);
+        }
+
+        if self.pending_editor_rename.is_some() {
+            return self.apply_editor_rename_action(button_idx);
+        }
+
+        if self.pending_pick.is_some() {
+            return self.apply_pick_action(button_idx);
+        }
+
         let role = self.dialog_button_role(button_idx);
+        if self.pending_trash_purge {
+            return if role == DialogButtonRole::Primary {
+                self.apply_trash_purge()
+            } else {
+                self.clear_pending_prompts();
+                self.state.dialog = None;
+                self.push_log("trash purge canceled");
+                true
+            };
+        }
+
         if self.pending_confirmation.is_some() {
             return if role == DialogButtonRole::Primary {
                 self.apply_confirmation()
             } else {
-                self.pending_confirmation = None;
+                self.clear_pending_prompts();
                 self.state.dialog = None;
                 self.push_log("operation canceled");
                 true
@@ -2750,7 +5488,7 @@ impl App {
             return if role == DialogButtonRole::Primary {
                 self.apply_rename()
             } else {
-                self.pending_rename = None;
+                self.clear_pending_prompts();
                 self.state.dialog = None;
                 self.push_log("copy/move canceled");
                 true
@@ -2761,29 +5499,62 @@ impl App {
             return if role == DialogButtonRole::Primary {
                 self.apply_mask()
             } else {
-                self.pending_mask = None;
+                self.clear_pending_prompts();
                 self.state.dialog = None;
                 self.push_log("mask selection canceled");
                 true
             };
         }
 
+        if self.pending_hotlist_add.is_some() {
+            return if role == DialogButtonRole::Primary {
+                self.apply_hotlist_add()
+            } else {
+                self.clear_pending_prompts();
+                self.state.dialog = None;
+                self.push_log("hotlist add canceled");
+                true
+            };
+        }
+
+        if self.pending_symlink_edit.is_some() {
+            return if role == DialogButtonRole::Primary {
+                self.apply_symlink_edit()
+            } else {
+                self.clear_pending_prompts();
+                self.state.dialog = None;
+                self.push_log("symlink edit canceled");
+                true
+            };
+        }
+
         if self.pending_mkdir.is_some() {
             return if role == DialogButtonRole::Primary {
                 self.apply_mkdir()
             } else {
-                self.pending_mkdir = None;
+                self.clear_pending_prompts();
                 self.state.dialog = None;
                 self.push_log("mkdir canceled");
                 true
             };
         }
 
+        if self.pending_host_key.is_some() {
+            return if role == DialogButtonRole::Primary {
+                self.apply_host_key_trust()
+            } else {
+                self.clear_pending_prompts();
+                self.state.dialog = None;
+                self.push_log("host key rejected");
+                true
+            };
+        }
+
         if self.pending_sftp_connect.is_some() {
             return if role == DialogButtonRole::Primary {
                 self.apply_sftp_connect()
             } else {
-                self.pending_sftp_connect = None;
+                self.clear_pending_prompts();
                 self.state.dialog = None;
                 self.push_log("sftp connect canceled");
                 true
@@ -2794,7 +5565,7 @@ impl App {
             return if role == DialogButtonRole::Primary {
                 self.apply_bookmark()
             } else {
-                self.pending_bookmark = None;
+                self.clear_pending_prompts();
                 self.state.dialog = None;
                 self.push_log("bookmark flow canceled");
                 true
@@ -2805,8 +5576,7 @@ impl App {
             return if role == DialogButtonRole::Primary {
                 self.apply_find()
             } else {
-                self.pending_find = None;
-                self.pending_editor_choice = None;
+                self.clear_pending_prompts();
                 self.state.dialog = None;
                 self.push_log("find canceled");
                 true
@@ -2817,18 +5587,29 @@ impl App {
             return if role == DialogButtonRole::Primary {
                 self.apply_editor_choice()
             } else {
-                self.pending_editor_choice = None;
+                self.clear_pending_prompts();
                 self.state.dialog = None;
                 self.push_log("editor setup canceled");
                 true
             };
         }
 
+        if self.pending_batch_rename.is_some() {
+            return if role == DialogButtonRole::Primary {
+                self.apply_batch_rename()
+            } else {
+                self.clear_pending_prompts();
+                self.state.dialog = None;
+                self.push_log("batch rename canceled");
+                true
+            };
+        }
+
         if self.pending_viewer_search {
             return if role == DialogButtonRole::Primary {
                 self.apply_viewer_search()
             } else {
-                self.pending_viewer_search = false;
+                self.clear_pending_prompts();
                 self.state.dialog = None;
                 self.push_log("viewer search canceled");
                 true
@@ -2840,72 +5621,142 @@ impl App {
     }
 
     fn cancel_dialog(&mut self) -> bool {
+        if let Some(pending) = self.pending_batch_failures.take() {
+            if pending.showing_details {
+                self.show_batch_failures(PendingBatchFailures {
+                    showing_details: false,
+                    ..pending
+                });
+            } else {
+                self.state.dialog = None;
+                self.push_log(format!(
+                    "batch {}: skipped {} failed item(s)",
+                    operation_name(pending.kind),
+                    pending.failures.len()
+                ));
+            }
+            return true;
+        }
+
         if self.pending_conflict.is_some() {
-            self.pending_conflict = None;
+            self.clear_pending_prompts();
             self.state.dialog = None;
             self.push_log("conflict resolution canceled");
             return true;
         }
 
+        if self.pending_trash_purge {
+            self.clear_pending_prompts();
+            self.state.dialog = None;
+            self.push_log("trash purge canceled");
+            return true;
+        }
+
         if self.pending_confirmation.is_some() {
-            self.pending_confirmation = None;
+            self.clear_pending_prompts();
             self.state.dialog = None;
             self.push_log("operation canceled");
             return true;
         }
 
         if self.pending_rename.is_some() {
-            self.pending_rename = None;
+            self.clear_pending_prompts();
             self.state.dialog = None;
             self.push_log("copy/move canceled");
             return true;
         }
 
         if self.pending_mask.is_some() {
-            self.pending_mask = None;
+            self.clear_pending_prompts();
             self.state.dialog = None;
             self.push_log("mask selection canceled");
             return true;
         }
 
         if self.pending_mkdir.is_some() {
-            self.pending_mkdir = None;
+            self.clear_pending_prompts();
             self.state.dialog = None;
             self.push_log("mkdir canceled");
             return true;
         }
 
+        if self.pending_symlink_edit.is_some() {
+            self.clear_pending_prompts();
+            self.state.dialog = None;
+            self.push_log("symlink edit canceled");
+            return true;
+        }
+
+        if let Some(PendingPick::Hotlist {
+            removing: Some(_), ..
+        }) = self.pending_pick
+        {
+            return self.finish_hotlist_removal(false);
+        }
+        if self.pending_pick.is_some() {
+            self.clear_pending_prompts();
+            self.state.dialog = None;
+            return true;
+        }
+
+        if self.pending_hotlist_add.is_some() {
+            self.clear_pending_prompts();
+            self.state.dialog = None;
+            self.push_log("hotlist add canceled");
+            return true;
+        }
+
+        if self.pending_host_key.is_some() {
+            self.clear_pending_prompts();
+            self.state.dialog = None;
+            self.push_log("host key rejected");
+            return true;
+        }
+
         if self.pending_sftp_connect.is_some() {
-            self.pending_sftp_connect = None;
+            self.clear_pending_prompts();
             self.state.dialog = None;
             self.push_log("sftp connect canceled");
             return true;
         }
 
         if self.pending_bookmark.is_some() {
-            self.pending_bookmark = None;
+            self.clear_pending_prompts();
             self.state.dialog = None;
             self.push_log("bookmark flow canceled");
             return true;
         }
 
         if self.pending_find.is_some() {
-            self.pending_find = None;
-            self.pending_editor_choice = None;
+            self.clear_pending_prompts();
             self.state.dialog = None;
             self.push_log("find canceled");
             return true;
         }
 
         if self.pending_editor_choice.is_some() {
-            self.pending_editor_choice = None;
+            self.clear_pending_prompts();
             self.state.dialog = None;
             self.push_log("editor setup canceled");
             return true;
         }
 
+        if self.pending_batch_rename.is_some() {
+            self.clear_pending_prompts();
+            self.state.dialog = None;
+            self.push_log("batch rename canceled");
+            return true;
+        }
+
+        if self.pending_editor_rename.is_some() {
+            self.clear_pending_prompts();
+            self.state.dialog = None;
+            self.push_log("rename in editor canceled");
+            return true;
+        }
+
         if self.pending_viewer_search {
-            self.pending_viewer_search = false;
+            self.clear_pending_prompts();
             self.state.dialog = None;
             self.push_log("viewer search canceled");
             return true;
@@ -2926,22 +5777,36 @@ impl App {
 
     fn apply_confirmation(&mut self) -> bool {
         let confirmation = self.pending_confirmation.take();
+        let form = self.copy_options_from_dialog();
         self.state.dialog = None;
         if let Some(confirmation) = confirmation {
             let result = match confirmation {
                 PendingConfirmation::DeleteOne {
+                    kind,
                     path,
                     name,
                     is_directory,
                 } => {
-                    let description = if is_directory {
+                    let description = if is_directory && kind == JobKind::Delete {
                         format!("delete queued (recursive): {name}")
                     } else {
-                        format!("delete queued: {name}")
+                        format!("{} queued: {name}", operation_name(kind))
                     };
-                    self.enqueue_job(JobKind::Delete, path, None, description)
+                    self.enqueue_job(kind, path, None, description)
                 }
-                PendingConfirmation::Batch(plan) => self.execute_batch_plan(plan),
+                PendingConfirmation::AbortProgress { progress_id } => {
+                    Ok(self.abort_visible_progress(progress_id))
+                }
+                PendingConfirmation::Batch(plan) => match form {
+                    Some((options, policy)) => self.start_conflict_resolution(
+                        plan.kind,
+                        Some(plan.batch_id),
+                        plan.items,
+                        options,
+                        policy,
+                    ),
+                    None => self.execute_batch_plan(plan),
+                },
             };
 
             return match result {
@@ -2958,11 +5823,12 @@ impl App {
 
     fn apply_rename(&mut self) -> bool {
         let pending = self.pending_rename.take();
+        let form = self.copy_options_from_dialog();
         let requested_name = self
             .state
             .dialog
             .as_ref()
-            .and_then(|dialog| dialog.input_value.as_ref())
+            .and_then(|dialog| dialog.input_text())
             .map(|value| value.trim().to_string())
             .unwrap_or_default();
         self.state.dialog = None;
@@ -2979,16 +5845,28 @@ impl App {
             self.show_alert("name cannot contain '/'");
             return true;
         }
+        if pending.kind == JobKind::Rename && requested_name == pending.source_name {
+            return true;
+        }
 
         let destination = pending.destination_dir.join(&requested_name);
+        if matches!(pending.kind, JobKind::Symlink | JobKind::Hardlink)
+            && destination == pending.source_path
+        {
+            self.show_alert("a link cannot take the place of its own target");
+            return true;
+        }
         let item = BatchOpItem {
             source: pending.source_path,
             destination: Some(destination),
             name: pending.source_name,
+            size_bytes: 0,
             overwrite_destination: false,
+            attributes: None,
         };
 
-        match self.start_conflict_resolution(pending.kind, None, vec![item]) {
+        let (options, policy) = form.unwrap_or((self.state.copy_options, None));
+        match self.start_conflict_resolution(pending.kind, None, vec![item], options, policy) {
             Ok(redraw) => redraw,
             Err(err) => {
                 self.show_alert(err.to_string());
@@ -3003,7 +5881,8 @@ impl App {
             .state
             .dialog
             .as_ref()
-            .and_then(|dialog| dialog.input_value.clone())
+            .and_then(|dialog| dialog.input_text())
+            .map(str::to_string)
             .unwrap_or_default();
         self.state.dialog = None;
 
@@ -3033,7 +5912,7 @@ impl App {
             .state
             .dialog
             .as_ref()
-            .and_then(|dialog| dialog.input_value.as_ref())
+            .and_then(|dialog| dialog.input_text())
             .map(|value| value.trim().to_string())
             .unwrap_or_default();
         self.state.dialog = None;
@@ -3072,106 +5951,47 @@ impl App {
     }
 
     fn apply_sftp_connect(&mut self) -> bool {
-        let pending = self.pending_sftp_connect.take();
-        let value = self
-            .state
-            .dialog
-            .as_ref()
-            .and_then(|dialog| dialog.input_value.as_ref())
-            .map(|value| value.trim().to_string())
-            .unwrap_or_default();
-        self.state.dialog = None;
-
-        let Some(mut pending) = pending else {
+        let Some(pending) = self.pending_sftp_connect.take() else {
+            self.state.dialog = None;
             return true;
         };
-        if value.is_empty() {
-            self.show_alert("sftp connect value cannot be empty");
-            return true;
-        }
-
-        match pending.stage {
-            SftpConnectStage::Address => {
-                if value.eq_ignore_ascii_case("local") {
-                    let result = env::current_dir()
-                        .map_err(|err| anyhow::anyhow!("cannot resolve local cwd: {err}"))
-                        .and_then(|cwd| self.attach_panel_to_local(pending.panel_id, cwd));
-                    return match result {
-                        Ok(redraw) => redraw,
-                        Err(err) => {
-                            self.show_alert(format!("sftp connect failed: {err}"));
-                            true
-                        }
-                    };
-                }
-
-                if let Some(name) = value.strip_prefix('@').map(str::trim) {
-                    match self.connect_using_bookmark_name(pending.panel_id, name) {
-                        Ok(redraw) => return redraw,
-                        Err(err) => {
-                            self.show_alert(format!("bookmark connect failed: {err}"));
-                            return true;
-                        }
-                    }
-                }
+        let target = match self.state.dialog.as_ref().map(|dialog| {
+            sftp_target_from_form(dialog, &load_ssh_config(), |host, port, user| {
+                probe_sftp_auth_hint(host, port, user).unwrap_or(SftpAuthHint::Either)
+            })
+        }) {
+            Some(Ok(target)) => target,
+            Some(Err((key, message))) => {
+                self.pending_sftp_connect = Some(pending);
+                return self.reject_dialog_field(key, message);
+            }
+            None => return true,
+        };
+        self.state.dialog = None;
 
-                match parse_sftp_address_input(value.as_str()) {
-                    Ok((user_from_address, host, port, root_path)) => {
-                        pending.draft.host = host;
-                        pending.draft.port = port;
-                        pending.draft.root_path = root_path;
-                        pending.draft.user = user_from_address;
-                    }
+        match target {
+            SftpConnectTarget::Local => {
+                let result = env::current_dir()
+                    .map_err(|err| anyhow::anyhow!("cannot resolve local cwd: {err}"))
+                    .and_then(|cwd| self.attach_panel_to_local(pending.panel_id, cwd));
+                match result {
+                    Ok(redraw) => redraw,
                     Err(err) => {
                         self.show_alert(format!("sftp connect failed: {err}"));
-                        return true;
+                        true
                     }
                 }
-
-                if pending.draft.user.is_none() {
-                    return self.prompt_sftp_login(pending);
-                }
-                self.proceed_sftp_auth_flow(pending)
             }
-            SftpConnectStage::Login => {
-                pending.draft.user = Some(value.trim().to_string());
-                self.proceed_sftp_auth_flow(pending)
-            }
-            SftpConnectStage::Password => {
-                let Some(user) = pending.draft.user.clone() else {
-                    self.show_alert("sftp connect failed: login is missing");
-                    return true;
-                };
-                let conn = SftpConnectionInfo {
-                    host: pending.draft.host.clone(),
-                    user,
-                    port: pending.draft.port,
-                    root_path: pending.draft.root_path.clone(),
-                    auth: SftpAuth::Password(value),
-                };
-                match self.attach_panel_to_sftp(pending.panel_id, conn) {
+            SftpConnectTarget::Bookmark(name) => {
+                match self.connect_using_bookmark_name(pending.panel_id, name.as_str()) {
                     Ok(redraw) => redraw,
                     Err(err) => {
-                        self.show_alert(format!("sftp connect failed: {err}"));
+                        self.show_alert(format!("bookmark connect failed: {err}"));
                         true
                     }
                 }
             }
-            SftpConnectStage::KeyPath => {
-                let Some(user) = pending.draft.user.clone() else {
-                    self.show_alert("sftp connect failed: login is missing");
-                    return true;
-                };
-                let conn = SftpConnectionInfo {
-                    host: pending.draft.host.clone(),
-                    user,
-                    port: pending.draft.port,
-                    root_path: pending.draft.root_path.clone(),
-                    auth: SftpAuth::KeyFile {
-                        path: expand_tilde_path(value.as_str()),
-                        passphrase: None,
-                    },
-                };
+            SftpConnectTarget::Sftp(conn) => {
                 match self.attach_panel_to_sftp(pending.panel_id, conn) {
                     Ok(redraw) => redraw,
                     Err(err) => {
@@ -3183,26 +6003,37 @@ impl App {
         }
     }
 
+    /// Keeps the current form open with `message` next to the field at fault.
+    fn reject_dialog_field(&mut self, key: &str, message: String) -> bool {
+        self.state.status_line = message.clone();
+        if let Some(dialog) = self.state.dialog.as_mut() {
+            dialog.reject(key, message);
+        }
+        true
+    }
+
     fn apply_bookmark(&mut self) -> bool {
-        let pending = self.pending_bookmark.take();
+        let Some(pending) = self.pending_bookmark.take() else {
+            self.state.dialog = None;
+            return true;
+        };
+        if pending.stage == BookmarkStage::Form {
+            return self.apply_bookmark_form(pending);
+        }
         let value = self
             .state
             .dialog
             .as_ref()
-            .and_then(|dialog| dialog.input_value.as_ref())
+            .and_then(|dialog| dialog.input_text())
             .map(|value| value.trim().to_string())
             .unwrap_or_default();
         self.state.dialog = None;
 
-        let Some(pending) = pending else {
-            return true;
-        };
-
         let result = match pending.action {
             BookmarkAction::Connect => self.bookmark_apply_connect(pending, value),
-            BookmarkAction::Add => self.bookmark_apply_add(pending, value),
             BookmarkAction::Edit => self.bookmark_apply_edit(pending, value),
             BookmarkAction::Delete => self.bookmark_apply_delete(pending, value),
+            BookmarkAction::Add => Err(anyhow::anyhow!("invalid stage for bookmark add")),
         };
 
         match result {
@@ -3224,169 +6055,72 @@ impl App {
         self.connect_using_bookmark_name(pending.panel_id, value.as_str())
     }
 
-    fn bookmark_apply_add(&mut self, mut pending: PendingBookmark, value: String) -> Result<bool> {
-        match pending.stage {
-            BookmarkStage::Name => {
-                if value.is_empty() {
-                    bail!("bookmark name cannot be empty");
-                }
-                let bookmarks = load_sftp_bookmarks()?;
-                if bookmarks.iter().any(|bookmark| bookmark.name == value) {
-                    bail!("bookmark '{}' already exists", value);
-                }
-                pending.draft.name = value;
-                pending.stage = BookmarkStage::Address;
-                self.pending_bookmark = Some(pending);
-                self.state.dialog = Some(input_dialog(
-                    "Bookmark Add",
-                    "Server address host[:port][/path]",
-                    "example.com:22/".to_string(),
-                    DialogTone::Default,
-                ));
-                self.state.status_line = "bookmark add: enter server".to_string();
-                Ok(true)
-            }
-            BookmarkStage::Address => {
-                let (user, host, port, root_path) = parse_sftp_address_input(value.as_str())?;
-                pending.draft.host = host;
-                pending.draft.port = port;
-                pending.draft.root_path = root_path;
-                if let Some(user) = user {
-                    pending.draft.user = user;
-                }
-                pending.stage = BookmarkStage::Login;
-                let default_login = if pending.draft.user.is_empty() {
-                    env::var("USER").unwrap_or_default()
-                } else {
-                    pending.draft.user.clone()
-                };
-                self.pending_bookmark = Some(pending);
-                self.state.dialog = Some(input_dialog(
-                    "Bookmark Add",
-                    "Login name",
-                    default_login,
-                    DialogTone::Default,
-                ));
-                self.state.status_line = "bookmark add: enter login".to_string();
-                Ok(true)
-            }
-            BookmarkStage::Login => {
-                if value.is_empty() {
-                    bail!("login cannot be empty");
-                }
-                pending.draft.user = value;
-                pending.stage = BookmarkStage::Password;
-                self.pending_bookmark = Some(pending);
-                self.state.dialog = Some(input_dialog_with_mask(
-                    "Bookmark Add",
-                    "Password (optional, leave empty for SSH agent auth)",
-                    String::new(),
-                    DialogTone::Warning,
-                    true,
-                ));
-                self.state.status_line = "bookmark add: enter password".to_string();
-                Ok(true)
-            }
-            BookmarkStage::Password => {
-                pending.draft.password = if value.is_empty() { None } else { Some(value) };
-                let mut bookmarks = load_sftp_bookmarks()?;
-                bookmarks.push(pending.draft.clone());
-                save_sftp_bookmarks(bookmarks.as_slice())?;
-                self.push_log(format!("bookmark added: {}", pending.draft.name));
-                Ok(true)
-            }
-            BookmarkStage::ConfirmDelete => bail!("invalid stage for bookmark add"),
-        }
-    }
-
     fn bookmark_apply_edit(&mut self, mut pending: PendingBookmark, value: String) -> Result<bool> {
-        match pending.stage {
-            BookmarkStage::Name => {
-                if value.is_empty() {
-                    bail!("bookmark name cannot be empty");
-                }
-                let bookmarks = load_sftp_bookmarks()?;
-                let bookmark = bookmarks
-                    .into_iter()
-                    .find(|bookmark| bookmark.name == value)
-                    .ok_or_else(|| anyhow::anyhow!("bookmark '{}' not found", value))?;
-                let default_address = format!(
-                    "{}:{}{}",
-                    bookmark.host.as_str(),
-                    bookmark.port,
-                    bookmark.root_path.display()
-                );
-                pending.lookup_name = Some(bookmark.name.clone());
-                pending.draft = bookmark;
-                pending.stage = BookmarkStage::Address;
-                self.pending_bookmark = Some(pending);
-                self.state.dialog = Some(input_dialog(
-                    "Bookmark Edit",
-                    "Server address host[:port][/path]",
-                    default_address,
-                    DialogTone::Default,
-                ));
-                self.state.status_line = "bookmark edit: update server".to_string();
-                Ok(true)
+        if pending.stage != BookmarkStage::Name {
+            bail!("invalid stage for bookmark edit");
+        }
+        if value.is_empty() {
+            bail!("bookmark name cannot be empty");
+        }
+        let bookmarks = load_sftp_bookmarks()?;
+        let bookmark = bookmarks
+            .into_iter()
+            .find(|bookmark| bookmark.name == value)
+            .ok_or_else(|| anyhow::anyhow!("bookmark '{}' not found", value))?;
+        self.state.dialog = Some(bookmark_form_dialog("Bookmark Edit", &bookmark));
+        pending.lookup_name = Some(bookmark.name.clone());
+        pending.draft = bookmark;
+        pending.stage = BookmarkStage::Form;
+        self.pending_bookmark = Some(pending);
+        self.state.status_line = "bookmark edit: update the form".to_string();
+        Ok(true)
+    }
+
+    /// Saves the add/edit form, or keeps it open when a field is invalid.
+    fn apply_bookmark_form(&mut self, pending: PendingBookmark) -> bool {
+        let mut bookmarks = match load_sftp_bookmarks() {
+            Ok(bookmarks) => bookmarks,
+            Err(err) => {
+                self.state.dialog = None;
+                self.show_alert(format!("bookmark failed: {err}"));
+                return true;
             }
-            BookmarkStage::Address => {
-                let (user, host, port, root_path) = parse_sftp_address_input(value.as_str())?;
-                pending.draft.host = host;
-                pending.draft.port = port;
-                pending.draft.root_path = root_path;
-                if let Some(user) = user {
-                    pending.draft.user = user;
-                }
-                pending.stage = BookmarkStage::Login;
-                let login = pending.draft.user.clone();
+        };
+        let lookup_name = pending.lookup_name.as_deref();
+        let draft = match self
+            .state
+            .dialog
+            .as_ref()
+            .map(|dialog| bookmark_from_form(dialog, &bookmarks, lookup_name))
+        {
+            Some(Ok(draft)) => draft,
+            Some(Err((key, message))) => {
                 self.pending_bookmark = Some(pending);
-                self.state.dialog = Some(input_dialog(
-                    "Bookmark Edit",
-                    "Login name",
-                    login,
-                    DialogTone::Default,
-                ));
-                self.state.status_line = "bookmark edit: update login".to_string();
-                Ok(true)
+                return self.reject_dialog_field(key, message);
             }
-            BookmarkStage::Login => {
-                if value.is_empty() {
-                    bail!("login cannot be empty");
+            None => return true,
+        };
+        self.state.dialog = None;
+
+        let verb = match lookup_name {
+            Some(lookup_name) => {
+                for bookmark in bookmarks.iter_mut() {
+                    if bookmark.name == lookup_name {
+                        *bookmark = draft.clone();
+                    }
                 }
-                pending.draft.user = value;
-                pending.stage = BookmarkStage::Password;
-                let existing_password = pending.draft.password.clone().unwrap_or_default();
-                self.pending_bookmark = Some(pending);
-                self.state.dialog = Some(input_dialog_with_mask(
-                    "Bookmark Edit",
-                    "Password (optional, leave empty for SSH agent auth)",
-                    existing_password,
-                    DialogTone::Warning,
-                    true,
-                ));
-                self.state.status_line = "bookmark edit: update password".to_string();
-                Ok(true)
+                "updated"
             }
-            BookmarkStage::Password => {
-                pending.draft.password = if value.is_empty() { None } else { Some(value) };
-                let target_name = pending
-                    .lookup_name
-                    .clone()
-                    .ok_or_else(|| anyhow::anyhow!("bookmark name is missing"))?;
-                let mut bookmarks = load_sftp_bookmarks()?;
-                let Some(existing) = bookmarks
-                    .iter_mut()
-                    .find(|bookmark| bookmark.name == target_name)
-                else {
-                    bail!("bookmark '{}' not found", target_name);
-                };
-                *existing = pending.draft.clone();
-                save_sftp_bookmarks(bookmarks.as_slice())?;
-                self.push_log(format!("bookmark updated: {}", pending.draft.name));
-                Ok(true)
+            None => {
+                bookmarks.push(draft.clone());
+                "added"
             }
-            BookmarkStage::ConfirmDelete => bail!("invalid stage for bookmark edit"),
+        };
+        match save_sftp_bookmarks(bookmarks.as_slice()) {
+            Ok(()) => self.push_log(format!("bookmark {verb}: {}", draft.name)),
+            Err(err) => self.show_alert(format!("bookmark failed: {err}")),
         }
+        true
     }
 
     fn bookmark_apply_delete(
@@ -3409,8 +6143,9 @@ impl App {
                 pending.stage = BookmarkStage::ConfirmDelete;
                 self.pending_bookmark = Some(pending);
                 self.state.dialog = Some(confirm_dialog(format!(
-                    "Delete bookmark '{}' ({}@{}:{})?",
-                    bookmark.name, bookmark.user, bookmark.host, bookmark.port
+                    "Delete bookmark '{}' ({})?",
+                    bookmark.name,
+                    bookmark.endpoint()
                 )));
                 self.state.status_line = "bookmark delete: confirm".to_string();
                 Ok(true)
@@ -3430,34 +6165,16 @@ impl App {
                 self.push_log(format!("bookmark deleted: {}", target_name));
                 Ok(true)
             }
-            BookmarkStage::Address | BookmarkStage::Login | BookmarkStage::Password => {
-                bail!("invalid stage for bookmark delete")
-            }
+            BookmarkStage::Form => bail!("invalid stage for bookmark delete"),
         }
     }
 
     fn connect_using_bookmark_name(&mut self, panel_id: PanelId, name: &str) -> Result<bool> {
-        if name.trim().is_empty() {
-            bail!("bookmark name cannot be empty");
-        }
-        let bookmarks = load_sftp_bookmarks()?;
-        let bookmark = bookmarks
-            .into_iter()
-            .find(|bookmark| bookmark.name == name)
-            .ok_or_else(|| anyhow::anyhow!("bookmark '{}' not found", name))?;
-        let auth = match bookmark.password {
-            Some(password) if !password.is_empty() => SftpAuth::Password(password),
-            _ => SftpAuth::Agent,
-        };
-        let conn = SftpConnectionInfo {
-            host: bookmark.host.clone(),
-            user: bookmark.user.clone(),
-            port: bookmark.port,
-            root_path: bookmark.root_path.clone(),
-            auth,
-        };
+        let conn = bookmark_connection(name)?;
         let redraw = self.attach_panel_to_sftp(panel_id, conn)?;
-        self.push_log(format!("bookmark connected: {}", bookmark.name));
+        if self.pending_host_key.is_none() {
+            self.push_log(format!("bookmark connected: {name}"));
+        }
         Ok(redraw)
     }
 
@@ -3467,7 +6184,7 @@ impl App {
             .state
             .dialog
             .as_ref()
-            .and_then(|dialog| dialog.input_value.as_ref())
+            .and_then(|dialog| dialog.input_text())
             .map(|value| value.trim().to_string())
             .unwrap_or_default();
         self.state.dialog = None;
@@ -3568,7 +6285,7 @@ impl App {
             .state
             .dialog
             .as_ref()
-            .and_then(|dialog| dialog.input_value.as_ref())
+            .and_then(|dialog| dialog.input_text())
             .map(|value| value.trim().to_string())
             .unwrap_or_default();
         self.state.dialog = None;
@@ -3606,6 +6323,15 @@ impl App {
                     }
                 }
             }
+            EditorChoiceContext::RenameNames(rename) => {
+                match self.edit_names_in_editor(selected.command.as_str(), rename) {
+                    Ok(redraw) => redraw,
+                    Err(err) => {
+                        self.show_alert(err.to_string());
+                        true
+                    }
+                }
+            }
             EditorChoiceContext::SettingsOnly => {
                 self.push_log(format!("editor saved: {}", selected.command));
                 if env::var("EDITOR")
@@ -3625,10 +6351,10 @@ impl App {
             .state
             .dialog
             .as_ref()
-            .and_then(|dialog| dialog.input_value.as_ref())
+            .and_then(|dialog| dialog.input_text())
             .map(|value| value.trim().to_string())
             .unwrap_or_default();
-        self.pending_viewer_search = false;
+        self.clear_pending_prompts();
         self.state.dialog = None;
 
         let Some(viewer) = self.state.viewer.as_mut() else {
@@ -3644,127 +6370,104 @@ impl App {
         true
     }
 
-    fn proceed_sftp_auth_flow(&mut self, pending: PendingSftpConnect) -> bool {
-        let Some(user) = pending.draft.user.clone() else {
-            return self.prompt_sftp_login(pending);
-        };
-        if user.trim().is_empty() {
-            return self.prompt_sftp_login(pending);
-        }
-
-        let hint = match probe_sftp_auth_hint(
-            pending.draft.host.as_str(),
-            pending.draft.port,
-            user.as_str(),
-        ) {
-            Ok(hint) => hint,
-            Err(_) => SftpAuthHint::Either,
+    fn prompt_host_key(&mut self, err: HostKeyError, resume: HostKeyResume) -> bool {
+        let info = match err {
+            HostKeyError::Unknown(info) => info,
+            HostKeyError::Changed(info) => {
+                let message = format!(
+                    "WARNING: host key for {} has CHANGED!\n{} {}\nPossible man-in-the-middle attack, connection refused.\nRemove the old entry from ~/.ssh/known_hosts if the change is expected.",
+                    info.endpoint(),
+                    info.key_type,
+                    info.fingerprint
+                );
+                self.show_alert(message.clone());
+                self.state.dialog = Some(host_key_refused_dialog("Host Key Changed", message));
+                self.state.status_line = format!("host key changed: {}", info.endpoint());
+                return true;
+            }
+            HostKeyError::Revoked(info) => {
+                let message = format!(
+                    "WARNING: host key for {} is REVOKED!\n{} {}\nThe key is marked @revoked in ~/.ssh/known_hosts, connection refused.",
+                    info.endpoint(),
+                    info.key_type,
+                    info.fingerprint
+                );
+                self.show_alert(message.clone());
+                self.state.dialog = Some(host_key_refused_dialog("Host Key Revoked", message));
+                self.state.status_line = format!("host key revoked: {}", info.endpoint());
+                return true;
+            }
         };
 
-        if hint == SftpAuthHint::KeyOnly {
-            return self.prompt_sftp_key_path(pending);
-        }
-
-        if hint == SftpAuthHint::PasswordOnly {
-            return self.prompt_sftp_password(pending);
-        }
-
-        self.prompt_sftp_password(pending)
-    }
-
-    fn prompt_sftp_login(&mut self, mut pending: PendingSftpConnect) -> bool {
-        pending.stage = SftpConnectStage::Login;
-        let default_login = pending
-            .draft
-            .user
-            .clone()
-            .unwrap_or_else(|| env::var("USER").unwrap_or_default());
-        self.pending_sftp_connect = Some(pending);
-        self.state.dialog = Some(input_dialog(
-            "SFTP Login",
-            "Enter login name",
-            default_login,
-            DialogTone::Default,
-        ));
-        self.state.status_line = "sftp connect: enter login".to_string();
-        true
-    }
-
-    fn prompt_sftp_password(&mut self, mut pending: PendingSftpConnect) -> bool {
-        pending.stage = SftpConnectStage::Password;
-        self.pending_sftp_connect = Some(pending);
-        self.state.dialog = Some(input_dialog_with_mask(
-            "SFTP Password",
-            "Enter password",
-            String::new(),
-            DialogTone::Warning,
-            true,
-        ));
-        self.state.status_line = "sftp connect: password required".to_string();
+        self.clear_pending_prompts();
+        self.state.dialog = Some(host_key_dialog(format!(
+            "Host {} is not in ~/.ssh/known_hosts.\n{} {}\nTrust this key and add it to known_hosts?",
+            info.endpoint(),
+            info.key_type,
+            info.fingerprint
+        )));
+        self.state.status_line = format!("sftp connect: verify host key for {}", info.endpoint());
+        self.pending_host_key = Some(PendingHostKey { info, resume });
         true
     }
 
-    fn prompt_sftp_key_path(&mut self, mut pending: PendingSftpConnect) -> bool {
-        pending.stage = SftpConnectStage::KeyPath;
-        self.pending_sftp_connect = Some(pending);
-        self.state.dialog = Some(input_dialog(
-            "SFTP Key",
-            "Enter private key path",
-            "~/.ssh/id_rsa".to_string(),
-            DialogTone::Warning,
+    fn apply_host_key_trust(&mut self) -> bool {
+        let pending = self.pending_host_key.take();
+        self.state.dialog = None;
+        let Some(pending) = pending else {
+            return true;
+        };
+        if let Err(err) = trust_host_key(&pending.info) {
+            self.show_alert(format!("known_hosts update failed: {err}"));
+            return true;
+        }
+        self.push_log(format!(
+            "host key trusted: {} {}",
+            pending.info.endpoint(),
+            pending.info.fingerprint
         ));
-        self.state.status_line = "sftp connect: key path required".to_string();
-        true
-    }
 
-    fn edit_dialog_input_backspace(&mut self) -> bool {
-        if self.pending_rename.is_none()
-            && self.pending_mask.is_none()
-            && self.pending_mkdir.is_none()
-            && self.pending_sftp_connect.is_none()
-            && self.pending_bookmark.is_none()
-            && self.pending_find.is_none()
-            && self.pending_editor_choice.is_none()
-            && !self.pending_viewer_search
-        {
-            return false;
-        }
-        if let Some(dialog) = self.state.dialog.as_mut() {
-            if let Some(value) = dialog.input_value.as_mut() {
-                value.pop();
-                return true;
+        match pending.resume {
+            HostKeyResume::Attach { panel_id, conn } => {
+                match self.attach_panel_to_sftp(panel_id, conn) {
+                    Ok(redraw) => redraw,
+                    Err(err) => {
+                        self.show_alert(format!("sftp connect failed: {err}"));
+                        true
+                    }
+                }
             }
         }
-        false
     }
 
-    fn edit_dialog_input_char(&mut self, c: char) -> bool {
-        if self.pending_rename.is_none()
-            && self.pending_mask.is_none()
-            && self.pending_mkdir.is_none()
-            && self.pending_sftp_connect.is_none()
-            && self.pending_bookmark.is_none()
-            && self.pending_find.is_none()
-            && self.pending_editor_choice.is_none()
-            && !self.pending_viewer_search
-        {
-            return false;
-        }
-        if c == '\0' {
-            return false;
-        }
+    fn dialog_input_editable(&self) -> bool {
+        self.pending_rename.is_some()
+            || self.pending_mask.is_some()
+            || self.pending_mkdir.is_some()
+            || self.pending_sftp_connect.is_some()
+            || self.pending_bookmark.is_some()
+            || self.pending_find.is_some()
+            || self.pending_editor_choice.is_some()
+            || self.pending_viewer_search
+            || matches!(self.pending_pick, Some(PendingPick::Hotlist { .. }))
+    }
 
-        if self.pending_rename.is_some() && c == '/' {
-            return true;
+    /// Line editing in the dialog's input (see `edit_text_input`). Returns
+    /// `None` for keys the dialog itself handles.
+    fn edit_dialog_input(&mut self, key: &KeyEvent) -> Option<bool> {
+        if !self.dialog_input_editable() {
+            return None;
         }
-
-        if let Some(dialog) = self.state.dialog.as_mut() {
-            if let Some(value) = dialog.input_value.as_mut() {
-                value.push(c);
-                return true;
-            }
+        // Names are typed into one directory, so a separator would turn the
+        // rename into a move.
+        let typing_name = self.pending_rename.is_some();
+        if typing_name
+            && key.code == KeyCode::Char('/')
+            && (key.modifiers.is_empty() || key.modifiers == KeyModifiers::SHIFT)
+        {
+            return Some(false);
         }
-        false
+        edit_text_input(self.state.dialog.as_mut()?.input.as_mut()?, key)
     }
 
     fn find_dialog_button_by_accelerator(&self, accelerator: char) -> Option<usize> {
@@ -3777,7 +6480,7 @@ impl App {
     }
 
     fn handle_top_menu_input(&mut self, key: &KeyEvent) -> Option<bool> {
-        if self.state.dialog.is_some() || self.state.screen_mode == ScreenMode::Viewer {
+        if self.state.dialog.is_some() || self.state.screen_mode != ScreenMode::Normal {
             return None;
         }
 
@@ -3834,7 +6537,7 @@ impl App {
     }
 
     fn open_top_menu(&mut self) -> Result<bool> {
-        if self.state.dialog.is_some() || self.state.screen_mode == ScreenMode::Viewer {
+        if self.state.dialog.is_some() || self.state.screen_mode != ScreenMode::Normal {
             return Ok(false);
         }
         if self.input_mode.is_some() || self.state.command_line.active {
@@ -3962,18 +6665,57 @@ impl App {
             MenuAction::PanelParent(panel_id) => {
                 self.run_with_panel_focus(panel_id, Self::go_to_parent)
             }
+            MenuAction::PanelNewTab(panel_id) => {
+                self.run_with_panel_focus(panel_id, |app| app.open_tab(false))
+            }
+            MenuAction::PanelDuplicateTab(panel_id) => {
+                self.run_with_panel_focus(panel_id, |app| app.open_tab(true))
+            }
+            MenuAction::PanelCloseTab(panel_id) => {
+                self.run_with_panel_focus(panel_id, Self::close_tab)
+            }
+            MenuAction::PanelHistory(panel_id) => {
+                self.run_with_panel_focus(panel_id, Self::open_history_popup)
+            }
+            MenuAction::PanelHotlist(panel_id) => {
+                self.run_with_panel_focus(panel_id, Self::open_hotlist_popup)
+            }
+            MenuAction::PanelHotlistAdd(panel_id) => {
+                self.run_with_panel_focus(panel_id, Self::open_hotlist_add)
+            }
             MenuAction::PanelCopy(panel_id) => {
                 self.run_with_panel_focus(panel_id, Self::queue_copy)
             }
             MenuAction::PanelMove(panel_id) => {
                 self.run_with_panel_focus(panel_id, Self::queue_move)
             }
+            MenuAction::PanelRename(panel_id) => {
+                self.run_with_panel_focus(panel_id, Self::queue_rename)
+            }
+            MenuAction::PanelBatchRename(panel_id) => {
+                self.run_with_panel_focus(panel_id, Self::open_batch_rename)
+            }
+            MenuAction::PanelEditorRename(panel_id) => {
+                self.run_with_panel_focus(panel_id, Self::open_editor_rename)
+            }
+            MenuAction::PanelSymlink(panel_id) => {
+                self.run_with_panel_focus(panel_id, |app| app.queue_link(JobKind::Symlink))
+            }
+            MenuAction::PanelHardlink(panel_id) => {
+                self.run_with_panel_focus(panel_id, |app| app.queue_link(JobKind::Hardlink))
+            }
+            MenuAction::PanelEditSymlink(panel_id) => {
+                self.run_with_panel_focus(panel_id, Self::open_symlink_edit)
+            }
             MenuAction::PanelDelete(panel_id) => {
                 self.run_with_panel_focus(panel_id, Self::queue_delete)
             }
             MenuAction::PanelMkdir(panel_id) => {
                 self.run_with_panel_focus(panel_id, Self::queue_mkdir)
             }
+            MenuAction::PanelProperties(panel_id) => {
+                self.run_with_panel_focus(panel_id, Self::open_properties)
+            }
             MenuAction::PanelConnectSftp(panel_id) => {
                 self.run_with_panel_focus(panel_id, Self::handle_sftp_action)
             }
@@ -4018,6 +6760,12 @@ impl App {
                 Ok(true)
             }
             MenuAction::EditorSettings => self.open_editor_settings(),
+            MenuAction::JobManager => self.open_job_manager(),
+            MenuAction::PanelTrash(panel_id) => {
+                self.run_with_panel_focus(panel_id, Self::open_trash_view)
+            }
+            MenuAction::EmptyTrash => self.confirm_empty_trash(),
+            MenuAction::PurgeTrash => self.open_trash_purge_prompt(),
         }
     }
 
@@ -4102,7 +6850,7 @@ impl App {
     }
 
     fn handle_command_line_input(&mut self, key: &KeyEvent) -> Option<bool> {
-        if self.state.dialog.is_some() || self.state.screen_mode == ScreenMode::Viewer {
+        if self.state.dialog.is_some() || self.state.screen_mode != ScreenMode::Normal {
             return None;
         }
         if self.input_mode.is_some() {
@@ -4177,6 +6925,106 @@ impl App {
         Some(true)
     }
 
+    fn handle_batch_progress_input(&mut self, key: &KeyEvent) -> Option<bool> {
+        let batch_id = self.state.batch_progress.as_ref()?.batch_id;
+        match key.code {
+            KeyCode::Esc if key.modifiers.is_empty() => Some(self.confirm_abort_progress(batch_id)),
+            KeyCode::Char('p') | KeyCode::Char('P')
+                if key.modifiers.is_empty() || key.modifiers == KeyModifiers::SHIFT =>
+            {
+                Some(self.toggle_pause_visible_progress(batch_id))
+            }
+            _ => None,
+        }
+    }
+
+    fn confirm_abort_progress(&mut self, progress_id: u64) -> bool {
+        let jobs = self.visible_progress_controls(progress_id).len();
+        if jobs == 0 {
+            return false;
+        }
+        let operation = self
+            .state
+            .batch_progress
+            .as_ref()
+            .map(|progress| operation_name(progress.operation))
+            .unwrap_or("job");
+        self.state.dialog = Some(confirm_dialog(format!(
+            "Abort {operation}: {jobs} unfinished job(s)?"
+        )));
+        self.pending_confirmation = Some(PendingConfirmation::AbortProgress { progress_id });
+        true
+    }
+
+    /// Controls of the unfinished jobs behind the progress overlay. The overlay id
+    /// is a batch id while batches are tracked, otherwise the id of a single job.
+    fn visible_progress_controls(&self, progress_id: u64) -> Vec<JobControl> {
+        if !self.batch_progress.contains_key(&progress_id) {
+            return self
+                .job_controls
+                .get(&progress_id)
+                .cloned()
+                .into_iter()
+                .collect();
+        }
+        self.state
+            .jobs
+            .iter()
+            .filter(|job| job.batch_id == Some(progress_id))
+            .filter_map(|job| self.job_controls.get(&job.id))
+            .cloned()
+            .collect()
+    }
+
+    fn abort_visible_progress(&mut self, progress_id: u64) -> bool {
+        let controls = self.visible_progress_controls(progress_id);
+        if controls.is_empty() {
+            return false;
+        }
+        for control in &controls {
+            control.cancel();
+        }
+        let operation = self
+            .state
+            .batch_progress
+            .as_ref()
+            .map(|progress| operation_name(progress.operation))
+            .unwrap_or("job");
+        self.push_log(format!(
+            "{operation} abort requested: {} job(s)",
+            controls.len()
+        ));
+        true
+    }
+
+    fn toggle_pause_visible_progress(&mut self, progress_id: u64) -> bool {
+        let controls = self.visible_progress_controls(progress_id);
+        let Some(progress) = self.state.batch_progress.as_mut() else {
+            return false;
+        };
+        if controls.is_empty() {
+            return false;
+        }
+        let paused = !progress.paused;
+        progress.paused = paused;
+        let operation = operation_name(progress.operation);
+        for control in &controls {
+            if paused {
+                control.pause();
+            } else {
+                control.resume();
+            }
+        }
+        if let Some(batch) = self.batch_progress.get_mut(&progress_id) {
+            batch.paused = paused;
+        }
+        self.push_log(format!(
+            "{operation} {}",
+            if paused { "paused" } else { "resumed" }
+        ));
+        true
+    }
+
     fn execute_command_line(&mut self, raw: &str) -> Result<bool> {
         let command = raw.trim();
         if command.is_empty() {
@@ -4215,16 +7063,13 @@ impl App {
 
         let current = self.active_panel().cwd.clone();
         let candidate = resolve_command_path(trimmed, &current, self.active_backend_spec());
-        let normalized = self
-            .active_backend()
-            .normalize_existing_path("command_cd", &candidate)?;
-
+        self.push_log(format!("cd {}", candidate.display()));
         let panel = self.active_panel_mut();
-        panel.cwd = normalized.clone();
+        panel.cwd = candidate;
         panel.find_view = None;
+        panel.trash_view = None;
         panel.selected_index = 0;
         panel.clear_selection_anchor();
-        self.push_log(format!("cd {}", normalized.display()));
         self.reload_panel(self.state.active_panel, false)
     }
 
@@ -4273,7 +7118,7 @@ impl App {
     }
 
     fn open_shell_mode(&mut self) -> Result<bool> {
-        if self.state.dialog.is_some() || self.state.screen_mode == ScreenMode::Viewer {
+        if self.state.dialog.is_some() || self.state.screen_mode != ScreenMode::Normal {
             return Ok(false);
         }
         if self.input_mode.is_some() || self.state.command_line.active {
@@ -4351,7 +7196,7 @@ impl App {
         refresh_viewer_search(&mut state);
         self.state.viewer = Some(state);
         self.state.screen_mode = ScreenMode::Viewer;
-        self.pending_viewer_search = false;
+        self.clear_pending_prompts();
         self.state.status_line = format!("command output: {command}");
     }
 
@@ -4378,6 +7223,14 @@ impl App {
 
     fn show_alert(&mut self, message: impl Into<String>) {
         let message = message.into();
+        self.clear_pending_prompts();
+        self.state.dialog = Some(alert_dialog(message.clone()));
+        self.push_log(message);
+    }
+
+    /// Closes the menu and drops every prompt waiting for an answer, before a
+    /// dialog that replaces them is shown.
+    fn clear_pending_prompts(&mut self) {
         self.state.top_menu.open = false;
         self.pending_confirmation = None;
         self.pending_rename = None;
@@ -4388,9 +7241,15 @@ impl App {
         self.pending_conflict = None;
         self.pending_find = None;
         self.pending_editor_choice = None;
+        self.pending_host_key = None;
+        self.pending_batch_failures = None;
+        self.pending_trash_purge = false;
         self.pending_viewer_search = false;
-        self.state.dialog = Some(alert_dialog(message.clone()));
-        self.push_log(message);
+        self.pending_batch_rename = None;
+        self.pending_editor_rename = None;
+        self.pending_symlink_edit = None;
+        self.pending_pick = None;
+        self.pending_hotlist_add = None;
     }
 }
 
@@ -4442,8 +7301,14 @@ fn map_key_to_command(key: &KeyEvent) -> Option<Command> {
         KeyCode::F(3) => Some(Command::OpenViewer),
         KeyCode::F(4) => Some(Command::OpenEditor),
         KeyCode::F(5) => Some(Command::Copy),
+        KeyCode::F(6) if key.modifiers.contains(KeyModifiers::SHIFT) => Some(Command::Rename),
+        KeyCode::F(18) => Some(Command::Rename),
         KeyCode::F(6) => Some(Command::Move),
         KeyCode::F(7) => Some(Command::Mkdir),
+        KeyCode::F(8) if key.modifiers.contains(KeyModifiers::SHIFT) => {
+            Some(Command::DeletePermanently)
+        }
+        KeyCode::F(20) => Some(Command::DeletePermanently),
         KeyCode::F(8) => Some(Command::Delete),
         KeyCode::F(9) => Some(Command::OpenTopMenu),
         KeyCode::F(10) => Some(Command::Quit),
@@ -4453,6 +7318,66 @@ fn map_key_to_command(key: &KeyEvent) -> Option<Command> {
             Some(Command::StartSearch)
         }
         KeyCode::Char('~') => Some(Command::GoHome),
+        KeyCode::Char('j') | KeyCode::Char('J') if key.modifiers == KeyModifiers::ALT => {
+            Some(Command::OpenJobManager)
+        }
+        KeyCode::Char('t') | KeyCode::Char('T') if key.modifiers == KeyModifiers::ALT => {
+            Some(Command::OpenTrash)
+        }
+        KeyCode::Char('p') | KeyCode::Char('P') if key.modifiers == KeyModifiers::ALT => {
+            Some(Command::OpenProperties)
+        }
+        KeyCode::Char('e') | KeyCode::Char('E') if key.modifiers == KeyModifiers::ALT => {
+            Some(Command::EditorRename)
+        }
+        KeyCode::Char(c)
+            if key.modifiers.contains(KeyModifiers::CONTROL)
+                && matches!(c, 't' | 'T' | '\u{14}') =>
+        {
+            Some(Command::NewTab)
+        }
+        KeyCode::Char(c)
+            if key.modifiers.contains(KeyModifiers::CONTROL)
+                && matches!(c, 'w' | 'W' | '\u{17}') =>
+        {
+            Some(Command::CloseTab)
+        }
+        KeyCode::Char('d') | KeyCode::Char('D') if key.modifiers == KeyModifiers::ALT => {
+            Some(Command::DuplicateTab)
+        }
+        KeyCode::Char(',') if key.modifiers == KeyModifiers::ALT => Some(Command::PrevTab),
+        KeyCode::Char('.') if key.modifiers == KeyModifiers::ALT => Some(Command::NextTab),
+        KeyCode::Char(c @ '1'..='9') if key.modifiers == KeyModifiers::ALT => {
+            Some(Command::SwitchTab(c as usize - '1' as usize))
+        }
+        KeyCode::Left if key.modifiers == KeyModifiers::ALT => Some(Command::HistoryBack),
+        KeyCode::Right if key.modifiers == KeyModifiers::ALT => Some(Command::HistoryForward),
+        KeyCode::Char('h') | KeyCode::Char('H') if key.modifiers == KeyModifiers::ALT => {
+            Some(Command::OpenHistory)
+        }
+        // Terminals send Ctrl+\ as 0x1c, which crossterm reports as Ctrl+4.
+        KeyCode::Char(c)
+            if key.modifiers.contains(KeyModifiers::CONTROL)
+                && matches!(c, '\\' | '4' | '\u{1c}') =>
+        {
+            Some(Command::OpenHotlist)
+        }
+        _ => None,
+    }
+}
+
+fn map_job_manager_key_to_command(key: &KeyEvent) -> Option<Command> {
+    match key.code {
+        KeyCode::Esc | KeyCode::F(10) => Some(Command::CloseJobManager),
+        KeyCode::Char('q') if key.modifiers.is_empty() => Some(Command::CloseJobManager),
+        KeyCode::Up => Some(Command::JobManagerUp),
+        KeyCode::Down => Some(Command::JobManagerDown),
+        KeyCode::Home | KeyCode::PageUp => Some(Command::JobManagerTop),
+        KeyCode::End | KeyCode::PageDown => Some(Command::JobManagerBottom),
+        KeyCode::Char('c') | KeyCode::Delete | KeyCode::F(8) => Some(Command::JobManagerCancel),
+        KeyCode::Char('r') if key.modifiers.is_empty() => Some(Command::JobManagerRetry),
+        KeyCode::Char('R') => Some(Command::JobManagerRetryAllFailed),
+        KeyCode::Char('x') if key.modifiers.is_empty() => Some(Command::JobManagerClearFinished),
         _ => None,
     }
 }
@@ -4543,54 +7468,25 @@ fn load_sftp_bookmarks() -> Result<Vec<SftpBookmark>> {
     }
     let content = fs::read_to_string(path.as_path())?;
     let mut bookmarks = Vec::new();
-    let mut current: Option<SftpBookmark> = None;
-
-    for line in content.lines() {
-        let trimmed = line.trim();
-        if trimmed.is_empty() || trimmed.starts_with('#') {
-            continue;
-        }
-        if trimmed == "[[bookmark]]" {
-            if let Some(bookmark) = current.take() {
-                validate_bookmark(&bookmark)?;
-                bookmarks.push(bookmark);
-            }
-            current = Some(SftpBookmark::empty());
-            continue;
-        }
-
-        let Some((key, value)) = trimmed.split_once('=') else {
-            continue;
-        };
-        let Some(bookmark) = current.as_mut() else {
-            continue;
-        };
-        let key = key.trim();
-        let raw_value = value.trim();
-
-        match key {
-            "name" => bookmark.name = parse_editor_value(raw_value).unwrap_or_default(),
-            "host" => bookmark.host = parse_editor_value(raw_value).unwrap_or_default(),
-            "port" => {
-                let parsed = raw_value
-                    .parse::<u16>()
-                    .map_err(|_| anyhow::anyhow!("invalid bookmark port: {raw_value}"))?;
-                bookmark.port = parsed;
-            }
-            "user" => bookmark.user = parse_editor_value(raw_value).unwrap_or_default(),
-            "root_path" => {
-                let raw = parse_editor_value(raw_value).unwrap_or_else(|| "/".to_string());
-                bookmark.root_path = PathBuf::from(raw);
-            }
-            "password" => {
-                let raw = parse_editor_value(raw_value).unwrap_or_default();
-                bookmark.password = if raw.is_empty() { None } else { Some(raw) };
+    for table in parse_tables(&content, "bookmark") {
+        let mut bookmark = SftpBookmark::empty();
+        for (key, value) in table {
+            match key.as_str() {
+                "name" => bookmark.name = value,
+                "host" => bookmark.host = value,
+                "port" => {
+                    let parsed = value
+                        .parse::<u16>()
+                        .map_err(|_| anyhow::anyhow!("invalid bookmark port: {value}"))?;
+                    bookmark.port = Some(parsed);
+                }
+                "user" => bookmark.user = value,
+                "root_path" if value.is_empty() => bookmark.root_path = PathBuf::from("/"),
+                "root_path" => bookmark.root_path = PathBuf::from(value),
+                "password" => bookmark.password = (!value.is_empty()).then_some(value),
+                _ => {}
             }
-            _ => {}
         }
-    }
-
-    if let Some(bookmark) = current.take() {
         validate_bookmark(&bookmark)?;
         bookmarks.push(bookmark);
     }
@@ -4599,6 +7495,53 @@ fn load_sftp_bookmarks() -> Result<Vec<SftpBookmark>> {
     Ok(bookmarks)
 }
 
+/// Connection details of the SFTP bookmark called `name`, with the host
+/// resolved through `~/.ssh/config`.
+fn bookmark_connection(name: &str) -> Result<SftpConnectionInfo> {
+    if name.trim().is_empty() {
+        bail!("bookmark name cannot be empty");
+    }
+    let bookmarks = load_sftp_bookmarks()?;
+    let bookmark = bookmarks
+        .into_iter()
+        .find(|bookmark| bookmark.name == name)
+        .ok_or_else(|| anyhow::anyhow!("bookmark '{}' not found", name))?;
+    Ok(bookmark_connection_info(&bookmark, &load_ssh_config()))
+}
+
+/// Where `bookmark` connects to. `HostName` and `ProxyJump` come from the
+/// ssh config; its `Port` and `User` fill in what the bookmark leaves unset.
+fn bookmark_connection_info(bookmark: &SftpBookmark, ssh_config: &SshConfig) -> SftpConnectionInfo {
+    let ssh_host = ssh_config.resolve(bookmark.host.as_str());
+    let auth = match &bookmark.password {
+        Some(password) if !password.is_empty() => SftpAuth::Password(password.clone()),
+        _ => SftpAuth::Agent,
+    };
+    let user = Some(bookmark.user.clone())
+        .filter(|user| !user.is_empty())
+        .or(ssh_host.user)
+        .or_else(|| env::var("USER").ok())
+        .unwrap_or_default();
+    SftpConnectionInfo {
+        host: ssh_host.host_name.unwrap_or_else(|| bookmark.host.clone()),
+        user,
+        port: bookmark.port.or(ssh_host.port).unwrap_or(22),
+        root_path: bookmark.root_path.clone(),
+        auth,
+        proxy_jump: ssh_host.proxy_jump,
+    }
+}
+
+/// Name of the saved bookmark for the server of `info`, if there is one.
+fn bookmark_for_connection(info: &SftpConnectionInfo) -> Result<Option<String>> {
+    let ssh_config = load_ssh_config();
+    let bookmark = load_sftp_bookmarks()?.into_iter().find(|bookmark| {
+        let conn = bookmark_connection_info(bookmark, &ssh_config);
+        conn.host == info.host && conn.user == info.user && conn.port == info.port
+    });
+    Ok(bookmark.map(|bookmark| bookmark.name))
+}
+
 fn save_sftp_bookmarks(bookmarks: &[SftpBookmark]) -> Result<()> {
     let Some(path) = sftp_bookmarks_path() else {
         bail!("cannot resolve bookmarks config path (HOME/XDG_CONFIG_HOME)");
@@ -4617,26 +7560,19 @@ fn save_sftp_bookmarks(bookmarks: &[SftpBookmark]) -> Result<()> {
     for bookmark in &sorted {
         validate_bookmark(bookmark)?;
         lines.push("[[bookmark]]".to_string());
-        lines.push(format!(
-            "name = \"{}\"",
-            escape_toml_string(bookmark.name.trim())
+        lines.push(string_line("name", bookmark.name.trim()));
+        lines.push(string_line("host", bookmark.host.trim()));
+        if let Some(port) = bookmark.port {
+            lines.push(format!("port = {port}"));
+        }
+        lines.push(string_line("user", bookmark.user.trim()));
+        lines.push(string_line(
+            "root_path",
+            bookmark.root_path.to_string_lossy().as_ref(),
         ));
-        lines.push(format!(
-            "host = \"{}\"",
-            escape_toml_string(bookmark.host.trim())
-        ));
-        lines.push(format!("port = {}", bookmark.port));
-        lines.push(format!(
-            "user = \"{}\"",
-            escape_toml_string(bookmark.user.trim())
-        ));
-        lines.push(format!(
-            "root_path = \"{}\"",
-            escape_toml_string(bookmark.root_path.to_string_lossy().as_ref())
-        ));
-        lines.push(format!(
-            "password = \"{}\"",
-            escape_toml_string(bookmark.password.as_deref().unwrap_or(""))
+        lines.push(string_line(
+            "password",
+            bookmark.password.as_deref().unwrap_or(""),
         ));
         lines.push(String::new());
     }
@@ -4644,16 +7580,34 @@ fn save_sftp_bookmarks(bookmarks: &[SftpBookmark]) -> Result<()> {
     Ok(())
 }
 
-fn validate_bookmark(bookmark: &SftpBookmark) -> Result<()> {
-    if bookmark.name.trim().is_empty() {
-        bail!("bookmark name cannot be empty");
-    }
-    if bookmark.host.trim().is_empty() {
-        bail!("bookmark host cannot be empty");
-    }
-    if bookmark.user.trim().is_empty() {
-        bail!("bookmark user cannot be empty");
+fn validate_bookmark(bookmark: &SftpBookmark) -> Result<()> {
+    if bookmark.name.trim().is_empty() {
+        bail!("bookmark name cannot be empty");
+    }
+    if bookmark.host.trim().is_empty() {
+        bail!("bookmark host cannot be empty");
+    }
+    Ok(())
+}
+
+fn load_hotlist() -> Result<Vec<HotlistEntry>> {
+    let Some(path) = hotlist_path() else {
+        return Ok(Vec::new());
+    };
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    hotlist_from_toml(&fs::read_to_string(path)?)
+}
+
+fn save_hotlist(entries: &[HotlistEntry]) -> Result<()> {
+    let Some(path) = hotlist_path() else {
+        bail!("cannot resolve hotlist config path (HOME/XDG_CONFIG_HOME)");
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
     }
+    fs::write(path, hotlist_to_toml(entries))?;
     Ok(())
 }
 
@@ -4671,7 +7625,7 @@ fn load_saved_editor_command() -> Option<String> {
         if key.trim() != "editor" {
             continue;
         }
-        let parsed = parse_editor_value(value.trim());
+        let parsed = parse_value(value.trim());
         if parsed.is_some() {
             return parsed;
         }
@@ -4685,26 +7639,11 @@ fn save_editor_command(command: &str) -> Result<()> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
-    let content = format!(
-        "# vcmc config\neditor = \"{}\"\n",
-        escape_toml_string(command.trim())
-    );
+    let content = format!("# vcmc config\n{}\n", string_line("editor", command.trim()));
     fs::write(path, content)?;
     Ok(())
 }
 
-fn config_dir_path() -> Option<PathBuf> {
-    if let Some(base) = env::var_os("XDG_CONFIG_HOME") {
-        let base = PathBuf::from(base);
-        if !base.as_os_str().is_empty() {
-            return Some(base.join("vcmc"));
-        }
-    }
-    env::var_os("HOME")
-        .map(PathBuf::from)
-        .map(|home| home.join(".config").join("vcmc"))
-}
-
 fn editor_config_path() -> Option<PathBuf> {
     config_dir_path().map(|path| path.join("config.toml"))
 }
@@ -4713,46 +7652,12 @@ fn sftp_bookmarks_path() -> Option<PathBuf> {
     config_dir_path().map(|path| path.join("bookmarks.toml"))
 }
 
-fn parse_editor_value(raw: &str) -> Option<String> {
-    let raw = raw.trim();
-    if raw.is_empty() {
-        return None;
-    }
-    if raw.starts_with('"') && raw.ends_with('"') && raw.len() >= 2 {
-        let inner = &raw[1..raw.len() - 1];
-        return Some(unescape_toml_string(inner));
-    }
-    Some(raw.to_string())
-}
-
-fn escape_toml_string(raw: &str) -> String {
-    raw.replace('\\', "\\\\").replace('"', "\\\"")
+fn history_path() -> Option<PathBuf> {
+    config_dir_path().map(|path| path.join("history.toml"))
 }
 
-fn unescape_toml_string(raw: &str) -> String {
-    let mut out = String::with_capacity(raw.len());
-    let mut chars = raw.chars();
-    while let Some(ch) = chars.next() {
-        if ch == '\\' {
-            if let Some(next) = chars.next() {
-                match next {
-                    '\\' => out.push('\\'),
-                    '"' => out.push('"'),
-                    'n' => out.push('\n'),
-                    't' => out.push('\t'),
-                    other => {
-                        out.push('\\');
-                        out.push(other);
-                    }
-                }
-            } else {
-                out.push('\\');
-            }
-        } else {
-            out.push(ch);
-        }
-    }
-    out
+fn hotlist_path() -> Option<PathBuf> {
+    config_dir_path().map(|path| path.join("hotlist.toml"))
 }
 
 fn command_in_path(binary: &str) -> bool {
@@ -4933,7 +7838,7 @@ fn source_item_label(path: &Path) -> String {
         .unwrap_or_else(|| path.display().to_string())
 }
 
-fn parse_sftp_address_input(input: &str) -> Result<(Option<String>, String, u16, PathBuf)> {
+fn parse_sftp_address_input(input: &str) -> Result<(Option<String>, String, Option<u16>, PathBuf)> {
     let mut value = input.trim().to_string();
     if value.is_empty() {
         return Err(anyhow::anyhow!("address cannot be empty"));
@@ -4958,12 +7863,12 @@ fn parse_sftp_address_input(input: &str) -> Result<(Option<String>, String, u16,
             let port = port_raw
                 .parse::<u16>()
                 .map_err(|_| anyhow::anyhow!("invalid port: {port_raw}"))?;
-            (host.to_string(), port)
+            (host.to_string(), Some(port))
         } else {
-            (host_port, 22)
+            (host_port, None)
         }
     } else {
-        (host_port, 22)
+        (host_port, None)
     };
 
     if host.trim().is_empty() {
@@ -4972,11 +7877,133 @@ fn parse_sftp_address_input(input: &str) -> Result<(Option<String>, String, u16,
     Ok((user, host, port, PathBuf::from(path_part)))
 }
 
+fn sftp_connect_dialog(address: String, login: String, auth: usize) -> DialogState {
+    form_dialog(
+        "SFTP Connect",
+        "Address: [user@]host[:port][/path], ssh config alias, 'local', or '@bookmark_name'\nLogin and key file default to the address and ~/.ssh/config",
+        vec![
+            DialogField::text("address", "Address", address),
+            DialogField::text("login", "Login", login),
+            DialogField::dropdown("auth", "Auth", &SFTP_AUTH_OPTIONS, auth),
+            DialogField::masked("password", "Password", ""),
+            DialogField::text("key_path", "Key file", ""),
+        ],
+        DialogTone::Default,
+    )
+}
+
+/// Line editing shared by dialog inputs and form text fields: typing,
+/// Backspace/Delete, Ctrl+W, Left/Right, Home/End and Ctrl+Left/Right word
+/// jumps. Returns `None` for keys it does not edit with.
+fn edit_text_input(input: &mut TextInput, key: &KeyEvent) -> Option<bool> {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Left if ctrl => input.move_word_left(),
+        KeyCode::Right if ctrl => input.move_word_right(),
+        KeyCode::Left => input.move_left(),
+        KeyCode::Right => input.move_right(),
+        KeyCode::Home => input.move_home(),
+        KeyCode::End => input.move_end(),
+        KeyCode::Backspace => return Some(input.backspace()),
+        KeyCode::Delete => return Some(input.delete()),
+        KeyCode::Char('w') if key.modifiers == KeyModifiers::CONTROL => {
+            return Some(input.delete_word_back());
+        }
+        KeyCode::Char(c) if key.modifiers.is_empty() || key.modifiers == KeyModifiers::SHIFT => {
+            if c == '\0' {
+                return Some(false);
+            }
+            input.insert_char(c);
+        }
+        _ => return None,
+    }
+    Some(true)
+}
+
+/// Reads the SFTP connect form. A user in the address wins over `Login`,
+/// which wins over `ssh_config` and `$USER`. `Auto` uses the password when
+/// one is typed, else a key file (typed or from ssh config); with neither it
+/// asks `probe_auth` what the server takes and uses the agent unless only a
+/// password will do.
+fn sftp_target_from_form(
+    dialog: &DialogState,
+    ssh_config: &SshConfig,
+    probe_auth: impl FnOnce(&str, u16, &str) -> SftpAuthHint,
+) -> std::result::Result<SftpConnectTarget, FieldError> {
+    let address = dialog.text("address");
+    if address.eq_ignore_ascii_case("local") {
+        return Ok(SftpConnectTarget::Local);
+    }
+    if let Some(name) = address.strip_prefix('@') {
+        return Ok(SftpConnectTarget::Bookmark(name.trim().to_string()));
+    }
+    let (user, host, port, root_path) =
+        parse_sftp_address_input(address).map_err(|err| ("address", err.to_string()))?;
+    let ssh_host = ssh_config.resolve(host.as_str());
+    let login = dialog.text("login");
+    let user = user
+        .or_else(|| (!login.is_empty()).then(|| login.to_string()))
+        .or_else(|| ssh_host.user.clone())
+        .or_else(|| env::var("USER").ok())
+        .filter(|user| !user.trim().is_empty())
+        .ok_or(("login", "login cannot be empty".to_string()))?;
+
+    let password = dialog.text("password");
+    let key_path = dialog.text("key_path");
+    let key_file = if key_path.is_empty() {
+        ssh_host.existing_identity_file()
+    } else {
+        Some(expand_tilde_path(key_path))
+    };
+    let typed_password = (!password.is_empty()).then(|| password.to_string());
+    let host = ssh_host.host_name.unwrap_or(host);
+    let port = port.or(ssh_host.port).unwrap_or(22);
+    let auth = match dialog.selected("auth").unwrap_or(SFTP_AUTH_AUTO) {
+        SFTP_AUTH_PASSWORD => SftpAuth::Password(
+            typed_password.ok_or(("password", "password cannot be empty".to_string()))?,
+        ),
+        SFTP_AUTH_KEY_FILE => SftpAuth::KeyFile {
+            path: key_file.ok_or(("key_path", "key file cannot be empty".to_string()))?,
+            // The password field doubles as the key's passphrase.
+            passphrase: typed_password,
+        },
+        SFTP_AUTH_AGENT => SftpAuth::Agent,
+        _ => match (typed_password, key_file) {
+            (Some(password), _) => SftpAuth::Password(password),
+            (None, Some(path)) => SftpAuth::KeyFile {
+                path,
+                passphrase: None,
+            },
+            // A jump host is not reachable from here, so it is not probed.
+            (None, None) if ssh_host.proxy_jump.is_some() => SftpAuth::Agent,
+            (None, None) => match probe_auth(host.as_str(), port, user.as_str()) {
+                SftpAuthHint::PasswordOnly => {
+                    return Err(("password", "the server only accepts a password".to_string()));
+                }
+                SftpAuthHint::KeyOnly | SftpAuthHint::Either => SftpAuth::Agent,
+            },
+        },
+    };
+
+    Ok(SftpConnectTarget::Sftp(SftpConnectionInfo {
+        host,
+        user,
+        port,
+        root_path,
+        auth,
+        proxy_jump: ssh_host.proxy_jump,
+    }))
+}
+
+/// Asks the server which login methods `user` may use, without logging in.
 fn probe_sftp_auth_hint(host: &str, port: u16, user: &str) -> Result<SftpAuthHint> {
-    let endpoint = format!("{host}:{port}");
-    let tcp = TcpStream::connect(endpoint.as_str())?;
-    tcp.set_read_timeout(Some(Duration::from_secs(8)))?;
-    tcp.set_write_timeout(Some(Duration::from_secs(8)))?;
+    let address = (host, port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("cannot resolve {host}"))?;
+    let tcp = TcpStream::connect_timeout(&address, SFTP_PROBE_TIMEOUT)?;
+    tcp.set_read_timeout(Some(SFTP_PROBE_TIMEOUT))?;
+    tcp.set_write_timeout(Some(SFTP_PROBE_TIMEOUT))?;
 
     let mut session = Session::new()?;
     session.set_tcp_stream(tcp);
@@ -4992,12 +8019,39 @@ fn probe_sftp_auth_hint(host: &str, port: u16, user: &str) -> Result<SftpAuthHin
     let hint = match (has_publickey, has_password) {
         (true, false) => SftpAuthHint::KeyOnly,
         (false, true) => SftpAuthHint::PasswordOnly,
-        (true, true) => SftpAuthHint::Either,
-        (false, false) => SftpAuthHint::Either,
+        (true, true) | (false, false) => SftpAuthHint::Either,
     };
     Ok(hint)
 }
 
+/// Reads the bookmark add/edit form. `editing` is the name of the bookmark
+/// being edited, which may keep its name.
+fn bookmark_from_form(
+    dialog: &DialogState,
+    bookmarks: &[SftpBookmark],
+    editing: Option<&str>,
+) -> std::result::Result<SftpBookmark, FieldError> {
+    let name = dialog.text("name");
+    if name.is_empty() {
+        return Err(("name", "bookmark name cannot be empty".to_string()));
+    }
+    if editing != Some(name) && bookmarks.iter().any(|bookmark| bookmark.name == name) {
+        return Err(("name", format!("bookmark '{name}' already exists")));
+    }
+    let (user, host, port, root_path) = parse_sftp_address_input(dialog.text("address"))
+        .map_err(|err| ("address", err.to_string()))?;
+    let user = user.unwrap_or_else(|| dialog.text("login").to_string());
+    let password = dialog.text("password");
+    Ok(SftpBookmark {
+        name: name.to_string(),
+        host,
+        port,
+        user,
+        root_path,
+        password: (!password.is_empty()).then(|| password.to_string()),
+    })
+}
+
 fn expand_tilde_path(raw: &str) -> PathBuf {
     if raw == "~" {
         if let Some(home) = env::var_os("HOME") {
@@ -5015,12 +8069,182 @@ fn expand_tilde_path(raw: &str) -> PathBuf {
     PathBuf::from(raw)
 }
 
+fn clip_text(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut clipped = text
+        .chars()
+        .take(max_chars.saturating_sub(3))
+        .collect::<String>();
+    clipped.push_str("...");
+    clipped
+}
+
+const PROPERTIES_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+fn format_properties_time(time: SystemTime) -> String {
+    DateTime::<Local>::from(time)
+        .format(PROPERTIES_TIME_FORMAT)
+        .to_string()
+}
+
+fn parse_properties_time(value: &str) -> Result<SystemTime> {
+    let naive = NaiveDateTime::parse_from_str(value, PROPERTIES_TIME_FORMAT)
+        .map_err(|_| anyhow::anyhow!("invalid time '{value}', expected YYYY-MM-DD HH:MM:SS"))?;
+    let local = Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .ok_or_else(|| anyhow::anyhow!("time '{value}' does not exist in the local timezone"))?;
+    Ok(SystemTime::from(local))
+}
+
+fn parse_mode_mask(value: &str, label: &str) -> Result<Option<ModeSpec>> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    ModeSpec::parse(value)
+        .map(Some)
+        .map_err(|err| anyhow::anyhow!("{label} mask: {err}"))
+}
+
+/// Name shown for an owner or group, falling back to the numeric id.
+fn account_label(name: Option<&String>, id: Option<u32>) -> String {
+    name.cloned()
+        .or_else(|| id.map(|id| id.to_string()))
+        .unwrap_or_default()
+}
+
+/// Accepts a numeric id anywhere; names resolve through the local account
+/// database, which says nothing about remote hosts.
+fn resolve_account_id(
+    value: &str,
+    local: bool,
+    kind: &str,
+    lookup: fn(&str) -> Option<u32>,
+) -> Result<u32> {
+    if let Ok(id) = value.parse::<u32>() {
+        return Ok(id);
+    }
+    if !local {
+        bail!("remote {kind} must be given as a numeric id: '{value}'");
+    }
+    lookup(value).ok_or_else(|| anyhow::anyhow!("unknown {kind} '{value}'"))
+}
+
+fn batch_rename_rule(dialog: &DialogState) -> Result<RenameRule> {
+    RenameRule::parse(
+        dialog.text("template"),
+        dialog.text("find"),
+        dialog.text("replace"),
+        CaseMode::from_index(dialog.selected("case").unwrap_or(0)),
+        (
+            dialog.text("counter_start"),
+            dialog.text("counter_step"),
+            dialog.text("counter_digits"),
+        ),
+    )
+}
+
+/// Writes the names to edit into a new temp file only the user can read.
+/// The file is created exclusively, so an existing file or symlink under the
+/// same name is never opened; another name is tried instead.
+fn write_rename_file(text: &str) -> Result<PathBuf> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos())
+        .unwrap_or(0);
+    for attempt in 0..100u32 {
+        let path = env::temp_dir().join(format!(
+            "vcmc-rename-{}-{nanos}-{attempt}.txt",
+            std::process::id()
+        ));
+        let mut file = match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(&path)
+        {
+            Ok(file) => file,
+            Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(err) => bail!("cannot create {}: {err}", path.display()),
+        };
+        if let Err(err) = file.write_all(text.as_bytes()) {
+            let _ = fs::remove_file(&path);
+            bail!("cannot write {}: {err}", path.display());
+        }
+        return Ok(path);
+    }
+    bail!(
+        "cannot create a temp file for renaming in {}",
+        env::temp_dir().display()
+    )
+}
+
+/// Renames an edited name list asks for, checked for conflicts, and the
+/// indexes of the entries whose lines were removed.
+fn editor_rename_changes(
+    pending: &PendingEditorRename,
+    edits: &[Option<String>],
+) -> (Vec<RenamePreview>, Vec<usize>) {
+    let mut previews = Vec::new();
+    let mut removed = Vec::new();
+    for (idx, (old, edit)) in pending.names.iter().zip(edits).enumerate() {
+        match edit {
+            Some(new) if new != old => previews.push(RenamePreview {
+                old: old.clone(),
+                new: new.clone(),
+                problem: None,
+            }),
+            Some(_) => {}
+            None => removed.push(idx),
+        }
+    }
+    let moving = pending
+        .names
+        .iter()
+        .zip(edits)
+        .filter(|(old, edit)| edit.as_ref() != Some(*old))
+        .map(|(old, _)| old.as_str())
+        .collect::<HashSet<_>>();
+    check_renames(&mut previews, &moving, &pending.existing);
+    (previews, removed)
+}
+
+fn rename_preview_line(preview: &RenamePreview) -> (String, bool) {
+    let text = match preview.problem.as_deref() {
+        Some(problem) => format!("{} -> {}  ({problem})", preview.old, preview.new),
+        None if preview.is_change() => format!("{} -> {}", preview.old, preview.new),
+        None => format!("{}  (unchanged)", preview.old),
+    };
+    (text, preview.problem.is_some())
+}
+
+/// Byte length of `name` without its extension; dotfiles count as all stem.
+fn file_stem_len(name: &str) -> usize {
+    match name.rfind('.') {
+        Some(idx) if idx > 0 => idx,
+        _ => name.len(),
+    }
+}
+
 fn operation_name(kind: JobKind) -> &'static str {
     match kind {
         JobKind::Copy => "copy",
         JobKind::Move => "move",
+        JobKind::Rename => "rename",
         JobKind::Delete => "delete",
         JobKind::Mkdir => "mkdir",
+        JobKind::Trash => "trash",
+        JobKind::Restore => "restore",
+        JobKind::Purge => "purge",
+        JobKind::Chattr => "chattr",
+        JobKind::Symlink => "symlink",
+        JobKind::Hardlink => "hardlink",
     }
 }
 
@@ -5061,6 +8285,29 @@ fn batch_summary(
             }
         }
         JobKind::Mkdir => format!("Run mkdir batch for {} item(s)? [y/N]", count),
+        JobKind::Trash => format!(
+            "Move {} item(s), {} to trash?",
+            count,
+            format_bytes(total_bytes)
+        ),
+        JobKind::Rename => format!("Rename {} item(s)?", count),
+        JobKind::Restore => format!("Restore {} item(s) from trash?", count),
+        JobKind::Chattr => format!("Change attributes of {} item(s)?", count),
+        JobKind::Symlink => format!(
+            "Symlink {} item(s) into {}?",
+            count,
+            destination_dir.display()
+        ),
+        JobKind::Hardlink => format!(
+            "Hardlink {} item(s) into {}?",
+            count,
+            destination_dir.display()
+        ),
+        JobKind::Purge => format!(
+            "Delete {} item(s), {} from trash permanently?",
+            count,
+            format_bytes(total_bytes)
+        ),
     }
 }
 
@@ -5240,6 +8487,82 @@ fn backend_spec_label(spec: &BackendSpec) -> String {
     }
 }
 
+/// Hotlist popup rows for the `shown` entries, as aligned group, name and
+/// target columns.
+fn hotlist_dialog_list(entries: &[HotlistEntry], shown: &[usize], selected: usize) -> DialogList {
+    let width = |text: &str| text.chars().count().min(24);
+    let group_width = shown
+        .iter()
+        .map(|idx| width(&entries[*idx].group))
+        .max()
+        .unwrap_or(0);
+    let name_width = shown
+        .iter()
+        .map(|idx| width(&entries[*idx].name))
+        .max()
+        .unwrap_or(0);
+    let lines = if shown.is_empty() {
+        let hint = if entries.is_empty() {
+            "empty; Alt+A adds the current directory"
+        } else {
+            "no entries match"
+        };
+        vec![(hint.to_string(), false)]
+    } else {
+        shown
+            .iter()
+            .map(|idx| {
+                let entry = &entries[*idx];
+                let mut line = String::new();
+                if group_width > 0 {
+                    line.push_str(&format!("{:group_width$}  ", entry.group));
+                }
+                line.push_str(&format!(
+                    "{:name_width$}  {}",
+                    entry.name,
+                    entry.target.label()
+                ));
+                (line, false)
+            })
+            .collect()
+    };
+    let title = if shown.len() == entries.len() {
+        "Hotlist".to_string()
+    } else {
+        format!("Hotlist: {} of {}", shown.len(), entries.len())
+    };
+    let mut list = DialogList {
+        title,
+        lines,
+        offset: 0,
+        selected: (!shown.is_empty()).then_some(selected),
+    };
+    list.move_selection(0);
+    list
+}
+
+fn history_entry_label(entry: &HistoryEntry) -> String {
+    match entry.backend {
+        BackendSpec::Local => entry.path.display().to_string(),
+        _ => format!(
+            "{} {}",
+            backend_spec_label(&entry.backend),
+            entry.path.display()
+        ),
+    }
+}
+
+/// Whether `a` and `b` are the same backend, so a directory of one can be
+/// shown on the other without reconnecting.
+fn same_backend(a: &BackendSpec, b: &BackendSpec) -> bool {
+    match (a, b) {
+        (BackendSpec::Sftp(a), BackendSpec::Sftp(b)) => {
+            a.host == b.host && a.user == b.user && a.port == b.port && a.proxy_jump == b.proxy_jump
+        }
+        _ => a == b,
+    }
+}
+
 fn parent_link_entry(parent: PathBuf) -> FsEntry {
     FsEntry {
         name: "..".to_string(),
@@ -5250,6 +8573,8 @@ fn parent_link_entry(parent: PathBuf) -> FsEntry {
         is_executable: false,
         is_hidden: false,
         is_virtual: true,
+        link_target: None,
+        link_broken: false,
     }
 }
 
@@ -5321,22 +8646,65 @@ fn next_selectable_menu_item(
     None
 }
 
-fn input_dialog(title: &str, body: &str, value: String, tone: DialogTone) -> DialogState {
-    input_dialog_with_mask(title, body, value, tone, false)
+/// Apply/Cancel form over `fields`; focus starts on the first field.
+fn form_dialog(title: &str, body: &str, fields: Vec<DialogField>, tone: DialogTone) -> DialogState {
+    DialogState {
+        title: title.to_string(),
+        body: body.to_string(),
+        input: None,
+        focused_field: (!fields.is_empty()).then_some(0),
+        fields,
+        list: None,
+        error: None,
+        buttons: vec![
+            DialogButton {
+                label: "Apply".to_string(),
+                accelerator: Some('a'),
+                role: DialogButtonRole::Primary,
+            },
+            DialogButton {
+                label: "Cancel".to_string(),
+                accelerator: Some('c'),
+                role: DialogButtonRole::Secondary,
+            },
+        ],
+        focused_button: 0,
+        tone,
+    }
 }
 
-fn input_dialog_with_mask(
-    title: &str,
-    body: &str,
-    value: String,
-    tone: DialogTone,
-    mask_input: bool,
-) -> DialogState {
+fn bookmark_form_dialog(title: &str, bookmark: &SftpBookmark) -> DialogState {
+    let port = bookmark
+        .port
+        .map(|port| format!(":{port}"))
+        .unwrap_or_default();
+    let address = format!("{}{port}{}", bookmark.host, bookmark.root_path.display());
+    form_dialog(
+        title,
+        "Address: host[:port][/path]\nPort and login left empty come from ~/.ssh/config\nPassword is optional, leave it empty for SSH agent auth",
+        vec![
+            DialogField::text("name", "Name", bookmark.name.clone()),
+            DialogField::text("address", "Address", address),
+            DialogField::text("login", "Login", bookmark.user.clone()),
+            DialogField::masked(
+                "password",
+                "Password",
+                bookmark.password.clone().unwrap_or_default(),
+            ),
+        ],
+        DialogTone::Default,
+    )
+}
+
+fn input_dialog(title: &str, body: &str, value: String, tone: DialogTone) -> DialogState {
     DialogState {
         title: title.to_string(),
         body: body.to_string(),
-        input_value: Some(value),
-        mask_input,
+        input: Some(TextInput::new(value)),
+        fields: Vec::new(),
+        focused_field: None,
+        list: None,
+        error: None,
         buttons: vec![
             DialogButton {
                 label: "Apply".to_string(),
@@ -5354,12 +8722,114 @@ fn input_dialog_with_mask(
     }
 }
 
+/// F5/F6 form: copy options as checkboxes and the conflict policy as a radio
+/// group. `name` is the editable target name of a single item; batches have
+/// none.
+/// Name input (single entry) and options for new links. Symlinks choose how
+/// the target is written; hardlinks have nothing to choose.
+fn link_dialog(kind: JobKind, body: String, name: Option<String>) -> DialogState {
+    let fields = if kind == JobKind::Symlink {
+        vec![
+            DialogField::radio("link_target", "Target", &["Absolute", "Relative"], 0),
+            DialogField::radio("conflict", "On conflict", &["Ask", "Overwrite", "Skip"], 0),
+        ]
+    } else {
+        Vec::new()
+    };
+    let title = match (kind, name.is_some()) {
+        (JobKind::Symlink, true) => "Symlink as",
+        (JobKind::Symlink, false) => "Symlink",
+        (_, true) => "Hardlink as",
+        (_, false) => "Hardlink",
+    };
+    let mut dialog = form_dialog(title, &body, fields, DialogTone::Default);
+    dialog.input = name.map(TextInput::new);
+    dialog.focused_field = None;
+    dialog
+}
+
+fn copy_options_dialog(
+    kind: JobKind,
+    body: String,
+    name: Option<String>,
+    options: &CopyOptions,
+) -> DialogState {
+    let mut fields = vec![
+        DialogField::checkbox(
+            "follow_symlinks",
+            "Follow symlinks (copy what links point to)",
+            options.follow_symlinks,
+        ),
+        DialogField::checkbox(
+            "preserve",
+            "Preserve attributes",
+            options.preserve_attributes,
+        ),
+    ];
+    if kind == JobKind::Copy {
+        fields.push(DialogField::checkbox(
+            "dive",
+            "Dive into existing subdirectory",
+            options.dive_into_existing,
+        ));
+    }
+    fields.push(DialogField::checkbox(
+        "stable_symlinks",
+        "Stable symlinks (make relative links absolute)",
+        options.stable_symlinks,
+    ));
+    fields.push(DialogField::checkbox(
+        "skip_errors",
+        "Skip all errors",
+        options.skip_errors,
+    ));
+    fields.push(DialogField::radio(
+        "conflict",
+        "On conflict",
+        &["Ask", "Overwrite", "Skip", "Newer"],
+        0,
+    ));
+
+    let title = match (kind, name.is_some()) {
+        (JobKind::Move, true) => "Move as",
+        (JobKind::Move, false) => "Move",
+        (_, true) => "Copy as",
+        (_, false) => "Copy",
+    };
+    DialogState {
+        title: title.to_string(),
+        body,
+        input: name.map(TextInput::new),
+        fields,
+        focused_field: None,
+        list: None,
+        error: None,
+        buttons: vec![
+            DialogButton {
+                label: "Apply".to_string(),
+                accelerator: Some('a'),
+                role: DialogButtonRole::Primary,
+            },
+            DialogButton {
+                label: "Cancel".to_string(),
+                accelerator: Some('c'),
+                role: DialogButtonRole::Secondary,
+            },
+        ],
+        focused_button: 0,
+        tone: DialogTone::Default,
+    }
+}
+
 fn confirm_dialog(body: String) -> DialogState {
     DialogState {
         title: "Confirm".to_string(),
         body,
-        input_value: None,
-        mask_input: false,
+        input: None,
+        fields: Vec::new(),
+        focused_field: None,
+        list: None,
+        error: None,
         buttons: vec![
             DialogButton {
                 label: "Yes".to_string(),
@@ -5381,8 +8851,11 @@ fn conflict_dialog(title: String, body: String) -> DialogState {
     DialogState {
         title,
         body,
-        input_value: None,
-        mask_input: false,
+        input: None,
+        fields: Vec::new(),
+        focused_field: None,
+        list: None,
+        error: None,
         buttons: vec![
             DialogButton {
                 label: "Overwrite".to_string(),
@@ -5430,12 +8903,93 @@ fn conflict_dialog(title: String, body: String) -> DialogState {
     }
 }
 
+fn host_key_dialog(body: String) -> DialogState {
+    DialogState {
+        title: "Unknown Host Key".to_string(),
+        body,
+        input: None,
+        fields: Vec::new(),
+        focused_field: None,
+        list: None,
+        error: None,
+        buttons: vec![
+            DialogButton {
+                label: "Trust".to_string(),
+                accelerator: Some('t'),
+                role: DialogButtonRole::Primary,
+            },
+            DialogButton {
+                label: "Reject".to_string(),
+                accelerator: Some('r'),
+                role: DialogButtonRole::Secondary,
+            },
+        ],
+        focused_button: 1,
+        tone: DialogTone::Warning,
+    }
+}
+
+fn host_key_refused_dialog(title: &str, body: String) -> DialogState {
+    DialogState {
+        title: title.to_string(),
+        ..alert_dialog(body)
+    }
+}
+
+fn batch_failures_dialog(body: String, details: bool) -> DialogState {
+    // Order matches the `BATCH_FAILURES_*` indices.
+    let mut buttons = vec![
+        DialogButton {
+            label: "Retry failed".to_string(),
+            accelerator: Some('r'),
+            role: DialogButtonRole::Primary,
+        },
+        DialogButton {
+            label: "Skip".to_string(),
+            accelerator: Some('s'),
+            role: DialogButtonRole::Secondary,
+        },
+    ];
+    buttons.push(if details {
+        DialogButton {
+            label: "Back".to_string(),
+            accelerator: Some('b'),
+            role: DialogButtonRole::Secondary,
+        }
+    } else {
+        DialogButton {
+            label: "Show details".to_string(),
+            accelerator: Some('d'),
+            role: DialogButtonRole::Secondary,
+        }
+    });
+    DialogState {
+        title: if details {
+            "Failed Items".to_string()
+        } else {
+            "Batch Failed".to_string()
+        },
+        body,
+        input: None,
+        fields: Vec::new(),
+        focused_field: None,
+        list: None,
+        error: None,
+        buttons,
+        focused_button: 0,
+        tone: DialogTone::Danger,
+    }
+}
+
 fn alert_dialog(body: String) -> DialogState {
     DialogState {
         title: "Error".to_string(),
         body,
-        input_value: None,
-        mask_input: false,
+        input: None,
+        fields: Vec::new(),
+        focused_field: None,
+        list: None,
+        error: None,
         buttons: vec![DialogButton {
             label: "OK".to_string(),
             accelerator: Some('o'),
@@ -5445,3 +8999,193 @@ fn alert_dialog(body: String) -> DialogState {
         tone: DialogTone::Danger,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+
+    use crossbeam_channel::{Receiver, unbounded};
+
+    use super::{
+        App, BATCH_FAILURES_RETRY, BatchOpItem, SFTP_AUTH_AUTO, SFTP_AUTH_PASSWORD, SftpAuthHint,
+        SftpConnectTarget, sftp_connect_dialog, sftp_target_from_form,
+    };
+    use crate::model::{
+        BackendSpec, DialogState, DirHistory, Event, HistoryEntry, JobKind, SftpAuth, TextInput,
+    };
+    use crate::ssh_config::SshConfig;
+    use crate::test_support::fixture_dir;
+
+    fn fill(dialog: &mut DialogState, key: &str, value: &str) {
+        let field = dialog
+            .fields
+            .iter_mut()
+            .find(|field| field.key == key)
+            .and_then(|field| field.text_input_mut())
+            .expect("text field");
+        *field = TextInput::new(value.to_string());
+    }
+
+    fn never_probed(_: &str, _: u16, _: &str) -> SftpAuthHint {
+        panic!("the server is only asked when nothing was typed")
+    }
+
+    #[test]
+    fn connect_form_picks_the_target_and_its_auth() {
+        let ssh_config = SshConfig::parse(
+            "Host box\n  HostName box.example.invalid\n  Port 2200\n  User carol\n",
+            Path::new("/"),
+        );
+        let form = |address: &str| {
+            sftp_connect_dialog(address.to_string(), "alice".to_string(), SFTP_AUTH_AUTO)
+        };
+        let target = |dialog: &DialogState, probe: fn(&str, u16, &str) -> SftpAuthHint| {
+            sftp_target_from_form(dialog, &ssh_config, probe)
+        };
+
+        assert!(matches!(
+            target(&form("local"), never_probed),
+            Ok(SftpConnectTarget::Local)
+        ));
+        assert!(matches!(
+            target(&form("@work"), never_probed),
+            Ok(SftpConnectTarget::Bookmark(name)) if name == "work"
+        ));
+
+        let mut typed = form("bob@host.invalid:2222/srv");
+        fill(&mut typed, "password", "secret");
+        let Ok(SftpConnectTarget::Sftp(conn)) = target(&typed, never_probed) else {
+            panic!("sftp target expected");
+        };
+        assert_eq!(
+            (conn.user.as_str(), conn.host.as_str(), conn.port),
+            ("bob", "host.invalid", 2222)
+        );
+        assert_eq!(conn.root_path, PathBuf::from("/srv"));
+        assert_eq!(conn.auth, SftpAuth::Password("secret".to_string()));
+
+        // The form's login wins over ssh config, which fills in the rest.
+        let Ok(SftpConnectTarget::Sftp(conn)) = target(&form("box"), |host, port, user| {
+            assert_eq!((host, port, user), ("box.example.invalid", 2200, "alice"));
+            SftpAuthHint::Either
+        }) else {
+            panic!("sftp target expected");
+        };
+        assert_eq!(conn.auth, SftpAuth::Agent);
+        assert!(matches!(
+            target(&form("box"), |_, _, _| SftpAuthHint::PasswordOnly),
+            Err(("password", _))
+        ));
+
+        let password_form =
+            sftp_connect_dialog("box".to_string(), String::new(), SFTP_AUTH_PASSWORD);
+        assert!(matches!(
+            target(&password_form, never_probed),
+            Err(("password", _))
+        ));
+    }
+
+    fn settle_left_listing(app: &mut App, event_rx: &Receiver<Event>) {
+        while app.state.left_panel.loading {
+            let event = event_rx
+                .recv_timeout(Duration::from_secs(5))
+                .expect("listing arrives");
+            app.on_event(event);
+        }
+    }
+
+    #[test]
+    fn history_moves_only_once_the_listing_arrives() {
+        let dir = fs::canonicalize(fixture_dir("app-history")).expect("canonical");
+        fs::create_dir_all(dir.join("a")).expect("a created");
+        fs::create_dir_all(dir.join("b")).expect("b created");
+        let (event_tx, event_rx) = unbounded();
+        let mut app = App::bootstrap(dir.join("b"), event_tx).expect("app started");
+        settle_left_listing(&mut app, &event_rx);
+        let visit = |name: &str| HistoryEntry {
+            backend: BackendSpec::Local,
+            path: dir.join(name),
+        };
+        app.state.left_panel.history = DirHistory {
+            entries: vec![visit("a"), visit("b")],
+            position: 1,
+        };
+
+        app.step_history(false).expect("went back");
+        assert_eq!(app.state.left_panel.history.position, 1);
+        settle_left_listing(&mut app, &event_rx);
+        assert_eq!(app.state.left_panel.history.position, 0);
+        assert_eq!(app.state.left_panel.cwd, dir.join("a"));
+
+        // A listing that fails leaves the history where it was.
+        fs::remove_dir(dir.join("b")).expect("b removed");
+        app.step_history(true).expect("went forward");
+        settle_left_listing(&mut app, &event_rx);
+        assert!(app.state.left_panel.error_message.is_some());
+        let history = &app.state.left_panel.history;
+        assert_eq!((history.position, history.entries.len()), (0, 2));
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn retry_queues_only_the_failed_items_again() {
+        let dir = fs::canonicalize(fixture_dir("app-retry")).expect("canonical");
+        fs::create_dir_all(dir.join("taken")).expect("taken created");
+        let (event_tx, event_rx) = unbounded();
+        let mut app = App::bootstrap(dir.clone(), event_tx).expect("app started");
+        settle_left_listing(&mut app, &event_rx);
+
+        let mkdir = |name: &str| BatchOpItem {
+            source: dir.join(name),
+            destination: None,
+            name: name.to_string(),
+            size_bytes: 0,
+            overwrite_destination: false,
+            attributes: None,
+        };
+        let batch_id = app.next_batch_id;
+        app.next_batch_id += 1;
+        let items = vec![mkdir("fresh"), mkdir("taken"), mkdir("missing/child")];
+        let copy_options = app.state.copy_options;
+        app.enqueue_batch_jobs(JobKind::Mkdir, Some(batch_id), items, 0, copy_options)
+            .expect("batch queued");
+        while app.pending_batch_failures.is_none() {
+            let event = event_rx
+                .recv_timeout(Duration::from_secs(5))
+                .expect("batch finishes");
+            app.on_event(event);
+        }
+        assert_eq!(
+            app.pending_batch_failures
+                .as_ref()
+                .map(|pending| pending.failures.len()),
+            Some(2)
+        );
+
+        let first_ids = app.job_requests.keys().copied().collect::<Vec<_>>();
+        assert!(app.apply_batch_failures_action(BATCH_FAILURES_RETRY));
+        let retried = app
+            .job_requests
+            .values()
+            .filter(|request| !first_ids.contains(&request.id))
+            .collect::<Vec<_>>();
+        let first_max = first_ids.iter().max().copied().unwrap_or(0);
+        assert!(retried.iter().all(|request| request.id > first_max));
+        assert!(retried.iter().all(|request| request.kind == JobKind::Mkdir));
+        assert!(
+            retried
+                .iter()
+                .all(|request| request.batch_id != Some(batch_id))
+        );
+        // Workers finish in any order, so the failures may too.
+        let mut sources = retried
+            .iter()
+            .map(|request| request.source.clone())
+            .collect::<Vec<_>>();
+        sources.sort();
+        assert_eq!(sources, [dir.join("missing/child"), dir.join("taken")]);
+        fs::remove_dir_all(dir).ok();
+    }
+}
@@ -1,40 +1,103 @@
 #![allow(dead_code)]
 
 use std::collections::HashMap;
+use std::env;
 use std::fs;
 use std::io::{Read, Write};
 use std::net::TcpStream;
+use std::os::fd::{AsRawFd, RawFd};
+use std::os::unix::net::UnixStream;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, OnceLock};
 use std::thread;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use anyhow::{Result, bail};
+use crossbeam_channel::{Receiver, bounded};
 use flate2::read::GzDecoder;
-use ssh2::{FileStat, OpenFlags, OpenType, RenameFlags, Session, Sftp};
+use ssh2::{
+    BlockDirections, Channel, ErrorCode, FileStat, OpenFlags, OpenType, RenameFlags, Session, Sftp,
+};
 use tar::Archive as TarArchive;
 use zip::ZipArchive;
 
-use crate::fs::FsAdapter;
+use crate::errors::{AppError, HostKeyError};
+use crate::fs::{FsAdapter, stable_link_target};
+use crate::known_hosts::verify_host_key;
 use crate::model::{
-    ArchiveConnectionInfo, BackendSpec, FsEntry, FsEntryType, SftpAuth, SftpConnectionInfo,
-    SortMode,
+    ArchiveConnectionInfo, BackendSpec, CopyOptions, EntryProperties, FsEntry, FsEntryType,
+    SftpAuth, SftpConnectionInfo, SortMode,
 };
+use crate::ssh_config::load_ssh_config;
 
 const S_IFMT: u32 = 0o170000;
 const S_IFDIR: u32 = 0o040000;
 const S_IFLNK: u32 = 0o120000;
 const SFTP_CONNECT_ATTEMPTS: usize = 3;
+const SFTP_SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(300);
+const SFTP_POOL_MAX_IDLE_PER_CONNECTION: usize = 4;
+const LIBSSH2_FX_NO_SUCH_FILE: i32 = 2;
+const LIBSSH2_FX_PERMISSION_DENIED: i32 = 3;
+const LIBSSH2_FX_NO_CONNECTION: i32 = 6;
+const LIBSSH2_FX_CONNECTION_LOST: i32 = 7;
+const LIBSSH2_FX_NO_SUCH_PATH: i32 = 10;
+const TUNNEL_POLL_TIMEOUT_MS: libc::c_int = 1000;
+const ARCHIVE_STREAM_CHUNK_SIZE: usize = 64 * 1024;
+const ARCHIVE_STREAM_QUEUE_DEPTH: usize = 4;
 
 pub trait FsBackend: Send + Sync {
     fn backend_name(&self) -> &'static str;
     fn list_dir(&self, path: &Path, sort_mode: SortMode, show_hidden: bool)
     -> Result<Vec<FsEntry>>;
     fn stat_entry(&self, path: &Path) -> Result<FsEntry>;
+    fn entry_properties(&self, path: &Path) -> Result<EntryProperties>;
+    fn set_permissions(&self, path: &Path, mode: u32) -> Result<()>;
+    /// Changes the owning user and/or group; `None` keeps the current id.
+    fn set_owner(&self, path: &Path, uid: Option<u32>, gid: Option<u32>) -> Result<()>;
+    fn set_times(&self, path: &Path, modified: SystemTime) -> Result<()>;
+    /// Gives `path` the mode and times of `source`, plus its ownership where
+    /// the backend is allowed to set it. Used after copying across backends.
+    fn preserve_attributes(&self, path: &Path, source: &EntryProperties) -> Result<()>;
+    /// Target of the symlink at `path`; fails when `path` is not a symlink.
+    fn read_link(&self, path: &Path) -> Result<PathBuf>;
+    /// Whether anything is at `path`, a dangling symlink included: unlike
+    /// `stat_entry` it does not follow links.
+    fn entry_exists(&self, path: &Path) -> bool {
+        self.read_link(path).is_ok() || self.stat_entry(path).is_ok()
+    }
+    /// Creates a symlink at `link` pointing to `target`.
+    fn create_symlink(&self, target: &Path, link: &Path) -> Result<()>;
+    /// Creates a hard link at `link` to the existing file `target`.
+    fn create_hard_link(&self, target: &Path, link: &Path) -> Result<()>;
     fn create_dir(&self, path: &Path) -> Result<()>;
     fn remove_path(&self, path: &Path) -> Result<()>;
     fn move_path(&self, source: &Path, destination: &Path) -> Result<PathBuf>;
     fn copy_path(&self, source: &Path, destination: &Path) -> Result<PathBuf>;
+    /// Same as `move_path`; backends that move by copying report bytes through
+    /// `on_progress(file, copied, size)` and honour `options` for the copies.
+    fn move_path_with_progress(
+        &self,
+        source: &Path,
+        destination: &Path,
+        _options: &CopyOptions,
+        _on_progress: &mut dyn FnMut(&Path, u64, u64) -> bool,
+        _on_warning: &mut dyn FnMut(&Path, anyhow::Error),
+    ) -> Result<PathBuf> {
+        self.move_path(source, destination)
+    }
+    /// Same as `copy_path`, reporting `on_progress(file, copied, size)` per chunk;
+    /// a false return cancels the copy and removes the partial file. Copies
+    /// whose attributes could not be preserved are kept and go to `on_warning`.
+    fn copy_path_with_progress(
+        &self,
+        source: &Path,
+        destination: &Path,
+        _options: &CopyOptions,
+        _on_progress: &mut dyn FnMut(&Path, u64, u64) -> bool,
+        _on_warning: &mut dyn FnMut(&Path, anyhow::Error),
+    ) -> Result<PathBuf> {
+        self.copy_path(source, destination)
+    }
     fn normalize_existing_path(&self, operation: &'static str, path: &Path) -> Result<PathBuf>;
     fn normalize_new_path(&self, operation: &'static str, path: &Path) -> Result<PathBuf>;
     fn read_file(&self, path: &Path) -> Result<Vec<u8>>;
@@ -47,6 +110,21 @@ pub trait FsBackend: Send + Sync {
         Ok((bytes, truncated))
     }
     fn write_file(&self, path: &Path, bytes: &[u8]) -> Result<()>;
+    fn open_read(&self, path: &Path) -> Result<Box<dyn Read + Send>>;
+    fn open_write(&self, path: &Path) -> Result<Box<dyn WriteStream>>;
+}
+
+/// File opened by `FsBackend::open_write`. `finish` flushes and closes it and
+/// reports a failure to do so, which dropping the stream would swallow.
+pub trait WriteStream: Write + Send {
+    fn finish(self: Box<Self>) -> Result<()>;
+}
+
+impl WriteStream for fs::File {
+    fn finish(mut self: Box<Self>) -> Result<()> {
+        self.flush()?;
+        Ok(())
+    }
 }
 
 pub fn backend_from_spec(spec: &BackendSpec) -> Arc<dyn FsBackend> {
@@ -80,6 +158,38 @@ impl FsBackend for LocalFsBackend {
         Ok(self.fs.stat_entry(path)?)
     }
 
+    fn entry_properties(&self, path: &Path) -> Result<EntryProperties> {
+        Ok(self.fs.entry_properties(path)?)
+    }
+
+    fn set_permissions(&self, path: &Path, mode: u32) -> Result<()> {
+        Ok(self.fs.set_permissions(path, mode)?)
+    }
+
+    fn set_owner(&self, path: &Path, uid: Option<u32>, gid: Option<u32>) -> Result<()> {
+        Ok(self.fs.set_owner(path, uid, gid)?)
+    }
+
+    fn set_times(&self, path: &Path, modified: SystemTime) -> Result<()> {
+        Ok(self.fs.set_times(path, modified)?)
+    }
+
+    fn preserve_attributes(&self, path: &Path, source: &EntryProperties) -> Result<()> {
+        Ok(self.fs.preserve_attributes(path, source)?)
+    }
+
+    fn read_link(&self, path: &Path) -> Result<PathBuf> {
+        Ok(self.fs.read_link(path)?)
+    }
+
+    fn create_symlink(&self, target: &Path, link: &Path) -> Result<()> {
+        Ok(self.fs.create_symlink(target, link)?)
+    }
+
+    fn create_hard_link(&self, target: &Path, link: &Path) -> Result<()> {
+        Ok(self.fs.create_hard_link(target, link)?)
+    }
+
     fn create_dir(&self, path: &Path) -> Result<()> {
         Ok(self.fs.create_dir(path)?)
     }
@@ -96,6 +206,40 @@ impl FsBackend for LocalFsBackend {
         Ok(self.fs.copy_path(source, destination)?)
     }
 
+    fn move_path_with_progress(
+        &self,
+        source: &Path,
+        destination: &Path,
+        options: &CopyOptions,
+        on_progress: &mut dyn FnMut(&Path, u64, u64) -> bool,
+        on_warning: &mut dyn FnMut(&Path, anyhow::Error),
+    ) -> Result<PathBuf> {
+        Ok(self.fs.move_path_with_progress(
+            source,
+            destination,
+            options,
+            on_progress,
+            &mut |path, err| on_warning(path, err.into()),
+        )?)
+    }
+
+    fn copy_path_with_progress(
+        &self,
+        source: &Path,
+        destination: &Path,
+        options: &CopyOptions,
+        on_progress: &mut dyn FnMut(&Path, u64, u64) -> bool,
+        on_warning: &mut dyn FnMut(&Path, anyhow::Error),
+    ) -> Result<PathBuf> {
+        Ok(self.fs.copy_path_with_progress(
+            source,
+            destination,
+            options,
+            on_progress,
+            &mut |path, err| on_warning(path, err.into()),
+        )?)
+    }
+
     fn normalize_existing_path(&self, operation: &'static str, path: &Path) -> Result<PathBuf> {
         Ok(self.fs.normalize_existing_path(operation, path)?)
     }
@@ -127,6 +271,16 @@ impl FsBackend for LocalFsBackend {
         fs::write(normalized, bytes)?;
         Ok(())
     }
+
+    fn open_read(&self, path: &Path) -> Result<Box<dyn Read + Send>> {
+        let normalized = self.fs.normalize_existing_path("read", path)?;
+        Ok(Box::new(fs::File::open(normalized)?))
+    }
+
+    fn open_write(&self, path: &Path) -> Result<Box<dyn WriteStream>> {
+        let normalized = self.fs.normalize_new_path("write", path)?;
+        Ok(Box::new(fs::File::create(normalized)?))
+    }
 }
 
 #[derive(Clone)]
@@ -134,6 +288,12 @@ pub struct SftpFsBackend {
     conn: SftpConnectionInfo,
 }
 
+struct PooledSftpSession {
+    session: Session,
+    sftp: Sftp,
+    last_used: Instant,
+}
+
 impl SftpFsBackend {
     pub fn new(conn: SftpConnectionInfo) -> Self {
         Self { conn }
@@ -144,6 +304,7 @@ impl SftpFsBackend {
         for attempt in 1..=SFTP_CONNECT_ATTEMPTS {
             match self.connect_once() {
                 Ok(conn) => return Ok(conn),
+                Err(err) if err.downcast_ref::<HostKeyError>().is_some() => return Err(err),
                 Err(err) => {
                     last_error = Some(err);
                     if attempt < SFTP_CONNECT_ATTEMPTS {
@@ -161,14 +322,17 @@ impl SftpFsBackend {
     }
 
     fn connect_once(&self) -> Result<(Session, Sftp)> {
-        let endpoint = format!("{}:{}", self.conn.host, self.conn.port);
-        let tcp = TcpStream::connect(endpoint.as_str())?;
-        tcp.set_read_timeout(Some(Duration::from_secs(30)))?;
-        tcp.set_write_timeout(Some(Duration::from_secs(30)))?;
+        let transport = connect_ssh_transport(
+            self.conn.host.as_str(),
+            self.conn.port,
+            self.conn.proxy_jump.as_deref(),
+            Duration::from_secs(30),
+        )?;
 
         let mut session = Session::new()?;
-        session.set_tcp_stream(tcp);
+        session.set_tcp_stream(transport);
         session.handshake()?;
+        verify_host_key(&session, self.conn.host.as_str(), self.conn.port)?;
 
         match &self.conn.auth {
             SftpAuth::Agent => session.userauth_agent(self.conn.user.as_str())?,
@@ -190,6 +354,112 @@ impl SftpFsBackend {
         Ok((session, sftp))
     }
 
+    /// Takes an idle session for this connection from the pool, or opens a new one.
+    /// The flag tells whether the session was reused and may have gone stale.
+    fn checkout_session(&self) -> Result<(PooledSftpSession, bool)> {
+        let reused = sftp_session_pool()
+            .lock()
+            .ok()
+            .and_then(|mut pool| pool.get_mut(&self.conn).and_then(Vec::pop))
+            .filter(|pooled| pooled.last_used.elapsed() < SFTP_SESSION_IDLE_TIMEOUT);
+        if let Some(pooled) = reused {
+            return Ok((pooled, true));
+        }
+        Ok((self.fresh_session()?, false))
+    }
+
+    fn fresh_session(&self) -> Result<PooledSftpSession> {
+        let (session, sftp) = self.connect()?;
+        Ok(PooledSftpSession {
+            session,
+            sftp,
+            last_used: Instant::now(),
+        })
+    }
+
+    fn checkin_session(&self, mut pooled: PooledSftpSession) {
+        pooled.last_used = Instant::now();
+        let overflow = {
+            let Ok(mut pool) = sftp_session_pool().lock() else {
+                return;
+            };
+            let idle = pool.entry(self.conn.clone()).or_default();
+            if idle.len() < SFTP_POOL_MAX_IDLE_PER_CONNECTION {
+                idle.push(pooled);
+                None
+            } else {
+                Some(pooled)
+            }
+        };
+        drop(overflow);
+    }
+
+    /// Runs `op` on a pooled SFTP handle. A reused session that fails with a
+    /// transport error is discarded; a read is then retried once on a fresh
+    /// connection, while a write surfaces the error because it may already
+    /// have reached the server.
+    fn with_sftp<T>(&self, kind: SftpOp, mut op: impl FnMut(&Sftp) -> Result<T>) -> Result<T> {
+        let (pooled, reused) = self.checkout_session()?;
+        let err = match op(&pooled.sftp) {
+            Ok(value) => {
+                self.checkin_session(pooled);
+                return Ok(value);
+            }
+            Err(err) if retries_on_fresh_session(kind, reused, &err) => err,
+            Err(err) => {
+                if !is_stale_session_error(&err) {
+                    self.checkin_session(pooled);
+                }
+                return Err(err);
+            }
+        };
+        drop(pooled);
+        tracing::debug!(
+            "sftp session to {}@{} went stale, reconnecting: {err}",
+            self.conn.user,
+            self.conn.host
+        );
+
+        let fresh = self.fresh_session()?;
+        let result = op(&fresh.sftp);
+        match &result {
+            Err(err) if is_stale_session_error(err) => {}
+            _ => self.checkin_session(fresh),
+        }
+        result
+    }
+
+    /// Opens a remote file that keeps its pooled session checked out until the
+    /// stream is dropped.
+    fn open_stream(
+        &self,
+        kind: SftpOp,
+        open: impl Fn(&Sftp) -> Result<ssh2::File>,
+    ) -> Result<SftpFileStream> {
+        let (pooled, reused) = self.checkout_session()?;
+        let (pooled, file) = match open(&pooled.sftp) {
+            Ok(file) => (pooled, file),
+            Err(err) if retries_on_fresh_session(kind, reused, &err) => {
+                drop(pooled);
+                let fresh = self.fresh_session()?;
+                let file = open(&fresh.sftp)?;
+                (fresh, file)
+            }
+            Err(err) => {
+                if !is_stale_session_error(&err) {
+                    self.checkin_session(pooled);
+                }
+                return Err(err);
+            }
+        };
+        Ok(SftpFileStream {
+            backend: self.clone(),
+            file: Some(file),
+            pooled: Some(pooled),
+            failed: false,
+        })
+    }
+
     fn resolve_path(&self, path: &Path) -> PathBuf {
         if path.is_absolute() {
             path.to_path_buf()
@@ -197,6 +467,342 @@ impl SftpFsBackend {
             self.conn.root_path.join(path)
         }
     }
+
+    fn realpath_or_resolved(&self, sftp: &Sftp, path: &Path) -> PathBuf {
+        let resolved = self.resolve_path(path);
+        match sftp.realpath(resolved.as_path()) {
+            Ok(path) => path,
+            Err(_) => resolved,
+        }
+    }
+}
+
+struct SftpFileStream {
+    backend: SftpFsBackend,
+    file: Option<ssh2::File>,
+    pooled: Option<PooledSftpSession>,
+    failed: bool,
+}
+
+impl SftpFileStream {
+    fn file(&mut self) -> std::io::Result<&mut ssh2::File> {
+        self.file
+            .as_mut()
+            .ok_or_else(|| std::io::Error::other("sftp stream is closed"))
+    }
+
+    fn track<T>(&mut self, result: std::io::Result<T>) -> std::io::Result<T> {
+        if result.is_err() {
+            self.failed = true;
+        }
+        result
+    }
+
+    /// Flushes and closes the remote handle, keeping the outcome that `Drop`
+    /// would have to discard.
+    fn close(&mut self) -> Result<()> {
+        let Some(mut file) = self.file.take() else {
+            return Ok(());
+        };
+        let result = file
+            .flush()
+            .map_err(anyhow::Error::from)
+            .and_then(|()| Ok(file.close()?));
+        if result.is_err() {
+            self.failed = true;
+        }
+        result
+    }
+}
+
+impl WriteStream for SftpFileStream {
+    fn finish(mut self: Box<Self>) -> Result<()> {
+        self.close()
+    }
+}
+
+impl Read for SftpFileStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let result = self.file()?.read(buf);
+        self.track(result)
+    }
+}
+
+impl Write for SftpFileStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let result = self.file()?.write(buf);
+        self.track(result)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        let result = self.file()?.flush();
+        self.track(result)
+    }
+}
+
+/// Fallback for streams that are not finished, e.g. on an error path; the
+/// handle is still closed but the outcome is lost.
+impl Drop for SftpFileStream {
+    fn drop(&mut self) {
+        drop(self.file.take());
+        let pooled = self.pooled.take().filter(|_| !self.failed);
+        if let Some(pooled) = pooled {
+            self.backend.checkin_session(pooled);
+        }
+    }
+}
+
+/// Byte stream an SSH session runs over: a direct TCP connection, or one end of a
+/// socket pair bridged to a `direct-tcpip` channel on a ProxyJump host.
+pub enum SshTransport {
+    Direct(TcpStream),
+    Tunnel(UnixStream),
+}
+
+impl AsRawFd for SshTransport {
+    fn as_raw_fd(&self) -> RawFd {
+        match self {
+            Self::Direct(stream) => stream.as_raw_fd(),
+            Self::Tunnel(stream) => stream.as_raw_fd(),
+        }
+    }
+}
+
+struct JumpHop {
+    host: String,
+    port: u16,
+    user: String,
+    identity_files: Vec<PathBuf>,
+}
+
+/// Opens the transport to `host:port`, tunnelling through every `ProxyJump` hop
+/// (comma-separated `[user@]host[:port]`, aliases resolved through `~/.ssh/config`).
+pub fn connect_ssh_transport(
+    host: &str,
+    port: u16,
+    proxy_jump: Option<&str>,
+    timeout: Duration,
+) -> Result<SshTransport> {
+    let hops = proxy_jump.map(parse_proxy_jump).unwrap_or_default();
+    let Some((first, rest)) = hops.split_first() else {
+        return Ok(SshTransport::Direct(connect_tcp(host, port, timeout)?));
+    };
+
+    let mut transport =
+        SshTransport::Direct(connect_tcp(first.host.as_str(), first.port, timeout)?);
+    let mut current = first;
+    let targets = rest
+        .iter()
+        .map(|hop| (hop.host.as_str(), hop.port))
+        .chain(std::iter::once((host, port)));
+    for (index, (next_host, next_port)) in targets.enumerate() {
+        let session = open_jump_session(transport, current)?;
+        transport =
+            SshTransport::Tunnel(spawn_jump_tunnel(session, next_host, next_port, timeout)?);
+        if let Some(next) = rest.get(index) {
+            current = next;
+        }
+    }
+    Ok(transport)
+}
+
+fn connect_tcp(host: &str, port: u16, timeout: Duration) -> Result<TcpStream> {
+    let endpoint = format!("{host}:{port}");
+    let tcp = TcpStream::connect(endpoint.as_str())?;
+    tcp.set_read_timeout(Some(timeout))?;
+    tcp.set_write_timeout(Some(timeout))?;
+    Ok(tcp)
+}
+
+fn parse_proxy_jump(value: &str) -> Vec<JumpHop> {
+    let config = load_ssh_config();
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|hop| !hop.is_empty() && !hop.eq_ignore_ascii_case("none"))
+        .map(|hop| {
+            let hop = hop.strip_prefix("ssh://").unwrap_or(hop);
+            let (user, host_port) = match hop.rsplit_once('@') {
+                Some((user, rest)) => (Some(user.to_string()), rest),
+                None => (None, hop),
+            };
+            let (alias, port) = match host_port.rsplit_once(':') {
+                Some((alias, port)) if port.chars().all(|ch| ch.is_ascii_digit()) => {
+                    (alias, port.parse::<u16>().ok())
+                }
+                _ => (host_port, None),
+            };
+            let resolved = config.resolve(alias);
+            JumpHop {
+                host: resolved.host_name.unwrap_or_else(|| alias.to_string()),
+                port: port.or(resolved.port).unwrap_or(22),
+                user: user
+                    .or(resolved.user)
+                    .unwrap_or_else(|| env::var("USER").unwrap_or_default()),
+                identity_files: resolved.identity_files,
+            }
+        })
+        .collect()
+}
+
+fn open_jump_session(transport: SshTransport, hop: &JumpHop) -> Result<Session> {
+    let mut session = Session::new()?;
+    session.set_tcp_stream(transport);
+    session.handshake()?;
+    verify_host_key(&session, hop.host.as_str(), hop.port)?;
+
+    if session.userauth_agent(hop.user.as_str()).is_err() || !session.authenticated() {
+        let home = env::var_os("HOME").map(PathBuf::from).unwrap_or_default();
+        let default_keys = ["id_ed25519", "id_ecdsa", "id_rsa"]
+            .into_iter()
+            .map(|name| home.join(".ssh").join(name));
+        for key in hop.identity_files.iter().cloned().chain(default_keys) {
+            if !key.is_file() {
+                continue;
+            }
+            if session
+                .userauth_pubkey_file(hop.user.as_str(), None, key.as_path(), None)
+                .is_ok()
+                && session.authenticated()
+            {
+                break;
+            }
+        }
+    }
+    if !session.authenticated() {
+        bail!(
+            "proxy jump auth failed for {}@{}:{}",
+            hop.user,
+            hop.host,
+            hop.port
+        );
+    }
+    Ok(session)
+}
+
+fn spawn_jump_tunnel(
+    session: Session,
+    host: &str,
+    port: u16,
+    timeout: Duration,
+) -> Result<UnixStream> {
+    let channel = session.channel_direct_tcpip(host, port, None)?;
+    let (local, remote) = UnixStream::pair()?;
+    local.set_read_timeout(Some(timeout))?;
+    local.set_write_timeout(Some(timeout))?;
+    thread::spawn(move || pump_jump_tunnel(session, channel, remote));
+    Ok(local)
+}
+
+fn pump_jump_tunnel(session: Session, mut channel: Channel, mut socket: UnixStream) {
+    session.set_blocking(false);
+    if socket.set_nonblocking(true).is_err() {
+        return;
+    }
+    let socket_fd = socket.as_raw_fd();
+    let session_fd = session.as_raw_fd();
+    // libssh2 may also be waiting to flush its own output to the session.
+    let session_events = || match session.block_directions() {
+        BlockDirections::Outbound | BlockDirections::Both => libc::POLLIN | libc::POLLOUT,
+        _ => libc::POLLIN,
+    };
+    let mut buffer = vec![0_u8; 32 * 1024];
+    loop {
+        let mut idle = true;
+        match socket.read(buffer.as_mut_slice()) {
+            Ok(0) => break,
+            Ok(read) => {
+                let wait = || wait_for_fds(&[(session_fd, session_events())]);
+                if write_all_nonblocking(&mut channel, &buffer[..read], wait).is_err() {
+                    break;
+                }
+                idle = false;
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {}
+            Err(_) => break,
+        }
+        match channel.read(buffer.as_mut_slice()) {
+            Ok(0) if channel.eof() => break,
+            Ok(0) => {}
+            Ok(read) => {
+                let wait = || wait_for_fds(&[(socket_fd, libc::POLLOUT)]);
+                if write_all_nonblocking(&mut socket, &buffer[..read], wait).is_err() {
+                    break;
+                }
+                idle = false;
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {}
+            Err(_) => break,
+        }
+        if idle {
+            wait_for_fds(&[(socket_fd, libc::POLLIN), (session_fd, session_events())]);
+        }
+    }
+    let _ = channel.close();
+}
+
+/// Blocks in poll(2) until one of `fds` is ready for its events or hung up.
+/// The timeout only bounds the wait in case libssh2 buffered data without
+/// the socket showing it.
+fn wait_for_fds(fds: &[(RawFd, libc::c_short)]) {
+    let mut poll_fds = fds
+        .iter()
+        .map(|(fd, events)| libc::pollfd {
+            fd: *fd,
+            events: *events,
+            revents: 0,
+        })
+        .collect::<Vec<_>>();
+    // SAFETY: `poll_fds` is a live, correctly sized array of pollfd structs.
+    unsafe {
+        libc::poll(
+            poll_fds.as_mut_ptr(),
+            poll_fds.len() as libc::nfds_t,
+            TUNNEL_POLL_TIMEOUT_MS,
+        );
+    }
+}
+
+fn write_all_nonblocking(
+    writer: &mut impl Write,
+    mut bytes: &[u8],
+    wait: impl Fn(),
+) -> std::io::Result<()> {
+    while !bytes.is_empty() {
+        match writer.write(bytes) {
+            Ok(0) => return Err(std::io::ErrorKind::WriteZero.into()),
+            Ok(written) => bytes = &bytes[written..],
+            Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => wait(),
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(())
+}
+
+/// Drops pooled SFTP sessions that have been idle longer than the pool timeout.
+pub fn prune_idle_sftp_sessions() {
+    let expired = {
+        let Ok(mut pool) = sftp_session_pool().lock() else {
+            return;
+        };
+        let mut expired = Vec::new();
+        pool.retain(|_, idle| {
+            let (stale, fresh): (Vec<_>, Vec<_>) = idle
+                .drain(..)
+                .partition(|pooled| pooled.last_used.elapsed() >= SFTP_SESSION_IDLE_TIMEOUT);
+            expired.extend(stale);
+            *idle = fresh;
+            !idle.is_empty()
+        });
+        expired
+    };
+    drop(expired);
+}
+
+fn sftp_session_pool() -> &'static Mutex<HashMap<SftpConnectionInfo, Vec<PooledSftpSession>>> {
+    static POOL: OnceLock<Mutex<HashMap<SftpConnectionInfo, Vec<PooledSftpSession>>>> =
+        OnceLock::new();
+    POOL.get_or_init(|| Mutex::new(HashMap::new()))
 }
 
 impl FsBackend for SftpFsBackend {
@@ -210,104 +816,260 @@ impl FsBackend for SftpFsBackend {
         sort_mode: SortMode,
         show_hidden: bool,
     ) -> Result<Vec<FsEntry>> {
-        let (_session, sftp) = self.connect()?;
-        let dir = self.normalize_existing_path("list_dir", path)?;
-        let mut entries = Vec::new();
-        for (entry_path, stat) in sftp.readdir(&dir)? {
-            let Some(name) = entry_path
-                .file_name()
-                .map(|name| name.to_string_lossy().to_string())
-            else {
-                continue;
-            };
-            if name == "." || name == ".." {
-                continue;
+        self.with_sftp(SftpOp::Read, |sftp| {
+            let dir = self.realpath_or_resolved(sftp, path);
+            let mut entries = Vec::new();
+            for (entry_path, stat) in sftp.readdir(&dir)? {
+                let Some(name) = entry_path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                else {
+                    continue;
+                };
+                if name == "." || name == ".." {
+                    continue;
+                }
+                let is_hidden = name.starts_with('.');
+                if !show_hidden && is_hidden {
+                    continue;
+                }
+                let entry_type = entry_type_from_stat(&stat);
+                // readdir reports links themselves; stat tells whether the
+                // target is there. Only a missing target makes the link broken.
+                let (link_target, link_broken) = if entry_type == FsEntryType::Symlink {
+                    let target = sftp.readlink(&entry_path).ok();
+                    let broken = target.is_some()
+                        && sftp
+                            .stat(&entry_path)
+                            .is_err_and(|err| is_no_such_file(&err));
+                    (target, broken)
+                } else {
+                    (None, false)
+                };
+                entries.push(FsEntry {
+                    name,
+                    path: entry_path.clone(),
+                    entry_type,
+                    size_bytes: stat.size.unwrap_or(0),
+                    modified_at: modified_from_stat(&stat),
+                    is_executable: is_exec_from_stat(&stat),
+                    is_hidden,
+                    is_virtual: false,
+                    link_target,
+                    link_broken,
+                });
             }
-            let is_hidden = name.starts_with('.');
-            if !show_hidden && is_hidden {
-                continue;
+
+            sort_entries(entries.as_mut_slice(), sort_mode);
+            if let Some(parent) = dir.parent() {
+                if parent != dir {
+                    entries.insert(0, parent_link(parent.to_path_buf()));
+                }
             }
-            entries.push(FsEntry {
-                name,
-                path: entry_path.clone(),
+            Ok(entries)
+        })
+    }
+
+    fn stat_entry(&self, path: &Path) -> Result<FsEntry> {
+        self.with_sftp(SftpOp::Read, |sftp| {
+            let normalized = self.realpath_or_resolved(sftp, path);
+            let stat = sftp.stat(normalized.as_path())?;
+            let name = normalized
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| normalized.display().to_string());
+            Ok(FsEntry {
+                name: name.clone(),
+                path: normalized,
                 entry_type: entry_type_from_stat(&stat),
                 size_bytes: stat.size.unwrap_or(0),
                 modified_at: modified_from_stat(&stat),
                 is_executable: is_exec_from_stat(&stat),
-                is_hidden,
+                is_hidden: name.starts_with('.'),
                 is_virtual: false,
-            });
-        }
+                link_target: None,
+                link_broken: false,
+            })
+        })
+    }
 
-        sort_entries(entries.as_mut_slice(), sort_mode);
-        if let Some(parent) = dir.parent() {
-            if parent != dir {
-                entries.insert(0, parent_link(parent.to_path_buf()));
-            }
-        }
-        Ok(entries)
+    fn entry_properties(&self, path: &Path) -> Result<EntryProperties> {
+        self.with_sftp(SftpOp::Read, |sftp| {
+            let normalized = self.realpath_or_resolved(sftp, path);
+            let stat = sftp.stat(normalized.as_path())?;
+            // SFTP v3 reports numeric ids only, and no inode or link count.
+            Ok(EntryProperties {
+                path: normalized,
+                entry_type: entry_type_from_stat(&stat),
+                size_bytes: stat.size.unwrap_or(0),
+                mode: stat.perm.unwrap_or(0) & 0o7777,
+                uid: stat.uid,
+                gid: stat.gid,
+                owner: None,
+                group: None,
+                modified_at: modified_from_stat(&stat),
+                accessed_at: accessed_from_stat(&stat),
+                inode: None,
+                links: None,
+            })
+        })
     }
 
-    fn stat_entry(&self, path: &Path) -> Result<FsEntry> {
-        let (_session, sftp) = self.connect()?;
-        let normalized = self.normalize_existing_path("stat", path)?;
-        let stat = sftp.stat(normalized.as_path())?;
-        let name = normalized
-            .file_name()
-            .map(|name| name.to_string_lossy().to_string())
-            .unwrap_or_else(|| normalized.display().to_string());
-        Ok(FsEntry {
-            name: name.clone(),
-            path: normalized,
-            entry_type: entry_type_from_stat(&stat),
-            size_bytes: stat.size.unwrap_or(0),
-            modified_at: modified_from_stat(&stat),
-            is_executable: is_exec_from_stat(&stat),
-            is_hidden: name.starts_with('.'),
-            is_virtual: false,
+    fn set_permissions(&self, path: &Path, mode: u32) -> Result<()> {
+        self.with_sftp(SftpOp::Write, |sftp| {
+            let normalized = self.realpath_or_resolved(sftp, path);
+            sftp.setstat(
+                normalized.as_path(),
+                FileStat {
+                    perm: Some(mode & 0o7777),
+                    ..empty_file_stat()
+                },
+            )?;
+            Ok(())
         })
     }
 
+    fn set_owner(&self, path: &Path, uid: Option<u32>, gid: Option<u32>) -> Result<()> {
+        self.with_sftp(SftpOp::Write, |sftp| {
+            let normalized = self.realpath_or_resolved(sftp, path);
+            // uid and gid travel together on the wire, so fill in the one kept.
+            let current = sftp.stat(normalized.as_path())?;
+            sftp.setstat(
+                normalized.as_path(),
+                FileStat {
+                    uid: uid.or(current.uid),
+                    gid: gid.or(current.gid),
+                    ..empty_file_stat()
+                },
+            )?;
+            Ok(())
+        })
+    }
+
+    fn set_times(&self, path: &Path, modified: SystemTime) -> Result<()> {
+        let mtime = modified
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| anyhow::anyhow!("time is before 1970: {}", path.display()))?
+            .as_secs();
+        self.with_sftp(SftpOp::Write, |sftp| {
+            let normalized = self.realpath_or_resolved(sftp, path);
+            // atime and mtime are set together, so keep the current atime.
+            let current = sftp.stat(normalized.as_path())?;
+            sftp.setstat(
+                normalized.as_path(),
+                FileStat {
+                    atime: Some(current.atime.unwrap_or(mtime)),
+                    mtime: Some(mtime),
+                    ..empty_file_stat()
+                },
+            )?;
+            Ok(())
+        })
+    }
+
+    fn preserve_attributes(&self, path: &Path, source: &EntryProperties) -> Result<()> {
+        let stat = preserved_file_stat(source);
+        self.with_sftp(SftpOp::Write, |sftp| {
+            let normalized = self.realpath_or_resolved(sftp, path);
+            set_preserved_stat(sftp, normalized.as_path(), stat.clone())
+        })
+    }
+
+    fn read_link(&self, path: &Path) -> Result<PathBuf> {
+        let normalized = self.resolve_path(path);
+        self.with_sftp(
+            SftpOp::Read,
+            |sftp| Ok(sftp.readlink(normalized.as_path())?),
+        )
+    }
+
+    fn create_symlink(&self, target: &Path, link: &Path) -> Result<()> {
+        let normalized = self.resolve_path(link);
+        self.with_sftp(SftpOp::Write, |sftp| {
+            sftp.symlink(target, normalized.as_path())?;
+            Ok(())
+        })
+    }
+
+    fn create_hard_link(&self, _target: &Path, link: &Path) -> Result<()> {
+        // libssh2 does not speak the hardlink@openssh.com extension.
+        bail!(
+            "hardlinks are not supported over SFTP: {}",
+            self.resolve_path(link).display()
+        )
+    }
+
     fn create_dir(&self, path: &Path) -> Result<()> {
-        let (_session, sftp) = self.connect()?;
-        let normalized = self.normalize_new_path("mkdir", path)?;
-        sftp.mkdir(normalized.as_path(), 0o755)?;
-        Ok(())
+        let normalized = self.resolve_path(path);
+        self.with_sftp(SftpOp::Write, |sftp| {
+            sftp.mkdir(normalized.as_path(), 0o755)?;
+            Ok(())
+        })
     }
 
     fn remove_path(&self, path: &Path) -> Result<()> {
-        let (_session, sftp) = self.connect()?;
-        let normalized = self.normalize_existing_path("remove", path)?;
-        remove_remote_path_recursive(&sftp, normalized.as_path())
+        // realpath would resolve a symlink and remove what it points to.
+        let resolved = self.resolve_path(path);
+        self.with_sftp(SftpOp::Write, |sftp| {
+            remove_remote_path_recursive(sftp, resolved.as_path())
+        })
     }
 
     fn move_path(&self, source: &Path, destination: &Path) -> Result<PathBuf> {
-        let (_session, sftp) = self.connect()?;
-        let source_path = self.normalize_existing_path("move", source)?;
-        let destination_path = self.normalize_new_path("move", destination)?;
-        sftp.rename(
-            source_path.as_path(),
-            destination_path.as_path(),
-            Some(RenameFlags::empty()),
-        )?;
-        Ok(destination_path)
+        let destination_path = self.resolve_path(destination);
+        self.with_sftp(SftpOp::Write, |sftp| {
+            let source_path = self.realpath_or_resolved(sftp, source);
+            sftp.rename(
+                source_path.as_path(),
+                destination_path.as_path(),
+                Some(RenameFlags::empty()),
+            )?;
+            Ok(destination_path.clone())
+        })
     }
 
     fn copy_path(&self, source: &Path, destination: &Path) -> Result<PathBuf> {
-        let (_session, sftp) = self.connect()?;
-        let source_path = self.normalize_existing_path("copy", source)?;
-        let destination_path = self.normalize_new_path("copy", destination)?;
-        copy_remote_path_recursive(&sftp, source_path.as_path(), destination_path.as_path())?;
-        Ok(destination_path)
+        self.copy_path_with_progress(
+            source,
+            destination,
+            &CopyOptions::default(),
+            &mut |_, _, _| true,
+            &mut |_, _| {},
+        )
+    }
+
+    fn copy_path_with_progress(
+        &self,
+        source: &Path,
+        destination: &Path,
+        options: &CopyOptions,
+        on_progress: &mut dyn FnMut(&Path, u64, u64) -> bool,
+        on_warning: &mut dyn FnMut(&Path, anyhow::Error),
+    ) -> Result<PathBuf> {
+        let destination_path = self.resolve_path(destination);
+        self.with_sftp(SftpOp::Write, |sftp| {
+            // realpath would resolve a symlink that is to be copied as a link.
+            let source_path = if options.follow_symlinks {
+                self.realpath_or_resolved(sftp, source)
+            } else {
+                self.resolve_path(source)
+            };
+            copy_remote_path_recursive(
+                sftp,
+                source_path.as_path(),
+                destination_path.as_path(),
+                options,
+                on_progress,
+                on_warning,
+            )?;
+            Ok(destination_path.clone())
+        })
     }
 
     fn normalize_existing_path(&self, _operation: &'static str, path: &Path) -> Result<PathBuf> {
-        let (_session, sftp) = self.connect()?;
-        let resolved = self.resolve_path(path);
-        match sftp.realpath(resolved.as_path()) {
-            Ok(path) => Ok(path),
-            Err(_) => Ok(resolved),
-        }
+        self.with_sftp(SftpOp::Read, |sftp| {
+            Ok(self.realpath_or_resolved(sftp, path))
+        })
     }
 
     fn normalize_new_path(&self, _operation: &'static str, path: &Path) -> Result<PathBuf> {
@@ -315,39 +1077,63 @@ impl FsBackend for SftpFsBackend {
     }
 
     fn read_file(&self, path: &Path) -> Result<Vec<u8>> {
-        let (_session, sftp) = self.connect()?;
-        let normalized = self.normalize_existing_path("read", path)?;
-        let mut file = sftp.open(normalized.as_path())?;
-        let mut buffer = Vec::new();
-        file.read_to_end(&mut buffer)?;
-        Ok(buffer)
+        self.with_sftp(SftpOp::Read, |sftp| {
+            let normalized = self.realpath_or_resolved(sftp, path);
+            let mut file = sftp.open(normalized.as_path())?;
+            let mut buffer = Vec::new();
+            file.read_to_end(&mut buffer)?;
+            Ok(buffer)
+        })
     }
 
     fn read_file_preview(&self, path: &Path, limit: usize) -> Result<(Vec<u8>, bool)> {
-        let (_session, sftp) = self.connect()?;
-        let normalized = self.normalize_existing_path("read", path)?;
-        let mut file = sftp.open(normalized.as_path())?;
-        let mut reader = (&mut file).take((limit as u64) + 1);
-        let mut bytes = Vec::with_capacity(limit.min(16 * 1024));
-        reader.read_to_end(&mut bytes)?;
-        let truncated = bytes.len() > limit;
-        if truncated {
-            bytes.truncate(limit);
-        }
-        Ok((bytes, truncated))
+        self.with_sftp(SftpOp::Read, |sftp| {
+            let normalized = self.realpath_or_resolved(sftp, path);
+            let mut file = sftp.open(normalized.as_path())?;
+            let mut reader = (&mut file).take((limit as u64) + 1);
+            let mut bytes = Vec::with_capacity(limit.min(16 * 1024));
+            reader.read_to_end(&mut bytes)?;
+            let truncated = bytes.len() > limit;
+            if truncated {
+                bytes.truncate(limit);
+            }
+            Ok((bytes, truncated))
+        })
     }
 
     fn write_file(&self, path: &Path, bytes: &[u8]) -> Result<()> {
-        let (_session, sftp) = self.connect()?;
-        let normalized = self.normalize_new_path("write", path)?;
-        let mut file = sftp.open_mode(
-            normalized.as_path(),
-            OpenFlags::CREATE | OpenFlags::TRUNCATE | OpenFlags::WRITE,
-            0o644,
-            OpenType::File,
-        )?;
-        file.write_all(bytes)?;
-        Ok(())
+        let normalized = self.resolve_path(path);
+        self.with_sftp(SftpOp::Write, |sftp| {
+            let mut file = sftp.open_mode(
+                normalized.as_path(),
+                OpenFlags::CREATE | OpenFlags::TRUNCATE | OpenFlags::WRITE,
+                0o644,
+                OpenType::File,
+            )?;
+            file.write_all(bytes)?;
+            Ok(())
+        })
+    }
+
+    fn open_read(&self, path: &Path) -> Result<Box<dyn Read + Send>> {
+        let stream = self.open_stream(SftpOp::Read, |sftp| {
+            let normalized = self.realpath_or_resolved(sftp, path);
+            Ok(sftp.open(normalized.as_path())?)
+        })?;
+        Ok(Box::new(stream))
+    }
+
+    fn open_write(&self, path: &Path) -> Result<Box<dyn WriteStream>> {
+        let normalized = self.resolve_path(path);
+        let stream = self.open_stream(SftpOp::Write, |sftp| {
+            Ok(sftp.open_mode(
+                normalized.as_path(),
+                OpenFlags::CREATE | OpenFlags::TRUNCATE | OpenFlags::WRITE,
+                0o644,
+                OpenType::File,
+            )?)
+        })?;
+        Ok(Box::new(stream))
     }
 }
 
@@ -363,6 +1149,23 @@ impl ArchiveFsBackend {
     fn index(&self) -> Result<ArchiveIndex> {
         build_archive_index(self.conn.archive_path.as_path())
     }
+
+    fn readable_member(&self, path: &Path) -> Result<(PathBuf, ArchiveFormat)> {
+        let normalized = self.normalize_existing_path("read", path)?;
+        let index = self.index()?;
+        let entry = index.entries.get(&normalized).ok_or_else(|| {
+            anyhow::anyhow!("path not found in archive: {}", normalized.display())
+        })?;
+        if entry.entry_type == FsEntryType::Directory {
+            bail!(
+                "cannot read directory from archive: {}",
+                normalized.display()
+            );
+        }
+        let format = detect_archive_format(self.conn.archive_path.as_path())
+            .ok_or_else(|| anyhow::anyhow!("unsupported archive format"))?;
+        Ok((normalized, format))
+    }
 }
 
 impl FsBackend for ArchiveFsBackend {
@@ -421,6 +1224,79 @@ impl FsBackend for ArchiveFsBackend {
             .ok_or_else(|| anyhow::anyhow!("path not found in archive: {}", normalized.display()))
     }
 
+    fn entry_properties(&self, path: &Path) -> Result<EntryProperties> {
+        let entry = self.stat_entry(path)?;
+        // Member modes are not indexed; report what extracting would create.
+        let mode = if entry.entry_type == FsEntryType::Directory {
+            0o755
+        } else {
+            0o644
+        };
+        Ok(EntryProperties {
+            path: entry.path,
+            entry_type: entry.entry_type,
+            size_bytes: entry.size_bytes,
+            mode,
+            uid: None,
+            gid: None,
+            owner: None,
+            group: None,
+            modified_at: entry.modified_at,
+            accessed_at: None,
+            inode: None,
+            links: None,
+        })
+    }
+
+    fn set_permissions(&self, path: &Path, _mode: u32) -> Result<()> {
+        bail!(
+            "archive backend is read-only (chmod is unsupported): {}",
+            path.display()
+        )
+    }
+
+    fn set_owner(&self, path: &Path, _uid: Option<u32>, _gid: Option<u32>) -> Result<()> {
+        bail!(
+            "archive backend is read-only (chown is unsupported): {}",
+            path.display()
+        )
+    }
+
+    fn set_times(&self, path: &Path, _modified: SystemTime) -> Result<()> {
+        bail!(
+            "archive backend is read-only (touch is unsupported): {}",
+            path.display()
+        )
+    }
+
+    fn preserve_attributes(&self, path: &Path, _source: &EntryProperties) -> Result<()> {
+        bail!(
+            "archive backend is read-only (attributes cannot be set): {}",
+            path.display()
+        )
+    }
+
+    fn read_link(&self, path: &Path) -> Result<PathBuf> {
+        bail!(
+            "archive backend does not index symlink targets: {}",
+            path.display()
+        )
+    }
+
+    fn create_symlink(&self, _target: &Path, link: &Path) -> Result<()> {
+        bail!(
+            "archive backend is read-only (symlink is unsupported): {}",
+            link.display()
+        )
+    }
+
+    fn create_hard_link(&self, _target: &Path, link: &Path) -> Result<()> {
+        bail!(
+            "archive backend is read-only (hardlink is unsupported): {}",
+            link.display()
+        )
+    }
+
     fn create_dir(&self, path: &Path) -> Result<()> {
         bail!(
             "archive backend is read-only (mkdir is unsupported): {}",
@@ -469,42 +1345,16 @@ impl FsBackend for ArchiveFsBackend {
     }
 
     fn read_file(&self, path: &Path) -> Result<Vec<u8>> {
-        let normalized = self.normalize_existing_path("read", path)?;
-        let index = self.index()?;
-        let entry = index.entries.get(&normalized).ok_or_else(|| {
-            anyhow::anyhow!("path not found in archive: {}", normalized.display())
-        })?;
-        if entry.entry_type == FsEntryType::Directory {
-            bail!(
-                "cannot read directory from archive: {}",
-                normalized.display()
-            );
-        }
-        read_archive_member(
-            self.conn.archive_path.as_path(),
-            normalized.as_path(),
-            detect_archive_format(self.conn.archive_path.as_path())
-                .ok_or_else(|| anyhow::anyhow!("unsupported archive format"))?,
-        )
+        let (member, format) = self.readable_member(path)?;
+        read_archive_member(self.conn.archive_path.as_path(), member.as_path(), format)
     }
 
     fn read_file_preview(&self, path: &Path, limit: usize) -> Result<(Vec<u8>, bool)> {
-        let normalized = self.normalize_existing_path("read", path)?;
-        let index = self.index()?;
-        let entry = index.entries.get(&normalized).ok_or_else(|| {
-            anyhow::anyhow!("path not found in archive: {}", normalized.display())
-        })?;
-        if entry.entry_type == FsEntryType::Directory {
-            bail!(
-                "cannot read directory from archive: {}",
-                normalized.display()
-            );
-        }
+        let (member, format) = self.readable_member(path)?;
         read_archive_member_preview(
             self.conn.archive_path.as_path(),
-            normalized.as_path(),
-            detect_archive_format(self.conn.archive_path.as_path())
-                .ok_or_else(|| anyhow::anyhow!("unsupported archive format"))?,
+            member.as_path(),
+            format,
             limit,
         )
     }
@@ -515,6 +1365,22 @@ impl FsBackend for ArchiveFsBackend {
             path.display()
         )
     }
+
+    fn open_read(&self, path: &Path) -> Result<Box<dyn Read + Send>> {
+        let (member, format) = self.readable_member(path)?;
+        Ok(Box::new(spawn_archive_member_stream(
+            self.conn.archive_path.clone(),
+            member,
+            format,
+        )))
+    }
+
+    fn open_write(&self, path: &Path) -> Result<Box<dyn WriteStream>> {
+        bail!(
+            "archive backend is read-only (write is unsupported): {}",
+            path.display()
+        )
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -548,6 +1414,8 @@ impl ArchiveIndex {
                 is_executable: false,
                 is_hidden: false,
                 is_virtual: false,
+                link_target: None,
+                link_broken: false,
             });
             return;
         }
@@ -569,6 +1437,8 @@ impl ArchiveIndex {
                     is_executable: false,
                     is_hidden: name.starts_with('.'),
                     is_virtual: false,
+                    link_target: None,
+                    link_broken: false,
                 },
             );
         }
@@ -592,6 +1462,8 @@ impl ArchiveIndex {
                 is_executable: false,
                 is_hidden: name.starts_with('.'),
                 is_virtual: false,
+                link_target: None,
+                link_broken: false,
             },
         );
         self.register_child(path.as_path());
@@ -607,6 +1479,8 @@ impl ArchiveIndex {
             is_executable: false,
             is_hidden: false,
             is_virtual: false,
+            link_target: None,
+            link_broken: false,
         });
 
         let mut cursor = PathBuf::from("/");
@@ -632,6 +1506,8 @@ impl ArchiveIndex {
                             is_executable: false,
                             is_hidden: name.starts_with('.'),
                             is_virtual: false,
+                            link_target: None,
+                            link_broken: false,
                         },
                     );
                 }
@@ -745,11 +1621,11 @@ fn read_archive_member(
     member: &Path,
     format: ArchiveFormat,
 ) -> Result<Vec<u8>> {
-    match format {
-        ArchiveFormat::Zip => read_zip_member(archive_path, member),
-        ArchiveFormat::Tar => read_tar_member(archive_path, member, false),
-        ArchiveFormat::TarGz => read_tar_member(archive_path, member, true),
-    }
+    with_archive_member_reader(archive_path, member, format, |reader| {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        Ok(bytes)
+    })
 }
 
 fn read_archive_member_preview(
@@ -758,15 +1634,30 @@ fn read_archive_member_preview(
     format: ArchiveFormat,
     limit: usize,
 ) -> Result<(Vec<u8>, bool)> {
+    with_archive_member_reader(archive_path, member, format, |reader| {
+        read_with_limit(reader, limit)
+    })
+}
+
+fn with_archive_member_reader<T>(
+    archive_path: &Path,
+    member: &Path,
+    format: ArchiveFormat,
+    on_member: impl FnOnce(&mut dyn Read) -> Result<T>,
+) -> Result<T> {
+    let file = fs::File::open(archive_path)?;
     match format {
-        ArchiveFormat::Zip => read_zip_member_preview(archive_path, member, limit),
-        ArchiveFormat::Tar => read_tar_member_preview(archive_path, member, false, limit),
-        ArchiveFormat::TarGz => read_tar_member_preview(archive_path, member, true, limit),
+        ArchiveFormat::Zip => with_zip_member_reader(file, member, on_member),
+        ArchiveFormat::Tar => with_tar_member_reader(file, member, on_member),
+        ArchiveFormat::TarGz => with_tar_member_reader(GzDecoder::new(file), member, on_member),
     }
 }
 
-fn read_zip_member(archive_path: &Path, member: &Path) -> Result<Vec<u8>> {
-    let file = fs::File::open(archive_path)?;
+fn with_zip_member_reader<T>(
+    file: fs::File,
+    member: &Path,
+    on_member: impl FnOnce(&mut dyn Read) -> Result<T>,
+) -> Result<T> {
     let mut archive = ZipArchive::new(file)?;
     for idx in 0..archive.len() {
         let mut file = archive.by_index(idx)?;
@@ -779,130 +1670,90 @@ fn read_zip_member(archive_path: &Path, member: &Path) -> Result<Vec<u8>> {
         if file.is_dir() || file.name().ends_with('/') {
             bail!("archive member is a directory: {}", member.display());
         }
-        let mut bytes = Vec::new();
-        file.read_to_end(&mut bytes)?;
-        return Ok(bytes);
+        return on_member(&mut file);
     }
     bail!("archive member not found: {}", member.display())
 }
 
-fn read_zip_member_preview(
-    archive_path: &Path,
+fn with_tar_member_reader<R: Read, T>(
+    reader: R,
     member: &Path,
-    limit: usize,
-) -> Result<(Vec<u8>, bool)> {
-    let file = fs::File::open(archive_path)?;
-    let mut archive = ZipArchive::new(file)?;
-    for idx in 0..archive.len() {
-        let mut file = archive.by_index(idx)?;
-        let Some(path) = archive_member_to_virtual_path(file.name()) else {
+    on_member: impl FnOnce(&mut dyn Read) -> Result<T>,
+) -> Result<T> {
+    let mut archive = TarArchive::new(reader);
+    for item in archive.entries()? {
+        let mut entry = item?;
+        let Some(path) = archive_member_to_virtual_path(entry.path()?.to_string_lossy().as_ref())
+        else {
             continue;
         };
         if path != member {
             continue;
         }
-        if file.is_dir() || file.name().ends_with('/') {
+        if entry.header().entry_type().is_dir() {
             bail!("archive member is a directory: {}", member.display());
         }
-        return read_with_limit(&mut file, limit);
+        return on_member(&mut entry);
     }
     bail!("archive member not found: {}", member.display())
 }
 
-fn read_tar_member(archive_path: &Path, member: &Path, compressed: bool) -> Result<Vec<u8>> {
-    if compressed {
-        let file = fs::File::open(archive_path)?;
-        let decoder = GzDecoder::new(file);
-        let mut archive = TarArchive::new(decoder);
-        for item in archive.entries()? {
-            let mut entry = item?;
-            let Some(path) =
-                archive_member_to_virtual_path(entry.path()?.to_string_lossy().as_ref())
-            else {
-                continue;
-            };
-            if path != member {
-                continue;
-            }
-            if entry.header().entry_type().is_dir() {
-                bail!("archive member is a directory: {}", member.display());
-            }
-            let mut bytes = Vec::new();
-            entry.read_to_end(&mut bytes)?;
-            return Ok(bytes);
-        }
-    } else {
-        let file = fs::File::open(archive_path)?;
-        let mut archive = TarArchive::new(file);
-        for item in archive.entries()? {
-            let mut entry = item?;
-            let Some(path) =
-                archive_member_to_virtual_path(entry.path()?.to_string_lossy().as_ref())
-            else {
-                continue;
-            };
-            if path != member {
-                continue;
-            }
-            if entry.header().entry_type().is_dir() {
-                bail!("archive member is a directory: {}", member.display());
+/// Streams an archive member through a bounded queue fed by a reader thread,
+/// since zip and tar member readers borrow their archive and cannot be returned.
+fn spawn_archive_member_stream(
+    archive_path: PathBuf,
+    member: PathBuf,
+    format: ArchiveFormat,
+) -> ArchiveMemberStream {
+    let (chunk_tx, chunk_rx) = bounded::<std::io::Result<Vec<u8>>>(ARCHIVE_STREAM_QUEUE_DEPTH);
+    thread::spawn(move || {
+        let result = with_archive_member_reader(&archive_path, &member, format, |reader| {
+            let mut buffer = vec![0_u8; ARCHIVE_STREAM_CHUNK_SIZE];
+            loop {
+                let read = reader.read(buffer.as_mut_slice())?;
+                if read == 0 || chunk_tx.send(Ok(buffer[..read].to_vec())).is_err() {
+                    return Ok(());
+                }
             }
-            let mut bytes = Vec::new();
-            entry.read_to_end(&mut bytes)?;
-            return Ok(bytes);
+        });
+        if let Err(err) = result {
+            let _ = chunk_tx.send(Err(std::io::Error::other(err.to_string())));
         }
+    });
+    ArchiveMemberStream {
+        chunk_rx,
+        chunk: Vec::new(),
+        offset: 0,
     }
-    bail!("archive member not found: {}", member.display())
 }
 
-fn read_tar_member_preview(
-    archive_path: &Path,
-    member: &Path,
-    compressed: bool,
-    limit: usize,
-) -> Result<(Vec<u8>, bool)> {
-    if compressed {
-        let file = fs::File::open(archive_path)?;
-        let decoder = GzDecoder::new(file);
-        let mut archive = TarArchive::new(decoder);
-        for item in archive.entries()? {
-            let mut entry = item?;
-            let Some(path) =
-                archive_member_to_virtual_path(entry.path()?.to_string_lossy().as_ref())
-            else {
-                continue;
-            };
-            if path != member {
-                continue;
-            }
-            if entry.header().entry_type().is_dir() {
-                bail!("archive member is a directory: {}", member.display());
-            }
-            return read_with_limit(&mut entry, limit);
-        }
-    } else {
-        let file = fs::File::open(archive_path)?;
-        let mut archive = TarArchive::new(file);
-        for item in archive.entries()? {
-            let mut entry = item?;
-            let Some(path) =
-                archive_member_to_virtual_path(entry.path()?.to_string_lossy().as_ref())
-            else {
-                continue;
-            };
-            if path != member {
-                continue;
-            }
-            if entry.header().entry_type().is_dir() {
-                bail!("archive member is a directory: {}", member.display());
+struct ArchiveMemberStream {
+    chunk_rx: Receiver<std::io::Result<Vec<u8>>>,
+    chunk: Vec<u8>,
+    offset: usize,
+}
+
+impl Read for ArchiveMemberStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.offset >= self.chunk.len() {
+            match self.chunk_rx.recv() {
+                Ok(Ok(chunk)) => {
+                    self.chunk = chunk;
+                    self.offset = 0;
+                }
+                Ok(Err(err)) => return Err(err),
+                Err(_) => return Ok(0),
             }
-            return read_with_limit(&mut entry, limit);
         }
+        let available = &self.chunk[self.offset..];
+        let count = available.len().min(buf.len());
+        buf[..count].copy_from_slice(&available[..count]);
+        self.offset += count;
+        Ok(count)
     }
-    bail!("archive member not found: {}", member.display())
 }
 
-fn read_with_limit<R: Read>(reader: &mut R, limit: usize) -> Result<(Vec<u8>, bool)> {
+fn read_with_limit<R: Read + ?Sized>(reader: &mut R, limit: usize) -> Result<(Vec<u8>, bool)> {
     let mut limited = reader.take((limit as u64) + 1);
     let mut bytes = Vec::with_capacity(limit.min(16 * 1024));
     limited.read_to_end(&mut bytes)?;
@@ -951,6 +1802,19 @@ fn normalize_archive_virtual_path(path: &Path) -> PathBuf {
     }
 }
 
+/// A `FileStat` with every field unset, so `setstat` only touches what the
+/// caller fills in.
+fn empty_file_stat() -> FileStat {
+    FileStat {
+        size: None,
+        uid: None,
+        gid: None,
+        perm: None,
+        atime: None,
+        mtime: None,
+    }
+}
+
 fn entry_type_from_stat(stat: &FileStat) -> FsEntryType {
     let mode = stat.perm.unwrap_or(0);
     match mode & S_IFMT {
@@ -969,6 +1833,11 @@ fn modified_from_stat(stat: &FileStat) -> Option<SystemTime> {
         .map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
 }
 
+fn accessed_from_stat(stat: &FileStat) -> Option<SystemTime> {
+    stat.atime
+        .map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
+}
+
 fn parent_link(parent: PathBuf) -> FsEntry {
     FsEntry {
         name: "..".to_string(),
@@ -979,6 +1848,8 @@ fn parent_link(parent: PathBuf) -> FsEntry {
         is_executable: false,
         is_hidden: false,
         is_virtual: true,
+        link_target: None,
+        link_broken: false,
     }
 }
 
@@ -1022,7 +1893,7 @@ fn cmp_modified_at(left: &FsEntry, right: &FsEntry) -> std::cmp::Ordering {
 }
 
 fn remove_remote_path_recursive(sftp: &Sftp, path: &Path) -> Result<()> {
-    let stat = sftp.stat(path)?;
+    let stat = sftp.lstat(path)?;
     if entry_type_from_stat(&stat) == FsEntryType::Directory {
         for (child_path, _) in sftp.readdir(path)? {
             let Some(name) = child_path
@@ -1070,7 +1941,78 @@ fn classify_sftp_error(err: &anyhow::Error) -> &'static str {
     }
 }
 
-fn copy_remote_path_recursive(sftp: &Sftp, source: &Path, destination: &Path) -> Result<()> {
+/// Whether an SFTP operation only reads, so it can be replayed safely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SftpOp {
+    Read,
+    Write,
+}
+
+/// Whether `err` from a `kind` operation on a `reused` session warrants one
+/// more attempt on a fresh connection.
+fn retries_on_fresh_session(kind: SftpOp, reused: bool, err: &anyhow::Error) -> bool {
+    kind == SftpOp::Read && reused && is_stale_session_error(err)
+}
+
+fn is_stale_session_error(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        if let Some(ssh_err) = cause.downcast_ref::<ssh2::Error>() {
+            return is_stale_ssh_error(ssh_err);
+        }
+        let Some(io_err) = cause.downcast_ref::<std::io::Error>() else {
+            return false;
+        };
+        if let Some(ssh_err) = io_err
+            .get_ref()
+            .and_then(|inner| inner.downcast_ref::<ssh2::Error>())
+        {
+            return is_stale_ssh_error(ssh_err);
+        }
+        matches!(
+            io_err.kind(),
+            std::io::ErrorKind::BrokenPipe
+                | std::io::ErrorKind::ConnectionReset
+                | std::io::ErrorKind::ConnectionAborted
+                | std::io::ErrorKind::NotConnected
+                | std::io::ErrorKind::TimedOut
+                | std::io::ErrorKind::UnexpectedEof
+        )
+    })
+}
+
+fn is_stale_ssh_error(err: &ssh2::Error) -> bool {
+    match err.code() {
+        ErrorCode::Session(_) => true,
+        ErrorCode::SFTP(code) => {
+            code == LIBSSH2_FX_NO_CONNECTION || code == LIBSSH2_FX_CONNECTION_LOST
+        }
+    }
+}
+
+/// Copies within one SFTP session; attributes that cannot be preserved go to
+/// `on_warning` and leave the copy in place.
+fn copy_remote_path_recursive(
+    sftp: &Sftp,
+    source: &Path,
+    destination: &Path,
+    options: &CopyOptions,
+    on_progress: &mut dyn FnMut(&Path, u64, u64) -> bool,
+    on_warning: &mut dyn FnMut(&Path, anyhow::Error),
+) -> Result<()> {
+    if !options.follow_symlinks
+        && entry_type_from_stat(&sftp.lstat(source)?) == FsEntryType::Symlink
+    {
+        if !on_progress(source, 0, 0) {
+            return Err(AppError::canceled("copy", destination).into());
+        }
+        let mut target = sftp.readlink(source)?;
+        if options.stable_symlinks {
+            target = stable_link_target(source, &target);
+        }
+        sftp.symlink(target.as_path(), destination)?;
+        return Ok(());
+    }
+
     let stat = sftp.stat(source)?;
     if entry_type_from_stat(&stat) == FsEntryType::Directory {
         let _ = sftp.mkdir(destination, 0o755);
@@ -1085,11 +2027,27 @@ fn copy_remote_path_recursive(sftp: &Sftp, source: &Path, destination: &Path) ->
                 continue;
             }
             let target = destination.join(name);
-            copy_remote_path_recursive(sftp, child_path.as_path(), target.as_path())?;
+            copy_remote_path_recursive(
+                sftp,
+                child_path.as_path(),
+                target.as_path(),
+                options,
+                on_progress,
+                on_warning,
+            )?;
+        }
+        if options.preserve_attributes
+            && let Err(err) = set_preserved_stat(sftp, destination, copied_file_stat(&stat))
+        {
+            on_warning(destination, err);
         }
         return Ok(());
     }
 
+    let size = stat.size.unwrap_or(0);
+    if !on_progress(source, 0, size) {
+        return Err(AppError::canceled("copy", destination).into());
+    }
     let mut src_file = sftp.open(source)?;
     let mut dst_file = sftp.open_mode(
         destination,
@@ -1097,6 +2055,7 @@ fn copy_remote_path_recursive(sftp: &Sftp, source: &Path, destination: &Path) ->
         0o644,
         OpenType::File,
     )?;
+    let mut copied = 0u64;
     let mut buffer = vec![0_u8; 32 * 1024];
     loop {
         let read = src_file.read(buffer.as_mut_slice())?;
@@ -1104,6 +2063,119 @@ fn copy_remote_path_recursive(sftp: &Sftp, source: &Path, destination: &Path) ->
             break;
         }
         dst_file.write_all(&buffer[..read])?;
+        copied = copied.saturating_add(read as u64);
+        if !on_progress(source, copied, size.max(copied)) {
+            drop(dst_file);
+            let _ = sftp.unlink(destination);
+            return Err(AppError::canceled("copy", destination).into());
+        }
+    }
+    if copied < size {
+        on_progress(source, copied, copied);
+    }
+    drop(dst_file);
+    if options.preserve_attributes
+        && let Err(err) = set_preserved_stat(sftp, destination, copied_file_stat(&stat))
+    {
+        on_warning(destination, err);
     }
     Ok(())
 }
+
+/// Applies preserved attributes to `path`. Only the server knows whether the
+/// login may hand files to other users, so ownership is tried first and
+/// dropped when the server refuses it.
+fn set_preserved_stat(sftp: &Sftp, path: &Path, stat: FileStat) -> Result<()> {
+    let with_owner = stat.uid.is_some() || stat.gid.is_some();
+    match sftp.setstat(path, stat.clone()) {
+        Err(err) if with_owner && is_permission_denied(&err) => {
+            sftp.setstat(
+                path,
+                FileStat {
+                    uid: None,
+                    gid: None,
+                    ..stat
+                },
+            )?;
+            Ok(())
+        }
+        result => Ok(result?),
+    }
+}
+
+fn is_permission_denied(err: &ssh2::Error) -> bool {
+    err.code() == ErrorCode::SFTP(LIBSSH2_FX_PERMISSION_DENIED)
+}
+
+fn is_no_such_file(err: &ssh2::Error) -> bool {
+    matches!(
+        err.code(),
+        ErrorCode::SFTP(LIBSSH2_FX_NO_SUCH_FILE | LIBSSH2_FX_NO_SUCH_PATH)
+    )
+}
+
+/// Stat fields to carry from a copied remote entry over to its copy.
+fn copied_file_stat(source: &FileStat) -> FileStat {
+    FileStat {
+        uid: source.uid,
+        gid: source.gid,
+        perm: source.perm.map(|perm| perm & 0o7777),
+        atime: source.atime.or(source.mtime),
+        mtime: source.mtime,
+        ..empty_file_stat()
+    }
+}
+
+/// Stat fields that give a remote entry the attributes of `source`. SFTP
+/// `setstat` follows symlinks, so a symlink source only keeps its times.
+fn preserved_file_stat(source: &EntryProperties) -> FileStat {
+    let seconds = |time: Option<SystemTime>| {
+        time.and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|since_epoch| since_epoch.as_secs())
+    };
+    let mtime = seconds(source.modified_at);
+    let is_symlink = source.entry_type == FsEntryType::Symlink;
+    FileStat {
+        uid: source.uid,
+        gid: source.gid,
+        perm: (!is_symlink).then_some(source.mode & 0o7777),
+        atime: seconds(source.accessed_at).or(mtime),
+        mtime,
+        ..empty_file_stat()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stale_sessions_are_told_apart_from_remote_errors() {
+        let dropped = anyhow::Error::new(ssh2::Error::new(ErrorCode::Session(-7), "socket send"))
+            .context("sftp readdir failed");
+        assert!(is_stale_session_error(&dropped));
+        let lost = anyhow::Error::new(ssh2::Error::new(
+            ErrorCode::SFTP(LIBSSH2_FX_CONNECTION_LOST),
+            "connection lost",
+        ));
+        assert!(is_stale_session_error(&lost));
+        let pipe = anyhow::Error::new(std::io::Error::from(std::io::ErrorKind::BrokenPipe));
+        assert!(is_stale_session_error(&pipe));
+
+        let missing = anyhow::Error::new(ssh2::Error::new(ErrorCode::SFTP(2), "no such file"));
+        assert!(!is_stale_session_error(&missing));
+        let denied = anyhow::Error::new(std::io::Error::from(std::io::ErrorKind::PermissionDenied));
+        assert!(!is_stale_session_error(&denied));
+    }
+
+    #[test]
+    fn only_reads_on_reused_sessions_are_replayed() {
+        let stale = anyhow::Error::new(ssh2::Error::new(ErrorCode::Session(-7), "socket send"));
+        assert!(retries_on_fresh_session(SftpOp::Read, true, &stale));
+        assert!(!retries_on_fresh_session(SftpOp::Write, true, &stale));
+        assert!(!retries_on_fresh_session(SftpOp::Read, false, &stale));
+
+        let missing = anyhow::Error::new(ssh2::Error::new(ErrorCode::SFTP(2), "no such file"));
+        assert!(!retries_on_fresh_session(SftpOp::Read, true, &missing));
+    }
+}
@@ -0,0 +1,142 @@
+//! Files under the vcmc config directory. They all share one TOML-like
+//! format: `[[table]]` headers, each followed by `key = "value"` lines.
+
+use std::env;
+use std::path::PathBuf;
+
+pub fn config_dir_path() -> Option<PathBuf> {
+    if let Some(base) = env::var_os("XDG_CONFIG_HOME") {
+        let base = PathBuf::from(base);
+        if !base.as_os_str().is_empty() {
+            return Some(base.join("vcmc"));
+        }
+    }
+    env::var_os("HOME")
+        .map(PathBuf::from)
+        .map(|home| home.join(".config").join("vcmc"))
+}
+
+/// The `[[name]]` tables of `content` in file order, each as its keys and
+/// parsed values. Comments, blank lines and keys before the first table are
+/// skipped.
+pub fn parse_tables(content: &str, name: &str) -> Vec<Vec<(String, String)>> {
+    let header = format!("[[{name}]]");
+    let mut tables = Vec::new();
+    let mut current: Option<Vec<(String, String)>> = None;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if trimmed.starts_with("[[") {
+            tables.extend(current.take());
+            current = (trimmed == header).then(Vec::new);
+            continue;
+        }
+        let Some((key, value)) = trimmed.split_once('=') else {
+            continue;
+        };
+        if let Some(table) = current.as_mut() {
+            let value = parse_value(value).unwrap_or_default();
+            table.push((key.trim().to_string(), value));
+        }
+    }
+    tables.extend(current);
+    tables
+}
+
+/// Value of `key` in a table from `parse_tables`.
+pub fn table_value<'a>(table: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    table
+        .iter()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value.as_str())
+}
+
+/// `key = "value"` with the value escaped.
+pub fn string_line(key: &str, value: &str) -> String {
+    format!("{key} = \"{}\"", escape_toml_string(value))
+}
+
+/// A value as written after `=`: a quoted string is unescaped, anything else
+/// is taken as it is.
+pub fn parse_value(raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return None;
+    }
+    if raw.starts_with('"') && raw.ends_with('"') && raw.len() >= 2 {
+        let inner = &raw[1..raw.len() - 1];
+        return Some(unescape_toml_string(inner));
+    }
+    Some(raw.to_string())
+}
+
+fn escape_toml_string(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    for ch in raw.chars() {
+        match ch {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+fn unescape_toml_string(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            if let Some(next) = chars.next() {
+                match next {
+                    '\\' => out.push('\\'),
+                    '"' => out.push('"'),
+                    'n' => out.push('\n'),
+                    'r' => out.push('\r'),
+                    't' => out.push('\t'),
+                    other => {
+                        out.push('\\');
+                        out.push(other);
+                    }
+                }
+            } else {
+                out.push('\\');
+            }
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_tables, parse_value, string_line};
+
+    #[test]
+    fn tables_keep_their_own_keys_and_escapes_round_trip() {
+        let tricky = "a \"quoted\" \\ path\twith\nbreaks";
+        let content = format!(
+            "# comment\nstray = \"x\"\n[[entry]]\n{}\nport = 22\n\n[[other]]\nname = \"skip\"\n[[entry]]\nname = plain\n",
+            string_line("name", tricky)
+        );
+        let tables = parse_tables(&content, "entry");
+        assert_eq!(
+            tables,
+            [
+                vec![
+                    ("name".to_string(), tricky.to_string()),
+                    ("port".to_string(), "22".to_string()),
+                ],
+                vec![("name".to_string(), "plain".to_string())],
+            ]
+        );
+        assert_eq!(parse_value("  "), None);
+        assert_eq!(parse_value("\"a\\qb\"").as_deref(), Some("a\\qb"));
+    }
+}
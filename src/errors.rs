@@ -36,10 +36,55 @@ pub enum AppError {
         path: PathBuf,
         reason: String,
     },
+    #[error("{operation} canceled: {path}")]
+    Canceled {
+        operation: &'static str,
+        path: PathBuf,
+    },
 }
 
 pub type AppResult<T> = Result<T, AppError>;
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostKeyInfo {
+    pub host: String,
+    pub port: u16,
+    pub key_type: String,
+    pub fingerprint: String,
+    pub key: Vec<u8>,
+}
+
+impl HostKeyInfo {
+    pub fn endpoint(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum HostKeyError {
+    #[error(
+        "host key for {} is not in known_hosts ({} {})",
+        .0.endpoint(),
+        .0.key_type,
+        .0.fingerprint
+    )]
+    Unknown(HostKeyInfo),
+    #[error(
+        "host key for {} has CHANGED ({} {}); possible man-in-the-middle attack",
+        .0.endpoint(),
+        .0.key_type,
+        .0.fingerprint
+    )]
+    Changed(HostKeyInfo),
+    #[error(
+        "host key for {} is REVOKED in known_hosts ({} {})",
+        .0.endpoint(),
+        .0.key_type,
+        .0.fingerprint
+    )]
+    Revoked(HostKeyInfo),
+}
+
 impl AppError {
     pub fn from_io(operation: &'static str, path: PathBuf, source: io::Error) -> Self {
         match source.kind() {
@@ -76,4 +121,116 @@ impl AppError {
             reason: reason.into(),
         }
     }
+
+    pub fn canceled(operation: &'static str, path: impl Into<PathBuf>) -> Self {
+        Self::Canceled {
+            operation,
+            path: path.into(),
+        }
+    }
+}
+
+/// Coarse failure category used to group job errors in batch summaries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorClass {
+    Permission,
+    NotFound,
+    Conflict,
+    InvalidPath,
+    Io,
+}
+
+impl ErrorClass {
+    pub const ALL: [Self; 5] = [
+        Self::Permission,
+        Self::NotFound,
+        Self::Conflict,
+        Self::InvalidPath,
+        Self::Io,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Permission => "permission",
+            Self::NotFound => "not found",
+            Self::Conflict => "conflict",
+            Self::InvalidPath => "invalid path",
+            Self::Io => "io",
+        }
+    }
+}
+
+impl AppError {
+    pub fn class(&self) -> ErrorClass {
+        match self {
+            Self::PermissionDenied { .. } => ErrorClass::Permission,
+            Self::NotFound { .. } => ErrorClass::NotFound,
+            Self::InvalidPath { .. } => ErrorClass::InvalidPath,
+            Self::Conflict { .. } => ErrorClass::Conflict,
+            Self::Io { .. } | Self::Canceled { .. } => ErrorClass::Io,
+        }
+    }
+}
+
+/// Classifies an error by the first recognizable cause in its chain; backend
+/// errors that never went through `AppError` still map by io kind or SFTP code.
+pub fn classify_error(err: &anyhow::Error) -> ErrorClass {
+    for cause in err.chain() {
+        if let Some(app) = cause.downcast_ref::<AppError>() {
+            return app.class();
+        }
+        if let Some(io_err) = cause.downcast_ref::<io::Error>() {
+            return match io_err.kind() {
+                io::ErrorKind::PermissionDenied => ErrorClass::Permission,
+                io::ErrorKind::NotFound => ErrorClass::NotFound,
+                io::ErrorKind::AlreadyExists => ErrorClass::Conflict,
+                _ => ErrorClass::Io,
+            };
+        }
+        if let Some(ssh_err) = cause.downcast_ref::<ssh2::Error>() {
+            // LIBSSH2_FX_* status codes from the SFTP protocol.
+            return match ssh_err.code() {
+                ssh2::ErrorCode::SFTP(2 | 10) => ErrorClass::NotFound,
+                ssh2::ErrorCode::SFTP(3) => ErrorClass::Permission,
+                ssh2::ErrorCode::SFTP(11) => ErrorClass::Conflict,
+                _ => ErrorClass::Io,
+            };
+        }
+    }
+    ErrorClass::Io
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_error_walks_context_chain() {
+        let err = anyhow::Error::new(AppError::NotFound {
+            operation: "copy",
+            path: PathBuf::from("/tmp/missing"),
+        })
+        .context("copy failed");
+        assert_eq!(classify_error(&err), ErrorClass::NotFound);
+
+        let err = anyhow::Error::new(io::Error::from(io::ErrorKind::PermissionDenied));
+        assert_eq!(classify_error(&err), ErrorClass::Permission);
+
+        let err = anyhow::Error::new(AppError::conflict("move", "/tmp/a", "exists"));
+        assert_eq!(classify_error(&err), ErrorClass::Conflict);
+
+        let err = anyhow::Error::new(AppError::invalid_path("mkdir", "/", "no name"));
+        assert_eq!(classify_error(&err), ErrorClass::InvalidPath);
+
+        let err = anyhow::anyhow!("connection reset");
+        assert_eq!(classify_error(&err), ErrorClass::Io);
+    }
+
+    #[test]
+    fn classify_error_maps_sftp_status_codes() {
+        let err = anyhow::Error::new(ssh2::Error::new(ssh2::ErrorCode::SFTP(3), "denied"));
+        assert_eq!(classify_error(&err), ErrorClass::Permission);
+        let err = anyhow::Error::new(ssh2::Error::new(ssh2::ErrorCode::SFTP(2), "no such file"));
+        assert_eq!(classify_error(&err), ErrorClass::NotFound);
+    }
 }
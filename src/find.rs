@@ -11,6 +11,7 @@ use std::time::{Duration, Instant};
 use anyhow::{Result, bail};
 use crossbeam_channel::Sender;
 
+use crate::fs::link_target_info;
 use crate::model::{Event, FindRequest, FindUpdate, FsEntry, FsEntryType};
 
 const FIND_PROGRESS_INTERVAL: Duration = Duration::from_millis(150);
@@ -494,6 +495,8 @@ fn content_entry_from_match(path: &Path, line_no: usize, snippet: &str, root: &P
             .map(|name| name.to_string_lossy().starts_with('.'))
             .unwrap_or(false),
         is_virtual: false,
+        link_target: None,
+        link_broken: false,
     }
 }
 
@@ -549,6 +552,11 @@ fn entry_from_path(path: &Path) -> FsEntry {
         .unwrap_or(0);
     let modified_at = metadata.as_ref().and_then(|meta| meta.modified().ok());
     let is_executable = metadata.as_ref().map(|meta| is_exec(meta)).unwrap_or(false);
+    let (link_target, link_broken) = if entry_type == FsEntryType::Symlink {
+        link_target_info(path)
+    } else {
+        (None, false)
+    };
 
     FsEntry {
         name: file_name.clone(),
@@ -559,6 +567,8 @@ fn entry_from_path(path: &Path) -> FsEntry {
         is_executable,
         is_hidden: file_name.starts_with('.'),
         is_virtual: false,
+        link_target,
+        link_broken,
     }
 }
 
@@ -2,16 +2,21 @@
 
 use std::cmp::Ordering;
 use std::env;
+use std::ffi::{CStr, CString};
 use std::fs;
+use std::io::{self, Read, Write};
+use std::os::unix::ffi::OsStrExt;
 #[cfg(unix)]
-use std::os::unix::fs::PermissionsExt;
-use std::path::{Path, PathBuf};
-use std::time::SystemTime;
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::path::{Component, Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use walkdir::WalkDir;
 
 use crate::errors::{AppError, AppResult};
-use crate::model::{FsEntry, FsEntryType, SortMode};
+use crate::model::{CopyOptions, EntryProperties, FsEntry, FsEntryType, SortMode};
+
+const COPY_CHUNK_SIZE: usize = 256 * 1024;
 
 #[derive(Debug, Default, Clone)]
 pub struct FsAdapter;
@@ -43,6 +48,11 @@ impl FsAdapter {
             let entry_type = map_entry_type(&metadata);
             let size_bytes = metadata.len();
             let modified_at = metadata.modified().ok();
+            let (link_target, link_broken) = if entry_type == FsEntryType::Symlink {
+                link_target_info(&path)
+            } else {
+                (None, false)
+            };
             entries.push(FsEntry {
                 name,
                 path,
@@ -52,6 +62,8 @@ impl FsAdapter {
                 is_executable: is_executable(&metadata),
                 is_hidden,
                 is_virtual: false,
+                link_target,
+                link_broken,
             });
         }
 
@@ -81,17 +93,78 @@ impl FsAdapter {
             is_executable: is_executable(&metadata),
             is_hidden,
             is_virtual: false,
+            link_target: None,
+            link_broken: false,
+        })
+    }
+
+    pub fn entry_properties(&self, path: &Path) -> AppResult<EntryProperties> {
+        let normalized = self.normalize_existing_path("stat", path)?;
+        let metadata = fs::symlink_metadata(&normalized)
+            .map_err(|err| AppError::from_io("stat", normalized.clone(), err))?;
+
+        Ok(EntryProperties {
+            owner: user_name(metadata.uid()),
+            group: group_name(metadata.gid()),
+            ..properties_from_metadata(normalized, &metadata)
         })
     }
 
+    /// Gives `path` the mode, times and, when running as root, the ownership
+    /// recorded in `source`. A symlink at `path` is changed itself, not its
+    /// target.
+    pub fn preserve_attributes(&self, path: &Path, source: &EntryProperties) -> AppResult<()> {
+        let absolute = self.absolute_path("preserve", path)?;
+        apply_preserved_attributes(&absolute, source)
+    }
+
+    pub fn read_link(&self, path: &Path) -> AppResult<PathBuf> {
+        let absolute = self.absolute_path("readlink", path)?;
+        fs::read_link(&absolute).map_err(|err| AppError::from_io("readlink", absolute, err))
+    }
+
+    pub fn create_symlink(&self, target: &Path, link: &Path) -> AppResult<()> {
+        let normalized = self.normalize_new_path("symlink", link)?;
+        std::os::unix::fs::symlink(target, &normalized)
+            .map_err(|err| AppError::from_io("symlink", normalized, err))
+    }
+
+    /// Creates a hard link at `link` to the file at `target`. A symlink at
+    /// `target` is linked itself, not what it points to.
+    pub fn create_hard_link(&self, target: &Path, link: &Path) -> AppResult<()> {
+        let source = self.absolute_path("hardlink", target)?;
+        let normalized = self.normalize_new_path("hardlink", link)?;
+        fs::hard_link(&source, &normalized)
+            .map_err(|err| AppError::from_io("hardlink", normalized, err))
+    }
+
+    pub fn set_permissions(&self, path: &Path, mode: u32) -> AppResult<()> {
+        let normalized = self.normalize_existing_path("chmod", path)?;
+        fs::set_permissions(&normalized, fs::Permissions::from_mode(mode & 0o7777))
+            .map_err(|err| AppError::from_io("chmod", normalized, err))
+    }
+
+    pub fn set_owner(&self, path: &Path, uid: Option<u32>, gid: Option<u32>) -> AppResult<()> {
+        let normalized = self.normalize_existing_path("chown", path)?;
+        std::os::unix::fs::chown(&normalized, uid, gid)
+            .map_err(|err| AppError::from_io("chown", normalized, err))
+    }
+
+    /// Sets the modification time, leaving the access time untouched.
+    pub fn set_times(&self, path: &Path, modified: SystemTime) -> AppResult<()> {
+        let normalized = self.normalize_existing_path("touch", path)?;
+        set_file_times("touch", &normalized, None, modified, true)
+    }
+
     pub fn create_dir(&self, path: &Path) -> AppResult<()> {
         let normalized = self.normalize_new_path("mkdir", path)?;
         fs::create_dir(&normalized).map_err(|err| AppError::from_io("mkdir", normalized, err))?;
         Ok(())
     }
 
+    /// Removes `path`; a symlink is removed itself, never what it points to.
     pub fn remove_path(&self, path: &Path) -> AppResult<()> {
-        let normalized = self.normalize_existing_path("remove", path)?;
+        let normalized = self.normalize_new_path("remove", path)?;
         let metadata = fs::symlink_metadata(&normalized)
             .map_err(|err| AppError::from_io("remove", normalized.clone(), err))?;
 
@@ -107,6 +180,26 @@ impl FsAdapter {
     }
 
     pub fn move_path(&self, source: &Path, destination: &Path) -> AppResult<PathBuf> {
+        self.move_path_with_progress(
+            source,
+            destination,
+            &CopyOptions::default(),
+            &mut |_, _, _| true,
+            &mut |_, _| {},
+        )
+    }
+
+    /// Moves `source`, reporting bytes through `on_progress` and attributes
+    /// that could not be preserved through `on_warning` only when the rename
+    /// crosses devices and falls back to copy + remove.
+    pub fn move_path_with_progress(
+        &self,
+        source: &Path,
+        destination: &Path,
+        options: &CopyOptions,
+        on_progress: &mut dyn FnMut(&Path, u64, u64) -> bool,
+        on_warning: &mut dyn FnMut(&Path, AppError),
+    ) -> AppResult<PathBuf> {
         let source_path = self.normalize_existing_path("move", source)?;
         let destination_path = self.resolve_destination_path("move", &source_path, destination)?;
         self.ensure_destination_is_safe("move", &source_path, &destination_path)?;
@@ -122,7 +215,13 @@ impl FsAdapter {
         match fs::rename(&source_path, &destination_path) {
             Ok(()) => Ok(destination_path),
             Err(err) if err.kind() == std::io::ErrorKind::CrossesDevices => {
-                self.copy_path(&source_path, &destination_path)?;
+                self.copy_path_with_progress(
+                    &source_path,
+                    &destination_path,
+                    options,
+                    on_progress,
+                    on_warning,
+                )?;
                 self.remove_path(&source_path)?;
                 Ok(destination_path)
             }
@@ -131,7 +230,33 @@ impl FsAdapter {
     }
 
     pub fn copy_path(&self, source: &Path, destination: &Path) -> AppResult<PathBuf> {
-        let source_path = self.normalize_existing_path("copy", source)?;
+        self.copy_path_with_progress(
+            source,
+            destination,
+            &CopyOptions::default(),
+            &mut |_, _, _| true,
+            &mut |_, _| {},
+        )
+    }
+
+    /// Copies `source`, calling `on_progress(file, copied, size)` when each file
+    /// starts and after every chunk written. Returning false cancels the copy and
+    /// removes the partially written file. A copy whose attributes cannot be
+    /// preserved is kept and passed to `on_warning`.
+    pub fn copy_path_with_progress(
+        &self,
+        source: &Path,
+        destination: &Path,
+        options: &CopyOptions,
+        on_progress: &mut dyn FnMut(&Path, u64, u64) -> bool,
+        on_warning: &mut dyn FnMut(&Path, AppError),
+    ) -> AppResult<PathBuf> {
+        // Canonicalizing would resolve a symlink that is to be copied as a link.
+        let source_path = if options.follow_symlinks {
+            self.normalize_existing_path("copy", source)?
+        } else {
+            self.normalize_new_path("copy", source)?
+        };
         let destination_path = self.resolve_destination_path("copy", &source_path, destination)?;
         self.ensure_destination_is_safe("copy", &source_path, &destination_path)?;
 
@@ -143,13 +268,23 @@ impl FsAdapter {
             ));
         }
 
-        let metadata = fs::symlink_metadata(&source_path)
-            .map_err(|err| AppError::from_io("copy", source_path.clone(), err))?;
-
+        let metadata = source_metadata(&source_path, options)?;
         if metadata.file_type().is_dir() && !metadata.file_type().is_symlink() {
-            copy_directory_recursive(&source_path, &destination_path)?;
+            copy_directory_recursive(
+                &source_path,
+                &destination_path,
+                options,
+                on_progress,
+                on_warning,
+            )?;
         } else {
-            copy_regular_or_symlink_file(&source_path, &destination_path)?;
+            copy_regular_or_symlink_file(
+                &source_path,
+                &destination_path,
+                options,
+                on_progress,
+                on_warning,
+            )?;
         }
 
         Ok(destination_path)
@@ -258,6 +393,90 @@ impl FsAdapter {
     }
 }
 
+/// Resolves a local user name through the passwd database.
+pub fn user_name(uid: u32) -> Option<String> {
+    let mut entry: libc::passwd = unsafe { std::mem::zeroed() };
+    with_lookup_buffer(
+        |buf, result| unsafe {
+            libc::getpwuid_r(uid, &mut entry, buf.as_mut_ptr(), buf.len(), result)
+        },
+        |found| {
+            unsafe { CStr::from_ptr(found.pw_name) }
+                .to_string_lossy()
+                .into_owned()
+        },
+    )
+}
+
+pub fn group_name(gid: u32) -> Option<String> {
+    let mut entry: libc::group = unsafe { std::mem::zeroed() };
+    with_lookup_buffer(
+        |buf, result| unsafe {
+            libc::getgrgid_r(gid, &mut entry, buf.as_mut_ptr(), buf.len(), result)
+        },
+        |found| {
+            unsafe { CStr::from_ptr(found.gr_name) }
+                .to_string_lossy()
+                .into_owned()
+        },
+    )
+}
+
+pub fn user_id(name: &str) -> Option<u32> {
+    let c_name = CString::new(name).ok()?;
+    let mut entry: libc::passwd = unsafe { std::mem::zeroed() };
+    with_lookup_buffer(
+        |buf, result| unsafe {
+            libc::getpwnam_r(
+                c_name.as_ptr(),
+                &mut entry,
+                buf.as_mut_ptr(),
+                buf.len(),
+                result,
+            )
+        },
+        |found| found.pw_uid,
+    )
+}
+
+pub fn group_id(name: &str) -> Option<u32> {
+    let c_name = CString::new(name).ok()?;
+    let mut entry: libc::group = unsafe { std::mem::zeroed() };
+    with_lookup_buffer(
+        |buf, result| unsafe {
+            libc::getgrnam_r(
+                c_name.as_ptr(),
+                &mut entry,
+                buf.as_mut_ptr(),
+                buf.len(),
+                result,
+            )
+        },
+        |found| found.gr_gid,
+    )
+}
+
+/// Runs a reentrant passwd/group lookup, growing the string buffer on `ERANGE`.
+/// `extract` runs while the buffer the entry points into is still alive.
+fn with_lookup_buffer<T, R>(
+    mut lookup: impl FnMut(&mut [libc::c_char], &mut *mut T) -> libc::c_int,
+    extract: impl FnOnce(&T) -> R,
+) -> Option<R> {
+    let mut buf = vec![0 as libc::c_char; 1024];
+    loop {
+        let mut result = std::ptr::null_mut();
+        let rc = lookup(&mut buf, &mut result);
+        if rc == libc::ERANGE && buf.len() < 1 << 20 {
+            buf.resize(buf.len() * 2, 0);
+            continue;
+        }
+        if rc != 0 || result.is_null() {
+            return None;
+        }
+        return Some(extract(unsafe { &*result }));
+    }
+}
+
 fn path_exists(path: &Path) -> AppResult<bool> {
     path.try_exists()
         .map_err(|err| AppError::from_io("stat", path.to_path_buf(), err))
@@ -273,9 +492,21 @@ fn parent_link(parent: PathBuf) -> FsEntry {
         is_executable: false,
         is_hidden: false,
         is_virtual: true,
+        link_target: None,
+        link_broken: false,
     }
 }
 
+/// Where the symlink at `path` points, and whether that target is missing.
+/// A target that exists but cannot be reached (no permission, a link loop)
+/// does not make the link broken.
+pub fn link_target_info(path: &Path) -> (Option<PathBuf>, bool) {
+    let target = fs::read_link(path).ok();
+    let broken = target.is_some()
+        && fs::metadata(path).is_err_and(|err| err.kind() == io::ErrorKind::NotFound);
+    (target, broken)
+}
+
 fn map_entry_type(metadata: &fs::Metadata) -> FsEntryType {
     let file_type = metadata.file_type();
     if file_type.is_dir() {
@@ -341,11 +572,23 @@ fn cmp_modified_at(left: &FsEntry, right: &FsEntry) -> Ordering {
     right_ts.cmp(&left_ts)
 }
 
-fn copy_directory_recursive(source: &Path, destination: &Path) -> AppResult<()> {
+fn copy_directory_recursive(
+    source: &Path,
+    destination: &Path,
+    options: &CopyOptions,
+    on_progress: &mut dyn FnMut(&Path, u64, u64) -> bool,
+    on_warning: &mut dyn FnMut(&Path, AppError),
+) -> AppResult<()> {
     fs::create_dir_all(destination)
         .map_err(|err| AppError::from_io("copy", destination.to_path_buf(), err))?;
 
-    for entry in WalkDir::new(source).follow_links(false).min_depth(1) {
+    // Directories come after their contents, so writing into them does not
+    // undo the mtime (or a read-only mode) preserved from the source.
+    for entry in WalkDir::new(source)
+        .follow_links(options.follow_symlinks)
+        .min_depth(1)
+        .contents_first(true)
+    {
         let entry = entry.map_err(|err| {
             let path = err.path().unwrap_or(source).to_path_buf();
             AppError::invalid_path("copy", path, err.to_string())
@@ -357,36 +600,255 @@ fn copy_directory_recursive(source: &Path, destination: &Path) -> AppResult<()>
         let target = destination.join(relative);
 
         if entry.file_type().is_dir() {
-            fs::create_dir_all(&target).map_err(|err| AppError::from_io("copy", target, err))?;
+            fs::create_dir_all(&target)
+                .map_err(|err| AppError::from_io("copy", target.clone(), err))?;
+            if options.preserve_attributes {
+                preserve_metadata(entry.path(), &target, options, on_warning)?;
+            }
             continue;
         }
 
-        copy_regular_or_symlink_file(entry.path(), &target)?;
+        copy_regular_or_symlink_file(entry.path(), &target, options, on_progress, on_warning)?;
     }
 
+    if options.preserve_attributes {
+        preserve_metadata(source, destination, options, on_warning)?;
+    }
     Ok(())
 }
 
-fn copy_regular_or_symlink_file(source: &Path, destination: &Path) -> AppResult<()> {
+fn copy_regular_or_symlink_file(
+    source: &Path,
+    destination: &Path,
+    options: &CopyOptions,
+    on_progress: &mut dyn FnMut(&Path, u64, u64) -> bool,
+    on_warning: &mut dyn FnMut(&Path, AppError),
+) -> AppResult<()> {
     if let Some(parent) = destination.parent() {
         fs::create_dir_all(parent)
             .map_err(|err| AppError::from_io("copy", parent.to_path_buf(), err))?;
     }
 
-    let metadata = fs::symlink_metadata(source)
-        .map_err(|err| AppError::from_io("copy", source.to_path_buf(), err))?;
+    let metadata = source_metadata(source, options)?;
     if metadata.file_type().is_symlink() {
-        let link_target = fs::read_link(source)
+        if !on_progress(source, 0, 0) {
+            return Err(AppError::canceled("copy", destination));
+        }
+        let mut link_target = fs::read_link(source)
             .map_err(|err| AppError::from_io("copy", source.to_path_buf(), err))?;
+        if options.stable_symlinks {
+            link_target = stable_link_target(source, &link_target);
+        }
         copy_symlink(&link_target, destination)?;
-        return Ok(());
+    } else {
+        copy_file_contents(source, destination, metadata.len(), on_progress)?;
+        fs::set_permissions(destination, metadata.permissions())
+            .map_err(|err| AppError::from_io("copy", destination.to_path_buf(), err))?;
+    }
+
+    if options.preserve_attributes {
+        let source = properties_from_metadata(source.to_path_buf(), &metadata);
+        if let Err(err) = apply_preserved_attributes(destination, &source) {
+            on_warning(destination, err);
+        }
+    }
+    Ok(())
+}
+
+/// The data is already copied when this runs, so a failure only costs the
+/// attributes and is handed to `on_warning`.
+fn preserve_metadata(
+    source: &Path,
+    destination: &Path,
+    options: &CopyOptions,
+    on_warning: &mut dyn FnMut(&Path, AppError),
+) -> AppResult<()> {
+    let metadata = source_metadata(source, options)?;
+    let source = properties_from_metadata(source.to_path_buf(), &metadata);
+    if let Err(err) = apply_preserved_attributes(destination, &source) {
+        on_warning(destination, err);
+    }
+    Ok(())
+}
+
+/// Applies ownership (root only, since anyone else may only give files away
+/// to themselves), then mode, then access and modification times. Owner goes
+/// first because `chown` clears setuid/setgid.
+fn apply_preserved_attributes(path: &Path, source: &EntryProperties) -> AppResult<()> {
+    let is_symlink = source.entry_type == FsEntryType::Symlink;
+    if running_as_root() && (source.uid.is_some() || source.gid.is_some()) {
+        std::os::unix::fs::lchown(path, source.uid, source.gid)
+            .map_err(|err| AppError::from_io("chown", path.to_path_buf(), err))?;
+    }
+    if !is_symlink {
+        fs::set_permissions(path, fs::Permissions::from_mode(source.mode & 0o7777))
+            .map_err(|err| AppError::from_io("chmod", path.to_path_buf(), err))?;
+    }
+    if let Some(modified) = source.modified_at {
+        set_file_times("touch", path, source.accessed_at, modified, !is_symlink)?;
+    }
+    Ok(())
+}
+
+fn running_as_root() -> bool {
+    unsafe { libc::geteuid() == 0 }
+}
+
+/// Sets the modification time and, when given, the access time (otherwise
+/// left untouched). With `follow` false a symlink is changed itself.
+fn set_file_times(
+    operation: &'static str,
+    path: &Path,
+    accessed: Option<SystemTime>,
+    modified: SystemTime,
+    follow: bool,
+) -> AppResult<()> {
+    let timespec = |time: SystemTime| {
+        let since_epoch = time
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| AppError::invalid_path(operation, path, "time is before 1970"))?;
+        Ok(libc::timespec {
+            tv_sec: since_epoch.as_secs() as libc::time_t,
+            tv_nsec: since_epoch.subsec_nanos() as libc::c_long,
+        })
+    };
+    let times = [
+        match accessed {
+            Some(accessed) => timespec(accessed)?,
+            None => libc::timespec {
+                tv_sec: 0,
+                tv_nsec: libc::UTIME_OMIT,
+            },
+        },
+        timespec(modified)?,
+    ];
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|_| AppError::invalid_path(operation, path, "path contains a NUL byte"))?;
+    let flags = if follow { 0 } else { libc::AT_SYMLINK_NOFOLLOW };
+    let rc = unsafe { libc::utimensat(libc::AT_FDCWD, c_path.as_ptr(), times.as_ptr(), flags) };
+    if rc != 0 {
+        return Err(AppError::from_io(
+            operation,
+            path.to_path_buf(),
+            io::Error::last_os_error(),
+        ));
     }
+    Ok(())
+}
 
-    fs::copy(source, destination)
+/// Properties readable from `metadata` alone; owner and group names are left
+/// for the caller to look up.
+fn properties_from_metadata(path: PathBuf, metadata: &fs::Metadata) -> EntryProperties {
+    EntryProperties {
+        path,
+        entry_type: map_entry_type(metadata),
+        size_bytes: metadata.len(),
+        mode: metadata.mode() & 0o7777,
+        uid: Some(metadata.uid()),
+        gid: Some(metadata.gid()),
+        owner: None,
+        group: None,
+        modified_at: metadata.modified().ok(),
+        accessed_at: metadata.accessed().ok(),
+        inode: Some(metadata.ino()),
+        links: Some(metadata.nlink()),
+    }
+}
+
+fn copy_file_contents(
+    source: &Path,
+    destination: &Path,
+    size: u64,
+    on_progress: &mut dyn FnMut(&Path, u64, u64) -> bool,
+) -> AppResult<()> {
+    if !on_progress(source, 0, size) {
+        return Err(AppError::canceled("copy", destination));
+    }
+    let mut reader = fs::File::open(source)
+        .map_err(|err| AppError::from_io("copy", source.to_path_buf(), err))?;
+    let mut writer = fs::File::create(destination)
         .map_err(|err| AppError::from_io("copy", destination.to_path_buf(), err))?;
+    let mut buffer = vec![0_u8; COPY_CHUNK_SIZE];
+    let mut copied = 0u64;
+    loop {
+        let read = match reader.read(buffer.as_mut_slice()) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(AppError::from_io("copy", source.to_path_buf(), err)),
+        };
+        writer
+            .write_all(&buffer[..read])
+            .map_err(|err| AppError::from_io("copy", destination.to_path_buf(), err))?;
+        copied = copied.saturating_add(read as u64);
+        if !on_progress(source, copied, size.max(copied)) {
+            drop(writer);
+            let _ = fs::remove_file(destination);
+            return Err(AppError::canceled("copy", destination));
+        }
+    }
+    if copied < size {
+        // The file shrank while copying; report it as finished at its real length.
+        on_progress(source, copied, copied);
+    }
     Ok(())
 }
 
+/// Metadata of the link itself, or of what it points to when following links.
+fn source_metadata(source: &Path, options: &CopyOptions) -> AppResult<fs::Metadata> {
+    let metadata = if options.follow_symlinks {
+        fs::metadata(source)
+    } else {
+        fs::symlink_metadata(source)
+    };
+    metadata.map_err(|err| AppError::from_io("copy", source.to_path_buf(), err))
+}
+
+/// Target for a new symlink at `link` that reaches `target` through a relative
+/// path, e.g. `../docs/a.txt`. Both paths are taken as absolute and lexical.
+pub fn relative_link_target(link: &Path, target: &Path) -> PathBuf {
+    let base = link.parent().unwrap_or(Path::new("/"));
+    let base = base.components().collect::<Vec<_>>();
+    let target_components = target.components().collect::<Vec<_>>();
+    let common = base
+        .iter()
+        .zip(&target_components)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut relative = PathBuf::new();
+    for _ in common..base.len() {
+        relative.push("..");
+    }
+    for component in &target_components[common..] {
+        relative.push(component.as_os_str());
+    }
+    if relative.as_os_str().is_empty() {
+        relative.push(".");
+    }
+    relative
+}
+
+/// Rewrites a relative symlink target so it still resolves once the link is
+/// copied elsewhere: `link/../target`, with `.` and `..` folded lexically.
+/// Absolute targets are returned unchanged.
+pub fn stable_link_target(link: &Path, target: &Path) -> PathBuf {
+    if target.is_absolute() {
+        return target.to_path_buf();
+    }
+    let base = link.parent().unwrap_or(Path::new("/"));
+    let mut stable = PathBuf::new();
+    for component in base.join(target).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                stable.pop();
+            }
+            other => stable.push(other.as_os_str()),
+        }
+    }
+    stable
+}
+
 #[cfg(unix)]
 fn copy_symlink(link_target: &Path, destination: &Path) -> AppResult<()> {
     use std::os::unix::fs as unix_fs;
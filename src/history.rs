@@ -0,0 +1,210 @@
+use std::path::PathBuf;
+
+use crate::config::{parse_tables, string_line, table_value};
+use crate::model::{
+    ArchiveConnectionInfo, BackendSpec, DirHistory, HistoryEntry, SftpAuth, SftpConnectionInfo,
+};
+
+const LEFT: &str = "left";
+const RIGHT: &str = "right";
+
+/// Both panels' histories as `history.toml`: one `[[visit]]` table per
+/// visit with `panel`, `kind` (`local`, `sftp` or `archive`) and `path`,
+/// then the backend's own keys. Entries after the current one are dropped
+/// and passwords are never written.
+pub fn history_to_toml(left: &DirHistory, right: &DirHistory) -> String {
+    let mut lines = vec!["# vcmc directory history".to_string(), String::new()];
+    for (panel, history) in [(LEFT, left), (RIGHT, right)] {
+        let kept = history
+            .position
+            .saturating_add(1)
+            .min(history.entries.len());
+        for entry in &history.entries[..kept] {
+            lines.push("[[visit]]".to_string());
+            lines.push(string_line("panel", panel));
+            visit_lines(entry, &mut lines);
+            lines.push(String::new());
+        }
+    }
+    lines.join("\n")
+}
+
+/// Reads text made by `history_to_toml` back into the left and right
+/// histories, each positioned at its last entry. Visits that do not parse
+/// are skipped. SFTP entries saved with a password come back with agent auth.
+pub fn history_from_toml(content: &str) -> (DirHistory, DirHistory) {
+    let mut left = DirHistory::default();
+    let mut right = DirHistory::default();
+    for table in parse_tables(content, "visit") {
+        let Some(entry) = parse_visit(&table) else {
+            continue;
+        };
+        match table_value(&table, "panel") {
+            Some(LEFT) => left.record(entry),
+            Some(RIGHT) => right.record(entry),
+            _ => {}
+        }
+    }
+    (left, right)
+}
+
+fn visit_lines(entry: &HistoryEntry, lines: &mut Vec<String>) {
+    let path = entry.path.to_string_lossy();
+    match &entry.backend {
+        BackendSpec::Local => lines.push(string_line("kind", "local")),
+        BackendSpec::Sftp(info) => {
+            lines.push(string_line("kind", "sftp"));
+            lines.push(string_line("host", &info.host));
+            lines.push(string_line("user", &info.user));
+            lines.push(format!("port = {}", info.port));
+            match &info.auth {
+                SftpAuth::Agent | SftpAuth::Password(_) => {}
+                SftpAuth::KeyFile { path, .. } => {
+                    lines.push(string_line("key", path.to_string_lossy().as_ref()));
+                }
+            }
+            if let Some(proxy_jump) = &info.proxy_jump {
+                lines.push(string_line("proxy_jump", proxy_jump));
+            }
+        }
+        BackendSpec::Archive(info) => {
+            lines.push(string_line("kind", "archive"));
+            lines.push(string_line(
+                "archive",
+                info.archive_path.to_string_lossy().as_ref(),
+            ));
+        }
+    }
+    lines.push(string_line("path", path.as_ref()));
+}
+
+fn parse_visit(table: &[(String, String)]) -> Option<HistoryEntry> {
+    let value = |key| table_value(table, key);
+    let path = value("path").filter(|path| !path.is_empty())?;
+    let backend = match value("kind")? {
+        "local" => BackendSpec::Local,
+        "sftp" => BackendSpec::Sftp(SftpConnectionInfo {
+            host: value("host")?.to_string(),
+            user: value("user")?.to_string(),
+            port: value("port")?.parse().ok()?,
+            root_path: PathBuf::from(path),
+            auth: match value("key") {
+                Some(key) => SftpAuth::KeyFile {
+                    path: PathBuf::from(key),
+                    passphrase: None,
+                },
+                None => SftpAuth::Agent,
+            },
+            proxy_jump: value("proxy_jump").map(str::to_string),
+        }),
+        "archive" => BackendSpec::Archive(ArchiveConnectionInfo {
+            archive_path: PathBuf::from(value("archive")?),
+        }),
+        _ => return None,
+    };
+    Some(HistoryEntry {
+        backend,
+        path: PathBuf::from(path),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{history_from_toml, history_to_toml};
+    use crate::model::{
+        ArchiveConnectionInfo, BackendSpec, DirHistory, HistoryEntry, SftpAuth, SftpConnectionInfo,
+    };
+
+    fn local(path: &str) -> HistoryEntry {
+        HistoryEntry {
+            backend: BackendSpec::Local,
+            path: PathBuf::from(path),
+        }
+    }
+
+    #[test]
+    fn recording_drops_forward_entries_and_ignores_reloads() {
+        let mut history = DirHistory::default();
+        assert_eq!(history.back(), None);
+        for path in ["/a", "/b", "/c"] {
+            history.record(local(path));
+        }
+        history.record(local("/c"));
+        assert_eq!(history.entries.len(), 3);
+        assert_eq!(history.back(), Some(1));
+        assert_eq!(history.forward(), None);
+
+        history.position = 0;
+        assert_eq!(history.forward(), Some(1));
+        history.record(local("/a"));
+        assert_eq!(history.entries.len(), 3);
+        history.record(local("/d"));
+        assert_eq!(
+            history.entries,
+            [local("/a"), local("/d")],
+            "a new visit replaces the forward entries"
+        );
+
+        for idx in 0..DirHistory::LIMIT {
+            history.record(local(&format!("/n{idx}")));
+        }
+        assert_eq!(history.entries.len(), DirHistory::LIMIT);
+        assert_eq!(history.position, DirHistory::LIMIT - 1);
+        assert_eq!(history.entries[0], local("/n0"));
+    }
+
+    #[test]
+    fn histories_round_trip_without_passwords() {
+        let sftp = HistoryEntry {
+            backend: BackendSpec::Sftp(SftpConnectionInfo {
+                host: "web1".to_string(),
+                user: "ops".to_string(),
+                port: 2222,
+                root_path: PathBuf::from("/"),
+                auth: SftpAuth::Password("hunter2".to_string()),
+                proxy_jump: Some("bastion".to_string()),
+            }),
+            path: PathBuf::from("/var/log"),
+        };
+        let archive = HistoryEntry {
+            backend: BackendSpec::Archive(ArchiveConnectionInfo {
+                archive_path: PathBuf::from("/tmp/bundle.tar"),
+            }),
+            path: PathBuf::from("/docs"),
+        };
+        let mut left = DirHistory::default();
+        for entry in [local("/home/dev"), sftp, archive.clone(), local("/tmp")] {
+            left.record(entry);
+        }
+        left.position = 2;
+        let mut right = DirHistory::default();
+        right.record(local("/srv/tab\there"));
+        right.record(local("/srv"));
+
+        let text = history_to_toml(&left, &right);
+        assert!(!text.contains("hunter2"));
+        let (restored_left, restored_right) = history_from_toml(&format!(
+            "{text}garbage line\n[[visit]]\npanel = \"left\"\nkind = \"sftp\"\npath = \"/x\"\n"
+        ));
+
+        assert_eq!(restored_left.entries.len(), 3);
+        assert_eq!(restored_left.position, 2);
+        assert_eq!(restored_left.entries[2], archive);
+        let BackendSpec::Sftp(info) = &restored_left.entries[1].backend else {
+            panic!("expected an sftp entry");
+        };
+        assert_eq!(
+            (info.user.as_str(), info.host.as_str(), info.port),
+            ("ops", "web1", 2222)
+        );
+        assert_eq!(info.auth, SftpAuth::Agent);
+        assert_eq!(info.proxy_jump.as_deref(), Some("bastion"));
+        assert_eq!(info.root_path, PathBuf::from("/var/log"));
+        assert_eq!(
+            restored_right.entries,
+            [local("/srv/tab\there"), local("/srv")]
+        );
+    }
+}
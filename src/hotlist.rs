@@ -0,0 +1,304 @@
+use std::ops::Range;
+use std::path::PathBuf;
+
+use anyhow::{Result, bail};
+
+use crate::config::{parse_tables, string_line};
+
+/// One hotlist directory. Entries of a group are kept next to each other, in
+/// the order the user arranged them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HotlistEntry {
+    pub name: String,
+    pub group: String,
+    pub target: HotlistTarget,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HotlistTarget {
+    Local(PathBuf),
+    /// A directory on the server of a saved SFTP bookmark, found by name so
+    /// the bookmark keeps the address and credentials.
+    Sftp {
+        bookmark: String,
+        path: PathBuf,
+    },
+    /// A directory inside a local archive.
+    Archive {
+        archive: PathBuf,
+        path: PathBuf,
+    },
+}
+
+impl HotlistTarget {
+    pub fn label(&self) -> String {
+        match self {
+            Self::Local(path) => path.display().to_string(),
+            Self::Sftp { bookmark, path } => format!("sftp:{bookmark} {}", path.display()),
+            Self::Archive { archive, path } => {
+                let name = archive
+                    .file_name()
+                    .map(|value| value.to_string_lossy().to_string())
+                    .unwrap_or_else(|| archive.display().to_string());
+                format!("archive:{name} {}", path.display())
+            }
+        }
+    }
+}
+
+/// Parses `hotlist.toml`: `[[entry]]` tables with `name`, `group`, `kind`
+/// (`local`, `sftp` or `archive`), `path`, and `bookmark` or `archive` for the
+/// last two kinds. Groups come back together, in order of first appearance.
+pub fn hotlist_from_toml(content: &str) -> Result<Vec<HotlistEntry>> {
+    let mut entries = Vec::new();
+    for table in parse_tables(content, "entry") {
+        let mut raw = RawEntry::default();
+        for (key, value) in table {
+            match key.as_str() {
+                "name" => raw.name = value,
+                "group" => raw.group = value,
+                "kind" => raw.kind = value,
+                "path" => raw.path = value,
+                "bookmark" => raw.bookmark = value,
+                "archive" => raw.archive = value,
+                _ => {}
+            }
+        }
+        entries.push(raw.into_entry()?);
+    }
+    group_hotlist(&mut entries);
+    Ok(entries)
+}
+
+pub fn hotlist_to_toml(entries: &[HotlistEntry]) -> String {
+    let mut lines = vec![
+        "# vcmc directory hotlist".to_string(),
+        "# kind: local (path), sftp (bookmark + path) or archive (archive + path)".to_string(),
+        String::new(),
+    ];
+    for entry in entries {
+        lines.push("[[entry]]".to_string());
+        lines.push(string_line("name", entry.name.trim()));
+        lines.push(string_line("group", entry.group.trim()));
+        let (kind, path) = match &entry.target {
+            HotlistTarget::Local(path) => ("local", path),
+            HotlistTarget::Sftp { bookmark, path } => {
+                lines.push(string_line("bookmark", bookmark));
+                ("sftp", path)
+            }
+            HotlistTarget::Archive { archive, path } => {
+                lines.push(string_line("archive", archive.to_string_lossy().as_ref()));
+                ("archive", path)
+            }
+        };
+        lines.push(string_line("kind", kind));
+        lines.push(string_line("path", path.to_string_lossy().as_ref()));
+        lines.push(String::new());
+    }
+    lines.join("\n")
+}
+
+/// Indices of the entries matching every word of `query` in their group,
+/// name or target; all of them for an empty query.
+pub fn filter_hotlist(entries: &[HotlistEntry], query: &str) -> Vec<usize> {
+    let words = query
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>();
+    entries
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| {
+            let haystack =
+                format!("{} {} {}", entry.group, entry.name, entry.target.label()).to_lowercase();
+            words.iter().all(|word| haystack.contains(word.as_str()))
+        })
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+/// Moves entry `idx` one place up or down within its group. At the edge of
+/// its group the whole group trades places with the neighbouring one, so
+/// groups are reordered with the same keys. Returns the entry's new index.
+pub fn move_hotlist_entry(entries: &mut [HotlistEntry], idx: usize, up: bool) -> Option<usize> {
+    let span = group_span(entries, idx)?;
+    if up {
+        if idx > span.start {
+            entries.swap(idx, idx - 1);
+            return Some(idx - 1);
+        }
+        let previous = group_span(entries, span.start.checked_sub(1)?)?;
+        let shift = span.start - previous.start;
+        entries[previous.start..span.end].rotate_left(shift);
+        Some(idx - shift)
+    } else {
+        if idx + 1 < span.end {
+            entries.swap(idx, idx + 1);
+            return Some(idx + 1);
+        }
+        let next = group_span(entries, span.end)?;
+        entries[span.start..next.end].rotate_left(span.len());
+        Some(idx + next.len())
+    }
+}
+
+/// Puts the entries of each group together, groups in order of first
+/// appearance and entries in their original order.
+pub fn group_hotlist(entries: &mut [HotlistEntry]) {
+    let mut groups: Vec<String> = Vec::new();
+    for entry in entries.iter() {
+        if !groups.contains(&entry.group) {
+            groups.push(entry.group.clone());
+        }
+    }
+    entries.sort_by_key(|entry| groups.iter().position(|group| *group == entry.group));
+}
+
+/// The run of entries sharing the group of entry `idx`.
+fn group_span(entries: &[HotlistEntry], idx: usize) -> Option<Range<usize>> {
+    let group = &entries.get(idx)?.group;
+    let start = entries[..idx]
+        .iter()
+        .rposition(|entry| entry.group != *group)
+        .map_or(0, |pos| pos + 1);
+    let end = entries[idx..]
+        .iter()
+        .position(|entry| entry.group != *group)
+        .map_or(entries.len(), |pos| idx + pos);
+    Some(start..end)
+}
+
+#[derive(Default)]
+struct RawEntry {
+    name: String,
+    group: String,
+    kind: String,
+    path: String,
+    bookmark: String,
+    archive: String,
+}
+
+impl RawEntry {
+    fn into_entry(self) -> Result<HotlistEntry> {
+        if self.name.trim().is_empty() {
+            bail!("hotlist entry name cannot be empty");
+        }
+        if self.path.trim().is_empty() {
+            bail!("hotlist entry '{}' has no path", self.name);
+        }
+        let path = PathBuf::from(self.path);
+        let target = match self.kind.as_str() {
+            "" | "local" => HotlistTarget::Local(path),
+            "sftp" if !self.bookmark.trim().is_empty() => HotlistTarget::Sftp {
+                bookmark: self.bookmark,
+                path,
+            },
+            "archive" if !self.archive.trim().is_empty() => HotlistTarget::Archive {
+                archive: PathBuf::from(self.archive),
+                path,
+            },
+            "sftp" => bail!("hotlist entry '{}' needs a bookmark", self.name),
+            "archive" => bail!("hotlist entry '{}' needs an archive", self.name),
+            other => bail!("hotlist entry '{}' has unknown kind '{other}'", self.name),
+        };
+        Ok(HotlistEntry {
+            name: self.name,
+            group: self.group,
+            target,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{
+        HotlistEntry, HotlistTarget, filter_hotlist, hotlist_from_toml, hotlist_to_toml,
+        move_hotlist_entry,
+    };
+
+    fn local(group: &str, name: &str) -> HotlistEntry {
+        HotlistEntry {
+            name: name.to_string(),
+            group: group.to_string(),
+            target: HotlistTarget::Local(PathBuf::from(format!("/{name}"))),
+        }
+    }
+
+    fn names(entries: &[HotlistEntry]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.name.as_str()).collect()
+    }
+
+    #[test]
+    fn hotlist_round_trips_and_keeps_groups_together() {
+        let content = r#"
+# hand-written
+[[entry]]
+name = "src"
+group = "work"
+path = "/home/dev/src"
+
+[[entry]]
+name = "logs \"web\""
+group = "servers"
+kind = "sftp"
+bookmark = "web1"
+path = "/var/log"
+
+[[entry]]
+name = "docs"
+group = "work"
+kind = "archive"
+archive = "/tmp/bundle.tar"
+path = "/docs"
+"#;
+        let entries = hotlist_from_toml(content).expect("hotlist parsed");
+        assert_eq!(names(&entries), ["src", "docs", "logs \"web\""]);
+        assert_eq!(
+            entries[2].target,
+            HotlistTarget::Sftp {
+                bookmark: "web1".to_string(),
+                path: PathBuf::from("/var/log"),
+            }
+        );
+        assert_eq!(entries[1].target.label(), "archive:bundle.tar /docs");
+
+        let reparsed = hotlist_from_toml(&hotlist_to_toml(&entries)).expect("hotlist reparsed");
+        assert_eq!(reparsed, entries);
+
+        let missing = "[[entry]]\nname = \"x\"\nkind = \"sftp\"\npath = \"/\"\n";
+        assert!(hotlist_from_toml(missing).is_err());
+    }
+
+    #[test]
+    fn filter_matches_every_word_anywhere() {
+        let entries = [local("work", "src"), local("servers", "logs")];
+        assert_eq!(filter_hotlist(&entries, ""), [0, 1]);
+        assert_eq!(filter_hotlist(&entries, "SERV"), [1]);
+        assert_eq!(filter_hotlist(&entries, "work /src"), [0]);
+        assert!(filter_hotlist(&entries, "work logs").is_empty());
+    }
+
+    #[test]
+    fn moving_past_the_group_edge_moves_the_group() {
+        let mut entries = vec![
+            local("a", "a1"),
+            local("a", "a2"),
+            local("b", "b1"),
+            local("b", "b2"),
+            local("b", "b3"),
+        ];
+        assert_eq!(move_hotlist_entry(&mut entries, 3, true), Some(2));
+        assert_eq!(names(&entries), ["a1", "a2", "b2", "b1", "b3"]);
+
+        assert_eq!(move_hotlist_entry(&mut entries, 2, true), Some(0));
+        assert_eq!(names(&entries), ["b2", "b1", "b3", "a1", "a2"]);
+
+        assert_eq!(move_hotlist_entry(&mut entries, 2, false), Some(4));
+        assert_eq!(names(&entries), ["a1", "a2", "b2", "b1", "b3"]);
+
+        assert_eq!(move_hotlist_entry(&mut entries, 0, true), None);
+        assert_eq!(move_hotlist_entry(&mut entries, 4, false), None);
+    }
+}
@@ -1,11 +1,24 @@
+use std::io::{Read, Write};
 use std::thread;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use crossbeam_channel::{Receiver, Sender, unbounded};
 use tracing::warn;
 
-use crate::backend::{FsBackend, backend_from_spec};
-use crate::model::{Event, JobRequest, JobStatus, JobUpdate, SortMode};
+use crate::backend::{FsBackend, WriteStream, backend_from_spec};
+use crate::errors::{AppError, classify_error};
+use crate::fs::{relative_link_target, stable_link_target};
+use crate::model::{
+    AttributeChange, BackendSpec, ByteProgress, CopyOptions, Event, FsEntryType, JobRequest,
+    JobStatus, JobUpdate, SortMode,
+};
+
+const COPY_CHUNK_SIZE: usize = 256 * 1024;
+const PROGRESS_EMIT_INTERVAL: Duration = Duration::from_millis(100);
+const SPEED_SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+const SPEED_SMOOTHING: f64 = 0.3;
+const PAUSE_POLL_INTERVAL: Duration = Duration::from_millis(50);
 
 pub struct WorkerPool {
     request_tx: Sender<JobRequest>,
@@ -52,67 +65,48 @@ impl Drop for WorkerPool {
 
 fn worker_loop(request_rx: Receiver<JobRequest>, event_tx: Sender<Event>) {
     for request in request_rx {
-        if event_tx
-            .send(Event::Job(JobUpdate {
-                id: request.id,
-                batch_id: request.batch_id,
-                kind: request.kind,
-                status: JobStatus::Running,
-                source: request.source.clone(),
-                destination: request.destination.clone(),
-                current_item: Some(source_label(&request.source)),
-                batch_completed: None,
-                batch_total: None,
-                message: Some("running".to_string()),
-            }))
-            .is_err()
-        {
+        let reporter = JobReporter {
+            request: &request,
+            event_tx: &event_tx,
+        };
+        if request.control.is_canceled() {
+            if !reporter.send_status(JobStatus::Canceled, format_job_canceled(&request)) {
+                break;
+            }
+            continue;
+        }
+        if !reporter.send_status(JobStatus::Running, "running".to_string()) {
             break;
         }
 
-        let mut send_progress = |current_item: String, completed: usize, total: usize| -> bool {
-            event_tx
-                .send(Event::Job(JobUpdate {
-                    id: request.id,
-                    batch_id: request.batch_id,
-                    kind: request.kind,
-                    status: JobStatus::Running,
-                    source: request.source.clone(),
-                    destination: request.destination.clone(),
-                    current_item: Some(current_item),
-                    batch_completed: Some(completed),
-                    batch_total: Some(total.max(1)),
-                    message: Some("running".to_string()),
-                }))
-                .is_ok()
-        };
-
-        let outcome = execute_job(&request, &mut send_progress);
-        let (status, destination, message) = match outcome {
+        let outcome = execute_job(&request, &reporter);
+        let (status, destination, error_class, message) = match outcome {
             Ok(final_destination) => (
                 JobStatus::Done,
                 final_destination.or(request.destination.clone()),
+                None,
                 Some(format_job_success(&request)),
             ),
+            Err(err) if is_cancellation(&err) => (
+                JobStatus::Canceled,
+                request.destination.clone(),
+                None,
+                Some(format_job_canceled(&request)),
+            ),
             Err(err) => (
                 JobStatus::Failed,
                 request.destination.clone(),
+                Some(classify_error(&err)),
                 Some(format_job_error(&request, &err)),
             ),
         };
 
         if event_tx
             .send(Event::Job(JobUpdate {
-                id: request.id,
-                batch_id: request.batch_id,
-                kind: request.kind,
-                status,
-                source: request.source.clone(),
                 destination,
-                current_item: Some(source_label(&request.source)),
-                batch_completed: None,
-                batch_total: None,
+                error_class,
                 message,
+                ..job_update(&request, status)
             }))
             .is_err()
         {
@@ -121,10 +115,128 @@ fn worker_loop(request_rx: Receiver<JobRequest>, event_tx: Sender<Event>) {
     }
 }
 
-fn execute_job<F>(request: &JobRequest, on_progress: &mut F) -> Result<Option<std::path::PathBuf>>
-where
-    F: FnMut(String, usize, usize) -> bool,
-{
+/// Publishes updates for one job and applies its cancel/pause flags.
+struct JobReporter<'a> {
+    request: &'a JobRequest,
+    event_tx: &'a Sender<Event>,
+}
+
+impl JobReporter<'_> {
+    fn send_status(&self, status: JobStatus, message: String) -> bool {
+        self.event_tx
+            .send(Event::Job(JobUpdate {
+                message: Some(message),
+                ..job_update(self.request, status)
+            }))
+            .is_ok()
+    }
+
+    fn progress(
+        &self,
+        current_item: String,
+        completed: usize,
+        total: usize,
+        bytes: ByteProgress,
+    ) -> bool {
+        self.event_tx
+            .send(Event::Job(JobUpdate {
+                current_item: Some(current_item),
+                batch_completed: Some(completed),
+                batch_total: Some(total.max(1)),
+                bytes: Some(bytes),
+                message: Some("running".to_string()),
+                ..job_update(self.request, JobStatus::Running)
+            }))
+            .is_ok()
+    }
+
+    /// Entry counters for jobs that walk a tree without moving bytes.
+    fn entry_progress(&self, current_item: String, completed: usize, total: usize) -> bool {
+        self.event_tx
+            .send(Event::Job(JobUpdate {
+                current_item: Some(current_item),
+                batch_completed: Some(completed),
+                batch_total: Some(total.max(1)),
+                message: Some("running".to_string()),
+                ..job_update(self.request, JobStatus::Running)
+            }))
+            .is_ok()
+    }
+
+    /// Reports an entry that was copied without its attributes; unlike
+    /// `item_failed` it does not count against the job.
+    fn item_warning(&self, path: &std::path::Path, err: &anyhow::Error) {
+        let _ = self.event_tx.send(Event::Job(JobUpdate {
+            current_item: Some(source_label(path)),
+            warning: Some(format!(
+                "{} warning: attributes not preserved target={} reason={err}",
+                operation_label(self.request.kind),
+                path.display()
+            )),
+            ..job_update(self.request, JobStatus::Running)
+        }));
+    }
+
+    /// Reports one failed entry of a job that keeps going; the update stays
+    /// `Running` and carries the error class.
+    fn item_failed(&self, path: &std::path::Path, err: &anyhow::Error) -> bool {
+        self.event_tx
+            .send(Event::Job(JobUpdate {
+                current_item: Some(source_label(path)),
+                error_class: Some(classify_error(err)),
+                message: Some(format!(
+                    "{} failed: target={} reason={err}",
+                    operation_label(self.request.kind),
+                    path.display()
+                )),
+                ..job_update(self.request, JobStatus::Running)
+            }))
+            .is_ok()
+    }
+
+    /// Blocks while the job is paused and returns false once it is canceled.
+    fn checkpoint(&self) -> bool {
+        let control = &self.request.control;
+        if control.is_paused() && !control.is_canceled() {
+            self.send_status(JobStatus::Paused, "paused".to_string());
+            while control.is_paused() && !control.is_canceled() {
+                thread::sleep(PAUSE_POLL_INTERVAL);
+            }
+            if !control.is_canceled() {
+                self.send_status(JobStatus::Running, "resumed".to_string());
+            }
+        }
+        !control.is_canceled()
+    }
+}
+
+fn job_update(request: &JobRequest, status: JobStatus) -> JobUpdate {
+    JobUpdate {
+        id: request.id,
+        batch_id: request.batch_id,
+        kind: request.kind,
+        status,
+        source: request.source.clone(),
+        destination: request.destination.clone(),
+        current_item: Some(source_label(&request.source)),
+        batch_completed: None,
+        batch_total: None,
+        bytes: None,
+        error_class: None,
+        message: None,
+        warning: None,
+    }
+}
+
+fn is_cancellation(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<AppError>()
+        .is_some_and(|err| matches!(err, AppError::Canceled { .. }))
+}
+
+fn execute_job(request: &JobRequest, reporter: &JobReporter) -> Result<Option<std::path::PathBuf>> {
+    if !reporter.checkpoint() {
+        return Err(AppError::canceled(operation_label(request.kind), &request.source).into());
+    }
     let source_backend = backend_from_spec(&request.source_backend);
     match request.kind {
         crate::model::JobKind::Copy => {
@@ -138,28 +250,38 @@ where
                 .ok_or_else(|| anyhow::anyhow!("copy requires destination backend"))?;
             let destination_backend = backend_from_spec(destination_backend_spec);
 
-            let resolved = if &request.source_backend == destination_backend_spec {
-                source_backend.copy_path(&request.source, destination)?
-            } else {
-                let total_items =
-                    count_transfer_items(source_backend.as_ref(), &request.source)?.max(1);
-                let mut completed_items = 0usize;
-                copy_between_backends(
-                    source_backend.as_ref(),
-                    destination_backend.as_ref(),
-                    &request.source,
-                    destination,
-                    &mut |copied_path| {
-                        completed_items = completed_items.saturating_add(1);
-                        let _ = on_progress(
-                            source_label(copied_path),
-                            completed_items.min(total_items),
-                            total_items,
-                        );
-                    },
-                )?;
-                destination.clone()
-            };
+            let options = &request.copy_options;
+            let totals = count_transfer_totals(source_backend.as_ref(), &request.source, options)?;
+            let mut meter = TransferMeter::new(totals);
+            let mut report = metered_progress(&mut meter, reporter);
+            // Skipping errors needs the entry-by-entry walk, so it bypasses
+            // the backend's own copy even within one backend.
+            let resolved =
+                if &request.source_backend == destination_backend_spec && !options.skip_errors {
+                    source_backend.copy_path_with_progress(
+                        &request.source,
+                        destination,
+                        options,
+                        &mut report,
+                        &mut |path, err| reporter.item_warning(path, &err),
+                    )?
+                } else {
+                    let mut skipped = SkippedErrors::new(reporter);
+                    copy_between_backends(
+                        source_backend.as_ref(),
+                        destination_backend.as_ref(),
+                        &request.source,
+                        destination,
+                        options,
+                        &mut report,
+                        &mut CopyIssues {
+                            on_error: &mut |path, err| skipped.handle(path, err),
+                            on_warning: &mut |path, err| reporter.item_warning(path, &err),
+                        },
+                    )?;
+                    skipped.finish()?;
+                    destination.clone()
+                };
             Ok(Some(resolved))
         }
         crate::model::JobKind::Move => {
@@ -174,30 +296,50 @@ where
             let destination_backend = backend_from_spec(destination_backend_spec);
 
             let resolved = if &request.source_backend == destination_backend_spec {
-                source_backend.move_path(&request.source, destination)?
+                // A same-backend move is usually a rename, so totals are only
+                // learned if the backend falls back to copying.
+                let mut meter = TransferMeter::new(TransferTotals::default());
+                source_backend.move_path_with_progress(
+                    &request.source,
+                    destination,
+                    &request.copy_options,
+                    &mut metered_progress(&mut meter, reporter),
+                    &mut |path, err| reporter.item_warning(path, &err),
+                )?
             } else {
-                let total_items =
-                    count_transfer_items(source_backend.as_ref(), &request.source)?.max(1);
-                let mut completed_items = 0usize;
+                let options = &request.copy_options;
+                let totals =
+                    count_transfer_totals(source_backend.as_ref(), &request.source, options)?;
+                let mut meter = TransferMeter::new(totals);
+                let mut skipped = SkippedErrors::new(reporter);
                 copy_between_backends(
                     source_backend.as_ref(),
                     destination_backend.as_ref(),
                     &request.source,
                     destination,
-                    &mut |copied_path| {
-                        completed_items = completed_items.saturating_add(1);
-                        let _ = on_progress(
-                            source_label(copied_path),
-                            completed_items.min(total_items),
-                            total_items,
-                        );
+                    options,
+                    &mut metered_progress(&mut meter, reporter),
+                    &mut CopyIssues {
+                        on_error: &mut |path, err| skipped.handle(path, err),
+                        on_warning: &mut |path, err| reporter.item_warning(path, &err),
                     },
                 )?;
+                // Anything left behind keeps the source in place.
+                skipped.finish()?;
                 source_backend.remove_path(&request.source)?;
                 destination.clone()
             };
             Ok(Some(resolved))
         }
+        crate::model::JobKind::Rename => {
+            let destination = request
+                .destination
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("rename requires destination"))?;
+            Ok(Some(
+                source_backend.move_path(&request.source, destination)?,
+            ))
+        }
         crate::model::JobKind::Delete => {
             source_backend.remove_path(&request.source)?;
             Ok(None)
@@ -206,64 +348,610 @@ where
             source_backend.create_dir(&request.source)?;
             Ok(None)
         }
+        crate::model::JobKind::Trash => {
+            if request.source_backend != BackendSpec::Local {
+                anyhow::bail!("trash is only available for local files");
+            }
+            let trashed = crate::trash::move_to_trash(&request.source)?;
+            Ok(Some(trashed))
+        }
+        crate::model::JobKind::Restore => {
+            let destination = request
+                .destination
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("restore requires destination"))?;
+            crate::trash::prepare_restore_target(destination)?;
+            let mut meter = TransferMeter::new(TransferTotals::default());
+            // A restored item keeps its attributes whatever the copy setting.
+            let restored = source_backend.move_path_with_progress(
+                &request.source,
+                destination,
+                &CopyOptions {
+                    preserve_attributes: true,
+                    ..CopyOptions::default()
+                },
+                &mut metered_progress(&mut meter, reporter),
+                &mut |path, err| reporter.item_warning(path, &err),
+            )?;
+            crate::trash::forget_trashed(&request.source)?;
+            Ok(Some(restored))
+        }
+        crate::model::JobKind::Purge => {
+            crate::trash::purge_trashed(&request.source)?;
+            Ok(None)
+        }
+        crate::model::JobKind::Chattr => {
+            let change = request
+                .attributes
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("chattr requires attribute changes"))?;
+            apply_attribute_change(source_backend.as_ref(), &request.source, change, reporter)?;
+            Ok(None)
+        }
+        crate::model::JobKind::Symlink => {
+            let link = request
+                .destination
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("symlink requires destination"))?;
+            let target = if request.copy_options.relative_symlinks {
+                relative_link_target(link, &request.source)
+            } else {
+                request.source.clone()
+            };
+            // Anything else at `link` was settled as a conflict before the
+            // job was queued.
+            if request.copy_options.replace_link {
+                if source_backend.read_link(link).is_err() {
+                    anyhow::bail!("{} is no longer a symlink", link.display());
+                }
+                source_backend.remove_path(link)?;
+            }
+            source_backend.create_symlink(&target, link)?;
+            Ok(Some(link.clone()))
+        }
+        crate::model::JobKind::Hardlink => {
+            let link = request
+                .destination
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("hardlink requires destination"))?;
+            source_backend.create_hard_link(&request.source, link)?;
+            Ok(Some(link.clone()))
+        }
+    }
+}
+
+/// Applies `change` to `root` and, when recursive, to everything below it.
+/// Failing entries are reported as they happen and do not stop the walk; the
+/// job fails at the end if any of them did.
+fn apply_attribute_change(
+    backend: &dyn FsBackend,
+    root: &std::path::Path,
+    change: &AttributeChange,
+    reporter: &JobReporter,
+) -> Result<()> {
+    let root_entry = backend.stat_entry(root)?;
+    let mut pending = vec![(root_entry.path, root_entry.entry_type)];
+    let mut discovered = 1usize;
+    let mut done = 0usize;
+    let mut failed = 0usize;
+    let mut first_error = None;
+    let mut record_failure = |path: &std::path::Path, err: anyhow::Error| {
+        reporter.item_failed(path, &err);
+        failed += 1;
+        first_error.get_or_insert(err);
+    };
+    let mut last_emit: Option<Instant> = None;
+
+    while let Some((path, entry_type)) = pending.pop() {
+        if !reporter.checkpoint() {
+            return Err(AppError::canceled("chattr", root).into());
+        }
+        // Directories change before they are listed, so a mode that grants
+        // read/search access also lets the walk descend.
+        if let Err(err) = apply_entry_attributes(backend, &path, entry_type, change) {
+            record_failure(&path, err);
+        }
+        if change.recursive && entry_type == FsEntryType::Directory {
+            match backend.list_dir(&path, SortMode::Name, true) {
+                Ok(entries) => {
+                    // chmod follows symlinks, so the walk leaves links alone.
+                    for entry in entries.into_iter().rev() {
+                        if entry.is_virtual || entry.entry_type == FsEntryType::Symlink {
+                            continue;
+                        }
+                        discovered += 1;
+                        pending.push((entry.path, entry.entry_type));
+                    }
+                }
+                Err(err) => record_failure(&path, err),
+            }
+        }
+
+        done += 1;
+        if pending.is_empty()
+            || last_emit.is_none_or(|last| last.elapsed() >= PROGRESS_EMIT_INTERVAL)
+        {
+            reporter.entry_progress(source_label(&path), done, discovered);
+            last_emit = Some(Instant::now());
+        }
+    }
+
+    match first_error {
+        Some(err) => Err(err.context(format!("{failed} of {done} item(s) failed"))),
+        None => Ok(()),
+    }
+}
+
+fn apply_entry_attributes(
+    backend: &dyn FsBackend,
+    path: &std::path::Path,
+    entry_type: FsEntryType,
+    change: &AttributeChange,
+) -> Result<()> {
+    if change.uid.is_some() || change.gid.is_some() {
+        backend.set_owner(path, change.uid, change.gid)?;
+    }
+    // chown may clear setuid/setgid, so the mode goes after the owner.
+    if let Some(spec) = change.mode_for(entry_type) {
+        let current = if spec.is_relative() {
+            backend.entry_properties(path)?.mode
+        } else {
+            0
+        };
+        let is_dir = entry_type == FsEntryType::Directory;
+        backend.set_permissions(path, spec.apply(current, is_dir))?;
+    }
+    if let Some(modified) = change.modified {
+        backend.set_times(path, modified)?;
     }
+    Ok(())
+}
+
+/// Where `copy_between_backends` sends the entries it could not copy in full.
+struct CopyIssues<'a> {
+    /// A failing entry: returning the error aborts the copy, returning `Ok`
+    /// skips the entry and carries on.
+    on_error: &'a mut dyn FnMut(&std::path::Path, anyhow::Error) -> Result<()>,
+    /// A copy that kept its data but not the attributes of its source.
+    on_warning: &'a mut dyn FnMut(&std::path::Path, anyhow::Error),
 }
 
+/// Copies `source` through plain backend calls, which works between any two
+/// backends, handing what goes wrong on the way to `issues`.
 fn copy_between_backends(
     source_backend: &dyn FsBackend,
     destination_backend: &dyn FsBackend,
     source: &std::path::Path,
     destination: &std::path::Path,
-    on_item_copied: &mut dyn FnMut(&std::path::Path),
+    options: &CopyOptions,
+    on_progress: &mut dyn FnMut(&std::path::Path, u64, u64) -> bool,
+    issues: &mut CopyIssues,
 ) -> Result<()> {
-    let source_entry = source_backend.stat_entry(source)?;
+    let is_link = (!options.follow_symlinks).then(|| source_backend.read_link(source).is_ok());
+    if is_link == Some(true) {
+        return copy_link_between_backends(
+            source_backend,
+            destination_backend,
+            source,
+            destination,
+            options,
+            on_progress,
+        )
+        .or_else(|err| (issues.on_error)(source, err));
+    }
+    copy_tree_between_backends(
+        source_backend,
+        destination_backend,
+        source,
+        destination,
+        options,
+        on_progress,
+        issues,
+    )
+}
+
+fn copy_tree_between_backends(
+    source_backend: &dyn FsBackend,
+    destination_backend: &dyn FsBackend,
+    source: &std::path::Path,
+    destination: &std::path::Path,
+    options: &CopyOptions,
+    on_progress: &mut dyn FnMut(&std::path::Path, u64, u64) -> bool,
+    issues: &mut CopyIssues,
+) -> Result<()> {
+    let source_entry = match source_backend.stat_entry(source) {
+        Ok(entry) => entry,
+        Err(err) => return (issues.on_error)(source, err),
+    };
     if source_entry.entry_type == crate::model::FsEntryType::Directory {
-        destination_backend.create_dir(destination)?;
-        on_item_copied(source);
-        for child in source_backend.list_dir(source, SortMode::Name, true)? {
+        if let Err(err) = destination_backend.create_dir(destination) {
+            return (issues.on_error)(destination, err);
+        }
+        let children = match source_backend.list_dir(source, SortMode::Name, true) {
+            Ok(children) => children,
+            Err(err) => return (issues.on_error)(source, err),
+        };
+        for child in children {
             if child.is_virtual {
                 continue;
             }
             let target = destination.join(&child.name);
-            copy_between_backends(
+            if child.entry_type == FsEntryType::Symlink && !options.follow_symlinks {
+                copy_link_between_backends(
+                    source_backend,
+                    destination_backend,
+                    child.path.as_path(),
+                    target.as_path(),
+                    options,
+                    on_progress,
+                )
+                .or_else(|err| (issues.on_error)(child.path.as_path(), err))?;
+                continue;
+            }
+            copy_tree_between_backends(
                 source_backend,
                 destination_backend,
                 child.path.as_path(),
                 target.as_path(),
-                on_item_copied,
+                options,
+                on_progress,
+                issues,
             )?;
         }
-        return Ok(());
+    } else if let Err(err) = copy_file_streaming(
+        source_backend,
+        destination_backend,
+        source,
+        destination,
+        source_entry.size_bytes,
+        on_progress,
+    ) {
+        return (issues.on_error)(source, err);
     }
 
-    let content = source_backend.read_file(source)?;
-    destination_backend.write_file(destination, content.as_slice())?;
-    on_item_copied(source);
+    // Directories are finished last so their copied mtime survives the writes
+    // into them. The data is in place by now, so a failure only costs the
+    // attributes.
+    if !options.preserve_attributes {
+        return Ok(());
+    }
+    let attributes = match source_backend.entry_properties(source) {
+        Ok(attributes) => attributes,
+        Err(err) => return (issues.on_error)(source, err),
+    };
+    if let Err(err) = destination_backend.preserve_attributes(destination, &attributes) {
+        (issues.on_warning)(destination, err);
+    }
     Ok(())
 }
 
-fn count_transfer_items(backend: &dyn FsBackend, source: &std::path::Path) -> Result<usize> {
-    let entry = backend.stat_entry(source)?;
+/// Recreates the symlink `source` at `destination` instead of copying what it
+/// points to.
+fn copy_link_between_backends(
+    source_backend: &dyn FsBackend,
+    destination_backend: &dyn FsBackend,
+    source: &std::path::Path,
+    destination: &std::path::Path,
+    options: &CopyOptions,
+    on_progress: &mut dyn FnMut(&std::path::Path, u64, u64) -> bool,
+) -> Result<()> {
+    if !on_progress(source, 0, 0) {
+        return Err(AppError::canceled("copy", destination).into());
+    }
+    let mut target = source_backend.read_link(source)?;
+    if options.stable_symlinks {
+        target = stable_link_target(source, &target);
+    }
+    destination_backend.create_symlink(&target, destination)
+}
+
+/// Failure policy of a copy job: without skip-all-errors the first failure
+/// aborts it, with it every failure is reported and the job carries on, only
+/// to fail at the end.
+struct SkippedErrors<'a> {
+    reporter: &'a JobReporter<'a>,
+    failed: usize,
+    first_error: Option<anyhow::Error>,
+}
+
+impl<'a> SkippedErrors<'a> {
+    fn new(reporter: &'a JobReporter<'a>) -> Self {
+        Self {
+            reporter,
+            failed: 0,
+            first_error: None,
+        }
+    }
+
+    fn handle(&mut self, path: &std::path::Path, err: anyhow::Error) -> Result<()> {
+        if !self.reporter.request.copy_options.skip_errors || is_cancellation(&err) {
+            return Err(err);
+        }
+        self.reporter.item_failed(path, &err);
+        self.failed += 1;
+        self.first_error.get_or_insert(err);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<()> {
+        match self.first_error {
+            Some(err) => Err(err.context(format!("{} item(s) were skipped", self.failed))),
+            None => Ok(()),
+        }
+    }
+}
+
+fn copy_file_streaming(
+    source_backend: &dyn FsBackend,
+    destination_backend: &dyn FsBackend,
+    source: &std::path::Path,
+    destination: &std::path::Path,
+    size: u64,
+    on_progress: &mut dyn FnMut(&std::path::Path, u64, u64) -> bool,
+) -> Result<u64> {
+    if !on_progress(source, 0, size) {
+        return Err(AppError::canceled("copy", destination).into());
+    }
+    let mut reader = source_backend.open_read(source)?;
+    let writer = destination_backend.open_write(destination)?;
+    let result = pump_stream(
+        reader.as_mut(),
+        writer,
+        source,
+        destination,
+        size,
+        on_progress,
+    );
+    if matches!(&result, Err(err) if is_cancellation(err)) {
+        let _ = destination_backend.remove_path(destination);
+    }
+    result
+}
+
+/// Copies `reader` into `writer` chunk by chunk and finishes the writer, so
+/// a failed close fails the copy instead of passing for success.
+fn pump_stream(
+    reader: &mut dyn Read,
+    mut writer: Box<dyn WriteStream>,
+    source: &std::path::Path,
+    destination: &std::path::Path,
+    size: u64,
+    on_progress: &mut dyn FnMut(&std::path::Path, u64, u64) -> bool,
+) -> Result<u64> {
+    let mut buffer = vec![0_u8; COPY_CHUNK_SIZE];
+    let mut copied = 0u64;
+    loop {
+        let read = match reader.read(buffer.as_mut_slice()) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err.into()),
+        };
+        writer.write_all(&buffer[..read])?;
+        copied = copied.saturating_add(read as u64);
+        if !on_progress(source, copied, size.max(copied)) {
+            drop(writer);
+            return Err(AppError::canceled("copy", destination).into());
+        }
+    }
+    writer.finish()?;
+    if copied < size {
+        on_progress(source, copied, copied);
+    }
+    Ok(copied)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct TransferTotals {
+    files: usize,
+    bytes: u64,
+}
+
+/// Files and bytes below `source` as the copy will see them. With
+/// skip-all-errors an unreadable entry counts as nothing instead of failing
+/// the job before it starts.
+fn count_transfer_totals(
+    backend: &dyn FsBackend,
+    source: &std::path::Path,
+    options: &CopyOptions,
+) -> Result<TransferTotals> {
+    let link = TransferTotals { files: 1, bytes: 0 };
+    if !options.follow_symlinks && backend.read_link(source).is_ok() {
+        return Ok(link);
+    }
+    let entry = match backend.stat_entry(source) {
+        Ok(entry) => entry,
+        Err(_) if options.skip_errors => return Ok(TransferTotals::default()),
+        Err(err) => return Err(err),
+    };
     if entry.entry_type != crate::model::FsEntryType::Directory {
-        return Ok(1);
+        return Ok(TransferTotals {
+            files: 1,
+            bytes: entry.size_bytes,
+        });
     }
 
-    let mut total = 1usize;
-    for child in backend.list_dir(source, SortMode::Name, true)? {
+    let mut totals = TransferTotals::default();
+    let children = match backend.list_dir(source, SortMode::Name, true) {
+        Ok(children) => children,
+        Err(_) if options.skip_errors => return Ok(totals),
+        Err(err) => return Err(err),
+    };
+    for child in children {
         if child.is_virtual {
             continue;
         }
-        total = total.saturating_add(count_transfer_items(backend, child.path.as_path())?);
+        let child_totals = if child.entry_type == FsEntryType::Symlink && !options.follow_symlinks {
+            link
+        } else {
+            count_transfer_totals(backend, child.path.as_path(), options)?
+        };
+        totals.files = totals.files.saturating_add(child_totals.files);
+        totals.bytes = totals.bytes.saturating_add(child_totals.bytes);
+    }
+    Ok(totals)
+}
+
+fn metered_progress<'a>(
+    meter: &'a mut TransferMeter,
+    reporter: &'a JobReporter,
+) -> impl FnMut(&std::path::Path, u64, u64) -> bool + 'a {
+    move |path, copied, size| {
+        if meter.record(path, copied, size) {
+            reporter.progress(
+                source_label(path),
+                meter.files_done,
+                meter.files_total,
+                meter.progress,
+            );
+        }
+        reporter.checkpoint()
+    }
+}
+
+/// Folds per-chunk callbacks into job-wide byte counters and decides when a
+/// progress event is worth sending.
+struct TransferMeter {
+    progress: ByteProgress,
+    files_done: usize,
+    files_total: usize,
+    current_file: Option<std::path::PathBuf>,
+    current_finished: bool,
+    finished_bytes: u64,
+    sample_at: Instant,
+    sample_done: u64,
+    last_emit: Option<Instant>,
+}
+
+impl TransferMeter {
+    fn new(totals: TransferTotals) -> Self {
+        Self {
+            progress: ByteProgress {
+                total: totals.bytes,
+                ..ByteProgress::default()
+            },
+            files_done: 0,
+            files_total: totals.files,
+            current_file: None,
+            current_finished: false,
+            finished_bytes: 0,
+            sample_at: Instant::now(),
+            sample_done: 0,
+            last_emit: None,
+        }
+    }
+
+    /// Returns true when the caller should publish `progress`; events are
+    /// throttled to one per `PROGRESS_EMIT_INTERVAL` so small files do not flood the UI,
+    /// but the update that completes a file always goes out.
+    fn record(&mut self, path: &std::path::Path, copied: u64, size: u64) -> bool {
+        let is_new_file = self.current_file.as_deref() != Some(path);
+        if is_new_file {
+            self.finished_bytes = self.finished_bytes.saturating_add(self.progress.file_done);
+            self.current_file = Some(path.to_path_buf());
+            self.current_finished = false;
+        }
+        self.progress.file_done = copied;
+        self.progress.file_total = size;
+        self.progress.done = self.finished_bytes.saturating_add(copied);
+        self.progress.total = self.progress.total.max(self.progress.done);
+
+        let just_finished = !self.current_finished && copied >= size;
+        if just_finished {
+            self.current_finished = true;
+            self.files_done = self.files_done.saturating_add(1);
+            self.files_total = self.files_total.max(self.files_done);
+        }
+
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.sample_at);
+        if elapsed >= SPEED_SAMPLE_INTERVAL {
+            let sampled = self.progress.done.saturating_sub(self.sample_done);
+            let rate = sampled as f64 / elapsed.as_secs_f64();
+            let smoothed = if self.progress.bytes_per_sec == 0 {
+                rate
+            } else {
+                SPEED_SMOOTHING * rate
+                    + (1.0 - SPEED_SMOOTHING) * self.progress.bytes_per_sec as f64
+            };
+            self.progress.bytes_per_sec = smoothed.round() as u64;
+            self.sample_at = now;
+            self.sample_done = self.progress.done;
+        }
+
+        let due = self
+            .last_emit
+            .is_none_or(|at| now.duration_since(at) >= PROGRESS_EMIT_INTERVAL);
+        if due || just_finished {
+            self.last_emit = Some(now);
+            return true;
+        }
+        false
     }
-    Ok(total)
 }
 
 fn format_job_success(request: &JobRequest) -> String {
     match request.kind {
         crate::model::JobKind::Copy => format!("copy done: {}", request.source.display()),
         crate::model::JobKind::Move => format!("move done: {}", request.source.display()),
+        crate::model::JobKind::Rename => format!(
+            "rename done: {} -> {}",
+            request.source.display(),
+            request
+                .destination
+                .as_ref()
+                .map(|path| source_label(path))
+                .unwrap_or_default()
+        ),
         crate::model::JobKind::Delete => format!("delete done: {}", request.source.display()),
         crate::model::JobKind::Mkdir => format!("mkdir done: {}", request.source.display()),
+        crate::model::JobKind::Trash => format!("trash done: {}", request.source.display()),
+        crate::model::JobKind::Restore => format!(
+            "restore done: {}",
+            request
+                .destination
+                .as_ref()
+                .unwrap_or(&request.source)
+                .display()
+        ),
+        crate::model::JobKind::Purge => format!("purge done: {}", request.source.display()),
+        crate::model::JobKind::Chattr => {
+            format!("attributes updated: {}", request.source.display())
+        }
+        crate::model::JobKind::Symlink | crate::model::JobKind::Hardlink => format!(
+            "{} done: {} -> {}",
+            operation_label(request.kind),
+            request
+                .destination
+                .as_ref()
+                .map(|path| path.display().to_string())
+                .unwrap_or_default(),
+            request.source.display()
+        ),
+    }
+}
+
+fn format_job_canceled(request: &JobRequest) -> String {
+    format!(
+        "{} canceled: {}",
+        operation_label(request.kind),
+        request.source.display()
+    )
+}
+
+fn operation_label(kind: crate::model::JobKind) -> &'static str {
+    match kind {
+        crate::model::JobKind::Copy => "copy",
+        crate::model::JobKind::Move => "move",
+        crate::model::JobKind::Rename => "rename",
+        crate::model::JobKind::Delete => "delete",
+        crate::model::JobKind::Mkdir => "mkdir",
+        crate::model::JobKind::Trash => "trash",
+        crate::model::JobKind::Restore => "restore",
+        crate::model::JobKind::Purge => "purge",
+        crate::model::JobKind::Chattr => "chattr",
+        crate::model::JobKind::Symlink => "symlink",
+        crate::model::JobKind::Hardlink => "hardlink",
     }
 }
 
@@ -285,6 +973,11 @@ fn format_job_error(request: &JobRequest, err: &anyhow::Error) -> String {
             request.source.display(),
             dst
         ),
+        crate::model::JobKind::Rename => format!(
+            "rename failed: src={} dst={} reason={err}",
+            request.source.display(),
+            dst
+        ),
         crate::model::JobKind::Delete => {
             format!(
                 "delete failed: target={} reason={err}",
@@ -297,6 +990,35 @@ fn format_job_error(request: &JobRequest, err: &anyhow::Error) -> String {
                 request.source.display()
             )
         }
+        crate::model::JobKind::Trash => {
+            format!(
+                "trash failed: target={} reason={err}",
+                request.source.display()
+            )
+        }
+        crate::model::JobKind::Restore => format!(
+            "restore failed: src={} dst={} reason={err}",
+            request.source.display(),
+            dst
+        ),
+        crate::model::JobKind::Purge => {
+            format!(
+                "purge failed: target={} reason={err}",
+                request.source.display()
+            )
+        }
+        crate::model::JobKind::Chattr => {
+            format!(
+                "chattr failed: target={} reason={err}",
+                request.source.display()
+            )
+        }
+        crate::model::JobKind::Symlink | crate::model::JobKind::Hardlink => format!(
+            "{} failed: target={} link={} reason={err}",
+            operation_label(request.kind),
+            request.source.display(),
+            dst
+        ),
     }
 }
 
@@ -305,3 +1027,647 @@ fn source_label(path: &std::path::Path) -> String {
         .map(|name| name.to_string_lossy().to_string())
         .unwrap_or_else(|| path.display().to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::Path;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use crossbeam_channel::unbounded;
+
+    use super::{
+        CopyIssues, JobReporter, TransferMeter, TransferTotals, copy_between_backends,
+        copy_file_streaming, execute_job, is_cancellation, pump_stream,
+    };
+    use crate::backend::{FsBackend, LocalFsBackend, WriteStream};
+    use crate::mode::ModeSpec;
+    use crate::model::{
+        AttributeChange, BackendSpec, CopyOptions, Event, JobControl, JobKind, JobRequest,
+        JobStatus, JobUpdate,
+    };
+    use crate::test_support::fixture_dir;
+
+    #[test]
+    fn transfer_meter_accumulates_bytes_across_files() {
+        let mut meter = TransferMeter::new(TransferTotals {
+            files: 2,
+            bytes: 30,
+        });
+        assert!(meter.record(Path::new("a"), 0, 10));
+        meter.record(Path::new("a"), 10, 10);
+        meter.record(Path::new("b"), 5, 20);
+        assert_eq!(meter.files_done, 1);
+        assert_eq!(meter.progress.done, 15);
+        assert_eq!(meter.progress.file_done, 5);
+        assert_eq!(meter.progress.file_total, 20);
+
+        meter.record(Path::new("b"), 20, 20);
+        assert_eq!(meter.files_done, 2);
+        assert_eq!(meter.progress.done, 30);
+        assert_eq!(meter.progress.total, 30);
+    }
+
+    #[test]
+    fn transfer_meter_throttles_but_always_reports_a_finished_file() {
+        let mut meter = TransferMeter::new(TransferTotals {
+            files: 1,
+            bytes: 10,
+        });
+        assert!(meter.record(Path::new("a"), 0, 10));
+        assert!(!meter.record(Path::new("a"), 5, 10));
+        assert!(meter.record(Path::new("a"), 10, 10));
+        assert!(!meter.record(Path::new("a"), 10, 10));
+    }
+
+    #[test]
+    fn transfer_meter_grows_unknown_totals_and_handles_restarts() {
+        let mut meter = TransferMeter::new(TransferTotals::default());
+        meter.record(Path::new("a"), 8, 16);
+        meter.record(Path::new("a"), 4, 16);
+        assert_eq!(meter.progress.done, 4);
+        meter.record(Path::new("a"), 16, 16);
+        assert_eq!(meter.progress.done, 16);
+        assert_eq!(meter.progress.total, 16);
+        assert_eq!(meter.files_total, 1);
+    }
+
+    /// Destination whose close fails, as a remote write can when the server
+    /// runs out of space.
+    struct FailingClose;
+
+    impl std::io::Write for FailingClose {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl WriteStream for FailingClose {
+        fn finish(self: Box<Self>) -> anyhow::Result<()> {
+            anyhow::bail!("close failed")
+        }
+    }
+
+    #[test]
+    fn streaming_copy_writes_every_chunk_and_reports_the_end() {
+        let dir = fixture_dir("jobs-streaming");
+        let source = dir.join("data.bin");
+        let destination = dir.join("copy.bin");
+        let payload = (0..2 * super::COPY_CHUNK_SIZE + 17)
+            .map(|idx| (idx % 251) as u8)
+            .collect::<Vec<_>>();
+        fs::write(&source, &payload).expect("source written");
+
+        let backend = LocalFsBackend::default();
+        let mut reports = Vec::new();
+        let copied = copy_file_streaming(
+            &backend,
+            &backend,
+            &source,
+            &destination,
+            payload.len() as u64,
+            &mut |_, copied, total| {
+                reports.push((copied, total));
+                true
+            },
+        )
+        .expect("copy succeeds");
+
+        assert_eq!(copied, payload.len() as u64);
+        assert_eq!(fs::read(&destination).expect("copy readable"), payload);
+        assert_eq!(reports.first(), Some(&(0, payload.len() as u64)));
+        assert_eq!(
+            reports.last(),
+            Some(&(payload.len() as u64, payload.len() as u64))
+        );
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn streaming_copy_fails_when_the_destination_does_not_close() {
+        let mut reader: &[u8] = b"payload";
+        let err = pump_stream(
+            &mut reader,
+            Box::new(FailingClose),
+            Path::new("a"),
+            Path::new("b"),
+            7,
+            &mut |_, _, _| true,
+        )
+        .expect_err("close failure is reported");
+        assert!(err.to_string().contains("close failed"));
+    }
+
+    #[test]
+    fn canceled_copy_removes_partial_destination() {
+        let dir = fixture_dir("jobs-cancel-partial");
+        let source = dir.join("big.bin");
+        let destination = dir.join("copy.bin");
+        fs::write(&source, vec![7_u8; 3 * super::COPY_CHUNK_SIZE]).expect("source written");
+
+        let backend = LocalFsBackend::default();
+        let err = copy_between_backends(
+            &backend,
+            &backend,
+            &source,
+            &destination,
+            &CopyOptions::default(),
+            &mut |_, copied, _| copied == 0,
+            &mut CopyIssues {
+                on_error: &mut |_, err| Err(err),
+                on_warning: &mut |_, _| {},
+            },
+        )
+        .expect_err("copy is canceled after the first chunk");
+
+        assert!(is_cancellation(&err));
+        assert!(!destination.exists());
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn preserved_copies_keep_mode_and_times() {
+        let dir = fixture_dir("jobs-preserve");
+        let source = dir.join("tree");
+        fs::create_dir_all(source.join("sub")).expect("tree created");
+        fs::write(source.join("sub/data.txt"), b"data").expect("file written");
+        fs::set_permissions(
+            source.join("sub/data.txt"),
+            fs::Permissions::from_mode(0o640),
+        )
+        .expect("mode set");
+        let modified = UNIX_EPOCH + Duration::from_secs(1_500_000_000);
+        let backend = LocalFsBackend::default();
+        for path in ["sub/data.txt", "sub", ""] {
+            backend
+                .set_times(&source.join(path), modified)
+                .expect("mtime set");
+        }
+
+        let options = CopyOptions {
+            preserve_attributes: true,
+            ..CopyOptions::default()
+        };
+        let same_backend = dir.join("same");
+        backend
+            .copy_path_with_progress(
+                &source,
+                &same_backend,
+                &options,
+                &mut |_, _, _| true,
+                &mut |_, err| panic!("attributes not preserved: {err}"),
+            )
+            .expect("local copy succeeds");
+        let across_backends = dir.join("across");
+        copy_between_backends(
+            &backend,
+            &backend,
+            &source,
+            &across_backends,
+            &options,
+            &mut |_, _, _| true,
+            &mut CopyIssues {
+                on_error: &mut |_, err| Err(err),
+                on_warning: &mut |_, err| panic!("attributes not preserved: {err}"),
+            },
+        )
+        .expect("streamed copy succeeds");
+
+        for copy in [same_backend, across_backends] {
+            let file = fs::metadata(copy.join("sub/data.txt")).expect("file metadata");
+            assert_eq!(file.permissions().mode() & 0o7777, 0o640);
+            assert_eq!(file.modified().expect("mtime"), modified);
+            for path in ["sub", ""] {
+                let metadata = fs::metadata(copy.join(path)).expect("dir metadata");
+                assert_eq!(metadata.modified().expect("mtime"), modified);
+            }
+        }
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn attributes_that_cannot_be_set_only_warn() {
+        let dir = fixture_dir("jobs-preserve-warning");
+        let source = dir.join("old.txt");
+        fs::write(&source, b"old").expect("file written");
+        // Times before 1970 cannot be applied to the copy.
+        fs::File::options()
+            .write(true)
+            .open(&source)
+            .and_then(|file| file.set_modified(UNIX_EPOCH - Duration::from_secs(60)))
+            .expect("mtime set");
+        let options = CopyOptions {
+            preserve_attributes: true,
+            ..CopyOptions::default()
+        };
+
+        let backend = LocalFsBackend::default();
+        let mut warnings = Vec::new();
+        copy_between_backends(
+            &backend,
+            &backend,
+            &source,
+            &dir.join("streamed.txt"),
+            &options,
+            &mut |_, _, _| true,
+            &mut CopyIssues {
+                on_error: &mut |_, err| Err(err),
+                on_warning: &mut |path, _| warnings.push(path.to_path_buf()),
+            },
+        )
+        .expect("streamed copy keeps the data");
+        assert_eq!(warnings, [dir.join("streamed.txt")]);
+
+        let destination = dir.join("copied.txt");
+        let request = JobRequest {
+            id: 1,
+            batch_id: None,
+            kind: JobKind::Copy,
+            source_backend: BackendSpec::Local,
+            destination_backend: Some(BackendSpec::Local),
+            source: source.clone(),
+            destination: Some(destination.clone()),
+            attributes: None,
+            copy_options: options,
+            control: JobControl::default(),
+        };
+        let (event_tx, event_rx) = unbounded();
+        let reporter = JobReporter {
+            request: &request,
+            event_tx: &event_tx,
+        };
+        execute_job(&request, &reporter).expect("job succeeds");
+        assert_eq!(fs::read(&destination).expect("copied"), b"old");
+        assert!(event_rx.try_iter().any(|event| matches!(
+            event,
+            Event::Job(JobUpdate {
+                warning: Some(_),
+                error_class: None,
+                ..
+            })
+        )));
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn canceled_request_does_not_touch_destination() {
+        let dir = fixture_dir("jobs-cancel-request");
+        let source = dir.join("file.txt");
+        let destination = dir.join("out.txt");
+        fs::write(&source, b"payload").expect("source written");
+
+        let request = JobRequest {
+            id: 1,
+            batch_id: None,
+            kind: JobKind::Copy,
+            source_backend: BackendSpec::Local,
+            destination_backend: Some(BackendSpec::Local),
+            source: source.clone(),
+            destination: Some(destination.clone()),
+            attributes: None,
+            copy_options: CopyOptions::default(),
+            control: JobControl::default(),
+        };
+        request.control.cancel();
+        let (event_tx, _event_rx) = unbounded();
+        let reporter = JobReporter {
+            request: &request,
+            event_tx: &event_tx,
+        };
+
+        let err = execute_job(&request, &reporter).expect_err("job is canceled");
+        assert!(is_cancellation(&err));
+        assert!(!destination.exists());
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn removing_a_symlink_keeps_its_target() {
+        let dir = fixture_dir("jobs-remove-link");
+        fs::create_dir_all(dir.join("target/sub")).expect("target tree created");
+        fs::write(dir.join("target/sub/data.txt"), b"data").expect("file written");
+        std::os::unix::fs::symlink(dir.join("target"), dir.join("dir-link")).expect("dir link");
+        std::os::unix::fs::symlink(dir.join("target/sub/data.txt"), dir.join("file-link"))
+            .expect("file link");
+        std::os::unix::fs::symlink(dir.join("missing"), dir.join("dangling")).expect("dangling");
+
+        let backend = LocalFsBackend::default();
+        for link in ["dir-link", "file-link", "dangling"] {
+            backend.remove_path(&dir.join(link)).expect("link removed");
+            assert!(fs::symlink_metadata(dir.join(link)).is_err());
+        }
+        assert_eq!(
+            fs::read(dir.join("target/sub/data.txt")).expect("target kept"),
+            b"data"
+        );
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn symlinks_are_copied_as_links_unless_followed() {
+        let dir = fixture_dir("jobs-symlinks");
+        let source = dir.join("tree");
+        fs::create_dir_all(source.join("sub")).expect("tree created");
+        fs::write(source.join("data.txt"), b"data").expect("file written");
+        std::os::unix::fs::symlink("../data.txt", source.join("sub/link")).expect("link created");
+        let backend = LocalFsBackend::default();
+        let copy = |name: &str, options: CopyOptions| {
+            let destination = dir.join(name);
+            copy_between_backends(
+                &backend,
+                &backend,
+                &source,
+                &destination,
+                &options,
+                &mut |_, _, _| true,
+                &mut CopyIssues {
+                    on_error: &mut |_, err| Err(err),
+                    on_warning: &mut |_, _| {},
+                },
+            )
+            .expect("copy succeeds");
+            destination.join("sub/link")
+        };
+
+        let link = copy("links", CopyOptions::default());
+        assert_eq!(
+            fs::read_link(link).expect("copied as link"),
+            Path::new("../data.txt")
+        );
+        let stable = copy(
+            "stable",
+            CopyOptions {
+                stable_symlinks: true,
+                ..CopyOptions::default()
+            },
+        );
+        assert_eq!(
+            fs::read_link(stable).expect("copied as link"),
+            source.join("data.txt")
+        );
+        let followed = copy(
+            "followed",
+            CopyOptions {
+                follow_symlinks: true,
+                ..CopyOptions::default()
+            },
+        );
+        assert!(
+            !fs::symlink_metadata(&followed)
+                .expect("copied")
+                .is_symlink()
+        );
+        assert_eq!(fs::read(followed).expect("target copied"), b"data");
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn skip_errors_copies_the_rest_then_fails() {
+        let dir = fixture_dir("jobs-skip-errors");
+        let source = dir.join("tree");
+        let destination = dir.join("copy");
+        fs::create_dir_all(&source).expect("tree created");
+        fs::write(source.join("a.txt"), b"a").expect("file written");
+        fs::write(source.join("z.txt"), b"z").expect("file written");
+        // A dangling link cannot be followed, so it fails on its own.
+        std::os::unix::fs::symlink("missing", source.join("m.lnk")).expect("link created");
+
+        let request = JobRequest {
+            id: 1,
+            batch_id: None,
+            kind: JobKind::Copy,
+            source_backend: BackendSpec::Local,
+            destination_backend: Some(BackendSpec::Local),
+            source: source.clone(),
+            destination: Some(destination.clone()),
+            attributes: None,
+            copy_options: CopyOptions {
+                follow_symlinks: true,
+                skip_errors: true,
+                ..CopyOptions::default()
+            },
+            control: JobControl::default(),
+        };
+        let (event_tx, event_rx) = unbounded();
+        let reporter = JobReporter {
+            request: &request,
+            event_tx: &event_tx,
+        };
+
+        let err = execute_job(&request, &reporter).expect_err("one entry failed");
+        assert!(format!("{err:#}").contains("1 item(s) were skipped"));
+        assert_eq!(fs::read(destination.join("a.txt")).expect("copied"), b"a");
+        assert_eq!(fs::read(destination.join("z.txt")).expect("copied"), b"z");
+        assert!(event_rx.try_iter().any(|event| matches!(
+            event,
+            Event::Job(JobUpdate {
+                error_class: Some(_),
+                status: JobStatus::Running,
+                ..
+            })
+        )));
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn restore_recreates_parent_and_drops_trash_info() {
+        let dir = fixture_dir("jobs-restore");
+        let trashed = dir.join("Trash").join("files").join("notes.txt");
+        let info = dir.join("Trash").join("info").join("notes.txt.trashinfo");
+        let destination = dir.join("gone").join("notes.txt");
+        fs::create_dir_all(trashed.parent().expect("files dir")).expect("files dir");
+        fs::create_dir_all(info.parent().expect("info dir")).expect("info dir");
+        fs::write(&trashed, b"payload").expect("trashed written");
+        fs::write(&info, b"[Trash Info]\n").expect("info written");
+
+        let request = JobRequest {
+            id: 1,
+            batch_id: None,
+            kind: JobKind::Restore,
+            source_backend: BackendSpec::Local,
+            destination_backend: Some(BackendSpec::Local),
+            source: trashed.clone(),
+            destination: Some(destination.clone()),
+            attributes: None,
+            copy_options: CopyOptions::default(),
+            control: JobControl::default(),
+        };
+        let (event_tx, _event_rx) = unbounded();
+        let reporter = JobReporter {
+            request: &request,
+            event_tx: &event_tx,
+        };
+
+        let restored = execute_job(&request, &reporter).expect("restore succeeds");
+        assert_eq!(restored.as_deref(), Some(destination.as_path()));
+        assert_eq!(fs::read(&destination).expect("restored"), b"payload");
+        assert!(!trashed.exists());
+        assert!(!info.exists());
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn chattr_applies_mode_and_mtime() {
+        let dir = fixture_dir("jobs-chattr");
+        let target = dir.join("script.sh");
+        fs::write(&target, b"#!/bin/sh\n").expect("target written");
+        let modified = UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+
+        let request = JobRequest {
+            id: 1,
+            batch_id: None,
+            kind: JobKind::Chattr,
+            source_backend: BackendSpec::Local,
+            destination_backend: None,
+            source: target.clone(),
+            destination: None,
+            attributes: Some(AttributeChange {
+                file_mode: Some(ModeSpec::Absolute(0o750)),
+                modified: Some(modified),
+                ..AttributeChange::default()
+            }),
+            copy_options: CopyOptions::default(),
+            control: JobControl::default(),
+        };
+        let (event_tx, _event_rx) = unbounded();
+        let reporter = JobReporter {
+            request: &request,
+            event_tx: &event_tx,
+        };
+
+        execute_job(&request, &reporter).expect("chattr succeeds");
+        let metadata = fs::metadata(&target).expect("target metadata");
+        assert_eq!(metadata.permissions().mode() & 0o7777, 0o750);
+        assert_eq!(metadata.modified().expect("mtime"), modified);
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn recursive_chattr_uses_separate_file_and_dir_masks() {
+        let dir = fixture_dir("jobs-chattr-recursive");
+        let root = dir.join("tree");
+        fs::create_dir_all(root.join("sub")).expect("tree created");
+        fs::write(root.join("a.txt"), b"a").expect("file written");
+        fs::write(root.join("sub/b.txt"), b"b").expect("file written");
+        fs::set_permissions(root.join("a.txt"), fs::Permissions::from_mode(0o666))
+            .expect("mode set");
+        fs::set_permissions(root.join("sub/b.txt"), fs::Permissions::from_mode(0o600))
+            .expect("mode set");
+        std::os::unix::fs::symlink("a.txt", root.join("link")).expect("symlink created");
+
+        let request = JobRequest {
+            id: 1,
+            batch_id: None,
+            kind: JobKind::Chattr,
+            source_backend: BackendSpec::Local,
+            destination_backend: None,
+            source: root.clone(),
+            destination: None,
+            attributes: Some(AttributeChange {
+                file_mode: Some(ModeSpec::parse("go-w,u+x").expect("valid mode")),
+                dir_mode: Some(ModeSpec::Absolute(0o750)),
+                recursive: true,
+                ..AttributeChange::default()
+            }),
+            copy_options: CopyOptions::default(),
+            control: JobControl::default(),
+        };
+        let (event_tx, _event_rx) = unbounded();
+        let reporter = JobReporter {
+            request: &request,
+            event_tx: &event_tx,
+        };
+
+        execute_job(&request, &reporter).expect("chattr succeeds");
+        let mode = |path: &str| {
+            fs::metadata(root.join(path))
+                .expect("metadata")
+                .permissions()
+                .mode()
+                & 0o7777
+        };
+        assert_eq!(mode(""), 0o750);
+        assert_eq!(mode("sub"), 0o750);
+        assert_eq!(mode("a.txt"), 0o744);
+        assert_eq!(mode("sub/b.txt"), 0o700);
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn link_jobs_create_relative_links_and_retarget_them() {
+        let dir = fixture_dir("jobs-links");
+        let target = dir.join("docs").join("a.txt");
+        fs::create_dir_all(target.parent().expect("docs dir")).expect("docs dir");
+        fs::create_dir_all(dir.join("links")).expect("links dir");
+        fs::write(&target, b"a").expect("target written");
+        let run = |kind, source: &Path, link: &Path, relative_symlinks, replace_link| {
+            let request = JobRequest {
+                id: 1,
+                batch_id: None,
+                kind,
+                source_backend: BackendSpec::Local,
+                destination_backend: Some(BackendSpec::Local),
+                source: source.to_path_buf(),
+                destination: Some(link.to_path_buf()),
+                attributes: None,
+                copy_options: CopyOptions {
+                    relative_symlinks,
+                    replace_link,
+                    ..CopyOptions::default()
+                },
+                control: JobControl::default(),
+            };
+            let (event_tx, _event_rx) = unbounded();
+            let reporter = JobReporter {
+                request: &request,
+                event_tx: &event_tx,
+            };
+            execute_job(&request, &reporter)
+        };
+
+        let relative = dir.join("links").join("rel");
+        run(JobKind::Symlink, &target, &relative, true, false).expect("symlink created");
+        assert_eq!(
+            fs::read_link(&relative).expect("is a link"),
+            Path::new("../docs/a.txt")
+        );
+        assert_eq!(fs::read(&relative).expect("link resolves"), b"a");
+
+        // Only an edit replaces a link that is already there.
+        assert!(run(JobKind::Symlink, &target, &relative, false, false).is_err());
+
+        // Pointing an existing link elsewhere replaces the link only.
+        run(
+            JobKind::Symlink,
+            Path::new("/nowhere"),
+            &relative,
+            false,
+            true,
+        )
+        .expect("retargeted");
+        assert_eq!(
+            fs::read_link(&relative).expect("is a link"),
+            Path::new("/nowhere")
+        );
+        assert_eq!(fs::read(&target).expect("target kept"), b"a");
+
+        // A dangling link still counts as an entry and can be retargeted.
+        let local = LocalFsBackend::default();
+        assert!(local.stat_entry(&relative).is_err());
+        assert!(local.entry_exists(&relative));
+        run(JobKind::Symlink, &target, &relative, false, true).expect("dangling link retargeted");
+        assert_eq!(fs::read_link(&relative).expect("is a link"), target);
+
+        let hard = dir.join("links").join("hard");
+        run(JobKind::Hardlink, &target, &hard, false, false).expect("hardlink created");
+        fs::write(&hard, b"b").expect("written through the hardlink");
+        assert_eq!(fs::read(&target).expect("same inode"), b"b");
+        assert!(run(JobKind::Hardlink, &target, &hard, false, false).is_err());
+        fs::remove_dir_all(dir).ok();
+    }
+}
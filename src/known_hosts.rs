@@ -0,0 +1,310 @@
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::{Result, bail};
+use ssh2::{CheckResult, HashType, HostKeyType, KnownHostFileKind, Session};
+
+use crate::errors::{HostKeyError, HostKeyInfo};
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub fn known_hosts_path() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(".ssh").join("known_hosts"))
+}
+
+/// What `~/.ssh/known_hosts` says about a presented host key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HostKeyStatus {
+    Match,
+    NotFound,
+    Changed,
+    Revoked,
+}
+
+/// Checks the key presented during the handshake against `~/.ssh/known_hosts`.
+/// Unknown, changed and revoked keys are returned as `HostKeyError` so callers
+/// can ask the user or refuse.
+pub fn verify_host_key(session: &Session, host: &str, port: u16) -> Result<()> {
+    let info = presented_host_key(session, host, port)?;
+    let content = known_hosts_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .unwrap_or_default();
+    match check_known_hosts(content.as_str(), host, port, info.key.as_slice())? {
+        HostKeyStatus::Match => Ok(()),
+        HostKeyStatus::NotFound => Err(HostKeyError::Unknown(info).into()),
+        HostKeyStatus::Changed => Err(HostKeyError::Changed(info).into()),
+        HostKeyStatus::Revoked => Err(HostKeyError::Revoked(info).into()),
+    }
+}
+
+/// Appends the accepted key to `~/.ssh/known_hosts`, creating the file if needed.
+pub fn trust_host_key(info: &HostKeyInfo) -> Result<()> {
+    let path =
+        known_hosts_path().ok_or_else(|| anyhow::anyhow!("HOME is not set for known_hosts"))?;
+    if let Some(parent) = path.parent().filter(|parent| !parent.exists()) {
+        fs::create_dir_all(parent)?;
+        set_private_dir_permissions(parent)?;
+    }
+
+    let needs_newline = fs::read(&path)
+        .map(|bytes| bytes.last().is_some_and(|last| *last != b'\n'))
+        .unwrap_or(false);
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    if needs_newline {
+        file.write_all(b"\n")?;
+    }
+    writeln!(file, "{}", known_hosts_line(info))?;
+    Ok(())
+}
+
+fn presented_host_key(session: &Session, host: &str, port: u16) -> Result<HostKeyInfo> {
+    let (key, key_kind) = session
+        .host_key()
+        .ok_or_else(|| anyhow::anyhow!("server did not present a host key"))?;
+    let key_type = key_type_from_blob(key)
+        .unwrap_or_else(|| key_type_name(key_kind))
+        .to_string();
+    let fingerprint = session
+        .host_key_hash(HashType::Sha256)
+        .map(|hash| format!("SHA256:{}", encode_base64(hash, false)))
+        .unwrap_or_else(|| "SHA256:?".to_string());
+    Ok(HostKeyInfo {
+        host: host.to_string(),
+        port,
+        key_type,
+        fingerprint,
+        key: key.to_vec(),
+    })
+}
+
+/// Looks `key` up among the entries of its own key type: a host recorded
+/// with only an RSA key has simply not been seen offering ed25519. An
+/// `@revoked` key is refused whatever hosts its line names; `@cert-authority`
+/// lines are skipped because certificate host keys are not supported.
+fn check_known_hosts(content: &str, host: &str, port: u16, key: &[u8]) -> Result<HostKeyStatus> {
+    let key_type = key_type_from_blob(key);
+    let session = Session::new()?;
+    let mut known_hosts = session.known_hosts()?;
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (marker, entry) = match line.strip_prefix('@') {
+            Some(rest) => {
+                let (marker, entry) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+                (Some(marker), entry.trim_start())
+            }
+            None => (None, line),
+        };
+        let mut fields = entry.split_whitespace().skip(1);
+        let entry_type = fields.next();
+        match marker {
+            Some("revoked") => {
+                if fields.next().and_then(decode_base64).as_deref() == Some(key) {
+                    return Ok(HostKeyStatus::Revoked);
+                }
+            }
+            Some(other) => {
+                tracing::debug!("known_hosts: skipping unsupported @{other} entry");
+            }
+            None if key_type.is_some() && entry_type != key_type => {}
+            None => {
+                // Lines libssh2 cannot parse (unsupported key types) are skipped
+                // instead of failing the whole file.
+                let _ = known_hosts.read_str(entry, KnownHostFileKind::OpenSSH);
+            }
+        }
+    }
+    match known_hosts.check_port(host, port, key) {
+        CheckResult::Match => Ok(HostKeyStatus::Match),
+        CheckResult::NotFound => Ok(HostKeyStatus::NotFound),
+        CheckResult::Mismatch => Ok(HostKeyStatus::Changed),
+        CheckResult::Failure => bail!("known_hosts check failed for {host}:{port}"),
+    }
+}
+
+fn known_hosts_line(info: &HostKeyInfo) -> String {
+    format!(
+        "{} {} {}",
+        host_pattern(info.host.as_str(), info.port),
+        info.key_type,
+        encode_base64(info.key.as_slice(), true)
+    )
+}
+
+fn host_pattern(host: &str, port: u16) -> String {
+    if port == 22 {
+        host.to_string()
+    } else {
+        format!("[{host}]:{port}")
+    }
+}
+
+fn key_type_from_blob(blob: &[u8]) -> Option<&str> {
+    let len_bytes: [u8; 4] = blob.get(..4)?.try_into().ok()?;
+    let len = u32::from_be_bytes(len_bytes) as usize;
+    let name = blob.get(4..4 + len)?;
+    std::str::from_utf8(name).ok()
+}
+
+fn key_type_name(kind: HostKeyType) -> &'static str {
+    match kind {
+        HostKeyType::Rsa => "ssh-rsa",
+        HostKeyType::Dss => "ssh-dss",
+        HostKeyType::Ecdsa256 => "ecdsa-sha2-nistp256",
+        HostKeyType::Ecdsa384 => "ecdsa-sha2-nistp384",
+        HostKeyType::Ecdsa521 => "ecdsa-sha2-nistp521",
+        HostKeyType::Ed25519 => "ssh-ed25519",
+        HostKeyType::Unknown => "unknown",
+    }
+}
+
+fn encode_base64(bytes: &[u8], pad: bool) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b0 = chunk[0] as u32;
+        let b1 = chunk.get(1).copied().unwrap_or(0) as u32;
+        let b2 = chunk.get(2).copied().unwrap_or(0) as u32;
+        let triple = (b0 << 16) | (b1 << 8) | b2;
+        let symbols = chunk.len() + 1;
+        for idx in 0..4 {
+            if idx < symbols {
+                let value = (triple >> (18 - idx * 6)) & 0x3f;
+                out.push(BASE64_ALPHABET[value as usize] as char);
+            } else if pad {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+fn decode_base64(input: &str) -> Option<Vec<u8>> {
+    let mut bits = 0u32;
+    let mut bit_count = 0u32;
+    let mut out = Vec::with_capacity(input.len() / 4 * 3);
+    for byte in input.bytes().filter(|byte| *byte != b'=') {
+        let value = BASE64_ALPHABET.iter().position(|symbol| *symbol == byte)? as u32;
+        bits = (bits << 6) | value;
+        bit_count += 6;
+        if bit_count >= 8 {
+            bit_count -= 8;
+            out.push((bits >> bit_count) as u8);
+            bits &= (1 << bit_count) - 1;
+        }
+    }
+    Some(out)
+}
+
+#[cfg(unix)]
+fn set_private_dir_permissions(path: &std::path::Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o700))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_private_dir_permissions(_path: &std::path::Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        HostKeyStatus, check_known_hosts, encode_base64, host_pattern, key_type_from_blob,
+    };
+
+    const KEY_ONE: &str = "AAAAC3NzaC1lZDI1NTE5AAAAIPLZsHLxE7DbzjOXhHF/KAmElJmLEIL2XTer2apgQcp8";
+    const KEY_TWO: &str = "AAAAC3NzaC1lZDI1NTE5AAAAIJ6m0MTZCuAkGU1kpxBAXE+MBY+kh6Dxz00FB5Gz+k/1";
+    const HASHED_KNOWN_HOSTS: &str = concat!(
+        "# hashed entries for hashed.example.com and [ported.example.com]:2222\n",
+        "|1|W2IMd0r0nd2lwPwKYPTH9xeshJ4=|Yd78cHNXFlS77waZEjoaqA8UtsI= ssh-ed25519 ",
+        "AAAAC3NzaC1lZDI1NTE5AAAAIPLZsHLxE7DbzjOXhHF/KAmElJmLEIL2XTer2apgQcp8\n",
+        "|1|DQyYTgyxCLEu6SFoIhuqejh0UPI=|pheVZNFBXlsbjMdQ8y2dAmprinc= ssh-ed25519 ",
+        "AAAAC3NzaC1lZDI1NTE5AAAAIPLZsHLxE7DbzjOXhHF/KAmElJmLEIL2XTer2apgQcp8\n",
+    );
+
+    fn decode(input: &str) -> Vec<u8> {
+        super::decode_base64(input).expect("valid base64")
+    }
+
+    #[test]
+    fn encode_base64_handles_padding() {
+        assert_eq!(encode_base64(b"f", true), "Zg==");
+        assert_eq!(encode_base64(b"fo", true), "Zm8=");
+        assert_eq!(encode_base64(b"foo", true), "Zm9v");
+        assert_eq!(encode_base64(b"fo", false), "Zm8");
+        assert_eq!(encode_base64(&decode(KEY_ONE), true), KEY_ONE);
+    }
+
+    #[test]
+    fn key_type_is_read_from_key_blob() {
+        assert_eq!(key_type_from_blob(&decode(KEY_ONE)), Some("ssh-ed25519"));
+        assert_eq!(key_type_from_blob(&[0, 0, 0, 9, b's']), None);
+    }
+
+    #[test]
+    fn host_pattern_brackets_non_default_port() {
+        assert_eq!(host_pattern("example.com", 22), "example.com");
+        assert_eq!(host_pattern("example.com", 2222), "[example.com]:2222");
+    }
+
+    #[test]
+    fn check_known_hosts_matches_hashed_entries() {
+        let key = decode(KEY_ONE);
+        let result = check_known_hosts(HASHED_KNOWN_HOSTS, "hashed.example.com", 22, &key)
+            .expect("check runs");
+        assert!(result == HostKeyStatus::Match);
+        let result = check_known_hosts(HASHED_KNOWN_HOSTS, "ported.example.com", 2222, &key)
+            .expect("check runs");
+        assert!(result == HostKeyStatus::Match);
+    }
+
+    #[test]
+    fn check_known_hosts_reports_unknown_and_changed_keys() {
+        let changed = decode(KEY_TWO);
+        let result = check_known_hosts(HASHED_KNOWN_HOSTS, "hashed.example.com", 22, &changed)
+            .expect("check runs");
+        assert!(result == HostKeyStatus::Changed);
+        let result = check_known_hosts(HASHED_KNOWN_HOSTS, "other.example.com", 22, &changed)
+            .expect("check runs");
+        assert!(result == HostKeyStatus::NotFound);
+    }
+
+    #[test]
+    fn check_known_hosts_compares_only_keys_of_the_presented_type() {
+        let mut rsa_blob = vec![0, 0, 0, 7];
+        rsa_blob.extend_from_slice(b"ssh-rsa");
+        rsa_blob.extend_from_slice(&[0, 0, 0, 3, 1, 0, 1, 0, 0, 0, 4, 0xc3, 0x5a, 0x11, 0x07]);
+        let content = format!(
+            "typed.example.com ssh-rsa {}\n",
+            encode_base64(&rsa_blob, true)
+        );
+        let ed25519 = decode(KEY_ONE);
+        let result =
+            check_known_hosts(&content, "typed.example.com", 22, &ed25519).expect("check runs");
+        assert_eq!(result, HostKeyStatus::NotFound);
+        let result =
+            check_known_hosts(&content, "typed.example.com", 22, &rsa_blob).expect("check runs");
+        assert_eq!(result, HostKeyStatus::Match);
+    }
+
+    #[test]
+    fn check_known_hosts_refuses_revoked_keys_and_skips_cert_authorities() {
+        let content = format!(
+            "{HASHED_KNOWN_HOSTS}@revoked * ssh-ed25519 {KEY_ONE}\n@cert-authority *.example.com ssh-ed25519 {KEY_TWO}\n"
+        );
+        let key = decode(KEY_ONE);
+        let result =
+            check_known_hosts(&content, "hashed.example.com", 22, &key).expect("check runs");
+        assert_eq!(result, HostKeyStatus::Revoked);
+
+        let other = decode(KEY_TWO);
+        let result = check_known_hosts(&content, "ca.example.com", 22, &other).expect("check runs");
+        assert_eq!(result, HostKeyStatus::NotFound);
+    }
+}
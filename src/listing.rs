@@ -0,0 +1,240 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+use crossbeam_channel::Sender;
+
+use crate::backend::FsBackend;
+use crate::model::{
+    DirListing, DiveUpdate, Event, FsEntry, FsEntryType, ListingUpdate, PanelId, SortMode,
+};
+
+/// One panel reload, run on its own thread so a slow directory (huge local
+/// listings, SFTP round trips) never blocks input or rendering.
+pub struct ListingRequest {
+    pub id: u64,
+    pub panel_id: PanelId,
+    pub backend: Arc<dyn FsBackend>,
+    pub cwd: PathBuf,
+    pub sort_mode: SortMode,
+    pub show_hidden: bool,
+    pub cancel: Arc<AtomicBool>,
+}
+
+/// Lists `request.cwd` and sends the result as `Event::Listing`. A canceled
+/// listing sends nothing: the panel has already moved on.
+///
+/// The flag is checked between steps only. A `list_dir` already under way
+/// runs to completion and its result is dropped, so a superseded listing
+/// still costs the round trips it started.
+pub fn spawn_listing(request: ListingRequest, event_tx: Sender<Event>) {
+    thread::spawn(move || {
+        let canceled = || request.cancel.load(Ordering::Relaxed);
+        // A quick second reload can cancel this one before the thread gets
+        // to run; an SFTP listing then never checks out a session.
+        if canceled() {
+            return;
+        }
+        let result = match list_cwd(&request, &canceled) {
+            Ok(Some(listing)) => Ok(listing),
+            Ok(None) => return,
+            Err(err) => Err(err.to_string()),
+        };
+        if canceled() {
+            return;
+        }
+        let _ = event_tx.send(Event::Listing(ListingUpdate {
+            id: request.id,
+            panel_id: request.panel_id,
+            cwd: request.cwd,
+            result,
+        }));
+    });
+}
+
+/// Resolves `request.cwd` and lists it, or gives `None` when the listing
+/// was canceled in between.
+fn list_cwd(
+    request: &ListingRequest,
+    canceled: &dyn Fn() -> bool,
+) -> anyhow::Result<Option<DirListing>> {
+    let cwd = request
+        .backend
+        .normalize_existing_path("list_dir", &request.cwd)?;
+    if canceled() {
+        return Ok(None);
+    }
+    let entries = request
+        .backend
+        .list_dir(&cwd, request.sort_mode, request.show_hidden)?;
+    Ok(Some(DirListing { cwd, entries }))
+}
+
+/// Whether a copy with dive-into-existing can go into the directory already
+/// at `destination`, asked off the UI thread since it takes a listing.
+pub struct DiveRequest {
+    pub source_backend: Arc<dyn FsBackend>,
+    pub destination_backend: Arc<dyn FsBackend>,
+    pub source: PathBuf,
+    pub destination: PathBuf,
+    pub follow_symlinks: bool,
+}
+
+/// Answers `request` on its own thread and sends the result as `Event::Dive`.
+pub fn spawn_dive_listing(request: DiveRequest, event_tx: Sender<Event>) {
+    thread::spawn(move || {
+        let result = dive_children(&request).map_err(|err| err.to_string());
+        let _ = event_tx.send(Event::Dive(DiveUpdate {
+            source: request.source,
+            destination: request.destination,
+            result,
+        }));
+    });
+}
+
+/// Children of the source that take its place in the copy, or `None` when
+/// the source is not a real directory (a link copied as one) or the existing
+/// destination is not a directory.
+fn dive_children(request: &DiveRequest) -> anyhow::Result<Option<Vec<FsEntry>>> {
+    if !request.follow_symlinks && request.source_backend.read_link(&request.source).is_ok() {
+        return Ok(None);
+    }
+    let is_dir = |entry: anyhow::Result<FsEntry>| {
+        entry.is_ok_and(|entry| entry.entry_type == FsEntryType::Directory)
+    };
+    if !is_dir(request.source_backend.stat_entry(&request.source))
+        || !is_dir(request.destination_backend.stat_entry(&request.destination))
+    {
+        return Ok(None);
+    }
+    let children = request
+        .source_backend
+        .list_dir(&request.source, SortMode::Name, true)?;
+    Ok(Some(
+        children
+            .into_iter()
+            .filter(|child| !child.is_virtual)
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+
+    use crossbeam_channel::unbounded;
+
+    use super::{DiveRequest, ListingRequest, dive_children, spawn_listing};
+    use crate::backend::LocalFsBackend;
+    use crate::model::{Event, PanelId, SortMode};
+    use crate::test_support::fixture_dir;
+
+    fn listing_request(id: u64, cwd: &Path) -> ListingRequest {
+        ListingRequest {
+            id,
+            panel_id: PanelId::Left,
+            backend: Arc::new(LocalFsBackend::default()),
+            cwd: cwd.to_path_buf(),
+            sort_mode: SortMode::Name,
+            show_hidden: true,
+            cancel: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    #[test]
+    fn a_canceled_listing_sends_nothing() {
+        let dir = fixture_dir("listing-cancel");
+        let request = listing_request(1, &dir);
+        request.cancel.store(true, Ordering::Relaxed);
+        let (event_tx, event_rx) = unbounded();
+        spawn_listing(request, event_tx);
+        // The thread drops its sender when it is done, without sending.
+        assert!(event_rx.recv_timeout(Duration::from_secs(5)).is_err());
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn a_superseded_listing_leaves_only_the_new_one() {
+        let dir = fixture_dir("listing-supersede");
+        fs::create_dir_all(dir.join("old")).expect("old dir created");
+        fs::create_dir_all(dir.join("new/sub")).expect("new dir created");
+        fs::write(dir.join("new/a.txt"), b"a").expect("file written");
+        let (event_tx, event_rx) = unbounded();
+
+        let old = listing_request(1, &dir.join("old"));
+        let old_cancel = Arc::clone(&old.cancel);
+        old_cancel.store(true, Ordering::Relaxed);
+        spawn_listing(old, event_tx.clone());
+        spawn_listing(listing_request(2, &dir.join("new/sub/..")), event_tx);
+
+        let events = event_rx.iter().collect::<Vec<_>>();
+        let [Event::Listing(update)] = events.as_slice() else {
+            panic!("expected one listing, got {events:?}");
+        };
+        assert_eq!(update.id, 2);
+        assert_eq!(update.cwd, dir.join("new/sub/.."));
+        let listing = update.result.as_ref().expect("listed");
+        assert_eq!(
+            listing.cwd,
+            fs::canonicalize(dir.join("new")).expect("canonical")
+        );
+        let names = listing
+            .entries
+            .iter()
+            .filter(|entry| !entry.is_virtual)
+            .map(|entry| entry.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["sub", "a.txt"]);
+        fs::remove_dir_all(dir).ok();
+    }
+
+    fn dive_request(source: &Path, destination: &Path, follow_symlinks: bool) -> DiveRequest {
+        DiveRequest {
+            source_backend: Arc::new(LocalFsBackend::default()),
+            destination_backend: Arc::new(LocalFsBackend::default()),
+            source: source.to_path_buf(),
+            destination: destination.to_path_buf(),
+            follow_symlinks,
+        }
+    }
+
+    #[test]
+    fn dive_lists_the_source_only_for_directory_pairs() {
+        let dir = fixture_dir("listing-dive");
+        fs::create_dir_all(dir.join("src/sub")).expect("source tree created");
+        fs::write(dir.join("src/a.txt"), b"a").expect("file written");
+        fs::create_dir_all(dir.join("dst")).expect("destination created");
+        fs::write(dir.join("file.txt"), b"f").expect("file written");
+        std::os::unix::fs::symlink(dir.join("src"), dir.join("link")).expect("link created");
+
+        let children = dive_children(&dive_request(&dir.join("src"), &dir.join("dst"), false))
+            .expect("listed")
+            .expect("directory pair");
+        let mut names = children
+            .into_iter()
+            .map(|child| child.name)
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, ["a.txt", "sub"]);
+
+        let not_a_pair = [
+            dive_request(&dir.join("file.txt"), &dir.join("dst"), false),
+            dive_request(&dir.join("src"), &dir.join("file.txt"), false),
+            dive_request(&dir.join("link"), &dir.join("dst"), false),
+        ];
+        for request in not_a_pair {
+            assert!(dive_children(&request).expect("checked").is_none());
+        }
+        assert!(
+            dive_children(&dive_request(&dir.join("link"), &dir.join("dst"), true))
+                .expect("listed")
+                .is_some()
+        );
+        fs::remove_dir_all(dir).ok();
+    }
+}
@@ -1,17 +1,29 @@
 mod app;
 mod backend;
+mod config;
 mod errors;
 mod find;
 mod fs;
+mod history;
+mod hotlist;
 mod jobs;
+mod known_hosts;
+mod listing;
 mod menu;
+mod mode;
 mod model;
+mod rename;
 mod runtime;
 mod smoke;
+mod ssh_config;
 mod terminal;
+#[cfg(test)]
+mod test_support;
 mod theme;
+mod trash;
 mod ui;
 mod viewer;
+mod watch;
 
 use std::env;
 use std::time::Duration;
@@ -5,10 +5,23 @@ pub enum MenuAction {
     ActivatePanel(PanelId),
     PanelHome(PanelId),
     PanelParent(PanelId),
+    PanelHistory(PanelId),
+    PanelHotlist(PanelId),
+    PanelHotlistAdd(PanelId),
+    PanelNewTab(PanelId),
+    PanelDuplicateTab(PanelId),
+    PanelCloseTab(PanelId),
     PanelCopy(PanelId),
     PanelMove(PanelId),
+    PanelRename(PanelId),
+    PanelBatchRename(PanelId),
+    PanelEditorRename(PanelId),
+    PanelSymlink(PanelId),
+    PanelHardlink(PanelId),
+    PanelEditSymlink(PanelId),
     PanelDelete(PanelId),
     PanelMkdir(PanelId),
+    PanelProperties(PanelId),
     PanelConnectSftp(PanelId),
     PanelOpenArchiveVfs(PanelId),
     PanelOpenShell(PanelId),
@@ -23,6 +36,10 @@ pub enum MenuAction {
     Refresh,
     ViewerModesInfo,
     EditorSettings,
+    JobManager,
+    PanelTrash(PanelId),
+    EmptyTrash,
+    PurgeTrash,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -58,15 +75,35 @@ pub struct MenuGroupSpec {
     pub items: &'static [MenuItemSpec],
 }
 
-const LEFT_ITEMS: [MenuItemSpec; 19] = [
+const LEFT_ITEMS: [MenuItemSpec; 34] = [
     MenuItemSpec::action("Activate Left", MenuAction::ActivatePanel(PanelId::Left)),
     MenuItemSpec::action("Home", MenuAction::PanelHome(PanelId::Left)),
     MenuItemSpec::action("Parent", MenuAction::PanelParent(PanelId::Left)),
+    MenuItemSpec::action("History", MenuAction::PanelHistory(PanelId::Left)),
+    MenuItemSpec::action("Hotlist", MenuAction::PanelHotlist(PanelId::Left)),
+    MenuItemSpec::action("Add to Hotlist", MenuAction::PanelHotlistAdd(PanelId::Left)),
+    MenuItemSpec::separator("──── Tabs ─────"),
+    MenuItemSpec::action("New Tab", MenuAction::PanelNewTab(PanelId::Left)),
+    MenuItemSpec::action(
+        "Duplicate Tab",
+        MenuAction::PanelDuplicateTab(PanelId::Left),
+    ),
+    MenuItemSpec::action("Close Tab", MenuAction::PanelCloseTab(PanelId::Left)),
     MenuItemSpec::separator("──── Files ────"),
     MenuItemSpec::action("Copy", MenuAction::PanelCopy(PanelId::Left)),
     MenuItemSpec::action("Move", MenuAction::PanelMove(PanelId::Left)),
+    MenuItemSpec::action("Rename", MenuAction::PanelRename(PanelId::Left)),
+    MenuItemSpec::action("Batch Rename", MenuAction::PanelBatchRename(PanelId::Left)),
+    MenuItemSpec::action(
+        "Rename in Editor",
+        MenuAction::PanelEditorRename(PanelId::Left),
+    ),
+    MenuItemSpec::action("Symlink", MenuAction::PanelSymlink(PanelId::Left)),
+    MenuItemSpec::action("Hardlink", MenuAction::PanelHardlink(PanelId::Left)),
+    MenuItemSpec::action("Edit Symlink", MenuAction::PanelEditSymlink(PanelId::Left)),
     MenuItemSpec::action("Delete", MenuAction::PanelDelete(PanelId::Left)),
     MenuItemSpec::action("Mkdir", MenuAction::PanelMkdir(PanelId::Left)),
+    MenuItemSpec::action("Properties", MenuAction::PanelProperties(PanelId::Left)),
     MenuItemSpec::separator("─── Command ───"),
     MenuItemSpec::action("Connect SFTP", MenuAction::PanelConnectSftp(PanelId::Left)),
     MenuItemSpec::action(
@@ -93,24 +130,51 @@ const LEFT_ITEMS: [MenuItemSpec; 19] = [
         "Archive VFS",
         MenuAction::PanelOpenArchiveVfs(PanelId::Left),
     ),
+    MenuItemSpec::action("Trash", MenuAction::PanelTrash(PanelId::Left)),
 ];
 
-const OPTIONS_ITEMS: [MenuItemSpec; 4] = [
+const OPTIONS_ITEMS: [MenuItemSpec; 7] = [
     MenuItemSpec::action("Sort", MenuAction::ToggleSort),
     MenuItemSpec::action("Refresh", MenuAction::Refresh),
     MenuItemSpec::action("Viewer Modes", MenuAction::ViewerModesInfo),
     MenuItemSpec::action("Editor Settings", MenuAction::EditorSettings),
+    MenuItemSpec::action("Jobs", MenuAction::JobManager),
+    MenuItemSpec::action("Empty Trash", MenuAction::EmptyTrash),
+    MenuItemSpec::action("Purge Trash", MenuAction::PurgeTrash),
 ];
 
-const RIGHT_ITEMS: [MenuItemSpec; 19] = [
+const RIGHT_ITEMS: [MenuItemSpec; 34] = [
     MenuItemSpec::action("Activate Right", MenuAction::ActivatePanel(PanelId::Right)),
     MenuItemSpec::action("Home", MenuAction::PanelHome(PanelId::Right)),
     MenuItemSpec::action("Parent", MenuAction::PanelParent(PanelId::Right)),
+    MenuItemSpec::action("History", MenuAction::PanelHistory(PanelId::Right)),
+    MenuItemSpec::action("Hotlist", MenuAction::PanelHotlist(PanelId::Right)),
+    MenuItemSpec::action(
+        "Add to Hotlist",
+        MenuAction::PanelHotlistAdd(PanelId::Right),
+    ),
+    MenuItemSpec::separator("──── Tabs ─────"),
+    MenuItemSpec::action("New Tab", MenuAction::PanelNewTab(PanelId::Right)),
+    MenuItemSpec::action(
+        "Duplicate Tab",
+        MenuAction::PanelDuplicateTab(PanelId::Right),
+    ),
+    MenuItemSpec::action("Close Tab", MenuAction::PanelCloseTab(PanelId::Right)),
     MenuItemSpec::separator("──── Files ────"),
     MenuItemSpec::action("Copy", MenuAction::PanelCopy(PanelId::Right)),
     MenuItemSpec::action("Move", MenuAction::PanelMove(PanelId::Right)),
+    MenuItemSpec::action("Rename", MenuAction::PanelRename(PanelId::Right)),
+    MenuItemSpec::action("Batch Rename", MenuAction::PanelBatchRename(PanelId::Right)),
+    MenuItemSpec::action(
+        "Rename in Editor",
+        MenuAction::PanelEditorRename(PanelId::Right),
+    ),
+    MenuItemSpec::action("Symlink", MenuAction::PanelSymlink(PanelId::Right)),
+    MenuItemSpec::action("Hardlink", MenuAction::PanelHardlink(PanelId::Right)),
+    MenuItemSpec::action("Edit Symlink", MenuAction::PanelEditSymlink(PanelId::Right)),
     MenuItemSpec::action("Delete", MenuAction::PanelDelete(PanelId::Right)),
     MenuItemSpec::action("Mkdir", MenuAction::PanelMkdir(PanelId::Right)),
+    MenuItemSpec::action("Properties", MenuAction::PanelProperties(PanelId::Right)),
     MenuItemSpec::separator("─── Command ───"),
     MenuItemSpec::action("Connect SFTP", MenuAction::PanelConnectSftp(PanelId::Right)),
     MenuItemSpec::action(
@@ -137,6 +201,7 @@ const RIGHT_ITEMS: [MenuItemSpec; 19] = [
         "Archive VFS",
         MenuAction::PanelOpenArchiveVfs(PanelId::Right),
     ),
+    MenuItemSpec::action("Trash", MenuAction::PanelTrash(PanelId::Right)),
 ];
 
 const MENU_GROUPS: [MenuGroupSpec; 3] = [
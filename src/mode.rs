@@ -0,0 +1,218 @@
+use std::fmt;
+
+use anyhow::{Result, bail};
+
+const USER_BITS: u32 = 0o4700;
+const GROUP_BITS: u32 = 0o2070;
+const OTHER_BITS: u32 = 0o1007;
+const ALL_BITS: u32 = USER_BITS | GROUP_BITS | OTHER_BITS;
+
+/// Permission change in `chmod(1)` notation: an octal mode (`0644`) or
+/// comma-separated symbolic clauses (`u+x`, `go-w`, `a=rX,u+w`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModeSpec {
+    Absolute(u32),
+    Symbolic {
+        text: String,
+        clauses: Vec<ModeClause>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModeClause {
+    who: u32,
+    actions: Vec<(ModeOp, ModePerms)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ModeOp {
+    Add,
+    Remove,
+    Set,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct ModePerms {
+    read: bool,
+    write: bool,
+    exec: bool,
+    /// `X`: execute only for directories or files that are already executable.
+    exec_if_searchable: bool,
+    setid: bool,
+    sticky: bool,
+}
+
+impl ModeSpec {
+    pub fn parse(input: &str) -> Result<Self> {
+        let text = input.trim();
+        if text.is_empty() {
+            bail!("mode is empty");
+        }
+        if text.chars().all(|c| c.is_digit(8)) {
+            if text.len() > 4 {
+                bail!("octal mode '{text}' has more than 4 digits");
+            }
+            let mode = u32::from_str_radix(text, 8)?;
+            return Ok(Self::Absolute(mode));
+        }
+
+        let clauses = text
+            .split(',')
+            .map(|clause| parse_clause(clause, text))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::Symbolic {
+            text: text.to_string(),
+            clauses,
+        })
+    }
+
+    /// New permission bits for an entry whose current mode is `mode`.
+    pub fn apply(&self, mode: u32, is_dir: bool) -> u32 {
+        let clauses = match self {
+            Self::Absolute(mode) => return mode & 0o7777,
+            Self::Symbolic { clauses, .. } => clauses,
+        };
+
+        let mut mode = mode & 0o7777;
+        for clause in clauses {
+            for (op, perms) in &clause.actions {
+                let searchable = is_dir || mode & 0o111 != 0;
+                let bits = perms.bits(searchable) & clause.who;
+                mode = match op {
+                    ModeOp::Add => mode | bits,
+                    ModeOp::Remove => mode & !bits,
+                    ModeOp::Set => (mode & !clause.who) | bits,
+                };
+            }
+        }
+        mode
+    }
+
+    /// Whether applying the spec depends on the entry's current mode.
+    pub fn is_relative(&self) -> bool {
+        matches!(self, Self::Symbolic { .. })
+    }
+}
+
+impl fmt::Display for ModeSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Absolute(mode) => write!(f, "{mode:04o}"),
+            Self::Symbolic { text, .. } => f.write_str(text),
+        }
+    }
+}
+
+impl ModePerms {
+    /// Bits for every class; the clause's `who` mask narrows them down.
+    fn bits(self, searchable: bool) -> u32 {
+        let mut bits = 0;
+        if self.read {
+            bits |= 0o444;
+        }
+        if self.write {
+            bits |= 0o222;
+        }
+        if self.exec || (self.exec_if_searchable && searchable) {
+            bits |= 0o111;
+        }
+        if self.setid {
+            bits |= 0o6000;
+        }
+        if self.sticky {
+            bits |= 0o1000;
+        }
+        bits
+    }
+}
+
+fn parse_clause(clause: &str, full: &str) -> Result<ModeClause> {
+    let mut chars = clause.chars().peekable();
+    let mut who = 0;
+    while let Some(&c) = chars.peek() {
+        who |= match c {
+            'u' => USER_BITS,
+            'g' => GROUP_BITS,
+            'o' => OTHER_BITS,
+            'a' => ALL_BITS,
+            _ => break,
+        };
+        chars.next();
+    }
+    if who == 0 {
+        who = ALL_BITS;
+    }
+
+    let mut actions = Vec::new();
+    while let Some(c) = chars.next() {
+        let op = match c {
+            '+' => ModeOp::Add,
+            '-' => ModeOp::Remove,
+            '=' => ModeOp::Set,
+            _ => bail!("invalid mode '{full}': expected +, - or = before '{c}'"),
+        };
+        let mut perms = ModePerms::default();
+        while let Some(&c) = chars.peek() {
+            match c {
+                'r' => perms.read = true,
+                'w' => perms.write = true,
+                'x' => perms.exec = true,
+                'X' => perms.exec_if_searchable = true,
+                's' => perms.setid = true,
+                't' => perms.sticky = true,
+                '+' | '-' | '=' => break,
+                _ => bail!("invalid mode '{full}': unknown permission '{c}'"),
+            }
+            chars.next();
+        }
+        actions.push((op, perms));
+    }
+    if actions.is_empty() {
+        bail!("invalid mode '{full}': clause '{clause}' has no operator");
+    }
+    Ok(ModeClause { who, actions })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ModeSpec;
+
+    fn apply(spec: &str, mode: u32, is_dir: bool) -> u32 {
+        ModeSpec::parse(spec)
+            .expect("valid mode")
+            .apply(mode, is_dir)
+    }
+
+    #[test]
+    fn octal_modes_replace_permission_bits() {
+        assert_eq!(apply("644", 0o755, false), 0o644);
+        assert_eq!(apply("0755", 0o600, true), 0o755);
+        assert_eq!(apply("4755", 0o644, false), 0o4755);
+        assert!(ModeSpec::parse("75555").is_err());
+    }
+
+    #[test]
+    fn symbolic_clauses_add_remove_and_set() {
+        assert_eq!(apply("u+x", 0o644, false), 0o744);
+        assert_eq!(apply("go-w", 0o666, false), 0o644);
+        assert_eq!(apply("a=r,u+w", 0o777, false), 0o644);
+        assert_eq!(apply("+x", 0o600, false), 0o711);
+        assert_eq!(apply("u+rw-x", 0o100, false), 0o600);
+        assert_eq!(apply("g+s,+t", 0o755, true), 0o3755);
+    }
+
+    #[test]
+    fn capital_x_only_marks_directories_and_executables() {
+        assert_eq!(apply("a+X", 0o644, false), 0o644);
+        assert_eq!(apply("a+X", 0o744, false), 0o755);
+        assert_eq!(apply("a+X", 0o700, true), 0o711);
+    }
+
+    #[test]
+    fn malformed_specs_are_rejected() {
+        assert!(ModeSpec::parse("").is_err());
+        assert!(ModeSpec::parse("u").is_err());
+        assert!(ModeSpec::parse("u+z").is_err());
+        assert!(ModeSpec::parse("x+u").is_err());
+    }
+}
@@ -1,11 +1,17 @@
 #![allow(dead_code)]
 
 use std::collections::HashSet;
-use std::path::PathBuf;
-use std::time::SystemTime;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
 
 use crossterm::event::KeyEvent;
 
+use crate::errors::ErrorClass;
+use crate::mode::ModeSpec;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PanelId {
     Left,
@@ -18,7 +24,7 @@ pub enum FindKind {
     ContentRg,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum SftpAuth {
     Agent,
     Password(String),
@@ -28,13 +34,14 @@ pub enum SftpAuth {
     },
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SftpConnectionInfo {
     pub host: String,
     pub user: String,
     pub port: u16,
     pub root_path: PathBuf,
     pub auth: SftpAuth,
+    pub proxy_jump: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -84,6 +91,83 @@ pub struct FsEntry {
     pub is_executable: bool,
     pub is_hidden: bool,
     pub is_virtual: bool,
+    /// Where a symlink points, as stored in the link.
+    pub link_target: Option<PathBuf>,
+    /// The symlink's target does not exist.
+    pub link_broken: bool,
+}
+
+/// Ownership, mode and identity of one entry, as shown by the properties
+/// dialog. Fields the backend cannot report are `None`.
+#[derive(Debug, Clone)]
+pub struct EntryProperties {
+    pub path: PathBuf,
+    pub entry_type: FsEntryType,
+    pub size_bytes: u64,
+    pub mode: u32,
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+    pub owner: Option<String>,
+    pub group: Option<String>,
+    pub modified_at: Option<SystemTime>,
+    pub accessed_at: Option<SystemTime>,
+    pub inode: Option<u64>,
+    pub links: Option<u64>,
+}
+
+/// How copy, move and link jobs treat what they create, as chosen in the
+/// copy options dialog or the link form; everything is off by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CopyOptions {
+    /// Copy what symlinks point to instead of recreating the links.
+    pub follow_symlinks: bool,
+    /// Carry mode, access/modification times and, where the destination
+    /// allows it, ownership over to the copies.
+    pub preserve_attributes: bool,
+    /// Copy into a directory that already exists at the destination instead
+    /// of treating it as a conflict to replace.
+    pub dive_into_existing: bool,
+    /// Rewrite relative symlink targets as absolute ones, so copied links
+    /// still reach the original target.
+    pub stable_symlinks: bool,
+    /// Log entries that fail and keep copying the rest of the tree.
+    pub skip_errors: bool,
+    /// New symlinks reach their target through a path relative to the link.
+    pub relative_symlinks: bool,
+    /// A symlink job replaces the link already at its destination, which is
+    /// how a link gets a new target.
+    pub replace_link: bool,
+}
+
+/// Metadata applied by a `JobKind::Chattr` job; `None` keeps the current value.
+/// Files and directories take separate modes, and `recursive` applies the
+/// change to everything below a directory as well.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AttributeChange {
+    pub file_mode: Option<ModeSpec>,
+    pub dir_mode: Option<ModeSpec>,
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+    pub modified: Option<SystemTime>,
+    pub recursive: bool,
+}
+
+impl AttributeChange {
+    pub fn is_empty(&self) -> bool {
+        self.file_mode.is_none()
+            && self.dir_mode.is_none()
+            && self.uid.is_none()
+            && self.gid.is_none()
+            && self.modified.is_none()
+    }
+
+    pub fn mode_for(&self, entry_type: FsEntryType) -> Option<&ModeSpec> {
+        if entry_type == FsEntryType::Directory {
+            self.dir_mode.as_ref()
+        } else {
+            self.file_mode.as_ref()
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -91,6 +175,7 @@ pub struct PanelState {
     pub backend_label: String,
     pub cwd: PathBuf,
     pub find_view: Option<FindPanelState>,
+    pub trash_view: Option<TrashPanelState>,
     pub all_entries: Vec<FsEntry>,
     pub entries: Vec<FsEntry>,
     pub selected_index: usize,
@@ -100,6 +185,8 @@ pub struct PanelState {
     pub selected_paths: HashSet<PathBuf>,
     pub selection_anchor: Option<usize>,
     pub error_message: Option<String>,
+    pub loading: bool,
+    pub history: DirHistory,
 }
 
 impl PanelState {
@@ -108,6 +195,7 @@ impl PanelState {
             backend_label: "local".to_string(),
             cwd,
             find_view: None,
+            trash_view: None,
             all_entries: Vec::new(),
             entries: Vec::new(),
             selected_index: 0,
@@ -117,6 +205,8 @@ impl PanelState {
             selected_paths: HashSet::new(),
             selection_anchor: None,
             error_message: None,
+            loading: false,
+            history: DirHistory::default(),
         }
     }
 
@@ -148,6 +238,28 @@ impl PanelState {
         self.entries.get(self.selected_index)
     }
 
+    /// Puts the cursor on the entry at `path`; false when it is not listed.
+    pub fn select_path(&mut self, path: &Path) -> bool {
+        match self.entries.iter().position(|entry| entry.path == path) {
+            Some(idx) => {
+                self.selected_index = idx;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Whether the entries are a listing of `dir`, so a reload can keep them
+    /// on screen until the fresh listing arrives.
+    pub fn lists_directory(&self, dir: &Path) -> bool {
+        self.find_view.is_none()
+            && self.trash_view.is_none()
+            && self
+                .all_entries
+                .iter()
+                .all(|entry| entry.is_virtual || entry.path.parent() == Some(dir))
+    }
+
     pub fn set_entries(&mut self, entries: Vec<FsEntry>) {
         let current_paths: HashSet<PathBuf> =
             entries.iter().map(|entry| entry.path.clone()).collect();
@@ -294,6 +406,72 @@ impl PanelState {
     }
 }
 
+/// A directory a panel has shown, with the backend it was shown on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryEntry {
+    pub backend: BackendSpec,
+    pub path: PathBuf,
+}
+
+/// Back/forward list of a panel; `position` is the entry shown now.
+#[derive(Debug, Clone, Default)]
+pub struct DirHistory {
+    pub entries: Vec<HistoryEntry>,
+    pub position: usize,
+}
+
+impl DirHistory {
+    pub const LIMIT: usize = 50;
+
+    /// Records a visit. Revisiting the current entry (a reload, or arriving
+    /// at an entry picked with back/forward) leaves the list as it is; any
+    /// other visit drops the forward entries.
+    pub fn record(&mut self, entry: HistoryEntry) {
+        if self.entries.get(self.position) == Some(&entry) {
+            return;
+        }
+        self.entries.truncate(self.position.saturating_add(1));
+        self.entries.push(entry);
+        if self.entries.len() > Self::LIMIT {
+            let excess = self.entries.len() - Self::LIMIT;
+            self.entries.drain(..excess);
+        }
+        self.position = self.entries.len() - 1;
+    }
+
+    pub fn back(&self) -> Option<usize> {
+        self.position
+            .checked_sub(1)
+            .filter(|_| !self.entries.is_empty())
+    }
+
+    pub fn forward(&self) -> Option<usize> {
+        let next = self.position + 1;
+        (next < self.entries.len()).then_some(next)
+    }
+}
+
+/// Tabs of one side. The active tab is `AppState::left_panel` or
+/// `right_panel`; `parked` holds the others in strip order, and the active
+/// tab sits at position `active` among them.
+#[derive(Debug, Clone, Default)]
+pub struct PanelTabs {
+    pub parked: Vec<PanelState>,
+    pub active: usize,
+}
+
+impl PanelTabs {
+    pub fn count(&self) -> usize {
+        self.parked.len() + 1
+    }
+
+    /// Every tab in strip order, with `current` standing in for the active one.
+    pub fn strip<'a>(&'a self, current: &'a PanelState) -> impl Iterator<Item = &'a PanelState> {
+        let (before, after) = self.parked.split_at(self.active);
+        before.iter().chain(std::iter::once(current)).chain(after)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct FindPanelState {
     pub kind: FindKind,
@@ -306,6 +484,119 @@ pub struct FindPanelState {
     pub case_sensitive: bool,
 }
 
+/// Item listed from a `.trashinfo` file; `trashed_path` points into `files/`.
+#[derive(Debug, Clone)]
+pub struct TrashItem {
+    pub trashed_path: PathBuf,
+    pub original_path: PathBuf,
+    pub deleted_at: Option<SystemTime>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TrashPanelState {
+    pub return_cwd: PathBuf,
+    pub items: Vec<TrashItem>,
+}
+
+impl TrashPanelState {
+    pub fn item(&self, trashed_path: &Path) -> Option<&TrashItem> {
+        self.items
+            .iter()
+            .find(|item| item.trashed_path == trashed_path)
+    }
+}
+
+/// Permission bits of the properties grid: rows are user, group, other and
+/// the special bits, columns are `r`/`w`/`x` (or setuid/setgid/sticky).
+pub const PERMISSION_GRID: [[u32; 3]; 4] = [
+    [0o400, 0o200, 0o100],
+    [0o040, 0o020, 0o010],
+    [0o004, 0o002, 0o001],
+    [0o4000, 0o2000, 0o1000],
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertiesField {
+    Mode,
+    Owner,
+    Group,
+    Modified,
+    Recursive,
+    FileMask,
+    DirMask,
+}
+
+impl PropertiesField {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Mode => Self::Owner,
+            Self::Owner => Self::Group,
+            Self::Group => Self::Modified,
+            Self::Modified => Self::Recursive,
+            Self::Recursive => Self::FileMask,
+            Self::FileMask => Self::DirMask,
+            Self::DirMask => Self::Mode,
+        }
+    }
+
+    pub fn prev(self) -> Self {
+        match self {
+            Self::Mode => Self::DirMask,
+            Self::Owner => Self::Mode,
+            Self::Group => Self::Owner,
+            Self::Modified => Self::Group,
+            Self::Recursive => Self::Modified,
+            Self::FileMask => Self::Recursive,
+            Self::DirMask => Self::FileMask,
+        }
+    }
+}
+
+/// Properties editor for the entry under the cursor; edits apply to every
+/// entry in `targets`. The file/dir masks, when set, replace the grid mode for
+/// files and directories respectively.
+#[derive(Debug, Clone)]
+pub struct PropertiesState {
+    pub targets: Vec<PathBuf>,
+    pub properties: EntryProperties,
+    pub mode: u32,
+    pub owner: String,
+    pub group: String,
+    pub modified: String,
+    pub recursive: bool,
+    pub file_mask: String,
+    pub dir_mask: String,
+    pub field: PropertiesField,
+    pub grid_row: usize,
+    pub grid_col: usize,
+}
+
+impl PropertiesState {
+    pub fn cursor_bit(&self) -> u32 {
+        PERMISSION_GRID[self.grid_row.min(3)][self.grid_col.min(2)]
+    }
+
+    pub fn toggle_cursor_bit(&mut self) {
+        self.mode ^= self.cursor_bit();
+    }
+
+    pub fn move_cursor(&mut self, rows: isize, cols: isize) {
+        self.grid_row = self.grid_row.saturating_add_signed(rows).min(3);
+        self.grid_col = self.grid_col.saturating_add_signed(cols).min(2);
+    }
+
+    pub fn field_value_mut(&mut self) -> Option<&mut String> {
+        match self.field {
+            PropertiesField::Mode | PropertiesField::Recursive => None,
+            PropertiesField::Owner => Some(&mut self.owner),
+            PropertiesField::Group => Some(&mut self.group),
+            PropertiesField::Modified => Some(&mut self.modified),
+            PropertiesField::FileMask => Some(&mut self.file_mask),
+            PropertiesField::DirMask => Some(&mut self.dir_mask),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TerminalSize {
     pub width: u16,
@@ -316,6 +607,7 @@ pub struct TerminalSize {
 pub enum ScreenMode {
     Normal,
     Viewer,
+    Jobs,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -348,6 +640,8 @@ pub struct AppState {
     pub top_menu: TopMenuState,
     pub left_panel: PanelState,
     pub right_panel: PanelState,
+    pub left_tabs: PanelTabs,
+    pub right_tabs: PanelTabs,
     pub status_line: String,
     pub activity_log: Vec<String>,
     pub dialog: Option<DialogState>,
@@ -356,7 +650,11 @@ pub struct AppState {
     pub find_progress: Option<FindProgressState>,
     pub command_line: CommandLineState,
     pub jobs: Vec<Job>,
+    pub job_manager: JobManagerState,
+    pub properties: Option<PropertiesState>,
+    pub copy_options: CopyOptions,
     pub terminal_size: TerminalSize,
+    pub spinner_frame: usize,
 }
 
 impl AppState {
@@ -367,6 +665,8 @@ impl AppState {
             top_menu: TopMenuState::default(),
             left_panel: PanelState::new(cwd.clone()),
             right_panel: PanelState::new(cwd),
+            left_tabs: PanelTabs::default(),
+            right_tabs: PanelTabs::default(),
             status_line: "Ready".to_string(),
             activity_log: Vec::new(),
             dialog: None,
@@ -375,10 +675,14 @@ impl AppState {
             find_progress: None,
             command_line: CommandLineState::default(),
             jobs: Vec::new(),
+            job_manager: JobManagerState::default(),
+            properties: None,
+            copy_options: CopyOptions::default(),
             terminal_size: TerminalSize {
                 width: 0,
                 height: 0,
             },
+            spinner_frame: 0,
         }
     }
 }
@@ -390,6 +694,11 @@ pub struct TopMenuState {
     pub item_index: usize,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct JobManagerState {
+    pub selected: usize,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct CommandLineState {
     pub input: String,
@@ -400,37 +709,415 @@ pub struct CommandLineState {
 pub struct DialogState {
     pub title: String,
     pub body: String,
-    pub input_value: Option<String>,
-    pub mask_input: bool,
+    pub input: Option<TextInput>,
+    /// Form controls shown between the input and the buttons.
+    pub fields: Vec<DialogField>,
+    /// Field with keyboard focus; `None` while the buttons (and the input) have it.
+    pub focused_field: Option<usize>,
+    /// Scrolling preview shown under the fields.
+    pub list: Option<DialogList>,
+    /// Validation message shown above the buttons.
+    pub error: Option<String>,
     pub buttons: Vec<DialogButton>,
     pub focused_button: usize,
     pub tone: DialogTone,
 }
 
 impl DialogState {
+    /// Moves focus forward through the buttons and then the form fields.
     pub fn focus_next(&mut self) {
-        if self.buttons.is_empty() {
-            self.focused_button = 0;
-            return;
+        match self.focused_field {
+            Some(idx) if idx + 1 < self.fields.len() => self.focused_field = Some(idx + 1),
+            Some(_) => {
+                self.focused_field = None;
+                self.focused_button = 0;
+            }
+            None if self.focused_button + 1 < self.buttons.len() => self.focused_button += 1,
+            None if !self.fields.is_empty() => self.focused_field = Some(0),
+            None => self.focused_button = 0,
         }
-        self.focused_button = (self.focused_button + 1) % self.buttons.len();
     }
 
     pub fn focus_prev(&mut self) {
-        if self.buttons.is_empty() {
-            self.focused_button = 0;
-            return;
+        match self.focused_field {
+            Some(0) => {
+                self.focused_field = None;
+                self.focused_button = self.buttons.len().saturating_sub(1);
+            }
+            Some(idx) => self.focused_field = Some(idx - 1),
+            None if self.focused_button > 0 => self.focused_button -= 1,
+            None if !self.fields.is_empty() => {
+                self.focused_field = Some(self.fields.len() - 1);
+            }
+            None => self.focused_button = self.buttons.len().saturating_sub(1),
         }
-        self.focused_button = if self.focused_button == 0 {
-            self.buttons.len().saturating_sub(1)
-        } else {
-            self.focused_button.saturating_sub(1)
-        };
+    }
+
+    /// Current text of the dialog's input line, if it has one.
+    pub fn input_text(&self) -> Option<&str> {
+        self.input.as_ref().map(|input| input.value.as_str())
     }
 
     pub fn focused_button(&self) -> Option<&DialogButton> {
         self.buttons.get(self.focused_button)
     }
+
+    pub fn focused_field_mut(&mut self) -> Option<&mut DialogField> {
+        self.focused_field.and_then(|idx| self.fields.get_mut(idx))
+    }
+
+    pub fn field(&self, key: &str) -> Option<&DialogField> {
+        self.fields.iter().find(|field| field.key == key)
+    }
+
+    pub fn checkbox(&self, key: &str) -> bool {
+        self.field(key)
+            .is_some_and(|field| matches!(field.kind, DialogFieldKind::Checkbox(true)))
+    }
+
+    /// Selected option index of a radio group or dropdown.
+    pub fn selected(&self, key: &str) -> Option<usize> {
+        match self.field(key)?.kind {
+            DialogFieldKind::Radio { selected, .. }
+            | DialogFieldKind::Dropdown { selected, .. } => Some(selected),
+            DialogFieldKind::Checkbox(_) | DialogFieldKind::Text { .. } => None,
+        }
+    }
+
+    /// Trimmed value of a text input; empty when there is no such field.
+    pub fn text(&self, key: &str) -> &str {
+        match self.field(key).map(|field| &field.kind) {
+            Some(DialogFieldKind::Text { input, .. }) => input.value.trim(),
+            _ => "",
+        }
+    }
+
+    /// Keeps the form open with `message` and moves focus to the field at fault.
+    pub fn reject(&mut self, key: &str, message: impl Into<String>) {
+        self.error = Some(message.into());
+        if let Some(idx) = self.fields.iter().position(|field| field.key == key) {
+            self.focused_field = Some(idx);
+        }
+    }
+}
+
+/// Lines shown in a dialog; flagged ones are highlighted. A list with a
+/// `selected` row is a pick list: Up/Down move the row and the dialog's
+/// primary button acts on it.
+#[derive(Debug, Clone, Default)]
+pub struct DialogList {
+    pub title: String,
+    pub lines: Vec<(String, bool)>,
+    pub offset: usize,
+    pub selected: Option<usize>,
+}
+
+impl DialogList {
+    /// Rows the dialog gives the list.
+    pub const HEIGHT: usize = 10;
+
+    pub fn scroll(&mut self, delta: isize) {
+        let max = self.lines.len().saturating_sub(Self::HEIGHT);
+        self.offset = self.offset.saturating_add_signed(delta).min(max);
+    }
+
+    /// Moves the selected row, scrolling to keep it in view.
+    pub fn move_selection(&mut self, delta: isize) {
+        let Some(selected) = self.selected else {
+            return;
+        };
+        let last = self.lines.len().saturating_sub(1);
+        let selected = selected.saturating_add_signed(delta).min(last);
+        self.selected = Some(selected);
+        if selected < self.offset {
+            self.offset = selected;
+        } else if selected >= self.offset + Self::HEIGHT {
+            self.offset = selected + 1 - Self::HEIGHT;
+        }
+    }
+}
+
+/// One-line text input with a cursor. `anchor` is the other end of the
+/// selection: typing replaces the selected text and plain cursor moves drop it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextInput {
+    pub value: String,
+    /// Byte offset into `value`, always on a char boundary.
+    pub cursor: usize,
+    pub anchor: Option<usize>,
+}
+
+impl TextInput {
+    /// Cursor at the end, nothing selected.
+    pub fn new(value: String) -> Self {
+        let cursor = value.len();
+        Self {
+            value,
+            cursor,
+            anchor: None,
+        }
+    }
+
+    /// Selects `range` (clamped to the value) with the cursor at its end.
+    pub fn with_selection(value: String, range: Range<usize>) -> Self {
+        let end = floor_char_boundary(&value, range.end);
+        let start = floor_char_boundary(&value, range.start.min(end));
+        Self {
+            value,
+            cursor: end,
+            anchor: (start < end).then_some(start),
+        }
+    }
+
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor?;
+        (anchor != self.cursor).then(|| anchor.min(self.cursor)..anchor.max(self.cursor))
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        self.delete_selection();
+        self.value.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    /// Deletes the selection or the char before the cursor.
+    pub fn backspace(&mut self) -> bool {
+        if self.delete_selection() {
+            return true;
+        }
+        let start = self.prev_boundary(self.cursor);
+        self.delete_range(start..self.cursor)
+    }
+
+    /// Deletes the selection or the char under the cursor.
+    pub fn delete(&mut self) -> bool {
+        if self.delete_selection() {
+            return true;
+        }
+        let end = self.next_boundary(self.cursor);
+        self.delete_range(self.cursor..end)
+    }
+
+    /// Deletes the selection or the word before the cursor (Ctrl+W).
+    pub fn delete_word_back(&mut self) -> bool {
+        if self.delete_selection() {
+            return true;
+        }
+        let start = self.word_start_before(self.cursor);
+        self.delete_range(start..self.cursor)
+    }
+
+    /// With a selection the cursor lands on its left edge instead.
+    pub fn move_left(&mut self) {
+        self.cursor = match self.selection() {
+            Some(range) => range.start,
+            None => self.prev_boundary(self.cursor),
+        };
+        self.anchor = None;
+    }
+
+    pub fn move_right(&mut self) {
+        self.cursor = match self.selection() {
+            Some(range) => range.end,
+            None => self.next_boundary(self.cursor),
+        };
+        self.anchor = None;
+    }
+
+    pub fn move_home(&mut self) {
+        self.cursor = 0;
+        self.anchor = None;
+    }
+
+    pub fn move_end(&mut self) {
+        self.cursor = self.value.len();
+        self.anchor = None;
+    }
+
+    pub fn move_word_left(&mut self) {
+        self.cursor = self.word_start_before(self.cursor);
+        self.anchor = None;
+    }
+
+    pub fn move_word_right(&mut self) {
+        let rest = &self.value[self.cursor..];
+        let skipped = rest
+            .char_indices()
+            .find(|(_, c)| is_word_char(*c))
+            .map_or(rest.len(), |(idx, _)| idx);
+        let word = &rest[skipped..];
+        let word_len = word
+            .char_indices()
+            .find(|(_, c)| !is_word_char(*c))
+            .map_or(word.len(), |(idx, _)| idx);
+        self.cursor += skipped + word_len;
+        self.anchor = None;
+    }
+
+    fn delete_selection(&mut self) -> bool {
+        match self.selection() {
+            Some(range) => self.delete_range(range),
+            None => {
+                self.anchor = None;
+                false
+            }
+        }
+    }
+
+    fn delete_range(&mut self, range: Range<usize>) -> bool {
+        self.anchor = None;
+        if range.is_empty() {
+            return false;
+        }
+        self.cursor = range.start;
+        self.value.replace_range(range, "");
+        true
+    }
+
+    fn prev_boundary(&self, idx: usize) -> usize {
+        self.value[..idx]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(prev, _)| prev)
+    }
+
+    fn next_boundary(&self, idx: usize) -> usize {
+        self.value[idx..]
+            .chars()
+            .next()
+            .map_or(idx, |c| idx + c.len_utf8())
+    }
+
+    /// Start of the word ending at or before `idx`, skipping separators first.
+    fn word_start_before(&self, idx: usize) -> usize {
+        let head = &self.value[..idx];
+        let trimmed = head.trim_end_matches(|c| !is_word_char(c));
+        trimmed.trim_end_matches(is_word_char).len()
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn floor_char_boundary(value: &str, idx: usize) -> usize {
+    let mut idx = idx.min(value.len());
+    while !value.is_char_boundary(idx) {
+        idx -= 1;
+    }
+    idx
+}
+
+#[derive(Debug, Clone)]
+pub struct DialogField {
+    /// Stable name the dialog's owner reads the value back by.
+    pub key: &'static str,
+    pub label: String,
+    pub kind: DialogFieldKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum DialogFieldKind {
+    Checkbox(bool),
+    Radio {
+        options: Vec<String>,
+        selected: usize,
+    },
+    /// One-line input; a masked one renders as `*` (passwords).
+    Text {
+        input: TextInput,
+        masked: bool,
+    },
+    /// Like a radio group, but only the selected option is shown.
+    Dropdown {
+        options: Vec<String>,
+        selected: usize,
+    },
+}
+
+impl DialogField {
+    pub fn checkbox(key: &'static str, label: &str, checked: bool) -> Self {
+        Self {
+            key,
+            label: label.to_string(),
+            kind: DialogFieldKind::Checkbox(checked),
+        }
+    }
+
+    pub fn radio(key: &'static str, label: &str, options: &[&str], selected: usize) -> Self {
+        Self {
+            key,
+            label: label.to_string(),
+            kind: DialogFieldKind::Radio {
+                options: options.iter().map(|option| option.to_string()).collect(),
+                selected: selected.min(options.len().saturating_sub(1)),
+            },
+        }
+    }
+
+    pub fn text(key: &'static str, label: &str, value: impl Into<String>) -> Self {
+        Self {
+            key,
+            label: label.to_string(),
+            kind: DialogFieldKind::Text {
+                input: TextInput::new(value.into()),
+                masked: false,
+            },
+        }
+    }
+
+    pub fn masked(key: &'static str, label: &str, value: impl Into<String>) -> Self {
+        Self {
+            key,
+            label: label.to_string(),
+            kind: DialogFieldKind::Text {
+                input: TextInput::new(value.into()),
+                masked: true,
+            },
+        }
+    }
+
+    pub fn dropdown(key: &'static str, label: &str, options: &[&str], selected: usize) -> Self {
+        Self {
+            key,
+            label: label.to_string(),
+            kind: DialogFieldKind::Dropdown {
+                options: options.iter().map(|option| option.to_string()).collect(),
+                selected: selected.min(options.len().saturating_sub(1)),
+            },
+        }
+    }
+
+    /// Space on the field: flips a checkbox, moves a radio group or dropdown
+    /// forward, types a space into a text input.
+    pub fn toggle(&mut self) {
+        match &mut self.kind {
+            DialogFieldKind::Checkbox(checked) => *checked = !*checked,
+            DialogFieldKind::Radio { .. } | DialogFieldKind::Dropdown { .. } => self.select_step(1),
+            DialogFieldKind::Text { input, .. } => input.insert_char(' '),
+        }
+    }
+
+    /// Moves a radio or dropdown selection by `delta`, wrapping around; other
+    /// kinds ignore it.
+    pub fn select_step(&mut self, delta: isize) {
+        if let DialogFieldKind::Radio { options, selected }
+        | DialogFieldKind::Dropdown { options, selected } = &mut self.kind
+        {
+            let len = options.len() as isize;
+            if len > 0 {
+                *selected = (*selected as isize + delta).rem_euclid(len) as usize;
+            }
+        }
+    }
+
+    /// The line being edited when this is a text input.
+    pub fn text_input_mut(&mut self) -> Option<&mut TextInput> {
+        match &mut self.kind {
+            DialogFieldKind::Text { input, .. } => Some(input),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -483,7 +1170,14 @@ pub enum Command {
     Refresh,
     Copy,
     Move,
+    Rename,
+    BatchRename,
+    EditorRename,
+    Symlink,
+    Hardlink,
+    EditSymlink,
     Delete,
+    DeletePermanently,
     Mkdir,
     ToggleSort,
     StartSearch,
@@ -493,31 +1187,118 @@ pub enum Command {
     InvertSelection,
     SelectRangeUp,
     SelectRangeDown,
+    OpenJobManager,
+    CloseJobManager,
+    OpenTrash,
+    OpenProperties,
+    NewTab,
+    DuplicateTab,
+    CloseTab,
+    NextTab,
+    PrevTab,
+    SwitchTab(usize),
+    HistoryBack,
+    HistoryForward,
+    OpenHistory,
+    OpenHotlist,
+    AddToHotlist,
+    EmptyTrash,
+    PurgeTrash,
+    JobManagerUp,
+    JobManagerDown,
+    JobManagerTop,
+    JobManagerBottom,
+    JobManagerCancel,
+    JobManagerRetry,
+    JobManagerRetryAllFailed,
+    JobManagerClearFinished,
 }
 
 #[derive(Debug, Clone)]
 pub enum Event {
     Input(KeyEvent),
     Tick,
-    Resize { width: u16, height: u16 },
+    Resize {
+        width: u16,
+        height: u16,
+    },
     Job(JobUpdate),
     Find(FindUpdate),
+    Listing(ListingUpdate),
+    Dive(DiveUpdate),
+    /// The watched directory `dir` of a panel changed on disk.
+    DirChanged {
+        panel_id: PanelId,
+        dir: PathBuf,
+    },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum JobKind {
     Copy,
     Move,
+    Rename,
     Delete,
     Mkdir,
+    Trash,
+    Restore,
+    Purge,
+    Chattr,
+    /// Link at `destination` pointing to `source`.
+    Symlink,
+    /// Hard link at `destination` to the file `source`.
+    Hardlink,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum JobStatus {
     Queued,
     Running,
+    Paused,
     Done,
     Failed,
+    Canceled,
+}
+
+impl JobStatus {
+    pub fn is_terminal(self) -> bool {
+        matches!(self, Self::Done | Self::Failed | Self::Canceled)
+    }
+}
+
+/// Cooperative cancel/pause flags shared between the UI and the worker running
+/// a job. Workers poll them between files and between copied chunks.
+#[derive(Debug, Clone, Default)]
+pub struct JobControl {
+    flags: Arc<JobControlFlags>,
+}
+
+#[derive(Debug, Default)]
+struct JobControlFlags {
+    canceled: AtomicBool,
+    paused: AtomicBool,
+}
+
+impl JobControl {
+    pub fn cancel(&self) {
+        self.flags.canceled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn pause(&self) {
+        self.flags.paused.store(true, Ordering::SeqCst);
+    }
+
+    pub fn resume(&self) {
+        self.flags.paused.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_canceled(&self) -> bool {
+        self.flags.canceled.load(Ordering::SeqCst)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.flags.paused.load(Ordering::SeqCst)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -531,6 +1312,8 @@ pub struct Job {
     pub current_item: Option<String>,
     pub batch_completed: Option<usize>,
     pub batch_total: Option<usize>,
+    pub bytes: Option<ByteProgress>,
+    pub error_class: Option<ErrorClass>,
     pub message: Option<String>,
 }
 
@@ -543,6 +1326,9 @@ pub struct JobRequest {
     pub destination_backend: Option<BackendSpec>,
     pub source: PathBuf,
     pub destination: Option<PathBuf>,
+    pub attributes: Option<AttributeChange>,
+    pub copy_options: CopyOptions,
+    pub control: JobControl,
 }
 
 #[derive(Debug, Clone)]
@@ -556,7 +1342,12 @@ pub struct JobUpdate {
     pub current_item: Option<String>,
     pub batch_completed: Option<usize>,
     pub batch_total: Option<usize>,
+    pub bytes: Option<ByteProgress>,
+    pub error_class: Option<ErrorClass>,
     pub message: Option<String>,
+    /// Something that went wrong without failing the job, such as a copy
+    /// that kept its data but not its attributes.
+    pub warning: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -610,6 +1401,32 @@ pub enum FindUpdate {
     },
 }
 
+/// A finished directory listing, sent back by the worker started for it.
+#[derive(Debug, Clone)]
+pub struct ListingUpdate {
+    pub id: u64,
+    pub panel_id: PanelId,
+    /// The directory as the panel asked for it, before normalization.
+    pub cwd: PathBuf,
+    pub result: Result<DirListing, String>,
+}
+
+/// Entries of a listed directory, along with its normalized path.
+#[derive(Debug, Clone)]
+pub struct DirListing {
+    pub cwd: PathBuf,
+    pub entries: Vec<FsEntry>,
+}
+
+/// Answer to a dive-into-existing check: the children of `source` when it
+/// and the existing `destination` are both directories, `None` otherwise.
+#[derive(Debug, Clone)]
+pub struct DiveUpdate {
+    pub source: PathBuf,
+    pub destination: PathBuf,
+    pub result: Result<Option<Vec<FsEntry>>, String>,
+}
+
 impl JobUpdate {
     pub fn into_job(self) -> Job {
         Job {
@@ -622,6 +1439,8 @@ impl JobUpdate {
             current_item: self.current_item,
             batch_completed: self.batch_completed,
             batch_total: self.batch_total,
+            bytes: self.bytes,
+            error_class: self.error_class,
             message: self.message,
         }
     }
@@ -635,6 +1454,31 @@ pub struct BatchProgressState {
     pub completed: usize,
     pub total: usize,
     pub failed: usize,
+    pub bytes: Option<ByteProgress>,
+    /// Entries done/discovered by recursive attribute jobs.
+    pub entries: Option<(usize, usize)>,
+    pub paused: bool,
+}
+
+/// Byte counters for a running transfer: the file currently being copied and
+/// the whole job (or batch), plus the smoothed throughput.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ByteProgress {
+    pub file_done: u64,
+    pub file_total: u64,
+    pub done: u64,
+    pub total: u64,
+    pub bytes_per_sec: u64,
+}
+
+impl ByteProgress {
+    pub fn eta(&self) -> Option<Duration> {
+        if self.bytes_per_sec == 0 || self.done >= self.total {
+            return None;
+        }
+        let remaining = self.total - self.done;
+        Some(Duration::from_secs(remaining.div_ceil(self.bytes_per_sec)))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -655,7 +1499,7 @@ fn normalize_mask(mask: &str) -> String {
     }
 }
 
-fn wildcard_match(pattern: &str, text: &str) -> bool {
+pub fn wildcard_match(pattern: &str, text: &str) -> bool {
     let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
     let text: Vec<char> = text.to_lowercase().chars().collect();
     let p_len = pattern.len();
@@ -683,3 +1527,106 @@ fn wildcard_match(pattern: &str, text: &str) -> bool {
 
     dp[p_len][t_len]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::TextInput;
+
+    fn input(value: &str) -> TextInput {
+        TextInput::new(value.to_string())
+    }
+
+    #[test]
+    fn text_input_deletes_stop_at_the_edges() {
+        let mut text = input("ab");
+        assert!(!text.delete());
+        assert_eq!((text.value.as_str(), text.cursor), ("ab", 2));
+
+        text.move_home();
+        assert!(!text.backspace());
+        assert!(text.delete());
+        assert_eq!((text.value.as_str(), text.cursor), ("b", 0));
+
+        text.move_end();
+        assert!(text.backspace());
+        assert!(!text.backspace());
+        assert_eq!((text.value.as_str(), text.cursor), ("", 0));
+    }
+
+    #[test]
+    fn text_input_moves_drop_the_selection() {
+        let mut text = TextInput::with_selection("hello".to_string(), 1..3);
+        assert_eq!(text.selection(), Some(1..3));
+        text.move_home();
+        assert_eq!((text.cursor, text.selection()), (0, None));
+        text.move_end();
+        assert_eq!((text.cursor, text.selection()), (5, None));
+
+        let mut text = TextInput::with_selection("hello".to_string(), 1..3);
+        text.move_left();
+        assert_eq!((text.cursor, text.selection()), (1, None));
+
+        let mut text = TextInput::with_selection("hello".to_string(), 1..3);
+        text.insert_char('X');
+        assert_eq!((text.value.as_str(), text.cursor), ("hXlo", 2));
+    }
+
+    #[test]
+    fn text_input_deletes_and_jumps_by_words() {
+        let mut text = input("cp foo/bar  ");
+        assert!(text.delete_word_back());
+        assert_eq!(text.value, "cp foo/");
+        assert!(text.delete_word_back());
+        assert_eq!(text.value, "cp ");
+        assert!(text.delete_word_back());
+        assert!(!text.delete_word_back());
+        assert_eq!((text.value.as_str(), text.cursor), ("", 0));
+
+        let mut text = input("one two_3 four");
+        text.move_home();
+        text.move_word_right();
+        assert_eq!(text.cursor, 3);
+        text.move_word_right();
+        assert_eq!(text.cursor, 9);
+        text.move_word_left();
+        assert_eq!(text.cursor, 4);
+        text.move_word_left();
+        text.move_word_left();
+        assert_eq!(text.cursor, 0);
+        text.move_end();
+        text.move_word_right();
+        assert_eq!(text.cursor, text.value.len());
+    }
+
+    #[test]
+    fn text_input_keeps_multibyte_chars_whole() {
+        let mut text = input("héllo wörld");
+        assert!(text.backspace());
+        text.move_word_left();
+        assert_eq!(text.cursor, "héllo ".len());
+        text.move_right();
+        text.move_right();
+        assert_eq!(text.cursor, "héllo wö".len());
+        assert!(text.backspace());
+        text.insert_char('ø');
+        assert_eq!(text.value, "héllo wørl");
+        assert_eq!(text.cursor, "héllo wø".len());
+
+        let mut text = input("a😀b");
+        text.move_left();
+        text.move_left();
+        assert_eq!(text.cursor, 1);
+        assert!(text.delete());
+        assert_eq!((text.value.as_str(), text.cursor), ("ab", 1));
+
+        let mut text = input("日本 語");
+        assert!(text.delete_word_back());
+        assert_eq!(text.value, "日本 ");
+
+        // A selection end inside a char is pulled back to its start.
+        let mut text = TextInput::with_selection("héllo".to_string(), 0..2);
+        assert_eq!(text.selection(), Some(0..1));
+        text.insert_char('J');
+        assert_eq!(text.value, "Jéllo");
+    }
+}
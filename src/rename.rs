@@ -0,0 +1,366 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{Context, Result, bail};
+use regex::Regex;
+
+/// Widest zero padding `[C]` takes; wider counters only make absurd names.
+const MAX_COUNTER_WIDTH: usize = 10;
+
+/// Case conversion applied after the template and the replacement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaseMode {
+    Keep,
+    Lower,
+    Upper,
+    Title,
+}
+
+impl CaseMode {
+    pub const LABELS: [&'static str; 4] = ["Keep", "lower", "UPPER", "Title"];
+
+    pub fn from_index(idx: usize) -> Self {
+        match idx {
+            1 => Self::Lower,
+            2 => Self::Upper,
+            3 => Self::Title,
+            _ => Self::Keep,
+        }
+    }
+
+    fn apply(self, name: &str) -> String {
+        match self {
+            Self::Keep => name.to_string(),
+            Self::Lower => name.to_lowercase(),
+            Self::Upper => name.to_uppercase(),
+            Self::Title => {
+                let mut word_start = true;
+                name.chars()
+                    .flat_map(|c| {
+                        let converted: Vec<char> = if word_start {
+                            c.to_uppercase().collect()
+                        } else {
+                            c.to_lowercase().collect()
+                        };
+                        word_start = !c.is_alphanumeric();
+                        converted
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// How a batch of names is rewritten: the template builds each name from
+/// `[N]` (name without extension), `[E]` (extension) and `[C]` (counter),
+/// then the regex replacement and the case conversion run over the result.
+#[derive(Debug, Clone)]
+pub struct RenameRule {
+    template: String,
+    find: Option<Regex>,
+    replace: String,
+    case: CaseMode,
+    counter_start: u64,
+    counter_step: u64,
+    counter_width: usize,
+}
+
+impl RenameRule {
+    pub fn parse(
+        template: &str,
+        find: &str,
+        replace: &str,
+        case: CaseMode,
+        counter: (&str, &str, &str),
+    ) -> Result<Self> {
+        if template.is_empty() {
+            bail!("template is empty");
+        }
+        let find = if find.is_empty() {
+            None
+        } else {
+            Some(Regex::new(find).with_context(|| format!("invalid regex '{find}'"))?)
+        };
+        let (start, step, width) = counter;
+        let counter_width = parse_number(width, "counter digits")?;
+        if counter_width > MAX_COUNTER_WIDTH as u64 {
+            bail!("counter digits must be at most {MAX_COUNTER_WIDTH}: '{counter_width}'");
+        }
+        Ok(Self {
+            template: template.to_string(),
+            find,
+            replace: replace.to_string(),
+            case,
+            counter_start: parse_number(start, "counter start")?,
+            counter_step: parse_number(step, "counter step")?,
+            counter_width: counter_width as usize,
+        })
+    }
+
+    /// New name for `name`, the `index`-th entry of the batch.
+    pub fn apply(&self, name: &str, index: usize) -> String {
+        let (stem, extension) = split_extension(name);
+        let counter = self
+            .counter_start
+            .saturating_add(self.counter_step.saturating_mul(index as u64));
+        let mut expanded = String::new();
+        let mut rest = self.template.as_str();
+        while let Some(open) = rest.find('[') {
+            expanded.push_str(&rest[..open]);
+            let token = &rest[open..];
+            let (value, len) = match token.get(..3) {
+                Some("[N]") => (stem.to_string(), 3),
+                Some("[E]") => {
+                    // "[N].[E]" should not leave a trailing dot on names
+                    // without an extension.
+                    if extension.is_empty() && expanded.ends_with('.') {
+                        expanded.pop();
+                    }
+                    (extension.to_string(), 3)
+                }
+                Some("[C]") => (format!("{counter:0width$}", width = self.counter_width), 3),
+                _ => ("[".to_string(), 1),
+            };
+            expanded.push_str(&value);
+            rest = &token[len..];
+        }
+        expanded.push_str(rest);
+
+        let replaced = match self.find.as_ref() {
+            Some(find) => find
+                .replace_all(&expanded, self.replace.as_str())
+                .into_owned(),
+            None => expanded,
+        };
+        self.case.apply(&replaced)
+    }
+}
+
+/// One row of the preview: the new name and, when it cannot be applied, why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenamePreview {
+    pub old: String,
+    pub new: String,
+    pub problem: Option<String>,
+}
+
+impl RenamePreview {
+    pub fn is_change(&self) -> bool {
+        self.old != self.new
+    }
+}
+
+/// Applies `rule` to `names` in order and checks the results against each
+/// other and against `existing`, the other names in the directory.
+pub fn preview_renames(
+    rule: &RenameRule,
+    names: &[String],
+    existing: &HashSet<String>,
+) -> Vec<RenamePreview> {
+    let mut previews = names
+        .iter()
+        .enumerate()
+        .map(|(idx, old)| RenamePreview {
+            old: old.clone(),
+            new: rule.apply(old, idx),
+            problem: None,
+        })
+        .collect::<Vec<_>>();
+
+    let renamed = names.iter().map(String::as_str).collect::<HashSet<_>>();
+    check_renames(&mut previews, &renamed, existing);
+    previews
+}
+
+/// Sets `problem` on every preview whose new name cannot be applied. `moving`
+/// holds the current names of all entries the batch renames or removes, and
+/// `existing` the names in the directory.
+pub fn check_renames(
+    previews: &mut [RenamePreview],
+    moving: &HashSet<&str>,
+    existing: &HashSet<String>,
+) {
+    let mut targets = HashMap::<String, usize>::new();
+    for preview in previews.iter() {
+        *targets.entry(preview.new.clone()).or_default() += 1;
+    }
+    for preview in previews.iter_mut() {
+        preview.problem = name_problem(&preview.new).or_else(|| {
+            if targets[&preview.new] > 1 {
+                Some("duplicate new name".to_string())
+            } else if !preview.is_change() {
+                None
+            } else if moving.contains(preview.new.as_str()) {
+                // Jobs run in no particular order, so a chain such as
+                // a -> b, b -> c could overwrite b before it moves.
+                Some("taken by another renamed entry".to_string())
+            } else if existing.contains(&preview.new) {
+                Some("already exists".to_string())
+            } else {
+                None
+            }
+        });
+    }
+}
+
+/// Names laid out for editing in an external editor, vidir-style: each line
+/// is the entry's number and its name, and removing a line removes the entry.
+pub fn names_to_edit_text(names: &[String]) -> String {
+    let width = names.len().to_string().len();
+    let mut text = String::from(
+        "# Edit the names after the numbers. Removing a line marks the entry for deletion.\n",
+    );
+    for (idx, name) in names.iter().enumerate() {
+        text.push_str(&format!("{:0width$} {name}\n", idx + 1));
+    }
+    text
+}
+
+/// Reads back text made by `names_to_edit_text`: for each of `names`, its new
+/// name, or `None` when its line was removed.
+pub fn parse_edited_names(names: &[String], text: &str) -> Result<Vec<Option<String>>> {
+    let mut edits = vec![None; names.len()];
+    for (line_idx, line) in text.lines().enumerate() {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line_no = line_idx + 1;
+        let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        let name = line[digits..]
+            .strip_prefix([' ', '\t'])
+            .with_context(|| format!("line {line_no}: expected '<number> <name>'"))?;
+        let number = line[..digits].parse::<usize>().unwrap_or(0);
+        let slot = number
+            .checked_sub(1)
+            .and_then(|idx| edits.get_mut(idx))
+            .with_context(|| format!("line {line_no}: no entry numbered {number}"))?;
+        if slot.is_some() {
+            bail!("line {line_no}: entry {number} appears more than once");
+        }
+        *slot = Some(name.to_string());
+    }
+    Ok(edits)
+}
+
+fn name_problem(name: &str) -> Option<String> {
+    if name.is_empty() {
+        Some("empty name".to_string())
+    } else if name == "." || name == ".." {
+        Some(format!("'{name}' is not a file name"))
+    } else if name.contains('/') {
+        Some("contains '/'".to_string())
+    } else {
+        None
+    }
+}
+
+/// Splits at the last dot; a leading dot belongs to the name (`.bashrc`).
+fn split_extension(name: &str) -> (&str, &str) {
+    match name.rfind('.') {
+        Some(idx) if idx > 0 => (&name[..idx], &name[idx + 1..]),
+        _ => (name, ""),
+    }
+}
+
+fn parse_number(value: &str, what: &str) -> Result<u64> {
+    let value = value.trim();
+    value
+        .parse()
+        .with_context(|| format!("{what} must be a number: '{value}'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{CaseMode, RenameRule, names_to_edit_text, parse_edited_names, preview_renames};
+
+    fn rule(template: &str, find: &str, replace: &str, case: CaseMode) -> RenameRule {
+        RenameRule::parse(template, find, replace, case, ("1", "1", "3")).expect("valid rule")
+    }
+
+    #[test]
+    fn templates_expand_name_extension_and_counter() {
+        let rule = rule("holiday_[C]_[N].[E]", "", "", CaseMode::Keep);
+        assert_eq!(rule.apply("IMG_0042.JPG", 0), "holiday_001_IMG_0042.JPG");
+        assert_eq!(rule.apply("notes", 4), "holiday_005_notes");
+        assert_eq!(rule.apply(".bashrc", 1), "holiday_002_.bashrc");
+        assert_eq!(rule.apply("a[b].txt", 0), "holiday_001_a[b].txt");
+    }
+
+    #[test]
+    fn regex_replacement_uses_capture_groups_before_case() {
+        let rule = rule(
+            "[N].[E]",
+            r"^app\.log\.(\d+)$",
+            "app-$1.log",
+            CaseMode::Upper,
+        );
+        assert_eq!(rule.apply("app.log.3", 0), "APP-3.LOG");
+        let title = super::RenameRule::parse("[N]", "", "", CaseMode::Title, ("0", "2", "0"))
+            .expect("valid rule");
+        assert_eq!(title.apply("my HOLIDAY-photos", 0), "My Holiday-Photos");
+        assert!(RenameRule::parse("[N]", "(", "", CaseMode::Keep, ("1", "1", "1")).is_err());
+        assert!(RenameRule::parse("[N]", "", "", CaseMode::Keep, ("x", "1", "1")).is_err());
+        assert!(RenameRule::parse("[C]", "", "", CaseMode::Keep, ("1", "1", "10")).is_ok());
+        assert!(RenameRule::parse("[C]", "", "", CaseMode::Keep, ("1", "1", "11")).is_err());
+    }
+
+    #[test]
+    fn previews_flag_duplicates_existing_names_and_chains() {
+        let names = ["a.txt", "b.txt", "c.log"].map(String::from).to_vec();
+        let existing = HashSet::from(["report.txt".to_string()]);
+
+        let same = rule("report.[E]", "", "", CaseMode::Keep);
+        let previews = preview_renames(&same, &names, &existing);
+        let problems = previews
+            .iter()
+            .map(|preview| preview.problem.as_deref())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            problems,
+            [Some("duplicate new name"), Some("duplicate new name"), None]
+        );
+
+        let unchanged =
+            preview_renames(&rule("[N].[E]", "", "", CaseMode::Keep), &names, &existing);
+        assert!(
+            unchanged
+                .iter()
+                .all(|preview| !preview.is_change() && preview.problem.is_none())
+        );
+
+        let renumber = RenameRule::parse("[C].[E]", "", "", CaseMode::Keep, ("1", "1", "0"))
+            .expect("valid rule");
+        let names = ["2.txt", "x.txt", "y.txt"].map(String::from).to_vec();
+        let previews = preview_renames(&renumber, &names, &existing);
+        let problems = previews
+            .iter()
+            .map(|preview| preview.problem.as_deref())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            problems,
+            [None, Some("taken by another renamed entry"), None]
+        );
+    }
+
+    #[test]
+    fn edited_name_lists_map_back_to_entries() {
+        let names = ["a.txt", "b.txt", "c d.txt"].map(String::from).to_vec();
+        let text = names_to_edit_text(&names);
+        assert!(text.ends_with("1 a.txt\n2 b.txt\n3 c d.txt\n"));
+
+        let edited = "# comment\n3 c  d.TXT\r\n\n1 a.txt\n";
+        assert_eq!(
+            parse_edited_names(&names, edited).expect("valid edit"),
+            [
+                Some("a.txt".to_string()),
+                None,
+                Some("c  d.TXT".to_string())
+            ]
+        );
+        assert!(parse_edited_names(&names, "1 a\n1 b\n").is_err());
+        assert!(parse_edited_names(&names, "4 d\n").is_err());
+        assert!(parse_edited_names(&names, "b.txt\n").is_err());
+    }
+}
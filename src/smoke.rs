@@ -16,9 +16,9 @@ use crate::find::{is_fd_available, spawn_fd_search};
 use crate::fs::FsAdapter;
 use crate::jobs::WorkerPool;
 use crate::model::{
-    AppState, ArchiveConnectionInfo, BackendSpec, Event, FindKind, FindRequest, FindUpdate,
-    JobKind, JobRequest, JobStatus, PanelId, PanelState, SftpAuth, SftpConnectionInfo, SortMode,
-    ViewerMode,
+    AppState, ArchiveConnectionInfo, BackendSpec, CopyOptions, Event, FindKind, FindRequest,
+    FindUpdate, JobControl, JobKind, JobRequest, JobStatus, PanelId, PanelState, SftpAuth,
+    SftpConnectionInfo, SortMode, ViewerMode,
 };
 use crate::viewer::{
     jump_to_next_match, load_viewer_state, load_viewer_state_from_preview, refresh_viewer_search,
@@ -41,6 +41,7 @@ pub struct SmokeReport {
     pub viewer_binary_mode_ok: bool,
     pub viewer_scroll_probe_ok: bool,
     pub conflict_matrix_ok: bool,
+    pub panel_tabs_ok: bool,
     pub archive_vfs_browse_ok: bool,
     pub archive_vfs_copy_out_ok: bool,
     pub fd_find_enabled: bool,
@@ -73,6 +74,7 @@ impl SmokeReport {
                 "viewer_binary_mode_ok: {}\n",
                 "viewer_scroll_probe_ok: {}\n",
                 "conflict_matrix_ok: {}\n",
+                "panel_tabs_ok: {}\n",
                 "archive_vfs_browse_ok: {}\n",
                 "archive_vfs_copy_out_ok: {}\n",
                 "fd_find_enabled: {}\n",
@@ -99,6 +101,7 @@ impl SmokeReport {
             self.viewer_binary_mode_ok,
             self.viewer_scroll_probe_ok,
             self.conflict_matrix_ok,
+            self.panel_tabs_ok,
             self.archive_vfs_browse_ok,
             self.archive_vfs_copy_out_ok,
             self.fd_find_enabled,
@@ -158,6 +161,9 @@ fn run_smoke_inner(temp_root: &Path) -> Result<SmokeReport> {
         destination_backend: Some(BackendSpec::Local),
         source: workload.copy_source.clone(),
         destination: Some(workload.copy_destination_dir.clone()),
+        attributes: None,
+        copy_options: CopyOptions::default(),
+        control: JobControl::default(),
     };
 
     let copy_start = Instant::now();
@@ -174,7 +180,7 @@ fn run_smoke_inner(temp_root: &Path) -> Result<SmokeReport> {
         match event_rx.recv_timeout(Duration::from_millis(2)) {
             Ok(Event::Job(update)) => match update.status {
                 JobStatus::Done => copy_done = true,
-                JobStatus::Failed => {
+                JobStatus::Failed | JobStatus::Canceled => {
                     bail!(
                         "copy job failed: {}",
                         update
@@ -182,7 +188,7 @@ fn run_smoke_inner(temp_root: &Path) -> Result<SmokeReport> {
                             .unwrap_or_else(|| "unknown failure".to_string())
                     );
                 }
-                JobStatus::Queued | JobStatus::Running => {}
+                JobStatus::Queued | JobStatus::Running | JobStatus::Paused => {}
             },
             Ok(_) => {}
             Err(crossbeam_channel::RecvTimeoutError::Timeout) => {}
@@ -223,6 +229,9 @@ fn run_smoke_inner(temp_root: &Path) -> Result<SmokeReport> {
             destination_backend: Some(BackendSpec::Local),
             source: source.clone(),
             destination: Some(destination),
+            attributes: None,
+            copy_options: CopyOptions::default(),
+            control: JobControl::default(),
         })?;
         batch_copy_job_ids.push(id);
     }
@@ -262,6 +271,9 @@ fn run_smoke_inner(temp_root: &Path) -> Result<SmokeReport> {
             destination_backend: Some(BackendSpec::Local),
             source: source_path,
             destination: Some(destination),
+            attributes: None,
+            copy_options: CopyOptions::default(),
+            control: JobControl::default(),
         })?;
         batch_move_job_ids.push(id);
     }
@@ -304,6 +316,9 @@ fn run_smoke_inner(temp_root: &Path) -> Result<SmokeReport> {
             destination_backend: None,
             source: target.clone(),
             destination: None,
+            attributes: None,
+            copy_options: CopyOptions::default(),
+            control: JobControl::default(),
         })?;
         batch_delete_job_ids.push(id);
     }
@@ -349,6 +364,7 @@ fn run_smoke_inner(temp_root: &Path) -> Result<SmokeReport> {
     }
 
     let conflict_matrix_ok = run_conflict_matrix_probe(temp_root)?;
+    let panel_tabs_ok = run_panel_tabs_probe(temp_root)?;
     let (archive_vfs_browse_ok, archive_vfs_copy_out_ok) = run_archive_vfs_probe(temp_root)?;
     let (fd_find_enabled, fd_find_ok) = run_fd_find_probe(temp_root)?;
     let viewer_search_hex_ok = run_viewer_search_hex_probe()?;
@@ -376,6 +392,7 @@ fn run_smoke_inner(temp_root: &Path) -> Result<SmokeReport> {
         viewer_binary_mode_ok,
         viewer_scroll_probe_ok,
         conflict_matrix_ok,
+        panel_tabs_ok,
         archive_vfs_browse_ok,
         archive_vfs_copy_out_ok,
         fd_find_enabled,
@@ -532,13 +549,16 @@ fn run_conflict_matrix_probe(temp_root: &Path) -> Result<bool> {
 
     let (event_tx, event_rx) = unbounded();
     let mut app = App::bootstrap(conflict_root.clone(), event_tx)?;
+    wait_for_listings(&mut app, &event_rx, "conflict-probe")?;
 
     move_active_selection_to(&mut app, "left_src")?;
     press_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+    wait_for_listings(&mut app, &event_rx, "conflict-probe")?;
 
     press_key(&mut app, KeyCode::Tab, KeyModifiers::NONE);
     move_active_selection_to(&mut app, "right_dst")?;
     press_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+    wait_for_listings(&mut app, &event_rx, "conflict-probe")?;
 
     press_key(&mut app, KeyCode::Tab, KeyModifiers::NONE);
     move_active_selection_to(&mut app, "alpha.txt")?;
@@ -553,10 +573,10 @@ fn run_conflict_matrix_probe(temp_root: &Path) -> Result<bool> {
         .as_ref()
         .map(|dialog| dialog.title.as_str())
         .unwrap_or_default();
-    if confirm_title != "Confirm" {
-        bail!("conflict probe expected confirm dialog, got '{confirm_title}'");
+    if confirm_title != "Copy" {
+        bail!("conflict probe expected copy options dialog, got '{confirm_title}'");
     }
-    press_key(&mut app, KeyCode::Char('y'), KeyModifiers::ALT);
+    press_key(&mut app, KeyCode::Char('a'), KeyModifiers::ALT);
 
     let mut saw_conflict = false;
     let mut used_rename = false;
@@ -623,6 +643,162 @@ fn run_conflict_matrix_probe(temp_root: &Path) -> Result<bool> {
     Ok(true)
 }
 
+fn run_panel_tabs_probe(temp_root: &Path) -> Result<bool> {
+    let tabs_root = temp_root.join("step34_tabs");
+    let left_dir = tabs_root.join("left_src");
+    let archive_dir = tabs_root.join("left_archive");
+    let right_one = tabs_root.join("right_one");
+    let right_two = tabs_root.join("right_two");
+    fs::create_dir_all(&archive_dir)?;
+    fs::create_dir_all(&right_one)?;
+    fs::create_dir_all(&right_two)?;
+    create_text_file(&left_dir.join("local.txt"), "local-tab\n")?;
+    let archive_payload = "archive-tab\n";
+    create_tar_archive_with_member(
+        &archive_dir.join("bundle.tar"),
+        "docs/readme.txt",
+        archive_payload.as_bytes(),
+    )?;
+
+    let (event_tx, event_rx) = unbounded();
+    let mut app = App::bootstrap(tabs_root.clone(), event_tx)?;
+    wait_for_listings(&mut app, &event_rx, "tabs-probe")?;
+
+    // Right side: tab 1 in right_one, tab 2 in right_two, tab 3 in the archive.
+    press_key(&mut app, KeyCode::Tab, KeyModifiers::NONE);
+    open_entry(&mut app, &event_rx, "right_one")?;
+    press_key(&mut app, KeyCode::Char('t'), KeyModifiers::CONTROL);
+    wait_for_listings(&mut app, &event_rx, "tabs-probe")?;
+    press_key(&mut app, KeyCode::Backspace, KeyModifiers::NONE);
+    wait_for_listings(&mut app, &event_rx, "tabs-probe")?;
+    open_entry(&mut app, &event_rx, "right_two")?;
+    press_key(&mut app, KeyCode::Char('t'), KeyModifiers::CONTROL);
+    wait_for_listings(&mut app, &event_rx, "tabs-probe")?;
+    press_key(&mut app, KeyCode::Backspace, KeyModifiers::NONE);
+    wait_for_listings(&mut app, &event_rx, "tabs-probe")?;
+    open_entry(&mut app, &event_rx, "left_archive")?;
+    open_entry(&mut app, &event_rx, "bundle.tar")?;
+    expect_shown_tab(&app, PanelId::Right, (2, 3), "archive:bundle.tar", "/")?;
+
+    press_key(&mut app, KeyCode::Char('1'), KeyModifiers::ALT);
+    wait_for_listings(&mut app, &event_rx, "tabs-probe")?;
+    expect_shown_tab(&app, PanelId::Right, (0, 3), "local", &right_one)?;
+    press_key(&mut app, KeyCode::Char(','), KeyModifiers::ALT);
+    wait_for_listings(&mut app, &event_rx, "tabs-probe")?;
+    expect_shown_tab(&app, PanelId::Right, (2, 3), "archive:bundle.tar", "/")?;
+    if !active_panel_state(app.state())
+        .entries
+        .iter()
+        .any(|entry| entry.name == "docs")
+    {
+        bail!("tabs probe expected the archive tab to list the archive");
+    }
+
+    // With the archive tab shown on the right, F5 from the left has nowhere
+    // to copy to.
+    press_key(&mut app, KeyCode::Tab, KeyModifiers::NONE);
+    open_entry(&mut app, &event_rx, "left_src")?;
+    move_active_selection_to(&mut app, "local.txt")?;
+    press_key(&mut app, KeyCode::F(5), KeyModifiers::NONE);
+    let dialog_title = app
+        .state()
+        .dialog
+        .as_ref()
+        .map(|dialog| dialog.title.clone())
+        .unwrap_or_default();
+    if dialog_title != "Error" {
+        bail!("tabs probe expected F5 into the archive tab to be refused, got '{dialog_title}'");
+    }
+    press_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+
+    // Switching the right side back to right_two retargets F5 there.
+    press_key(&mut app, KeyCode::Tab, KeyModifiers::NONE);
+    press_key(&mut app, KeyCode::Char('2'), KeyModifiers::ALT);
+    wait_for_listings(&mut app, &event_rx, "tabs-probe")?;
+    expect_shown_tab(&app, PanelId::Right, (1, 3), "local", &right_two)?;
+    press_key(&mut app, KeyCode::Tab, KeyModifiers::NONE);
+    copy_selected_entry(&mut app, &event_rx, "local.txt")?;
+
+    // A second left tab on the archive copies out of the archive.
+    press_key(&mut app, KeyCode::Char('t'), KeyModifiers::CONTROL);
+    wait_for_listings(&mut app, &event_rx, "tabs-probe")?;
+    press_key(&mut app, KeyCode::Backspace, KeyModifiers::NONE);
+    wait_for_listings(&mut app, &event_rx, "tabs-probe")?;
+    open_entry(&mut app, &event_rx, "left_archive")?;
+    open_entry(&mut app, &event_rx, "bundle.tar")?;
+    open_entry(&mut app, &event_rx, "docs")?;
+    expect_shown_tab(&app, PanelId::Left, (1, 2), "archive:bundle.tar", "/docs")?;
+    copy_selected_entry(&mut app, &event_rx, "readme.txt")?;
+
+    if fs::read_to_string(right_two.join("local.txt"))? != "local-tab\n"
+        || fs::read_to_string(right_two.join("readme.txt"))? != archive_payload
+        || fs::read_dir(&right_one)?.next().is_some()
+    {
+        bail!("tabs probe expected both copies in right_two only");
+    }
+
+    // Closing a tab shows its neighbour; the last tab of a side stays.
+    press_key(&mut app, KeyCode::Char('w'), KeyModifiers::CONTROL);
+    wait_for_listings(&mut app, &event_rx, "tabs-probe")?;
+    expect_shown_tab(&app, PanelId::Left, (0, 1), "local", &left_dir)?;
+    press_key(&mut app, KeyCode::Char('w'), KeyModifiers::CONTROL);
+    expect_shown_tab(&app, PanelId::Left, (0, 1), "local", &left_dir)?;
+
+    press_key(&mut app, KeyCode::Tab, KeyModifiers::NONE);
+    press_key(&mut app, KeyCode::Char('.'), KeyModifiers::ALT);
+    wait_for_listings(&mut app, &event_rx, "tabs-probe")?;
+    press_key(&mut app, KeyCode::Char('w'), KeyModifiers::CONTROL);
+    wait_for_listings(&mut app, &event_rx, "tabs-probe")?;
+    expect_shown_tab(&app, PanelId::Right, (1, 2), "local", &right_two)?;
+    press_key(&mut app, KeyCode::Char('1'), KeyModifiers::ALT);
+    wait_for_listings(&mut app, &event_rx, "tabs-probe")?;
+    expect_shown_tab(&app, PanelId::Right, (0, 2), "local", &right_one)?;
+
+    Ok(true)
+}
+
+/// Checks which tab is shown on `panel_id` as (position, count), and its
+/// backend label and directory.
+fn expect_shown_tab(
+    app: &App,
+    panel_id: PanelId,
+    (active, count): (usize, usize),
+    backend_label: &str,
+    cwd: impl AsRef<Path>,
+) -> Result<()> {
+    let state = app.state();
+    let (panel, tabs) = match panel_id {
+        PanelId::Left => (&state.left_panel, &state.left_tabs),
+        PanelId::Right => (&state.right_panel, &state.right_tabs),
+    };
+    let shown = (tabs.active, tabs.count());
+    if shown != (active, count) {
+        bail!("tabs probe expected {panel_id:?} tab {active}/{count}, got {shown:?}");
+    }
+    if panel.backend_label != backend_label || panel.cwd != cwd.as_ref() {
+        bail!(
+            "tabs probe expected {panel_id:?} on {backend_label} {}, got {} {}",
+            cwd.as_ref().display(),
+            panel.backend_label,
+            panel.cwd.display()
+        );
+    }
+    Ok(())
+}
+
+fn open_entry(app: &mut App, event_rx: &Receiver<Event>, entry_name: &str) -> Result<()> {
+    move_active_selection_to(app, entry_name)?;
+    press_key(app, KeyCode::Enter, KeyModifiers::NONE);
+    wait_for_listings(app, event_rx, "tabs-probe")
+}
+
+fn copy_selected_entry(app: &mut App, event_rx: &Receiver<Event>, entry_name: &str) -> Result<()> {
+    move_active_selection_to(app, entry_name)?;
+    press_key(app, KeyCode::F(5), KeyModifiers::NONE);
+    press_key(app, KeyCode::Char('a'), KeyModifiers::ALT);
+    wait_for_app_jobs(app, event_rx, Duration::from_secs(20), "tabs-probe")
+}
+
 fn run_archive_vfs_probe(temp_root: &Path) -> Result<(bool, bool)> {
     let archive_root = temp_root.join("step34_archive");
     let archive_path = archive_root.join("bundle.tar");
@@ -658,6 +834,9 @@ fn run_archive_vfs_probe(temp_root: &Path) -> Result<(bool, bool)> {
         destination_backend: Some(BackendSpec::Local),
         source: PathBuf::from("/docs/readme.txt"),
         destination: Some(copy_out_target.clone()),
+        attributes: None,
+        copy_options: CopyOptions::default(),
+        control: JobControl::default(),
     })?;
     wait_for_terminal_updates(
         &event_rx,
@@ -879,11 +1058,7 @@ fn wait_for_app_jobs(
 ) -> Result<()> {
     let started = Instant::now();
     loop {
-        let has_active = app
-            .state()
-            .jobs
-            .iter()
-            .any(|job| matches!(job.status, JobStatus::Queued | JobStatus::Running));
+        let has_active = app.state().jobs.iter().any(|job| !job.status.is_terminal());
         if !has_active {
             return Ok(());
         }
@@ -901,6 +1076,24 @@ fn wait_for_app_jobs(
     }
 }
 
+/// Feeds events to the app until neither panel is waiting for a listing.
+fn wait_for_listings(app: &mut App, event_rx: &Receiver<Event>, scope: &str) -> Result<()> {
+    let started = Instant::now();
+    while app.state().left_panel.loading || app.state().right_panel.loading {
+        if started.elapsed() > Duration::from_secs(20) {
+            bail!("{scope} timed out while waiting for panel listings");
+        }
+        match event_rx.recv_timeout(Duration::from_millis(25)) {
+            Ok(event) => {
+                let _ = app.on_event(event);
+            }
+            Err(crossbeam_channel::RecvTimeoutError::Timeout) => {}
+            Err(err) => bail!("{scope} failed receiving app event: {err}"),
+        }
+    }
+    Ok(())
+}
+
 fn make_temp_root() -> PathBuf {
     let ts = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -1009,6 +1202,7 @@ fn sftp_smoke_connection_from_env() -> Option<SftpConnectionInfo> {
         port,
         root_path,
         auth,
+        proxy_jump: env::var("VCMC_SFTP_SMOKE_PROXY_JUMP").ok(),
     })
 }
 
@@ -1036,13 +1230,13 @@ fn wait_for_terminal_updates(
                 if !expected.contains(&update.id) {
                     continue;
                 }
-                if !matches!(update.status, JobStatus::Done | JobStatus::Failed) {
+                if !update.status.is_terminal() {
                     continue;
                 }
                 if !completed.insert(update.id) {
                     continue;
                 }
-                if update.status == JobStatus::Failed {
+                if update.status != JobStatus::Done {
                     bail!(
                         "{scope} failed for job {}: {}",
                         update.id,
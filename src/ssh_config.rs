@@ -0,0 +1,440 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::model::wildcard_match;
+
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// Settings resolved for one host alias from OpenSSH client config.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SshHostConfig {
+    pub host_name: Option<String>,
+    pub user: Option<String>,
+    pub port: Option<u16>,
+    pub identity_files: Vec<PathBuf>,
+    pub proxy_jump: Option<String>,
+}
+
+impl SshHostConfig {
+    /// First configured identity file that exists on disk.
+    pub fn existing_identity_file(&self) -> Option<PathBuf> {
+        self.identity_files
+            .iter()
+            .find(|path| path.is_file())
+            .cloned()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SshConfig {
+    blocks: Vec<HostBlock>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct HostBlock {
+    patterns: Option<Vec<String>>,
+    options: Vec<(String, String)>,
+}
+
+impl HostBlock {
+    fn matches(&self, alias: &str) -> bool {
+        let Some(patterns) = self.patterns.as_ref() else {
+            return true;
+        };
+        let mut matched = false;
+        for pattern in patterns {
+            if let Some(negated) = pattern.strip_prefix('!') {
+                if wildcard_match(negated, alias) {
+                    return false;
+                }
+            } else if wildcard_match(pattern, alias) {
+                matched = true;
+            }
+        }
+        matched
+    }
+}
+
+pub fn ssh_config_path() -> Option<PathBuf> {
+    home_dir().map(|home| home.join(".ssh").join("config"))
+}
+
+/// Loads `~/.ssh/config`; a missing or unreadable file yields an empty config.
+pub fn load_ssh_config() -> SshConfig {
+    let Some(path) = ssh_config_path() else {
+        return SshConfig::default();
+    };
+    SshConfig::load(path.as_path()).unwrap_or_default()
+}
+
+impl SshConfig {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+        Ok(Self::parse(content.as_str(), base_dir))
+    }
+
+    /// Parses config text. Relative `Include` paths are resolved against `base_dir`.
+    pub fn parse(content: &str, base_dir: &Path) -> Self {
+        let mut config = Self::default();
+        config.parse_lines(content, base_dir, None, 0);
+        config
+    }
+
+    pub fn resolve(&self, alias: &str) -> SshHostConfig {
+        let mut resolved = SshHostConfig::default();
+        for block in self.blocks.iter().filter(|block| block.matches(alias)) {
+            for (key, value) in &block.options {
+                match key.as_str() {
+                    "hostname" if resolved.host_name.is_none() => {
+                        resolved.host_name = Some(expand_host_tokens(value, alias));
+                    }
+                    "user" if resolved.user.is_none() => {
+                        resolved.user = Some(value.clone());
+                    }
+                    "port" if resolved.port.is_none() => {
+                        resolved.port = value.parse::<u16>().ok();
+                    }
+                    "identityfile" => {
+                        let path = expand_path(expand_host_tokens(value, alias).as_str());
+                        if !resolved.identity_files.contains(&path) {
+                            resolved.identity_files.push(path);
+                        }
+                    }
+                    "proxyjump" if resolved.proxy_jump.is_none() => {
+                        resolved.proxy_jump = Some(value.clone());
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if resolved
+            .proxy_jump
+            .as_deref()
+            .is_some_and(|value| value.eq_ignore_ascii_case("none"))
+        {
+            resolved.proxy_jump = None;
+        }
+        resolved
+    }
+
+    fn parse_lines(
+        &mut self,
+        content: &str,
+        base_dir: &Path,
+        patterns: Option<Vec<String>>,
+        depth: usize,
+    ) {
+        let mut current = patterns;
+        self.blocks.push(HostBlock {
+            patterns: current.clone(),
+            options: Vec::new(),
+        });
+
+        for raw_line in content.lines() {
+            let Some((key, args)) = split_config_line(raw_line) else {
+                continue;
+            };
+            match key.as_str() {
+                "host" => {
+                    current = Some(args);
+                    self.blocks.push(HostBlock {
+                        patterns: current.clone(),
+                        options: Vec::new(),
+                    });
+                }
+                "match" => {
+                    // Match criteria are not evaluated; options under them never apply.
+                    current = Some(vec!["!*".to_string()]);
+                    self.blocks.push(HostBlock {
+                        patterns: current.clone(),
+                        options: Vec::new(),
+                    });
+                }
+                "include" => {
+                    if depth >= MAX_INCLUDE_DEPTH {
+                        continue;
+                    }
+                    for pattern in &args {
+                        for path in expand_include(pattern.as_str(), base_dir) {
+                            if let Ok(included) = fs::read_to_string(&path) {
+                                self.parse_lines(
+                                    included.as_str(),
+                                    base_dir,
+                                    current.clone(),
+                                    depth + 1,
+                                );
+                            }
+                        }
+                    }
+                    self.blocks.push(HostBlock {
+                        patterns: current.clone(),
+                        options: Vec::new(),
+                    });
+                }
+                _ => {
+                    if let (Some(block), Some(value)) = (self.blocks.last_mut(), args.first()) {
+                        block.options.push((key, value.clone()));
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn split_config_line(raw_line: &str) -> Option<(String, Vec<String>)> {
+    let line = raw_line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let split_at = line.find(|ch: char| ch.is_whitespace() || ch == '=')?;
+    let key = line[..split_at].to_ascii_lowercase();
+    let rest = line[split_at..].trim_start();
+    let rest = rest.strip_prefix('=').unwrap_or(rest).trim_start();
+    let args = split_arguments(rest);
+    if args.is_empty() {
+        return None;
+    }
+    Some((key, args))
+}
+
+fn split_arguments(input: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut has_token = false;
+    for ch in input.chars() {
+        match ch {
+            '"' => {
+                in_quotes = !in_quotes;
+                has_token = true;
+            }
+            '#' if !in_quotes && !has_token => break,
+            ch if ch.is_whitespace() && !in_quotes => {
+                if has_token {
+                    args.push(std::mem::take(&mut current));
+                    has_token = false;
+                }
+            }
+            ch => {
+                current.push(ch);
+                has_token = true;
+            }
+        }
+    }
+    if has_token {
+        args.push(current);
+    }
+    args
+}
+
+fn expand_include(pattern: &str, base_dir: &Path) -> Vec<PathBuf> {
+    let expanded = expand_path(pattern);
+    let path = if expanded.is_absolute() {
+        expanded
+    } else {
+        base_dir.join(expanded)
+    };
+    let file_pattern = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if !file_pattern.contains(['*', '?']) {
+        return vec![path];
+    }
+
+    let Some(dir) = path.parent() else {
+        return Vec::new();
+    };
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut matches: Vec<PathBuf> = read_dir
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            wildcard_match(
+                file_pattern.as_str(),
+                entry.file_name().to_string_lossy().as_ref(),
+            )
+        })
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect();
+    matches.sort();
+    matches
+}
+
+fn expand_host_tokens(value: &str, alias: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '%' {
+            out.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('h') => out.push_str(alias),
+            Some('d') => {
+                if let Some(home) = home_dir() {
+                    out.push_str(home.to_string_lossy().as_ref());
+                }
+            }
+            Some('%') => out.push('%'),
+            Some(other) => {
+                out.push('%');
+                out.push(other);
+            }
+            None => out.push('%'),
+        }
+    }
+    out
+}
+
+fn expand_path(raw: &str) -> PathBuf {
+    let Some(home) = home_dir() else {
+        return PathBuf::from(raw);
+    };
+    if raw == "~" {
+        return home;
+    }
+    match raw.strip_prefix("~/") {
+        Some(rest) => home.join(rest),
+        None => PathBuf::from(raw),
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME").map(PathBuf::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use super::{SshConfig, split_arguments};
+    use crate::test_support::fixture_dir;
+
+    #[test]
+    fn resolve_alias_with_explicit_settings() {
+        let dir = fixture_dir("ssh-config-alias");
+        let config = SshConfig::parse(
+            r#"
+Host prod-db
+    HostName 10.0.0.15
+    User deploy
+    Port 2222
+    IdentityFile /keys/prod_ed25519
+    ProxyJump bastion
+
+Host *
+    User fallback
+    IdentityFile /keys/default
+"#,
+            dir.as_path(),
+        );
+        let resolved = config.resolve("prod-db");
+        assert_eq!(resolved.host_name.as_deref(), Some("10.0.0.15"));
+        assert_eq!(resolved.user.as_deref(), Some("deploy"));
+        assert_eq!(resolved.port, Some(2222));
+        assert_eq!(resolved.proxy_jump.as_deref(), Some("bastion"));
+        assert_eq!(
+            resolved.identity_files,
+            vec![
+                PathBuf::from("/keys/prod_ed25519"),
+                PathBuf::from("/keys/default")
+            ]
+        );
+
+        let other = config.resolve("unrelated");
+        assert_eq!(other.host_name, None);
+        assert_eq!(other.user.as_deref(), Some("fallback"));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn wildcard_and_negated_patterns_follow_first_match_wins() {
+        let dir = fixture_dir("ssh-config-wildcard");
+        let config = SshConfig::parse(
+            r#"
+Host *.prod !legacy.prod
+    HostName %h.example.net
+    Port=2200
+
+Host web?.prod legacy.prod
+    User "web user"
+    ProxyJump none
+"#,
+            dir.as_path(),
+        );
+        let web = config.resolve("web1.prod");
+        assert_eq!(web.host_name.as_deref(), Some("web1.prod.example.net"));
+        assert_eq!(web.port, Some(2200));
+        assert_eq!(web.user.as_deref(), Some("web user"));
+        assert_eq!(web.proxy_jump, None);
+
+        let legacy = config.resolve("legacy.prod");
+        assert_eq!(legacy.host_name, None);
+        assert_eq!(legacy.port, None);
+        assert_eq!(legacy.user.as_deref(), Some("web user"));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn include_reads_glob_matched_files_relative_to_config_dir() {
+        let dir = fixture_dir("ssh-config-include");
+        fs::create_dir_all(dir.join("config.d")).expect("config.d created");
+        fs::write(
+            dir.join("config.d").join("10-jump.conf"),
+            "Host bastion\n    HostName jump.example.com\n    User ops\n",
+        )
+        .expect("fixture written");
+        fs::write(
+            dir.join("config.d").join("20-db.conf"),
+            "Host prod-db\n    HostName db.internal\n    ProxyJump bastion\n",
+        )
+        .expect("fixture written");
+        fs::write(
+            dir.join("config"),
+            "Include config.d/*.conf\n\nHost prod-db\n    User dba\n    HostName ignored\n",
+        )
+        .expect("fixture written");
+
+        let config = SshConfig::load(dir.join("config").as_path()).expect("config loaded");
+        let bastion = config.resolve("bastion");
+        assert_eq!(bastion.host_name.as_deref(), Some("jump.example.com"));
+        assert_eq!(bastion.user.as_deref(), Some("ops"));
+
+        let db = config.resolve("prod-db");
+        assert_eq!(db.host_name.as_deref(), Some("db.internal"));
+        assert_eq!(db.proxy_jump.as_deref(), Some("bastion"));
+        assert_eq!(db.user.as_deref(), Some("dba"));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn include_inside_host_block_keeps_outer_condition() {
+        let dir = fixture_dir("ssh-config-nested");
+        fs::write(dir.join("extra.conf"), "Port 2022\n").expect("fixture written");
+        let config = SshConfig::parse(
+            "Host staging\n    Include extra.conf\n    User stage\nMatch host staging\n    User never\n",
+            dir.as_path(),
+        );
+        let staging = config.resolve("staging");
+        assert_eq!(staging.port, Some(2022));
+        assert_eq!(staging.user.as_deref(), Some("stage"));
+        assert_eq!(config.resolve("other").port, None);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn split_arguments_handles_quotes_and_comments() {
+        assert_eq!(
+            split_arguments(r#"a "b c"  d # trailing"#),
+            vec!["a".to_string(), "b c".to_string(), "d".to_string()]
+        );
+    }
+}
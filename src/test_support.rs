@@ -0,0 +1,17 @@
+//! Helpers shared by the unit tests of several modules.
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Creates a fresh, uniquely named directory under the system temp dir.
+/// Tests remove it themselves when they are done.
+pub fn fixture_dir(name: &str) -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos())
+        .unwrap_or(0);
+    let dir = std::env::temp_dir().join(format!("vcmc-{name}-{nanos}"));
+    fs::create_dir_all(&dir).expect("fixture dir created");
+    dir
+}
@@ -37,6 +37,10 @@ pub struct ThemeStyle {
 pub struct DirColorsTheme {
     pub dir: ThemeStyle,
     pub link: ThemeStyle,
+    /// Symlink whose target is missing (`or`).
+    pub orphan: ThemeStyle,
+    /// The missing target shown after an orphan's name (`mi`).
+    pub missing: ThemeStyle,
     pub exec: Option<ThemeStyle>,
     pub reset: ThemeStyle,
     pub exts: HashMap<String, ThemeStyle>,
@@ -53,6 +57,14 @@ impl DirColorsTheme {
                 fg: Some(ThemeColor::Magenta),
                 bold: false,
             },
+            orphan: ThemeStyle {
+                fg: Some(ThemeColor::Red),
+                bold: true,
+            },
+            missing: ThemeStyle {
+                fg: Some(ThemeColor::Red),
+                bold: false,
+            },
             exec: None,
             reset: ThemeStyle::default(),
             exts: HashMap::new(),
@@ -66,6 +78,9 @@ impl DirColorsTheme {
                 bold: true,
             };
         }
+        if entry.link_broken {
+            return self.orphan;
+        }
 
         if let Some(ext) = extension_key(entry) {
             if let Some(style) = self.exts.get(ext.as_str()) {
@@ -81,6 +96,15 @@ impl DirColorsTheme {
         }
     }
 
+    /// Style of the `-> target` part shown after a symlink's name.
+    pub fn style_for_link_target(&self, entry: &FsEntry) -> ThemeStyle {
+        if entry.link_broken {
+            self.missing
+        } else {
+            ThemeStyle::default()
+        }
+    }
+
     fn apply_dircolors_text(&mut self, content: &str) {
         for raw_line in content.lines() {
             let line = raw_line.split('#').next().unwrap_or("").trim();
@@ -120,6 +144,8 @@ impl DirColorsTheme {
         match upper.as_str() {
             "DIR" | "DI" => self.dir = style,
             "LINK" | "LN" => self.link = style,
+            "ORPHAN" | "OR" => self.orphan = style,
+            "MISSING" | "MI" => self.missing = style,
             "EXEC" | "EX" => self.exec = Some(style),
             "RESET" | "RS" => self.reset = style,
             _ => {
@@ -302,7 +328,10 @@ fn map_sgr_color(code: u16) -> Option<ThemeColor> {
 
 #[cfg(test)]
 mod tests {
+    use std::path::PathBuf;
+
     use super::{DirColorsTheme, ThemeColor, normalize_extension_key, parse_style_codes};
+    use crate::model::{FsEntry, FsEntryType};
 
     #[test]
     fn parse_dircolors_tokens_and_extensions() {
@@ -311,6 +340,7 @@ mod tests {
             r#"
 DIR 01;34
 LINK 01;36
+ORPHAN 01;31
 EXEC 01;32
 *.rs 00;33
 RESET 0
@@ -318,6 +348,8 @@ RESET 0
         );
         assert_eq!(theme.dir.fg, Some(ThemeColor::Blue));
         assert_eq!(theme.link.fg, Some(ThemeColor::Cyan));
+        assert_eq!(theme.orphan.fg, Some(ThemeColor::Red));
+        assert!(theme.orphan.bold);
         assert_eq!(theme.exec.and_then(|s| s.fg), Some(ThemeColor::Green));
         assert_eq!(
             theme.exts.get("*.rs").and_then(|s| s.fg),
@@ -325,6 +357,33 @@ RESET 0
         );
     }
 
+    #[test]
+    fn broken_links_use_orphan_and_missing_styles() {
+        let mut theme = DirColorsTheme::fallback();
+        theme.apply_ls_colors("ln=01;36:or=05;37:mi=00;33:*.txt=00;32");
+        let mut entry = FsEntry {
+            name: "notes.txt".to_string(),
+            path: PathBuf::from("/tmp/notes.txt"),
+            entry_type: FsEntryType::Symlink,
+            size_bytes: 0,
+            modified_at: None,
+            is_executable: false,
+            is_hidden: false,
+            is_virtual: false,
+            link_target: Some(PathBuf::from("gone.txt")),
+            link_broken: true,
+        };
+        assert_eq!(theme.style_for_entry(&entry).fg, Some(ThemeColor::White));
+        assert_eq!(
+            theme.style_for_link_target(&entry).fg,
+            Some(ThemeColor::Yellow)
+        );
+
+        entry.link_broken = false;
+        assert_eq!(theme.style_for_entry(&entry).fg, Some(ThemeColor::Green));
+        assert_eq!(theme.style_for_link_target(&entry).fg, None);
+    }
+
     #[test]
     fn parse_style_codes_handles_bold_and_color() {
         let style = parse_style_codes("01;31").expect("style parsed");
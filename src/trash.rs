@@ -0,0 +1,559 @@
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::os::unix::ffi::OsStringExt;
+use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use chrono::{Local, NaiveDateTime, TimeZone};
+
+use crate::errors::{AppError, AppResult};
+use crate::model::TrashItem;
+
+const TRASH_INFO_EXTENSION: &str = ".trashinfo";
+const TRASH_INFO_HEADER: &str = "[Trash Info]";
+const TRASH_DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+const STICKY_BIT: u32 = 0o1000;
+const MOUNTS_PATH: &str = "/proc/self/mounts";
+
+/// One trash directory as described by the freedesktop.org Trash spec: the
+/// home trash stores absolute original paths, per-mount trashes store them
+/// relative to the mount's top directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrashDir {
+    pub root: PathBuf,
+    pub topdir: Option<PathBuf>,
+}
+
+impl TrashDir {
+    pub fn files_dir(&self) -> PathBuf {
+        self.root.join("files")
+    }
+
+    pub fn info_dir(&self) -> PathBuf {
+        self.root.join("info")
+    }
+
+    fn ensure_layout(&self) -> AppResult<()> {
+        for dir in [self.root.clone(), self.files_dir(), self.info_dir()] {
+            if dir.is_dir() {
+                continue;
+            }
+            fs::DirBuilder::new()
+                .recursive(true)
+                .mode(0o700)
+                .create(&dir)
+                .map_err(|err| AppError::from_io("trash", dir.clone(), err))?;
+        }
+        Ok(())
+    }
+
+    /// Path as written to `Path=`; per-mount trashes prefer paths relative to
+    /// their top directory so the mount can move.
+    fn info_path_for(&self, original: &Path) -> PathBuf {
+        self.topdir
+            .as_ref()
+            .and_then(|topdir| original.strip_prefix(topdir).ok())
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| original.to_path_buf())
+    }
+}
+
+/// `$XDG_DATA_HOME/Trash`, falling back to `~/.local/share/Trash`.
+pub fn home_trash_dir() -> Option<TrashDir> {
+    let data_home = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| {
+            env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share"))
+        })?;
+    Some(TrashDir {
+        root: data_home.join("Trash"),
+        topdir: None,
+    })
+}
+
+/// Home trash first, then every per-mount trash of the current user that
+/// already exists.
+pub fn trash_dirs() -> Vec<TrashDir> {
+    let mut dirs = Vec::new();
+    if let Some(home) = home_trash_dir() {
+        dirs.push(home);
+    }
+    let uid = unsafe { libc::getuid() };
+    let mounts = fs::read_to_string(MOUNTS_PATH).unwrap_or_default();
+    for topdir in mounts.lines().filter_map(mount_point_from_line) {
+        for root in [
+            topdir.join(".Trash").join(uid.to_string()),
+            topdir.join(format!(".Trash-{uid}")),
+        ] {
+            let is_dir = fs::symlink_metadata(&root).is_ok_and(|metadata| metadata.is_dir());
+            if is_dir && !dirs.iter().any(|dir| dir.root == root) {
+                dirs.push(TrashDir {
+                    root,
+                    topdir: Some(topdir.clone()),
+                });
+            }
+        }
+    }
+    dirs
+}
+
+/// Lists trashed items from the `.trashinfo` files of every trash directory.
+/// Info files without a matching item (or unreadable ones) are skipped.
+pub fn list_trash() -> Vec<TrashItem> {
+    let mut items = Vec::new();
+    for dir in trash_dirs() {
+        let Ok(read_dir) = fs::read_dir(dir.info_dir()) else {
+            continue;
+        };
+        for info in read_dir.flatten() {
+            let info_name = info.file_name().to_string_lossy().to_string();
+            let Some(name) = info_name.strip_suffix(TRASH_INFO_EXTENSION) else {
+                continue;
+            };
+            let trashed_path = dir.files_dir().join(name);
+            if fs::symlink_metadata(&trashed_path).is_err() {
+                continue;
+            }
+            let Ok(contents) = fs::read_to_string(info.path()) else {
+                continue;
+            };
+            let Some((original, deleted_at)) = parse_trash_info(&contents) else {
+                continue;
+            };
+            let original_path = match dir.topdir.as_ref() {
+                Some(topdir) if original.is_relative() => topdir.join(original),
+                _ => original,
+            };
+            items.push(TrashItem {
+                trashed_path,
+                original_path,
+                deleted_at,
+            });
+        }
+    }
+    items
+}
+
+/// Location of the `.trashinfo` file that belongs to an item in `files/`.
+pub fn info_path_for_trashed(trashed: &Path) -> Option<PathBuf> {
+    let files_dir = trashed.parent()?;
+    let name = trashed.file_name()?.to_string_lossy();
+    Some(
+        files_dir
+            .parent()?
+            .join("info")
+            .join(format!("{name}{TRASH_INFO_EXTENSION}")),
+    )
+}
+
+/// Permanently removes a trashed item together with its `.trashinfo`.
+pub fn purge_trashed(trashed: &Path) -> AppResult<()> {
+    let metadata = fs::symlink_metadata(trashed)
+        .map_err(|err| AppError::from_io("purge", trashed.to_path_buf(), err))?;
+    let removed = if metadata.is_dir() {
+        fs::remove_dir_all(trashed)
+    } else {
+        fs::remove_file(trashed)
+    };
+    removed.map_err(|err| AppError::from_io("purge", trashed.to_path_buf(), err))?;
+    forget_trashed(trashed)
+}
+
+/// Drops the `.trashinfo` of an item that left the trash; a missing info file
+/// is not an error.
+pub fn forget_trashed(trashed: &Path) -> AppResult<()> {
+    let Some(info_path) = info_path_for_trashed(trashed) else {
+        return Ok(());
+    };
+    match fs::remove_file(&info_path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(AppError::from_io("purge", info_path, err)),
+    }
+}
+
+/// Recreates the parent directories of a restore target that were removed
+/// after the item went to the trash.
+pub fn prepare_restore_target(original: &Path) -> AppResult<()> {
+    let Some(parent) = original.parent().filter(|parent| !parent.exists()) else {
+        return Ok(());
+    };
+    fs::create_dir_all(parent)
+        .map_err(|err| AppError::from_io("restore", parent.to_path_buf(), err))
+}
+
+/// Moves a local path into the trash that lives on the same filesystem and
+/// returns where it ended up.
+pub fn move_to_trash(path: &Path) -> AppResult<PathBuf> {
+    let home =
+        home_trash_dir().ok_or_else(|| AppError::invalid_path("trash", path, "HOME is not set"))?;
+    move_to_trash_with_home(path, &home)
+}
+
+fn move_to_trash_with_home(path: &Path, home: &TrashDir) -> AppResult<PathBuf> {
+    let original = absolute_path(path)?;
+    let metadata = fs::symlink_metadata(&original)
+        .map_err(|err| AppError::from_io("trash", original.clone(), err))?;
+    if original.starts_with(&home.root) {
+        return Err(AppError::invalid_path(
+            "trash",
+            &original,
+            "item is already in the trash",
+        ));
+    }
+
+    let trash = trash_dir_for(&original, metadata.dev(), home)?;
+
+    let file_name = original
+        .file_name()
+        .ok_or_else(|| AppError::invalid_path("trash", &original, "path has no file name"))?
+        .to_string_lossy()
+        .to_string();
+    let info_contents = trash_info_contents(&trash.info_path_for(&original));
+
+    // The spec reserves a name by creating its .trashinfo exclusively first.
+    for attempt in 1usize.. {
+        let name = if attempt == 1 {
+            file_name.clone()
+        } else {
+            format!("{file_name}.{attempt}")
+        };
+        let info_path = trash
+            .info_dir()
+            .join(format!("{name}{TRASH_INFO_EXTENSION}"));
+        let target = trash.files_dir().join(&name);
+        if fs::symlink_metadata(&target).is_ok() {
+            continue;
+        }
+        let mut info = match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&info_path)
+        {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(AppError::from_io("trash", info_path, err)),
+        };
+        if let Err(err) = info.write_all(info_contents.as_bytes()) {
+            let _ = fs::remove_file(&info_path);
+            return Err(AppError::from_io("trash", info_path, err));
+        }
+        drop(info);
+
+        if let Err(err) = fs::rename(&original, &target) {
+            let _ = fs::remove_file(&info_path);
+            return Err(AppError::from_io("trash", original, err));
+        }
+        return Ok(target);
+    }
+    unreachable!("trash name attempts are unbounded")
+}
+
+/// Picks the home trash when the item shares its filesystem, otherwise the
+/// trash at the top of the item's mount, and creates it if needed.
+fn trash_dir_for(original: &Path, device: u64, home: &TrashDir) -> AppResult<TrashDir> {
+    let home_device = existing_ancestor(&home.root)
+        .and_then(|path| fs::metadata(path).ok())
+        .map(|metadata| metadata.dev());
+    if home_device == Some(device) {
+        home.ensure_layout()?;
+        return Ok(home.clone());
+    }
+
+    let topdir = mount_top_dir(original, device);
+    let uid = unsafe { libc::getuid() };
+    mount_trash_dir(&topdir, uid)
+}
+
+/// `$topdir/.Trash/$uid` when the admin made `.Trash` a sticky directory and
+/// the user's directory in it is usable; otherwise, as the spec requires,
+/// `$topdir/.Trash-$uid`.
+fn mount_trash_dir(topdir: &Path, uid: u32) -> AppResult<TrashDir> {
+    let is_symlink =
+        |path: &Path| fs::symlink_metadata(path).is_ok_and(|metadata| metadata.is_symlink());
+    let shared = topdir.join(".Trash");
+    let shared_is_valid = fs::symlink_metadata(&shared)
+        .is_ok_and(|metadata| metadata.is_dir() && metadata.permissions().mode() & STICKY_BIT != 0);
+    if shared_is_valid {
+        let trash = TrashDir {
+            root: shared.join(uid.to_string()),
+            topdir: Some(topdir.to_path_buf()),
+        };
+        if !is_symlink(&trash.root) && trash.ensure_layout().is_ok() {
+            return Ok(trash);
+        }
+    }
+
+    let trash = TrashDir {
+        root: topdir.join(format!(".Trash-{uid}")),
+        topdir: Some(topdir.to_path_buf()),
+    };
+    if is_symlink(&trash.root) {
+        return Err(AppError::invalid_path(
+            "trash",
+            trash.root,
+            "trash directory is a symlink",
+        ));
+    }
+    trash.ensure_layout()?;
+    Ok(trash)
+}
+
+fn mount_top_dir(path: &Path, device: u64) -> PathBuf {
+    let mut top = path.to_path_buf();
+    while let Some(parent) = top.parent() {
+        let same_device = fs::metadata(parent).is_ok_and(|metadata| metadata.dev() == device);
+        if !same_device {
+            break;
+        }
+        top = parent.to_path_buf();
+    }
+    if top == path {
+        path.parent().map(Path::to_path_buf).unwrap_or(top)
+    } else {
+        top
+    }
+}
+
+fn existing_ancestor(path: &Path) -> Option<&Path> {
+    path.ancestors().find(|ancestor| ancestor.exists())
+}
+
+fn absolute_path(path: &Path) -> AppResult<PathBuf> {
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        env::current_dir()
+            .map_err(|err| AppError::from_io("trash", path.to_path_buf(), err))?
+            .join(path)
+    };
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    Ok(normalized)
+}
+
+fn trash_info_contents(original: &Path) -> String {
+    format!(
+        "{TRASH_INFO_HEADER}\nPath={}\nDeletionDate={}\n",
+        percent_encode_path(original),
+        Local::now().format(TRASH_DATE_FORMAT)
+    )
+}
+
+fn parse_trash_info(contents: &str) -> Option<(PathBuf, Option<SystemTime>)> {
+    let mut lines = contents.lines();
+    if lines.next()?.trim() != TRASH_INFO_HEADER {
+        return None;
+    }
+    let mut original = None;
+    let mut deleted_at = None;
+    for line in lines {
+        if let Some(value) = line.strip_prefix("Path=") {
+            original = Some(percent_decode_path(value.trim()));
+        } else if let Some(value) = line.strip_prefix("DeletionDate=") {
+            deleted_at = parse_deletion_date(value.trim());
+        }
+    }
+    original.map(|original| (original, deleted_at))
+}
+
+fn parse_deletion_date(value: &str) -> Option<SystemTime> {
+    let naive = NaiveDateTime::parse_from_str(value, TRASH_DATE_FORMAT).ok()?;
+    let seconds = Local.from_local_datetime(&naive).earliest()?.timestamp();
+    u64::try_from(seconds)
+        .ok()
+        .map(|seconds| UNIX_EPOCH + Duration::from_secs(seconds))
+}
+
+fn mount_point_from_line(line: &str) -> Option<PathBuf> {
+    let field = line.split_whitespace().nth(1)?;
+    // Mount points escape blanks and backslashes as octal (`\040`).
+    let bytes = field.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        let octal = bytes
+            .get(idx + 1..idx + 4)
+            .filter(|_| bytes[idx] == b'\\')
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .and_then(|digits| u8::from_str_radix(digits, 8).ok());
+        if let Some(byte) = octal {
+            decoded.push(byte);
+            idx += 4;
+        } else {
+            decoded.push(bytes[idx]);
+            idx += 1;
+        }
+    }
+    Some(PathBuf::from(std::ffi::OsString::from_vec(decoded)))
+}
+
+fn percent_decode_path(value: &str) -> PathBuf {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        let escaped = bytes
+            .get(idx + 1..idx + 3)
+            .filter(|_| bytes[idx] == b'%')
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .and_then(|digits| u8::from_str_radix(digits, 16).ok());
+        if let Some(byte) = escaped {
+            decoded.push(byte);
+            idx += 3;
+        } else {
+            decoded.push(bytes[idx]);
+            idx += 1;
+        }
+    }
+    PathBuf::from(std::ffi::OsString::from_vec(decoded))
+}
+
+fn percent_encode_path(path: &Path) -> String {
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut encoded = String::new();
+    for byte in path.as_os_str().as_bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                encoded.push(*byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::fixture_dir;
+
+    #[test]
+    fn trashed_file_gets_info_and_unique_name() {
+        let root = fixture_dir("trash-move");
+        let home = TrashDir {
+            root: root.join("Trash"),
+            topdir: None,
+        };
+        let source = root.join("notes v1.txt");
+
+        fs::write(&source, b"first").expect("write source");
+        let first = move_to_trash_with_home(&source, &home).expect("trash first");
+        fs::write(&source, b"second").expect("write source again");
+        let second = move_to_trash_with_home(&source, &home).expect("trash second");
+
+        assert!(!source.exists());
+        assert_eq!(first, home.files_dir().join("notes v1.txt"));
+        assert_eq!(second, home.files_dir().join("notes v1.txt.2"));
+        assert_eq!(fs::read(&second).expect("read trashed"), b"second");
+
+        let info = fs::read_to_string(home.info_dir().join("notes v1.txt.trashinfo"))
+            .expect("read trashinfo");
+        let mut lines = info.lines();
+        assert_eq!(lines.next(), Some(TRASH_INFO_HEADER));
+        assert_eq!(
+            lines.next().map(str::to_string),
+            Some(format!(
+                "Path={}",
+                percent_encode_path(&root.join("notes v1.txt"))
+            ))
+        );
+        assert!(
+            lines
+                .next()
+                .is_some_and(|line| line.starts_with("DeletionDate="))
+        );
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn trash_info_round_trips_path_and_date() {
+        let original = Path::new("/srv/a b/%report.txt");
+        let (path, deleted_at) =
+            parse_trash_info(&trash_info_contents(original)).expect("parse trashinfo");
+        assert_eq!(path, original);
+        let age = SystemTime::now()
+            .duration_since(deleted_at.expect("deletion date"))
+            .expect("date in the past");
+        assert!(age < Duration::from_secs(5));
+        assert!(parse_trash_info("Path=/tmp/x\n").is_none());
+    }
+
+    #[test]
+    fn purge_removes_item_and_info() {
+        let root = fixture_dir("trash-purge");
+        let home = TrashDir {
+            root: root.join("Trash"),
+            topdir: None,
+        };
+        let source = root.join("old");
+        fs::create_dir_all(source.join("nested")).expect("create source");
+        let trashed = move_to_trash_with_home(&source, &home).expect("trash dir");
+        let info = info_path_for_trashed(&trashed).expect("info path");
+        assert!(info.is_file());
+
+        purge_trashed(&trashed).expect("purge");
+        assert!(!trashed.exists());
+        assert!(!info.exists());
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn mount_points_decode_octal_escapes() {
+        assert_eq!(
+            mount_point_from_line("/dev/sdb1 /media/usb\\040disk vfat rw 0 0"),
+            Some(PathBuf::from("/media/usb disk"))
+        );
+    }
+
+    #[test]
+    fn percent_encoding_keeps_path_separators() {
+        assert_eq!(
+            percent_encode_path(Path::new("/tmp/a b/%é.txt")),
+            "/tmp/a%20b/%25%C3%A9.txt"
+        );
+    }
+
+    #[test]
+    fn mount_trash_falls_back_to_the_per_user_directory() {
+        let topdir = fixture_dir("trash-mount");
+        let uid = 4242;
+        let fallback = topdir.join(".Trash-4242");
+
+        let trash = mount_trash_dir(&topdir, uid).expect("no .Trash");
+        assert_eq!(trash.root, fallback);
+        assert!(trash.files_dir().is_dir() && trash.info_dir().is_dir());
+        fs::remove_dir_all(&fallback).expect("reset fallback");
+
+        let shared = topdir.join(".Trash");
+        fs::create_dir(&shared).expect("shared created");
+        fs::set_permissions(&shared, fs::Permissions::from_mode(0o777)).expect("not sticky");
+        let trash = mount_trash_dir(&topdir, uid).expect(".Trash without sticky bit");
+        assert_eq!(trash.root, fallback);
+
+        fs::set_permissions(&shared, fs::Permissions::from_mode(0o1777)).expect("sticky");
+        std::os::unix::fs::symlink(&topdir, shared.join("4242")).expect("uid dir symlinked");
+        let trash = mount_trash_dir(&topdir, uid).expect("symlinked .Trash/$uid");
+        assert_eq!(trash.root, fallback);
+
+        fs::remove_file(shared.join("4242")).expect("symlink removed");
+        let trash = mount_trash_dir(&topdir, uid).expect("valid .Trash");
+        assert_eq!(trash.root, shared.join("4242"));
+        assert!(trash.files_dir().is_dir());
+        fs::remove_dir_all(topdir).ok();
+    }
+}
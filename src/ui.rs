@@ -7,13 +7,15 @@ use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 
 use crate::menu::top_menu_groups;
 use crate::model::{
-    AppState, BatchProgressState, CommandLineState, DialogButtonRole, DialogState, DialogTone,
-    FindKind, FindProgressState, FsEntry, FsEntryType, JobKind, PanelId, PanelState, ScreenMode,
-    SortMode, ViewerMode, ViewerState,
+    AppState, BatchProgressState, CommandLineState, DialogButtonRole, DialogFieldKind, DialogList,
+    DialogState, DialogTone, FindKind, FindProgressState, FsEntry, FsEntryType, Job, JobKind,
+    JobStatus, PERMISSION_GRID, PanelId, PanelState, PanelTabs, PropertiesField, PropertiesState,
+    ScreenMode, SortMode, TextInput, ViewerMode, ViewerState,
 };
 use crate::theme::{DirColorsTheme, ThemeColor, ThemeStyle};
 
 const COL_SEP: &str = "│";
+const SPINNER_FRAMES: [&str; 4] = ["|", "/", "-", "\\"];
 
 pub fn render(frame: &mut Frame, state: &AppState, theme: &DirColorsTheme) {
     let chunks = Layout::default()
@@ -30,11 +32,43 @@ pub fn render(frame: &mut Frame, state: &AppState, theme: &DirColorsTheme) {
 
     if state.screen_mode == ScreenMode::Viewer {
         render_viewer(frame, chunks[1], state.viewer.as_ref());
+    } else if state.screen_mode == ScreenMode::Jobs {
+        render_job_manager(frame, chunks[1], state);
     } else {
         let panel_chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
             .split(chunks[1]);
+        // Both sides get a strip as soon as either has tabs, so the panels
+        // stay the same height.
+        let show_tabs = state.left_tabs.count() > 1 || state.right_tabs.count() > 1;
+        let panel_chunks = if show_tabs {
+            let sides = [
+                (
+                    panel_chunks[0],
+                    &state.left_tabs,
+                    &state.left_panel,
+                    PanelId::Left,
+                ),
+                (
+                    panel_chunks[1],
+                    &state.right_tabs,
+                    &state.right_panel,
+                    PanelId::Right,
+                ),
+            ];
+            sides.map(|(area, tabs, panel, panel_id)| {
+                let rows = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Length(1), Constraint::Min(1)])
+                    .split(area);
+                let line = tab_strip_line(tabs, panel, state.active_panel == panel_id);
+                frame.render_widget(Paragraph::new(line), rows[0]);
+                rows[1]
+            })
+        } else {
+            [panel_chunks[0], panel_chunks[1]]
+        };
 
         render_panel(
             frame,
@@ -42,6 +76,7 @@ pub fn render(frame: &mut Frame, state: &AppState, theme: &DirColorsTheme) {
             "Left",
             &state.left_panel,
             state.active_panel == PanelId::Left,
+            state.spinner_frame,
             theme,
         );
         render_panel(
@@ -50,6 +85,7 @@ pub fn render(frame: &mut Frame, state: &AppState, theme: &DirColorsTheme) {
             "Right",
             &state.right_panel,
             state.active_panel == PanelId::Right,
+            state.spinner_frame,
             theme,
         );
     }
@@ -61,10 +97,19 @@ pub fn render(frame: &mut Frame, state: &AppState, theme: &DirColorsTheme) {
         render_top_menu_popup(frame, chunks[0], chunks[1], state);
     }
 
-    if let Some(progress) = state.batch_progress.as_ref() {
+    // The job manager already lists per-job progress.
+    if let Some(progress) = state
+        .batch_progress
+        .as_ref()
+        .filter(|_| state.screen_mode != ScreenMode::Jobs)
+    {
         render_batch_progress_overlay(frame, progress);
     }
 
+    if let Some(properties) = &state.properties {
+        render_properties(frame, properties);
+    }
+
     if let Some(dialog) = &state.dialog {
         render_dialog(frame, dialog);
     }
@@ -231,12 +276,176 @@ fn render_viewer(frame: &mut Frame, area: Rect, viewer: Option<&ViewerState>) {
     frame.render_widget(Paragraph::new(lines), inner);
 }
 
+fn render_job_manager(frame: &mut Frame, area: Rect, state: &AppState) {
+    let block = Block::default()
+        .title(format!("Jobs ({})", state.jobs.len()))
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        );
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    if inner.width == 0 || inner.height == 0 {
+        return;
+    }
+
+    let width = inner.width as usize;
+    let label_style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let mut lines = vec![Line::styled(
+        job_manager_row(
+            "#",
+            "Kind",
+            "Status",
+            "Progress",
+            "Source -> Destination",
+            width,
+        ),
+        label_style,
+    )];
+
+    if state.jobs.is_empty() {
+        lines.push(Line::styled(
+            "no jobs yet",
+            Style::default().fg(Color::DarkGray),
+        ));
+        frame.render_widget(Paragraph::new(lines), inner);
+        return;
+    }
+
+    // Header plus two detail lines for the selected job.
+    let visible_rows = (inner.height as usize).saturating_sub(3).max(1);
+    let selected = state.job_manager.selected.min(state.jobs.len() - 1);
+    let offset = selected.saturating_sub(visible_rows - 1);
+    for (idx, job) in state
+        .jobs
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible_rows)
+    {
+        let target = job
+            .destination
+            .as_ref()
+            .map(|destination| format!("{} -> {}", job.source.display(), destination.display()))
+            .unwrap_or_else(|| job.source.display().to_string());
+        let row = job_manager_row(
+            job.id.to_string().as_str(),
+            operation_label(job.kind),
+            job_status_label(job.status),
+            job_progress_label(job).as_str(),
+            target.as_str(),
+            width,
+        );
+        let style = if idx == selected {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(job_status_color(job.status))
+        };
+        lines.push(Line::styled(row, style));
+    }
+
+    let body_height = inner.height.saturating_sub(2);
+    frame.render_widget(
+        Paragraph::new(lines),
+        Rect {
+            height: body_height,
+            ..inner
+        },
+    );
+
+    let job = &state.jobs[selected];
+    let details = vec![
+        Line::from(vec![
+            Span::styled("Current: ", label_style),
+            Span::raw(truncate_name(
+                job.current_item.as_deref().unwrap_or("-"),
+                width.saturating_sub(9),
+            )),
+        ]),
+        Line::from(vec![
+            Span::styled("Message: ", label_style),
+            Span::raw(truncate_name(
+                job.message.as_deref().unwrap_or("-"),
+                width.saturating_sub(9),
+            )),
+        ]),
+    ];
+    frame.render_widget(
+        Paragraph::new(details),
+        Rect {
+            y: inner.y + body_height,
+            height: inner.height - body_height,
+            ..inner
+        },
+    );
+}
+
+fn job_manager_row(
+    id: &str,
+    kind: &str,
+    status: &str,
+    progress: &str,
+    target: &str,
+    width: usize,
+) -> String {
+    let row = format!("{id:>5} {kind:<6} {status:<8} {progress:<16} ");
+    let target_width = width.saturating_sub(row.chars().count());
+    format!("{row}{}", truncate_name(target, target_width))
+}
+
+fn job_status_label(status: JobStatus) -> &'static str {
+    match status {
+        JobStatus::Queued => "queued",
+        JobStatus::Running => "running",
+        JobStatus::Paused => "paused",
+        JobStatus::Done => "done",
+        JobStatus::Failed => "FAILED",
+        JobStatus::Canceled => "canceled",
+    }
+}
+
+fn job_status_color(status: JobStatus) -> Color {
+    match status {
+        JobStatus::Queued => Color::Gray,
+        JobStatus::Running => Color::LightGreen,
+        JobStatus::Paused => Color::Yellow,
+        JobStatus::Done => Color::DarkGray,
+        JobStatus::Failed => Color::LightRed,
+        JobStatus::Canceled => Color::Magenta,
+    }
+}
+
+fn job_progress_label(job: &Job) -> String {
+    match job.status {
+        JobStatus::Done => "100%".to_string(),
+        JobStatus::Queued | JobStatus::Failed | JobStatus::Canceled => "-".to_string(),
+        JobStatus::Running | JobStatus::Paused => {
+            if let Some(bytes) = job.bytes.filter(|bytes| bytes.total > 0) {
+                let percent = bytes.done.min(bytes.total) * 100 / bytes.total;
+                format!("{percent:>3}% {}/s", human_size(bytes.bytes_per_sec))
+            } else if let (Some(done), Some(total)) = (job.batch_completed, job.batch_total) {
+                format!("{done}/{total}")
+            } else {
+                "...".to_string()
+            }
+        }
+    }
+}
+
 fn render_panel(
     frame: &mut Frame,
     area: Rect,
     name: &str,
     panel: &PanelState,
     active: bool,
+    spinner_frame: usize,
     theme: &DirColorsTheme,
 ) {
     let border_style = if active {
@@ -248,12 +457,14 @@ fn render_panel(
     };
 
     let title = format!(
-        "{name} [{}] <{}> {}{}{}",
+        "{name} [{}] <{}> {}{}{}{}{}",
         sort_label(panel.sort_mode),
         panel.backend_label,
         panel.cwd.display(),
         find_suffix(panel),
+        trash_suffix(panel),
         search_suffix(panel),
+        loading_suffix(panel, spinner_frame),
     );
 
     let block = Block::default()
@@ -275,7 +486,12 @@ fn render_panel(
     }
 
     if panel.entries.is_empty() {
-        let content = Paragraph::new("Empty directory")
+        let placeholder = if panel.loading {
+            "Loading..."
+        } else {
+            "Empty directory"
+        };
+        let content = Paragraph::new(placeholder)
             .style(Style::default().fg(Color::DarkGray))
             .block(block);
         frame.render_widget(content, area);
@@ -316,7 +532,7 @@ fn build_entry_lines(
     let end = (start + rows_capacity).min(panel.entries.len());
 
     let mut lines = Vec::with_capacity(rows_capacity + 1);
-    lines.push(render_table_header(layout));
+    lines.push(render_table_header(layout, panel.trash_view.is_some()));
 
     for (offset, entry) in panel.entries[start..end].iter().enumerate() {
         let idx = start + offset;
@@ -338,7 +554,7 @@ fn build_entry_lines(
             Style::default()
         };
 
-        let name = entry_name(entry);
+        let name = panel_entry_name(panel, entry);
         let size_text = if entry.is_virtual {
             "-".to_string()
         } else {
@@ -353,14 +569,7 @@ fn build_entry_lines(
                 size_width,
                 modified_width,
             } => {
-                spans.push(Span::styled(
-                    format!(
-                        "{:<name_width$}",
-                        truncate_name(&name, name_width),
-                        name_width = name_width
-                    ),
-                    base_style.patch(type_style(entry, theme)),
-                ));
+                spans.extend(name_cell_spans(&name, entry, name_width, base_style, theme));
                 spans.push(Span::styled(COL_SEP, base_style));
                 spans.push(Span::styled(
                     format!(
@@ -384,14 +593,7 @@ fn build_entry_lines(
                 name_width,
                 size_width,
             } => {
-                spans.push(Span::styled(
-                    format!(
-                        "{:<name_width$}",
-                        truncate_name(&name, name_width),
-                        name_width = name_width
-                    ),
-                    base_style.patch(type_style(entry, theme)),
-                ));
+                spans.extend(name_cell_spans(&name, entry, name_width, base_style, theme));
                 spans.push(Span::styled(COL_SEP, base_style));
                 spans.push(Span::styled(
                     format!(
@@ -403,14 +605,7 @@ fn build_entry_lines(
                 ));
             }
             TableLayout::Minimal { name_width } => {
-                spans.push(Span::styled(
-                    format!(
-                        "{:<name_width$}",
-                        truncate_name(&name, name_width),
-                        name_width = name_width
-                    ),
-                    base_style.patch(type_style(entry, theme)),
-                ));
+                spans.extend(name_cell_spans(&name, entry, name_width, base_style, theme));
             }
         }
         lines.push(Line::from(spans));
@@ -479,6 +674,12 @@ fn footer_mode(state: &AppState, panel: &PanelState) -> FooterMode {
         FooterMode::Viewer
     } else if state.dialog.is_some() {
         FooterMode::Dialog
+    } else if state.properties.is_some() {
+        FooterMode::Properties
+    } else if state.screen_mode == ScreenMode::Jobs {
+        FooterMode::Jobs
+    } else if panel.trash_view.is_some() {
+        FooterMode::Trash
     } else if !panel.selected_paths.is_empty() {
         FooterMode::Selection
     } else {
@@ -492,6 +693,9 @@ fn footer_mode_label(mode: FooterMode) -> &'static str {
         FooterMode::Selection => "SELECTION",
         FooterMode::Dialog => "DIALOG",
         FooterMode::Viewer => "VIEWER",
+        FooterMode::Jobs => "JOBS",
+        FooterMode::Trash => "TRASH",
+        FooterMode::Properties => "PROPERTIES",
     }
 }
 
@@ -595,6 +799,55 @@ fn build_footer_buttons(
             FooterButtonSpec::new("Home", "Top", true, false),
             FooterButtonSpec::new("End", "Bottom", true, false),
         ],
+        FooterMode::Trash => {
+            let current_entry_operable = !panel.selected_paths.is_empty()
+                || panel
+                    .selected_entry()
+                    .is_some_and(|entry| !entry.is_virtual);
+            let current_entry_viewable = panel.selected_entry().is_some_and(|entry| {
+                !entry.is_virtual && entry.entry_type != FsEntryType::Directory
+            });
+            vec![
+                FooterButtonSpec::new("F2", "Sort", true, false),
+                FooterButtonSpec::new("F3", "View", current_entry_viewable, false),
+                FooterButtonSpec::new("F5", "Copy", current_entry_operable, false),
+                FooterButtonSpec::new("F6", "Restore", current_entry_operable, false),
+                FooterButtonSpec::new("F7", "Purge", true, false),
+                FooterButtonSpec::new("F8", "Delete", current_entry_operable, false),
+                FooterButtonSpec::new("F9", "Menu", true, false),
+                FooterButtonSpec::new("F10", "Quit", true, false),
+            ]
+        }
+        FooterMode::Properties => {
+            let field = state.properties.as_ref().map(|properties| properties.field);
+            let on_grid = field == Some(PropertiesField::Mode);
+            let can_toggle = on_grid || field == Some(PropertiesField::Recursive);
+            vec![
+                FooterButtonSpec::new("Tab", "Field", true, false),
+                FooterButtonSpec::new("Arrows", "Move", on_grid, false),
+                FooterButtonSpec::new("Space", "Toggle", can_toggle, false),
+                FooterButtonSpec::new("0-7", "Octal", on_grid, false),
+                FooterButtonSpec::new("Enter", "Apply", true, false),
+                FooterButtonSpec::new("Esc", "Cancel", true, false),
+            ]
+        }
+        FooterMode::Jobs => {
+            let selected = state.jobs.get(state.job_manager.selected);
+            let cancelable = selected.is_some_and(|job| !job.status.is_terminal());
+            let retryable = selected
+                .is_some_and(|job| matches!(job.status, JobStatus::Failed | JobStatus::Canceled));
+            let any_failed = state.jobs.iter().any(|job| job.status == JobStatus::Failed);
+            let any_finished = state.jobs.iter().any(|job| job.status.is_terminal());
+            vec![
+                FooterButtonSpec::new("Up", "Select", true, false),
+                FooterButtonSpec::new("Down", "Select", true, false),
+                FooterButtonSpec::new("c", "Cancel", cancelable, false),
+                FooterButtonSpec::new("r", "Retry", retryable, false),
+                FooterButtonSpec::new("R", "RetryAll", any_failed, false),
+                FooterButtonSpec::new("x", "Clear", any_finished, false),
+                FooterButtonSpec::new("Esc", "Close", true, false),
+            ]
+        }
     }
 }
 
@@ -604,6 +857,9 @@ enum FooterMode {
     Selection,
     Dialog,
     Viewer,
+    Jobs,
+    Trash,
+    Properties,
 }
 
 struct FooterButtonSpec {
@@ -757,11 +1013,22 @@ fn top_menu_cell_style(cell: &FooterCellSpec) -> Style {
 }
 
 fn render_dialog(frame: &mut Frame, dialog: &DialogState) {
-    let area = centered_rect(
-        78,
-        if dialog.input_value.is_some() { 9 } else { 7 },
-        frame.area(),
-    );
+    let body_lines = dialog.body.lines().count() as u16;
+    let field_lines = dialog_field_lines(dialog);
+    let fields_height = field_lines.len() as u16;
+    let error_height = u16::from(dialog.error.is_some());
+    let list_height = dialog.list.as_ref().map_or(0, |list| {
+        list.lines.len().clamp(1, DialogList::HEIGHT) as u16 + 2
+    });
+    // Forms size to their fields; plain prompts keep room around the body.
+    let body_height = match (fields_height > 0, dialog.input.is_some()) {
+        (true, _) => body_lines + 1,
+        (false, true) => body_lines.max(3),
+        (false, false) => body_lines.max(4),
+    };
+    let input_height = if dialog.input.is_some() { 3 } else { 0 };
+    let height = 3 + body_height + input_height + fields_height + list_height + error_height;
+    let area = centered_rect(78, height, frame.area());
     frame.render_widget(Clear, area);
 
     let border_color = match dialog.tone {
@@ -781,15 +1048,20 @@ fn render_dialog(frame: &mut Frame, dialog: &DialogState) {
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    let constraints = if dialog.input_value.is_some() {
-        vec![
-            Constraint::Min(1),
-            Constraint::Length(3),
-            Constraint::Length(1),
-        ]
-    } else {
-        vec![Constraint::Min(1), Constraint::Length(1)]
-    };
+    let mut constraints = vec![Constraint::Min(1)];
+    if dialog.input.is_some() {
+        constraints.push(Constraint::Length(3));
+    }
+    if fields_height > 0 {
+        constraints.push(Constraint::Length(fields_height));
+    }
+    if list_height > 0 {
+        constraints.push(Constraint::Length(list_height));
+    }
+    if error_height > 0 {
+        constraints.push(Constraint::Length(error_height));
+    }
+    constraints.push(Constraint::Length(1));
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints(constraints)
@@ -798,40 +1070,287 @@ fn render_dialog(frame: &mut Frame, dialog: &DialogState) {
     let body = Paragraph::new(dialog.body.as_str()).alignment(Alignment::Center);
     frame.render_widget(body, chunks[0]);
 
-    let button_row_idx = if let Some(value) = dialog.input_value.as_ref() {
+    let mut next_chunk = 1;
+    if let Some(input) = dialog.input.as_ref() {
         let label = input_label(dialog);
-        let visible_value = if dialog.mask_input {
-            "*".repeat(value.chars().count())
-        } else {
-            value.clone()
-        };
         let input_block = Block::default()
             .title(label)
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Yellow));
-        let input = Paragraph::new(Line::styled(
-            format!("{visible_value}|"),
+        let input = Paragraph::new(text_input_line(input)).block(input_block);
+        frame.render_widget(input, chunks[next_chunk]);
+        next_chunk += 1;
+    }
+    if fields_height > 0 {
+        frame.render_widget(Paragraph::new(field_lines), chunks[next_chunk]);
+        next_chunk += 1;
+    }
+    if let Some(list) = dialog.list.as_ref() {
+        render_dialog_list(frame, list, chunks[next_chunk]);
+        next_chunk += 1;
+    }
+    if let Some(error) = dialog.error.as_deref() {
+        let error = Paragraph::new(Line::styled(
+            error.to_string(),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ))
+        .alignment(Alignment::Center);
+        frame.render_widget(error, chunks[next_chunk]);
+        next_chunk += 1;
+    }
+
+    let buttons = Paragraph::new(render_button_row(dialog)).alignment(Alignment::Center);
+    frame.render_widget(buttons, chunks[next_chunk]);
+}
+
+/// One line per form field: `[x] label` for checkboxes, `label: value` for
+/// text inputs, `label: < choice >` for dropdowns and `label: (*) one  ( ) two`
+/// for radio groups. Labels other than checkboxes share one column width.
+fn dialog_field_lines(dialog: &DialogState) -> Vec<Line<'static>> {
+    let focus_style = Style::default()
+        .fg(Color::Black)
+        .bg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let label_width = dialog
+        .fields
+        .iter()
+        .filter(|field| !matches!(field.kind, DialogFieldKind::Checkbox(_)))
+        .map(|field| field.label.chars().count() + 1)
+        .max()
+        .unwrap_or(0);
+    dialog
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(idx, field)| {
+            let focused = dialog.focused_field == Some(idx);
+            let label = format!("{:<label_width$}", format!("{}:", field.label));
+            let text = match &field.kind {
+                DialogFieldKind::Checkbox(checked) => {
+                    format!("[{}] {}", if *checked { 'x' } else { ' ' }, field.label)
+                }
+                DialogFieldKind::Radio { options, selected } => {
+                    let choices = options
+                        .iter()
+                        .enumerate()
+                        .map(|(option_idx, option)| {
+                            let mark = if option_idx == *selected { '*' } else { ' ' };
+                            format!("({mark}) {option}")
+                        })
+                        .collect::<Vec<_>>()
+                        .join("  ");
+                    format!("{label} {choices}")
+                }
+                DialogFieldKind::Text { input, masked } => {
+                    return text_field_line(&label, input, *masked, focused.then_some(focus_style));
+                }
+                DialogFieldKind::Dropdown { options, selected } => {
+                    let choice = options.get(*selected).map(String::as_str).unwrap_or("-");
+                    format!("{label} < {choice} >")
+                }
+            };
+            let span = if focused {
+                Span::styled(text, focus_style)
+            } else {
+                Span::raw(text)
+            };
+            Line::from(vec![Span::raw("  "), span])
+        })
+        .collect()
+}
+
+fn render_properties(frame: &mut Frame, properties: &PropertiesState) {
+    let lines = properties_lines(properties);
+    let area = centered_rect(70, lines.len() as u16 + 2, frame.area());
+    frame.render_widget(Clear, area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Properties")
+        .border_style(
             Style::default()
-                .fg(Color::White)
+                .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD),
-        ))
-        .block(input_block);
-        frame.render_widget(input, chunks[1]);
-        2
+        );
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+fn properties_lines(properties: &PropertiesState) -> Vec<Line<'static>> {
+    const ROW_LABELS: [&str; 4] = ["user", "group", "other", "special"];
+    const COLUMN_LABELS: [[&str; 3]; 4] = [
+        ["read", "write", "exec"],
+        ["read", "write", "exec"],
+        ["read", "write", "exec"],
+        ["setuid", "setgid", "sticky"],
+    ];
+
+    let label_style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let focus_style = Style::default()
+        .fg(Color::Black)
+        .bg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let info = &properties.properties;
+    let optional = |value: Option<u64>| value.map_or_else(|| "-".to_string(), |v| v.to_string());
+    let id = |value: Option<u32>| value.map_or_else(|| "-".to_string(), |v| v.to_string());
+
+    let target = if properties.targets.len() > 1 {
+        format!(
+            "{} (applies to {} items)",
+            info.path.display(),
+            properties.targets.len()
+        )
     } else {
-        1
+        info.path.display().to_string()
     };
+    let mut lines = vec![
+        Line::from(vec![Span::styled("Path: ", label_style), Span::raw(target)]),
+        Line::from(vec![
+            Span::styled("Type: ", label_style),
+            Span::raw(format!("{:<10}", entry_type_label(info.entry_type))),
+            Span::styled("Size: ", label_style),
+            Span::raw(format!("{:<10}", human_size(info.size_bytes))),
+            Span::styled("Inode: ", label_style),
+            Span::raw(format!("{:<10}", optional(info.inode))),
+            Span::styled("Links: ", label_style),
+            Span::raw(optional(info.links)),
+        ]),
+        Line::raw(""),
+    ];
 
-    let buttons = Paragraph::new(render_button_row(dialog)).alignment(Alignment::Center);
-    frame.render_widget(buttons, chunks[button_row_idx]);
+    let on_grid = properties.field == PropertiesField::Mode;
+    for (row, bits) in PERMISSION_GRID.iter().enumerate() {
+        let mut spans = vec![Span::styled(
+            format!("  {:<9}", ROW_LABELS[row]),
+            label_style,
+        )];
+        for (col, bit) in bits.iter().enumerate() {
+            let mark = if properties.mode & bit != 0 { 'x' } else { ' ' };
+            let cell = format!("[{mark}] {:<7}", COLUMN_LABELS[row][col]);
+            if on_grid && properties.grid_row == row && properties.grid_col == col {
+                spans.push(Span::styled(cell, focus_style));
+            } else {
+                spans.push(Span::raw(cell));
+            }
+            spans.push(Span::raw("  "));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let mut mode_spans = vec![
+        Span::styled("Mode: ", label_style),
+        Span::styled(
+            format!("{:04o}", properties.mode),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(format!("  {}", symbolic_mode(properties.mode))),
+    ];
+    if properties.mode != info.mode {
+        mode_spans.push(Span::raw(format!("  (was {:04o})", info.mode)));
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::from(mode_spans));
+
+    let checkbox = format!("[{}]", if properties.recursive { 'x' } else { ' ' });
+    let fields = [
+        (
+            PropertiesField::Owner,
+            "Owner: ",
+            properties.owner.as_str(),
+            format!("uid {}", id(info.uid)),
+        ),
+        (
+            PropertiesField::Group,
+            "Group: ",
+            properties.group.as_str(),
+            format!("gid {}", id(info.gid)),
+        ),
+        (
+            PropertiesField::Modified,
+            "Modified: ",
+            properties.modified.as_str(),
+            "YYYY-MM-DD HH:MM:SS".to_string(),
+        ),
+        (
+            PropertiesField::Recursive,
+            "Recursive:",
+            checkbox.as_str(),
+            "apply to directory contents, symlinks skipped".to_string(),
+        ),
+        (
+            PropertiesField::FileMask,
+            "Files: ",
+            properties.file_mask.as_str(),
+            "mode for files, e.g. 0644, u+x, go-w".to_string(),
+        ),
+        (
+            PropertiesField::DirMask,
+            "Dirs: ",
+            properties.dir_mask.as_str(),
+            "mode for directories, e.g. 0755, a+X".to_string(),
+        ),
+    ];
+    for (field, label, value, hint) in fields {
+        let value = if properties.field != field {
+            Span::raw(value.to_string())
+        } else if field == PropertiesField::Recursive {
+            Span::styled(value.to_string(), focus_style)
+        } else {
+            Span::styled(format!("{value}|"), focus_style)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{label:<11}"), label_style),
+            value,
+            Span::styled(format!("  ({hint})"), Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+    lines
+}
+
+fn entry_type_label(entry_type: FsEntryType) -> &'static str {
+    match entry_type {
+        FsEntryType::Directory => "directory",
+        FsEntryType::File => "file",
+        FsEntryType::Symlink => "symlink",
+        FsEntryType::Other => "other",
+    }
+}
+
+/// `ls -l` style permission string, with `s`/`t` where special bits are set.
+fn symbolic_mode(mode: u32) -> String {
+    let mut out = String::with_capacity(9);
+    for (row, special) in [(0usize, 's'), (1, 's'), (2, 't')] {
+        let bits = PERMISSION_GRID[row];
+        out.push(if mode & bits[0] != 0 { 'r' } else { '-' });
+        out.push(if mode & bits[1] != 0 { 'w' } else { '-' });
+        let exec = mode & bits[2] != 0;
+        let special_set = mode & PERMISSION_GRID[3][row] != 0;
+        out.push(match (exec, special_set) {
+            (true, true) => special,
+            (false, true) => special.to_ascii_uppercase(),
+            (true, false) => 'x',
+            (false, false) => '-',
+        });
+    }
+    out
 }
 
 fn render_batch_progress_overlay(frame: &mut Frame, progress: &BatchProgressState) {
-    let area = centered_rect(62, 8, frame.area());
+    let height = if progress.bytes.is_some() { 10 } else { 8 };
+    let area = centered_rect(62, height, frame.area());
     frame.render_widget(Clear, area);
+    let title = if progress.paused {
+        format!("Progress #{} (paused)", progress.batch_id)
+    } else {
+        format!("Progress #{}", progress.batch_id)
+    };
     let block = Block::default()
         .borders(Borders::ALL)
-        .title(format!("Progress #{}", progress.batch_id))
+        .title(title)
         .border_style(
             Style::default()
                 .fg(Color::LightBlue)
@@ -843,54 +1362,139 @@ fn render_batch_progress_overlay(frame: &mut Frame, progress: &BatchProgressStat
         return;
     }
 
-    let completed = progress.completed.min(progress.total);
-    let total = progress.total.max(1);
-    let percent = (completed * 100) / total;
+    let label_style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let bar_style = Style::default().fg(Color::Green);
     let bar_width = inner.width.saturating_sub(8) as usize;
-    let filled = (bar_width * completed) / total;
-    let progress_bar = format!(
-        "[{}{}] {:>3}%",
-        "=".repeat(filled),
-        "-".repeat(bar_width.saturating_sub(filled)),
-        percent
-    );
+    let completed = progress.completed.min(progress.total);
     let current_file = truncate_name(progress.current_file.as_str(), inner.width as usize);
-    let lines = vec![
+    let mut lines = vec![
         Line::from(vec![
-            Span::styled(
-                "Operation: ",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ),
+            Span::styled("Operation: ", label_style),
             Span::raw(operation_label(progress.operation)),
         ]),
         Line::from(vec![
-            Span::styled(
-                "Current file: ",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ),
+            Span::styled("Current file: ", label_style),
             Span::raw(current_file),
         ]),
-        Line::from(vec![
-            Span::styled(
-                "Files: ",
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            ),
+    ];
+
+    if let Some((done, total)) = progress.entries {
+        lines.push(Line::from(vec![
+            Span::styled("Entries: ", label_style),
+            Span::raw(format!(
+                "{}/{}  items: {}/{}  failed: {}",
+                done, total, completed, progress.total, progress.failed
+            )),
+        ]));
+        lines.push(Line::styled(
+            progress_bar(done as u64, total as u64, bar_width),
+            bar_style,
+        ));
+        lines.push(progress_control_row(progress.paused));
+        frame.render_widget(Paragraph::new(lines), inner);
+        return;
+    }
+
+    let Some(bytes) = progress.bytes else {
+        lines.push(Line::from(vec![
+            Span::styled("Files: ", label_style),
             Span::raw(format!(
                 "{}/{}  failed: {}",
                 completed, progress.total, progress.failed
             )),
+        ]));
+        lines.push(Line::styled(
+            progress_bar(completed as u64, progress.total as u64, bar_width),
+            bar_style,
+        ));
+        lines.push(progress_control_row(progress.paused));
+        frame.render_widget(Paragraph::new(lines), inner);
+        return;
+    };
+
+    let eta = bytes
+        .eta()
+        .map(|eta| format_eta(eta.as_secs()))
+        .unwrap_or_else(|| "--:--".to_string());
+    lines.extend([
+        Line::from(vec![
+            Span::styled("File: ", label_style),
+            Span::raw(format!(
+                "{} / {}",
+                human_size(bytes.file_done),
+                human_size(bytes.file_total)
+            )),
         ]),
-        Line::styled(progress_bar, Style::default().fg(Color::Green)),
-    ];
+        Line::styled(
+            progress_bar(bytes.file_done, bytes.file_total, bar_width),
+            bar_style,
+        ),
+        Line::from(vec![
+            Span::styled("Total: ", label_style),
+            Span::raw(format!(
+                "{} / {}  files: {}/{}  failed: {}",
+                human_size(bytes.done),
+                human_size(bytes.total),
+                completed,
+                progress.total,
+                progress.failed
+            )),
+        ]),
+        Line::styled(progress_bar(bytes.done, bytes.total, bar_width), bar_style),
+        Line::from(vec![
+            Span::styled("Speed: ", label_style),
+            Span::raw(format!("{}/s  ", human_size(bytes.bytes_per_sec))),
+            Span::styled("ETA: ", label_style),
+            Span::raw(eta),
+        ]),
+        progress_control_row(progress.paused),
+    ]);
     frame.render_widget(Paragraph::new(lines), inner);
 }
 
+fn progress_control_row(paused: bool) -> Line<'static> {
+    let button_style = Style::default()
+        .fg(Color::White)
+        .add_modifier(Modifier::BOLD);
+    let pause_label = if paused {
+        "[ Resume (p) ]"
+    } else {
+        "[ Pause (p) ]"
+    };
+    Line::from(vec![
+        Span::styled(pause_label, button_style),
+        Span::raw(" "),
+        Span::styled("[ Abort (Esc) ]", button_style),
+    ])
+    .alignment(Alignment::Center)
+}
+
+fn progress_bar(done: u64, total: u64, width: usize) -> String {
+    let total = total.max(1);
+    let done = done.min(total);
+    let percent = (done * 100) / total;
+    let filled = ((width as u64 * done) / total) as usize;
+    format!(
+        "[{}{}] {:>3}%",
+        "=".repeat(filled),
+        "-".repeat(width.saturating_sub(filled)),
+        percent
+    )
+}
+
+fn format_eta(seconds: u64) -> String {
+    let hours = seconds / 3600;
+    let minutes = (seconds % 3600) / 60;
+    let secs = seconds % 60;
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{secs:02}")
+    } else {
+        format!("{minutes:02}:{secs:02}")
+    }
+}
+
 fn render_button_row(dialog: &DialogState) -> Line<'static> {
     let mut spans = Vec::new();
 
@@ -899,7 +1503,7 @@ fn render_button_row(dialog: &DialogState) -> Line<'static> {
             spans.push(Span::raw(" "));
         }
 
-        let is_focused = idx == dialog.focused_button;
+        let is_focused = dialog.focused_field.is_none() && idx == dialog.focused_button;
         let label = button.label.as_str();
         let style = if is_focused {
             Style::default()
@@ -919,20 +1523,99 @@ fn render_button_row(dialog: &DialogState) -> Line<'static> {
     Line::from(spans)
 }
 
+fn render_dialog_list(frame: &mut Frame, list: &DialogList, area: Rect) {
+    let total = list.lines.len();
+    let first = list.offset.min(total);
+    let last = (first + DialogList::HEIGHT).min(total);
+    let title = if total > DialogList::HEIGHT {
+        format!("{} {}-{}/{}", list.title, first + 1, last, total)
+    } else {
+        list.title.clone()
+    };
+    let lines = list.lines[first..last]
+        .iter()
+        .enumerate()
+        .map(|(idx, (text, flagged))| {
+            let mut style = if *flagged {
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            if list.selected == Some(first + idx) {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            Line::styled(text.clone(), style)
+        })
+        .collect::<Vec<_>>();
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// `label value` for a form text input; a focused one (given its focus
+/// style) shows the cursor and selection.
+fn text_field_line(
+    label: &str,
+    input: &TextInput,
+    masked: bool,
+    focus_style: Option<Style>,
+) -> Line<'static> {
+    let shown = if masked {
+        masked_input(input)
+    } else {
+        input.clone()
+    };
+    let Some(focus_style) = focus_style else {
+        return Line::from(format!("  {label} {}", shown.value));
+    };
+    let mut spans = vec![
+        Span::raw("  "),
+        Span::styled(format!("{label} "), focus_style),
+    ];
+    spans.extend(text_input_line(&shown).spans);
+    Line::from(spans)
+}
+
+/// `input` with every char shown as `*`, keeping the cursor and selection.
+fn masked_input(input: &TextInput) -> TextInput {
+    let stars = |end: usize| input.value[..end].chars().count();
+    TextInput {
+        value: "*".repeat(stars(input.value.len())),
+        cursor: stars(input.cursor),
+        anchor: input.anchor.map(stars),
+    }
+}
+
+/// The input's text with `|` at the cursor and the selection in reverse video.
+fn text_input_line(input: &TextInput) -> Line<'static> {
+    let style = Style::default()
+        .fg(Color::White)
+        .add_modifier(Modifier::BOLD);
+    let selection = input.selection().unwrap_or(input.cursor..input.cursor);
+    let value = input.value.as_str();
+    let cursor = Span::styled("|", style);
+    let mut spans = vec![Span::styled(value[..selection.start].to_string(), style)];
+    let cursor_first = input.cursor == selection.start;
+    if cursor_first {
+        spans.push(cursor.clone());
+    }
+    spans.push(Span::styled(
+        value[selection.clone()].to_string(),
+        style.add_modifier(Modifier::REVERSED),
+    ));
+    if !cursor_first {
+        spans.push(cursor);
+    }
+    spans.push(Span::styled(value[selection.end..].to_string(), style));
+    Line::from(spans)
+}
+
 fn input_label(dialog: &DialogState) -> &'static str {
     let title = dialog.title.to_ascii_lowercase();
     if title.contains("mask") {
         "Mask"
-    } else if title.contains("sftp connect") {
-        "Address"
-    } else if title.contains("sftp") && title.contains("address") {
-        "Address"
-    } else if title.contains("sftp") && title.contains("login") {
-        "Login"
-    } else if title.contains("sftp") && title.contains("password") {
-        "Password"
-    } else if title.contains("sftp") && title.contains("key") {
-        "Key Path"
     } else if title.contains("editor") {
         "Choice"
     } else {
@@ -940,6 +1623,37 @@ fn input_label(dialog: &DialogState) -> &'static str {
     }
 }
 
+/// Name column of a panel row, padded to `width`. A symlink's target follows
+/// its name in its own colour, so a dangling one stands out.
+fn name_cell_spans(
+    name: &str,
+    entry: &FsEntry,
+    width: usize,
+    base_style: Style,
+    theme: &DirColorsTheme,
+) -> Vec<Span<'static>> {
+    let name_style = base_style.patch(type_style(entry, theme));
+    let Some(target) = entry.link_target.as_ref().filter(|_| !entry.is_virtual) else {
+        return vec![Span::styled(
+            format!("{:<width$}", truncate_name(name, width)),
+            name_style,
+        )];
+    };
+    let cell = format!(
+        "{:<width$}",
+        truncate_name(&format!("{name} -> {}", target.display()), width)
+    );
+    let split = cell
+        .char_indices()
+        .nth(name.chars().count())
+        .map_or(cell.len(), |(idx, _)| idx);
+    let target_style = base_style.patch(theme_style_to_ratatui(theme.style_for_link_target(entry)));
+    vec![
+        Span::styled(cell[..split].to_string(), name_style),
+        Span::styled(cell[split..].to_string(), target_style),
+    ]
+}
+
 fn type_style(entry: &FsEntry, theme: &DirColorsTheme) -> Style {
     theme_style_to_ratatui(theme.style_for_entry(entry))
 }
@@ -1035,7 +1749,12 @@ fn fixed_table_layout(total_width: usize) -> TableLayout {
     }
 }
 
-fn render_table_header(layout: TableLayout) -> Line<'static> {
+fn render_table_header(layout: TableLayout, trash: bool) -> Line<'static> {
+    let (name_label, modified_label) = if trash {
+        ("Original path", "Deleted")
+    } else {
+        ("Name", "Modified")
+    };
     let text = match layout {
         TableLayout::Full {
             name_width,
@@ -1043,9 +1762,9 @@ fn render_table_header(layout: TableLayout) -> Line<'static> {
             modified_width,
         } => format!(
             "{:<name_width$}{COL_SEP}{:>size_width$}{COL_SEP}{:>modified_width$}",
-            "Name",
+            name_label,
             "Size",
-            "Modified",
+            modified_label,
             name_width = name_width,
             size_width = size_width,
             modified_width = modified_width
@@ -1055,13 +1774,13 @@ fn render_table_header(layout: TableLayout) -> Line<'static> {
             size_width,
         } => format!(
             "{:<name_width$}{COL_SEP}{:>size_width$}",
-            "Name",
+            name_label,
             "Size",
             name_width = name_width,
             size_width = size_width
         ),
         TableLayout::Minimal { name_width } => {
-            format!("{:<name_width$}", "Name", name_width = name_width)
+            format!("{:<name_width$}", name_label, name_width = name_width)
         }
     };
 
@@ -1153,6 +1872,21 @@ fn render_selection_line(
     )
 }
 
+/// Trash entries are shown under the path they will be restored to.
+fn panel_entry_name(panel: &PanelState, entry: &FsEntry) -> String {
+    let Some(item) = panel
+        .trash_view
+        .as_ref()
+        .and_then(|trash| trash.item(&entry.path))
+    else {
+        return entry_name(entry);
+    };
+    entry_name(&FsEntry {
+        name: item.original_path.display().to_string(),
+        ..entry.clone()
+    })
+}
+
 fn entry_name(entry: &FsEntry) -> String {
     if entry.is_virtual {
         return entry.name.clone();
@@ -1255,6 +1989,52 @@ fn find_suffix(panel: &PanelState) -> String {
     }
 }
 
+/// One cell per tab: its number and directory name, plus the backend when it
+/// is not local. The shown tab is highlighted, brighter on the active side.
+fn tab_strip_line(tabs: &PanelTabs, current: &PanelState, side_active: bool) -> Line<'static> {
+    let mut spans = Vec::new();
+    for (idx, panel) in tabs.strip(current).enumerate() {
+        let dir = panel
+            .cwd
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| panel.cwd.display().to_string());
+        let label = if panel.backend_label == "local" {
+            format!(" {}:{dir} ", idx + 1)
+        } else {
+            format!(" {}:{dir} <{}> ", idx + 1, panel.backend_label)
+        };
+        let style = if idx != tabs.active {
+            Style::default().fg(Color::Gray)
+        } else if side_active {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Black).bg(Color::DarkGray)
+        };
+        spans.push(Span::styled(label, style));
+    }
+    Line::from(spans)
+}
+
+fn loading_suffix(panel: &PanelState, spinner_frame: usize) -> String {
+    if panel.loading {
+        format!(" {}", SPINNER_FRAMES[spinner_frame % SPINNER_FRAMES.len()])
+    } else {
+        String::new()
+    }
+}
+
+fn trash_suffix(panel: &PanelState) -> String {
+    panel
+        .trash_view
+        .as_ref()
+        .map(|trash| format!(" [trash:{}]", trash.items.len()))
+        .unwrap_or_default()
+}
+
 fn find_status_label(progress: &FindProgressState) -> String {
     let panel = match progress.panel_id {
         PanelId::Left => "L",
@@ -1358,8 +2138,15 @@ fn operation_label(kind: JobKind) -> &'static str {
     match kind {
         JobKind::Copy => "Copy",
         JobKind::Move => "Move",
+        JobKind::Rename => "Rename",
         JobKind::Delete => "Delete",
         JobKind::Mkdir => "Mkdir",
+        JobKind::Trash => "Trash",
+        JobKind::Restore => "Restore",
+        JobKind::Purge => "Purge",
+        JobKind::Chattr => "Attributes",
+        JobKind::Symlink => "Symlink",
+        JobKind::Hardlink => "Hardlink",
     }
 }
 
@@ -1440,7 +2227,18 @@ fn centered_rect(width_percent: u16, height: u16, area: Rect) -> Rect {
 
 #[cfg(test)]
 mod tests {
-    use super::{TableLayout, distribute_width, fit_footer_cell_text, fixed_table_layout};
+    use super::{
+        TableLayout, dialog_field_lines, distribute_width, fit_footer_cell_text,
+        fixed_table_layout, format_eta, job_manager_row, name_cell_spans, progress_bar,
+        symbolic_mode, tab_strip_line, text_input_line,
+    };
+    use crate::model::{
+        DialogField, DialogState, DialogTone, FsEntry, FsEntryType, PanelState, PanelTabs,
+        TextInput,
+    };
+    use crate::theme::{DirColorsTheme, ThemeColor};
+    use ratatui::style::{Color, Modifier, Style};
+    use std::path::PathBuf;
 
     #[test]
     fn fixed_table_layout_switches_modes_for_narrow_widths() {
@@ -1482,6 +2280,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn progress_bar_clamps_and_scales_to_width() {
+        assert_eq!(progress_bar(0, 0, 4), "[----]   0%");
+        assert_eq!(progress_bar(50, 100, 4), "[==--]  50%");
+        assert_eq!(progress_bar(150, 100, 4), "[====] 100%");
+    }
+
+    #[test]
+    fn eta_switches_to_hours_when_needed() {
+        assert_eq!(format_eta(75), "01:15");
+        assert_eq!(format_eta(3_725), "1:02:05");
+    }
+
+    #[test]
+    fn job_manager_row_truncates_target_to_width() {
+        let row = job_manager_row(
+            "12",
+            "Copy",
+            "running",
+            " 50% 1.0M/s",
+            "/src/a -> /dst/a",
+            60,
+        );
+        assert!(row.starts_with("   12 Copy   running   50% 1.0M/s"));
+        assert!(row.ends_with("/src/a -> /dst/a"));
+
+        let narrow = job_manager_row("12", "Copy", "running", "-", "/very/long/source/path", 48);
+        assert_eq!(narrow.chars().count(), 48);
+        assert!(narrow.ends_with("..."));
+    }
+
+    #[test]
+    fn symbolic_mode_marks_special_bits() {
+        assert_eq!(symbolic_mode(0o755), "rwxr-xr-x");
+        assert_eq!(symbolic_mode(0o4755), "rwsr-xr-x");
+        assert_eq!(symbolic_mode(0o2644), "rw-r-Sr--");
+        assert_eq!(symbolic_mode(0o1777), "rwxrwxrwt");
+    }
+
     fn layout_total_width(layout: TableLayout) -> usize {
         match layout {
             TableLayout::Full {
@@ -1496,4 +2333,142 @@ mod tests {
             TableLayout::Minimal { name_width } => name_width,
         }
     }
+
+    #[test]
+    fn dialog_fields_render_checkboxes_and_radio_groups() {
+        let dialog = DialogState {
+            title: "Copy".to_string(),
+            body: String::new(),
+            input: None,
+            fields: vec![
+                DialogField::checkbox("preserve", "Preserve attributes", true),
+                DialogField::radio("conflict", "On conflict", &["Ask", "Skip"], 1),
+            ],
+            focused_field: Some(1),
+            list: None,
+            error: None,
+            buttons: Vec::new(),
+            focused_button: 0,
+            tone: DialogTone::Default,
+        };
+        let text = dialog_field_lines(&dialog)
+            .iter()
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            text,
+            [
+                "  [x] Preserve attributes",
+                "  On conflict: ( ) Ask  (*) Skip"
+            ]
+        );
+    }
+
+    #[test]
+    fn symlink_names_show_their_target_in_its_own_colour() {
+        let mut theme = DirColorsTheme::fallback();
+        theme.missing.fg = Some(ThemeColor::Yellow);
+        let entry = FsEntry {
+            name: "latest".to_string(),
+            path: PathBuf::from("/srv/latest"),
+            entry_type: FsEntryType::Symlink,
+            size_bytes: 0,
+            modified_at: None,
+            is_executable: false,
+            is_hidden: false,
+            is_virtual: false,
+            link_target: Some(PathBuf::from("releases/v2")),
+            link_broken: true,
+        };
+
+        let spans = name_cell_spans("latest@", &entry, 28, Style::default(), &theme);
+        assert_eq!(spans[0].content, "latest@");
+        assert_eq!(spans[1].content, " -> releases/v2      ");
+        assert_eq!(spans[1].style.fg, Some(Color::Yellow));
+
+        let narrow = name_cell_spans("latest@", &entry, 12, Style::default(), &theme);
+        assert_eq!(narrow[0].content, "latest@");
+        assert_eq!(narrow[1].content, " -...");
+    }
+
+    #[test]
+    fn tab_strip_lists_tabs_in_order_around_the_shown_one() {
+        let mut remote = PanelState::new(PathBuf::from("/var/log"));
+        remote.backend_label = "sftp:ops@web1".to_string();
+        let tabs = PanelTabs {
+            parked: vec![PanelState::new(PathBuf::from("/home/dev")), remote],
+            active: 1,
+        };
+        let current = PanelState::new(PathBuf::from("/"));
+
+        let line = tab_strip_line(&tabs, &current, true);
+        assert_eq!(line.to_string(), " 1:dev  2:/  3:log <sftp:ops@web1> ");
+        assert_eq!(line.spans[1].style.bg, Some(Color::Yellow));
+        assert_eq!(line.spans[0].style.bg, None);
+
+        let inactive = tab_strip_line(&tabs, &current, false);
+        assert_eq!(inactive.spans[1].style.bg, Some(Color::DarkGray));
+    }
+
+    #[test]
+    fn text_input_shows_cursor_and_selection() {
+        let mut input = TextInput::with_selection("report.txt".to_string(), 0..6);
+        let line = text_input_line(&input);
+        assert_eq!(line.to_string(), "report|.txt");
+        assert!(
+            line.spans[1]
+                .style
+                .add_modifier
+                .contains(Modifier::REVERSED)
+        );
+        assert_eq!(line.spans[1].content, "report");
+
+        input.insert_char('x');
+        input.move_left();
+        input.move_word_left();
+        assert_eq!(text_input_line(&input).to_string(), "|x.txt");
+        input.move_end();
+        input.delete_word_back();
+        assert_eq!(text_input_line(&input).to_string(), "x.|");
+    }
+
+    #[test]
+    fn dialog_fields_render_text_and_dropdowns() {
+        let mut dialog = DialogState {
+            title: "SFTP Connect".to_string(),
+            body: String::new(),
+            input: None,
+            fields: vec![
+                DialogField::text("login", "Login", "alice".to_string()),
+                DialogField::dropdown("auth", "Auth", &["Auto", "Agent"], 1),
+                DialogField::masked("password", "Password", "abc".to_string()),
+            ],
+            focused_field: Some(0),
+            list: None,
+            error: None,
+            buttons: Vec::new(),
+            focused_button: 0,
+            tone: DialogTone::Default,
+        };
+        let text = dialog_field_lines(&dialog)
+            .iter()
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            text,
+            [
+                "  Login:    alice|",
+                "  Auth:     < Agent >",
+                "  Password: ***"
+            ]
+        );
+        dialog.focused_field = Some(2);
+        if let Some(input) = dialog.fields[2].text_input_mut() {
+            input.move_left();
+        }
+        assert_eq!(
+            dialog_field_lines(&dialog)[2].to_string(),
+            "  Password: **|*"
+        );
+    }
 }
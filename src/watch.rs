@@ -0,0 +1,341 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use crossbeam_channel::Sender;
+
+use crate::backend::FsBackend;
+use crate::model::{Event, PanelId, SortMode};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+const STOP_CHECK: Duration = Duration::from_millis(100);
+
+/// Watches one panel directory on its own thread and sends
+/// `Event::DirChanged` when its entries change. Dropping it stops the thread.
+pub struct DirWatch {
+    pub dir: PathBuf,
+    stop: Arc<AtomicBool>,
+}
+
+impl DirWatch {
+    /// Watches a local directory with inotify, falling back to polling when
+    /// inotify is unavailable (for instance, out of watches).
+    #[cfg(target_os = "linux")]
+    pub fn local(
+        panel_id: PanelId,
+        dir: PathBuf,
+        backend: Arc<dyn FsBackend>,
+        event_tx: Sender<Event>,
+    ) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        match inotify::Inotify::watch(&dir) {
+            Ok(inotify) => {
+                let (stop, dir) = (Arc::clone(&stop), dir.clone());
+                thread::spawn(move || inotify::run_watch(inotify, panel_id, dir, stop, event_tx));
+            }
+            Err(err) => {
+                tracing::debug!("inotify unavailable for {}: {err}", dir.display());
+                spawn_poll_watch(panel_id, dir.clone(), backend, Arc::clone(&stop), event_tx);
+            }
+        }
+        Self { dir, stop }
+    }
+
+    /// Watches a local directory by polling it, as there is no inotify here.
+    #[cfg(not(target_os = "linux"))]
+    pub fn local(
+        panel_id: PanelId,
+        dir: PathBuf,
+        backend: Arc<dyn FsBackend>,
+        event_tx: Sender<Event>,
+    ) -> Self {
+        Self::polling(panel_id, dir, backend, event_tx)
+    }
+
+    /// Watches a directory by listing it every few seconds and comparing
+    /// names, sizes and modification times. Used for SFTP, which has no
+    /// change notifications.
+    pub fn polling(
+        panel_id: PanelId,
+        dir: PathBuf,
+        backend: Arc<dyn FsBackend>,
+        event_tx: Sender<Event>,
+    ) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        spawn_poll_watch(panel_id, dir.clone(), backend, Arc::clone(&stop), event_tx);
+        Self { dir, stop }
+    }
+}
+
+impl Drop for DirWatch {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+fn spawn_poll_watch(
+    panel_id: PanelId,
+    dir: PathBuf,
+    backend: Arc<dyn FsBackend>,
+    stop: Arc<AtomicBool>,
+    event_tx: Sender<Event>,
+) {
+    thread::spawn(move || {
+        let mut last = listing_fingerprint(backend.as_ref(), &dir);
+        loop {
+            let started = Instant::now();
+            while started.elapsed() < POLL_INTERVAL {
+                if stop.load(Ordering::Relaxed) {
+                    return;
+                }
+                thread::sleep(STOP_CHECK);
+            }
+            // A failed listing (dropped connection) is not a change; the next
+            // successful one is compared against the last one that worked.
+            let Ok(current) = listing_fingerprint(backend.as_ref(), &dir) else {
+                continue;
+            };
+            if last.as_ref().is_ok_and(|last| *last != current) {
+                let changed = Event::DirChanged {
+                    panel_id,
+                    dir: dir.clone(),
+                };
+                if event_tx.send(changed).is_err() {
+                    return;
+                }
+            }
+            last = Ok(current);
+        }
+    });
+}
+
+fn listing_fingerprint(backend: &dyn FsBackend, dir: &Path) -> Result<u64> {
+    let entries = backend.list_dir(dir, SortMode::Name, true)?;
+    let mut hasher = DefaultHasher::new();
+    for entry in &entries {
+        entry.name.hash(&mut hasher);
+        entry.size_bytes.hash(&mut hasher);
+        entry.modified_at.hash(&mut hasher);
+    }
+    Ok(hasher.finish())
+}
+
+/// inotify exists on Linux only; elsewhere local directories are polled.
+#[cfg(target_os = "linux")]
+mod inotify {
+    use std::ffi::CString;
+    use std::fs::File;
+    use std::io::{self, Read};
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::{Duration, Instant};
+
+    use crossbeam_channel::Sender;
+    use tracing::debug;
+
+    use crate::model::{Event, PanelId};
+
+    /// Quiet time after the last change before the panel is told to reload.
+    pub(super) const DEBOUNCE: Duration = Duration::from_millis(300);
+    /// Longest a burst of changes (a build writing many files) holds back a reload.
+    pub(super) const MAX_DELAY: Duration = Duration::from_secs(1);
+    const INOTIFY_WAIT_MS: i32 = 100;
+
+    pub(super) struct Inotify {
+        file: File,
+    }
+
+    impl Inotify {
+        pub(super) fn watch(dir: &Path) -> io::Result<Self> {
+            let fd = unsafe { libc::inotify_init1(libc::IN_NONBLOCK | libc::IN_CLOEXEC) };
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            // Owned right away so the descriptor is closed on every error path.
+            let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+            let path = CString::new(dir.as_os_str().as_bytes())
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+            let mask = libc::IN_CREATE
+                | libc::IN_DELETE
+                | libc::IN_MODIFY
+                | libc::IN_ATTRIB
+                | libc::IN_MOVED_FROM
+                | libc::IN_MOVED_TO
+                | libc::IN_DELETE_SELF
+                | libc::IN_MOVE_SELF;
+            let wd = unsafe { libc::inotify_add_watch(fd.as_raw_fd(), path.as_ptr(), mask) };
+            if wd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(Self {
+                file: File::from(fd),
+            })
+        }
+
+        /// Waits up to `INOTIFY_WAIT_MS` for events and drains them; the events
+        /// themselves do not matter, only that something changed.
+        fn wait_for_change(&mut self) -> io::Result<bool> {
+            let mut poll_fd = libc::pollfd {
+                fd: self.file.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            let ready = unsafe { libc::poll(&mut poll_fd, 1, INOTIFY_WAIT_MS) };
+            if ready < 0 {
+                let err = io::Error::last_os_error();
+                return if err.kind() == io::ErrorKind::Interrupted {
+                    Ok(false)
+                } else {
+                    Err(err)
+                };
+            }
+            if ready == 0 {
+                return Ok(false);
+            }
+            let mut buf = [0u8; 4096];
+            let mut changed = false;
+            loop {
+                match self.file.read(&mut buf) {
+                    Ok(0) => return Ok(changed),
+                    Ok(_) => changed = true,
+                    Err(err) if err.kind() == io::ErrorKind::WouldBlock => return Ok(changed),
+                    Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                    Err(err) => return Err(err),
+                }
+            }
+        }
+    }
+
+    pub(super) fn run_watch(
+        mut inotify: Inotify,
+        panel_id: PanelId,
+        dir: PathBuf,
+        stop: Arc<AtomicBool>,
+        event_tx: Sender<Event>,
+    ) {
+        let mut debounce = Debounce::default();
+        while !stop.load(Ordering::Relaxed) {
+            match inotify.wait_for_change() {
+                Ok(true) => debounce.record(Instant::now()),
+                Ok(false) => {}
+                Err(err) => {
+                    debug!("inotify watch on {} failed: {err}", dir.display());
+                    return;
+                }
+            }
+            if debounce.is_due(Instant::now()) {
+                let changed = Event::DirChanged {
+                    panel_id,
+                    dir: dir.clone(),
+                };
+                if event_tx.send(changed).is_err() {
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Collapses a burst of changes into one reload: due once changes have been
+    /// quiet for `DEBOUNCE`, or `MAX_DELAY` after the first one.
+    #[derive(Default)]
+    pub(super) struct Debounce {
+        pending: Option<(Instant, Instant)>,
+    }
+
+    impl Debounce {
+        pub(super) fn record(&mut self, now: Instant) {
+            let first = self.pending.map_or(now, |(first, _)| first);
+            self.pending = Some((first, now));
+        }
+
+        pub(super) fn is_due(&mut self, now: Instant) -> bool {
+            let Some((first, last)) = self.pending else {
+                return false;
+            };
+            let due =
+                now.duration_since(last) >= DEBOUNCE || now.duration_since(first) >= MAX_DELAY;
+            if due {
+                self.pending = None;
+            }
+            due
+        }
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use std::fs;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    use crossbeam_channel::unbounded;
+
+    use super::DirWatch;
+    use super::inotify::{DEBOUNCE, Debounce, MAX_DELAY};
+    use crate::backend::LocalFsBackend;
+    use crate::model::{Event, PanelId};
+    use crate::test_support::fixture_dir;
+
+    #[test]
+    fn debounce_waits_for_quiet_but_not_forever() {
+        let start = Instant::now();
+        let mut debounce = Debounce::default();
+        assert!(!debounce.is_due(start));
+
+        debounce.record(start);
+        assert!(!debounce.is_due(start + DEBOUNCE / 2));
+        assert!(debounce.is_due(start + DEBOUNCE));
+        assert!(!debounce.is_due(start + DEBOUNCE * 2));
+
+        // Changes arriving faster than the debounce still reload at MAX_DELAY.
+        let step = DEBOUNCE / 2;
+        let mut now = start;
+        debounce.record(now);
+        while now.duration_since(start) + step < MAX_DELAY {
+            now += step;
+            debounce.record(now);
+            assert!(!debounce.is_due(now));
+        }
+        assert!(debounce.is_due(start + MAX_DELAY));
+    }
+
+    #[test]
+    fn local_watch_reports_new_files_once_per_burst() {
+        let dir = fixture_dir("watch-local");
+        let (event_tx, event_rx) = unbounded();
+        let watch = DirWatch::local(
+            PanelId::Right,
+            dir.clone(),
+            Arc::new(LocalFsBackend::default()),
+            event_tx,
+        );
+
+        for idx in 0..5 {
+            fs::write(dir.join(format!("build-{idx}.o")), b"obj").expect("file written");
+        }
+        match event_rx.recv_timeout(Duration::from_secs(5)) {
+            Ok(Event::DirChanged {
+                panel_id,
+                dir: changed,
+            }) => {
+                assert_eq!(panel_id, PanelId::Right);
+                assert_eq!(changed, dir);
+            }
+            other => panic!("expected a change event, got {other:?}"),
+        }
+        assert!(event_rx.recv_timeout(MAX_DELAY).is_err());
+
+        drop(watch);
+        fs::write(dir.join("late.o"), b"obj").expect("file written");
+        assert!(event_rx.recv_timeout(MAX_DELAY).is_err());
+        let _ = fs::remove_dir_all(dir);
+    }
+}